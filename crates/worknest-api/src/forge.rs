@@ -0,0 +1,542 @@
+//! Incoming webhooks from Git forges
+//!
+//! GitHub, Gitea (and Forgejo) and GitLab post push and pull request events
+//! in shapes of their own and prove them with the shared secret in ways of
+//! their own: GitHub and Gitea sign the body with HMAC-SHA256, GitLab sends
+//! the secret itself as a token. [`Forge::detect`] recognises the forge from
+//! the event header, and [`Forge::parse`] reads the fields Worknest needs
+//! into a [`ForgeEvent`].
+
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use worknest_core::models::PullRequestState;
+
+/// A Git forge that sends webhooks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    /// Gitea, and Forgejo which sends the same headers
+    Gitea,
+    GitLab,
+}
+
+impl Forge {
+    /// Recognise the forge from the event header it sends
+    pub fn detect(headers: &HeaderMap) -> Option<Self> {
+        // Gitea also sends `X-GitHub-Event` for compatibility, so it goes first
+        if headers.contains_key("X-Gitea-Event") {
+            Some(Forge::Gitea)
+        } else if headers.contains_key("X-GitHub-Event") {
+            Some(Forge::GitHub)
+        } else if headers.contains_key("X-Gitlab-Event") {
+            Some(Forge::GitLab)
+        } else {
+            None
+        }
+    }
+
+    /// Name of the forge
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Forge::GitHub => "GitHub",
+            Forge::Gitea => "Gitea",
+            Forge::GitLab => "GitLab",
+        }
+    }
+
+    /// Check that a webhook carries proof of the shared secret
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8], secret: &str) -> bool {
+        match self {
+            Forge::GitHub => header(headers, "X-Hub-Signature-256")
+                .and_then(|signature| signature.strip_prefix("sha256="))
+                .is_some_and(|signature| verify_signature(secret, body, signature)),
+            Forge::Gitea => header(headers, "X-Gitea-Signature")
+                .is_some_and(|signature| verify_signature(secret, body, signature)),
+            Forge::GitLab => header(headers, "X-Gitlab-Token")
+                .is_some_and(|token| constant_time_eq(token.as_bytes(), secret.as_bytes())),
+        }
+    }
+
+    /// Read a webhook's payload
+    pub fn parse(&self, headers: &HeaderMap, body: &[u8]) -> serde_json::Result<ForgeEvent> {
+        let name = match self {
+            Forge::GitHub => header(headers, "X-GitHub-Event"),
+            Forge::Gitea => header(headers, "X-Gitea-Event"),
+            Forge::GitLab => header(headers, "X-Gitlab-Event"),
+        }
+        .unwrap_or_default();
+
+        match (self, name) {
+            (Forge::GitHub | Forge::Gitea, "push") => {
+                let push: HubPush = serde_json::from_slice(body)?;
+                Ok(ForgeEvent::Push(Push {
+                    repository: push.repository.full_name,
+                    branch: branch_name(&push.git_ref),
+                    default_branch: push.repository.default_branch,
+                    commits: push.commits.into_iter().map(Commit::from).collect(),
+                }))
+            },
+            (Forge::GitHub | Forge::Gitea, "pull_request") => {
+                let event: HubPullRequestEvent = serde_json::from_slice(body)?;
+                let pr = event.pull_request;
+                let actor = pr.merged_by.or(event.sender);
+                Ok(ForgeEvent::PullRequest(PullRequest {
+                    repository: event.repository.full_name,
+                    number: pr.number,
+                    title: pr.title,
+                    body: pr.body.unwrap_or_default(),
+                    url: pr.html_url,
+                    branch: pr.head.git_ref,
+                    state: match (pr.state.as_str(), pr.merged) {
+                        (_, true) => PullRequestState::Merged,
+                        ("open", _) => PullRequestState::Open,
+                        _ => PullRequestState::Closed,
+                    },
+                    author: Some(pr.user.login),
+                    actor_username: actor.map(|user| user.login),
+                }))
+            },
+            (Forge::GitLab, "Push Hook") => {
+                let push: LabPush = serde_json::from_slice(body)?;
+                Ok(ForgeEvent::Push(Push {
+                    repository: push.project.path_with_namespace,
+                    branch: branch_name(&push.git_ref),
+                    default_branch: push.project.default_branch,
+                    commits: push.commits.into_iter().map(Commit::from).collect(),
+                }))
+            },
+            (Forge::GitLab, "Merge Request Hook") => {
+                let event: LabMergeRequestEvent = serde_json::from_slice(body)?;
+                let mr = event.object_attributes;
+                // The event names whoever acted, who is only known to be
+                // the author when the merge request was just opened
+                let opened = mr.action.as_deref() == Some("open");
+                Ok(ForgeEvent::PullRequest(PullRequest {
+                    repository: event.project.path_with_namespace,
+                    number: mr.iid,
+                    title: mr.title,
+                    body: mr.description.unwrap_or_default(),
+                    url: mr.url,
+                    branch: mr.source_branch,
+                    state: match mr.state.as_str() {
+                        "opened" => PullRequestState::Open,
+                        "merged" => PullRequestState::Merged,
+                        _ => PullRequestState::Closed,
+                    },
+                    author: opened.then(|| event.user.username.clone()),
+                    actor_username: Some(event.user.username),
+                }))
+            },
+            _ => Ok(ForgeEvent::Other(name.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Forge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A webhook event, in the same shape whichever forge sent it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForgeEvent {
+    Push(Push),
+    PullRequest(PullRequest),
+    /// An event Worknest does not act on, such as `ping`, by name
+    Other(String),
+}
+
+/// Commits pushed to a branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct Push {
+    /// Repository as the forge names it, such as `acme/worknest`
+    pub repository: String,
+    /// `None` when a tag was pushed
+    pub branch: Option<String>,
+    pub default_branch: Option<String>,
+    pub commits: Vec<Commit>,
+}
+
+impl Push {
+    /// Whether the push went to the repository's default branch, taking a
+    /// branch push as one when the forge does not say which is the default
+    pub fn on_default_branch(&self) -> bool {
+        match (&self.branch, &self.default_branch) {
+            (Some(branch), Some(default_branch)) => branch == default_branch,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// A pushed commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+    pub url: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+}
+
+/// A pull request, or a GitLab merge request, as of the event
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequest {
+    pub repository: String,
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    /// Branch the changes come from
+    pub branch: String,
+    pub state: PullRequestState,
+    /// Username of whoever opened it, when the event says
+    pub author: Option<String>,
+    /// Username of whoever caused the event, such as by merging
+    pub actor_username: Option<String>,
+}
+
+/// Check a hex HMAC-SHA256 signature of a body in constant time
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(signature) = decode_hex(signature) else {
+        return false;
+    };
+
+    // HMAC takes keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC key");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Branch of a `refs/heads/...` ref
+fn branch_name(git_ref: &str) -> Option<String> {
+    git_ref.strip_prefix("refs/heads/").map(str::to_string)
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+// GitHub and Gitea payloads
+
+#[derive(Deserialize)]
+struct HubPush {
+    #[serde(rename = "ref")]
+    git_ref: String,
+    repository: HubRepository,
+    #[serde(default)]
+    commits: Vec<PushedCommit>,
+}
+
+#[derive(Deserialize)]
+struct HubRepository {
+    full_name: String,
+    #[serde(default)]
+    default_branch: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HubPullRequestEvent {
+    pull_request: HubPullRequest,
+    repository: HubRepository,
+    #[serde(default)]
+    sender: Option<HubUser>,
+}
+
+#[derive(Deserialize)]
+struct HubPullRequest {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    head: HubBranch,
+    user: HubUser,
+    #[serde(default)]
+    merged_by: Option<HubUser>,
+}
+
+#[derive(Deserialize)]
+struct HubBranch {
+    #[serde(rename = "ref")]
+    git_ref: String,
+}
+
+#[derive(Deserialize)]
+struct HubUser {
+    login: String,
+}
+
+// GitLab payloads
+
+#[derive(Deserialize)]
+struct LabPush {
+    #[serde(rename = "ref")]
+    git_ref: String,
+    project: LabProject,
+    #[serde(default)]
+    commits: Vec<PushedCommit>,
+}
+
+#[derive(Deserialize)]
+struct LabProject {
+    path_with_namespace: String,
+    #[serde(default)]
+    default_branch: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LabMergeRequestEvent {
+    user: LabUser,
+    project: LabProject,
+    object_attributes: LabMergeRequest,
+}
+
+#[derive(Deserialize)]
+struct LabUser {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct LabMergeRequest {
+    iid: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    url: String,
+    state: String,
+    source_branch: String,
+    #[serde(default)]
+    action: Option<String>,
+}
+
+// Commits have the same shape on every forge
+
+#[derive(Deserialize)]
+struct PushedCommit {
+    id: String,
+    message: String,
+    url: String,
+    author: CommitAuthor,
+}
+
+#[derive(Deserialize)]
+struct CommitAuthor {
+    name: String,
+    #[serde(default)]
+    email: Option<String>,
+}
+
+impl From<PushedCommit> for Commit {
+    fn from(commit: PushedCommit) -> Self {
+        Self {
+            sha: commit.id,
+            message: commit.message,
+            url: commit.url,
+            author_name: commit.author.name,
+            author_email: non_empty(commit.author.email),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhooks::sign;
+    use serde_json::json;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_detect() {
+        let gitea = headers(&[("X-Gitea-Event", "push"), ("X-GitHub-Event", "push")]);
+        assert_eq!(Forge::detect(&gitea), Some(Forge::Gitea));
+        let github = headers(&[("X-GitHub-Event", "push")]);
+        assert_eq!(Forge::detect(&github), Some(Forge::GitHub));
+        let gitlab = headers(&[("X-Gitlab-Event", "Push Hook")]);
+        assert_eq!(Forge::detect(&gitlab), Some(Forge::GitLab));
+        assert_eq!(Forge::detect(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_verify() {
+        let body = br#"{"zen":"Keep it logically awesome."}"#;
+        let signature = sign("s3cret", std::str::from_utf8(body).unwrap());
+
+        let github = headers(&[("X-Hub-Signature-256", &signature)]);
+        assert!(Forge::GitHub.verify(&github, body, "s3cret"));
+        assert!(!Forge::GitHub.verify(&github, body, "other"));
+        assert!(!Forge::GitHub.verify(&github, b"{}", "s3cret"));
+        assert!(!Forge::GitHub.verify(&HeaderMap::new(), body, "s3cret"));
+
+        let hex = signature.strip_prefix("sha256=").unwrap();
+        let gitea = headers(&[("X-Gitea-Signature", hex)]);
+        assert!(Forge::Gitea.verify(&gitea, body, "s3cret"));
+        let gitea = headers(&[("X-Gitea-Signature", "zz")]);
+        assert!(!Forge::Gitea.verify(&gitea, body, "s3cret"));
+
+        let gitlab = headers(&[("X-Gitlab-Token", "s3cret")]);
+        assert!(Forge::GitLab.verify(&gitlab, body, "s3cret"));
+        assert!(!Forge::GitLab.verify(&gitlab, body, "s3cre"));
+    }
+
+    #[test]
+    fn test_parse_github_push() {
+        let body = json!({
+            "ref": "refs/heads/feature/WN-12-login-crash",
+            "repository": { "full_name": "acme/worknest", "default_branch": "main" },
+            "commits": [{
+                "id": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+                "message": "[WN-12] Fix login crash",
+                "url": "https://github.com/acme/worknest/commit/9fceb02",
+                "author": { "name": "Mona", "email": "mona@example.com", "username": "mona" }
+            }]
+        });
+        let event = Forge::GitHub
+            .parse(
+                &headers(&[("X-GitHub-Event", "push")]),
+                body.to_string().as_bytes(),
+            )
+            .unwrap();
+
+        let ForgeEvent::Push(push) = event else {
+            panic!("expected a push, got {:?}", event);
+        };
+        assert_eq!(push.repository, "acme/worknest");
+        assert_eq!(push.branch.as_deref(), Some("feature/WN-12-login-crash"));
+        assert!(!push.on_default_branch());
+        assert_eq!(
+            push.commits[0].author_email.as_deref(),
+            Some("mona@example.com")
+        );
+    }
+
+    #[test]
+    fn test_parse_github_pull_request() {
+        let body = json!({
+            "action": "closed",
+            "pull_request": {
+                "number": 42,
+                "title": "Fix login crash",
+                "body": "Fixes WN-12",
+                "html_url": "https://github.com/acme/worknest/pull/42",
+                "state": "closed",
+                "merged": true,
+                "head": { "ref": "feature/WN-12-login-crash" },
+                "user": { "login": "mona" },
+                "merged_by": { "login": "hubot", "email": null }
+            },
+            "repository": { "full_name": "acme/worknest" },
+            "sender": { "login": "hubot" }
+        });
+        let event = Forge::GitHub
+            .parse(
+                &headers(&[("X-GitHub-Event", "pull_request")]),
+                body.to_string().as_bytes(),
+            )
+            .unwrap();
+
+        let ForgeEvent::PullRequest(pr) = event else {
+            panic!("expected a pull request, got {:?}", event);
+        };
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.state, PullRequestState::Merged);
+        assert_eq!(pr.author.as_deref(), Some("mona"));
+        assert_eq!(pr.actor_username.as_deref(), Some("hubot"));
+    }
+
+    #[test]
+    fn test_parse_gitlab_events() {
+        let push = json!({
+            "ref": "refs/heads/main",
+            "project": { "path_with_namespace": "acme/worknest", "default_branch": "main" },
+            "commits": [{
+                "id": "da1560886d4f094c3e6c9ef40349f7d38b5d27d7",
+                "message": "Fixes WN-12",
+                "url": "https://gitlab.com/acme/worknest/-/commit/da15608",
+                "author": { "name": "Jordi", "email": "jordi@example.com" }
+            }]
+        });
+        let event = Forge::GitLab
+            .parse(
+                &headers(&[("X-Gitlab-Event", "Push Hook")]),
+                push.to_string().as_bytes(),
+            )
+            .unwrap();
+        let ForgeEvent::Push(push) = event else {
+            panic!("expected a push, got {:?}", event);
+        };
+        assert!(push.on_default_branch());
+        assert_eq!(push.commits.len(), 1);
+
+        let merge_request = json!({
+            "user": { "username": "jordi", "email": "jordi@example.com" },
+            "project": { "path_with_namespace": "acme/worknest" },
+            "object_attributes": {
+                "iid": 7,
+                "title": "WN-12 Fix login crash",
+                "description": null,
+                "url": "https://gitlab.com/acme/worknest/-/merge_requests/7",
+                "state": "opened",
+                "source_branch": "fix/WN-12-login-crash",
+                "action": "open"
+            }
+        });
+        let event = Forge::GitLab
+            .parse(
+                &headers(&[("X-Gitlab-Event", "Merge Request Hook")]),
+                merge_request.to_string().as_bytes(),
+            )
+            .unwrap();
+        let ForgeEvent::PullRequest(mr) = event else {
+            panic!("expected a merge request, got {:?}", event);
+        };
+        assert_eq!(mr.number, 7);
+        assert_eq!(mr.state, PullRequestState::Open);
+        assert_eq!(mr.author.as_deref(), Some("jordi"));
+        assert_eq!(mr.body, "");
+    }
+
+    #[test]
+    fn test_parse_other_events() {
+        let event = Forge::GitHub
+            .parse(&headers(&[("X-GitHub-Event", "ping")]), b"{}")
+            .unwrap();
+        assert_eq!(event, ForgeEvent::Other("ping".to_string()));
+
+        // Events Worknest acts on must have the fields it needs
+        assert!(Forge::GitHub
+            .parse(&headers(&[("X-GitHub-Event", "push")]), b"{}")
+            .is_err());
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! Online-first API server for web and optionally desktop clients.
 
+mod forge;
+mod mailer;
+mod webhooks;
+
 use std::fs;
 use std::net::SocketAddr;
 use std::path::PathBuf;
@@ -10,24 +14,49 @@ use std::sync::Arc;
 use axum::{
     body::Bytes,
     extract::{Multipart, Path, Request, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, Method, StatusCode},
     middleware::{self, Next},
-    response::{IntoResponse, Response},
-    routing::{get, post, put},
+    response::{
+        sse::{Event, Sse},
+        IntoResponse, Response,
+    },
+    routing::{delete, get, post, put},
     Json, Router,
 };
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Notify};
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use worknest_auth::AuthService;
+use forge::{Forge, ForgeEvent};
+use mailer::{Email, Mailer};
+use webhooks::WebhookClient;
+
+use worknest_auth::{
+    AuthError, AuthService, Claims, PermissionChecker, SessionTokens, PERSONAL_ACCESS_TOKEN_PREFIX,
+};
+use worknest_core::git;
 use worknest_core::models::{
-    Attachment, AttachmentId, Comment, CommentId, Priority, Project, ProjectId, Ticket, TicketId,
-    TicketStatus, TicketType, User,
+    AccessTokenId, Activity, ActivityAction, Attachment, AttachmentId, ChangeEvent, ChangeResource,
+    CodeLink, CodeLinkKind, Comment, CommentId, CustomField, CustomFieldId, CustomFieldType,
+    CustomFieldValue, DeliveryStatus, FieldValue, Label, LabelId, Notification, NotificationId,
+    NotificationKind, NotificationPreferences, PersonalAccessToken, Priority, Project, ProjectId,
+    ProjectMember, PullRequestState, RoleId, SavedView, SavedViewId, Session, SessionId, Sprint,
+    SprintId, SprintState, StatusCategory, Team, TeamId, TeamMember, Ticket, TicketDependency,
+    TicketId, TicketKey, TicketRollup, TicketType, Timesheet, TokenScope, User, UserId,
+    ViewDisplay, Webhook, WebhookDelivery, WebhookDeliveryId, WebhookEvent, WebhookId, Workflow,
+    WorkflowStatus, WorkflowTransition, Worklog, WorklogId,
 };
+use worknest_core::query::QueryError;
 use worknest_db::{
-    init_pool, run_migrations, AttachmentRepository, CommentRepository, DbError, DbPool,
-    ProjectRepository, Repository, TicketRepository, UserRepository,
+    init_pool, run_migrations, AccessTokenRepository, ActivityFilter, ActivityRepository,
+    AttachmentRepository, CodeLinkRepository, CommentRepository, CustomFieldRepository, DbError,
+    DbPool, DependencyRepository, LabelRepository, NotificationPreferenceRepository,
+    NotificationRepository, PermissionRepository, ProjectRepository, Repository, RoleRepository,
+    SavedViewRepository, SessionRepository, SprintRepository, TeamRepository, TicketCursor,
+    TicketQuery, TicketRepository, TicketSort, TicketSortField, UserRepository, WatcherRepository,
+    WebhookDeliveryRepository, WebhookRepository, WorkflowRepository, WorklogRepository,
 };
 
 /// Shared application state
@@ -36,19 +65,51 @@ struct AppState {
     #[allow(dead_code)]
     pool: Arc<DbPool>,
     auth_service: Arc<AuthService>,
-    #[allow(dead_code)]
+    permission_checker: Arc<PermissionChecker>,
     user_repo: Arc<UserRepository>,
     project_repo: Arc<ProjectRepository>,
+    role_repo: Arc<RoleRepository>,
+    team_repo: Arc<TeamRepository>,
     ticket_repo: Arc<TicketRepository>,
     comment_repo: Arc<CommentRepository>,
+    dependency_repo: Arc<DependencyRepository>,
     attachment_repo: Arc<AttachmentRepository>,
+    activity_repo: Arc<ActivityRepository>,
+    label_repo: Arc<LabelRepository>,
+    custom_field_repo: Arc<CustomFieldRepository>,
+    saved_view_repo: Arc<SavedViewRepository>,
+    workflow_repo: Arc<WorkflowRepository>,
+    sprint_repo: Arc<SprintRepository>,
+    worklog_repo: Arc<WorklogRepository>,
+    watcher_repo: Arc<WatcherRepository>,
+    notification_repo: Arc<NotificationRepository>,
+    notification_preference_repo: Arc<NotificationPreferenceRepository>,
+    /// Transport for notification emails; `None` when email is not configured
+    mailer: Option<Arc<dyn Mailer>>,
+    /// Public URL of the web app, linked from emails
+    app_url: Option<String>,
+    webhook_repo: Arc<WebhookRepository>,
+    webhook_delivery_repo: Arc<WebhookDeliveryRepository>,
+    webhook_client: WebhookClient,
+    /// Wakes the webhook worker when deliveries are queued
+    webhook_wakeup: Arc<Notify>,
+    code_link_repo: Arc<CodeLinkRepository>,
+    /// Secret shared with Git forges; `None` turns the Git integration off
+    git_webhook_secret: Option<String>,
+    /// Username of the account that resolves tickets closed from Git;
+    /// `None` only links commits and pull requests
+    git_user: Option<String>,
+    /// Changes pushed to clients subscribed to `/api/events`
+    changes: broadcast::Sender<ChangeEvent>,
 }
 
 // ============================================================================
 // Authentication Middleware & Extractor
 // ============================================================================
 
-/// Middleware to verify JWT token and attach authenticated user to request
+/// Middleware to verify the bearer token and attach authenticated user to request
+///
+/// Accepts JWT access tokens and personal access tokens (`wn_pat_...`).
 async fn auth_middleware(
     State(state): State<AppState>,
     mut request: Request,
@@ -58,26 +119,156 @@ async fn auth_middleware(
     let auth_header = request
         .headers()
         .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .ok_or_else(|| AppError::Unauthorized("Missing Authorization header".to_string()))?;
+        .and_then(|h| h.to_str().ok());
+
+    let token = match auth_header {
+        // Extract token from "Bearer <token>"
+        Some(auth_header) => auth_header.strip_prefix("Bearer ").ok_or_else(|| {
+            AppError::Unauthorized("Invalid Authorization header format".to_string())
+        })?,
+        None => {
+            return Err(AppError::Unauthorized(
+                "Missing Authorization header".to_string(),
+            ))
+        },
+    }
+    .to_string();
+
+    // Personal access tokens are limited to the routes their scopes cover
+    if token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX) {
+        let (user, pat) = state
+            .auth_service
+            .authenticate_personal_access_token(&token)
+            .map_err(|e| {
+                tracing::warn!("Access token verification failed: {:?}", e);
+                AppError::Unauthorized("Invalid or expired access token".to_string())
+            })?;
 
-    // Extract token from "Bearer <token>"
-    let token = auth_header
-        .strip_prefix("Bearer ")
-        .ok_or_else(|| AppError::Unauthorized("Invalid Authorization header format".to_string()))?;
+        authorize_token_scope(&pat, request.method(), request.uri().path())?;
 
-    // Verify token and get user
-    let user = state.auth_service.get_user_from_token(token).map_err(|e| {
+        request.extensions_mut().insert(user);
+
+        return Ok(next.run(request).await);
+    }
+
+    // Verify token and its session, and get user
+    let (user, claims) = state.auth_service.authenticate(&token).map_err(|e| {
         tracing::warn!("Token verification failed: {:?}", e);
-        AppError::Unauthorized("Invalid or expired token".to_string())
+        match e {
+            AuthError::SessionRevoked => {
+                AppError::Unauthorized("Session has been revoked".to_string())
+            },
+            _ => AppError::Unauthorized("Invalid or expired token".to_string()),
+        }
     })?;
 
-    // Attach user to request extensions for handlers to use
+    // Attach user and token claims to request extensions for handlers to use
     request.extensions_mut().insert(user);
+    request.extensions_mut().insert(claims);
 
     Ok(next.run(request).await)
 }
 
+/// What a personal access token needs to call a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenAccess {
+    /// Every token may call the route
+    Open,
+    /// Tokens holding the scope may call the route
+    Scope(TokenScope),
+    /// No token may call the route
+    Denied,
+}
+
+/// First segments after `/api/` of the routes that need a `tickets` scope
+const TICKET_SCOPE_ROUTES: &[&str] = &[
+    "tickets",
+    "comments",
+    "attachments",
+    "views",
+    "sprints",
+    "worklogs",
+    "timesheet",
+];
+
+/// First segments after `/api/` of the routes that need a `projects` scope
+const PROJECT_SCOPE_ROUTES: &[&str] = &["projects", "labels", "fields", "webhooks", "teams"];
+
+/// First segments after `/api/` of the routes closed to tokens: sessions,
+/// tokens, account settings and the user's own activity, live updates and
+/// notifications
+const TOKEN_DENIED_ROUTES: &[&str] = &[
+    "auth",
+    "tokens",
+    "users",
+    "activity",
+    "events",
+    "search",
+    "notifications",
+];
+
+/// What a personal access token needs to call a route, matched on whole
+/// path segments; `None` for paths outside the tables above
+///
+/// Ticket, comment, attachment, saved view, sprint, worklog and timesheet
+/// routes need a `tickets` scope; project, label, custom field, webhook and
+/// team routes a `projects` scope. `GET` needs `read`, anything else
+/// `write`. Listing users and reading the current user is open to every
+/// token.
+fn token_access(method: &Method, path: &str) -> Option<TokenAccess> {
+    let read = method == Method::GET;
+    let segments: Vec<&str> = path.strip_prefix("/api/")?.split('/').collect();
+    let first = segments[0];
+
+    if TICKET_SCOPE_ROUTES.contains(&first) {
+        Some(TokenAccess::Scope(if read {
+            TokenScope::TicketsRead
+        } else {
+            TokenScope::TicketsWrite
+        }))
+    } else if PROJECT_SCOPE_ROUTES.contains(&first) {
+        Some(TokenAccess::Scope(if read {
+            TokenScope::ProjectsRead
+        } else {
+            TokenScope::ProjectsWrite
+        }))
+    } else if read && (segments == ["users"] || segments == ["users", "me"]) {
+        Some(TokenAccess::Open)
+    } else if TOKEN_DENIED_ROUTES.contains(&first) {
+        Some(TokenAccess::Denied)
+    } else {
+        None
+    }
+}
+
+/// Check that a personal access token may call a route
+///
+/// See [`token_access`]; routes it does not know are off limits.
+fn authorize_token_scope(
+    pat: &PersonalAccessToken,
+    method: &Method,
+    path: &str,
+) -> Result<(), AppError> {
+    let required = match token_access(method, path) {
+        Some(TokenAccess::Open) => return Ok(()),
+        Some(TokenAccess::Scope(scope)) => scope,
+        Some(TokenAccess::Denied) | None => {
+            return Err(AppError::Forbidden(
+                "This endpoint is not available to personal access tokens".to_string(),
+            ))
+        },
+    };
+
+    if pat.allows(required) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "Access token lacks the {} scope",
+            required
+        )))
+    }
+}
+
 /// Extractor for authenticated user
 struct AuthUser(User);
 
@@ -97,6 +288,196 @@ impl axum::extract::FromRequestParts<AppState> for AuthUser {
     }
 }
 
+/// Extractor for the claims of the token that authenticated the request
+struct AuthClaims(Claims);
+
+impl axum::extract::FromRequestParts<AppState> for AuthClaims {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Claims>()
+            .cloned()
+            .map(AuthClaims)
+            .ok_or_else(|| AppError::Unauthorized("User not authenticated".to_string()))
+    }
+}
+
+// ============================================================================
+// Authorization Helpers
+// ============================================================================
+
+/// Check whether the user holds a permission, globally or on a project
+fn can(
+    state: &AppState,
+    user: &User,
+    project_id: Option<ProjectId>,
+    resource: &str,
+    action: &str,
+) -> Result<bool, AppError> {
+    state
+        .permission_checker
+        .has_permission(user.id, project_id, resource, action)
+        .map_err(|e| {
+            tracing::error!("Permission check failed: {:?}", e);
+            AppError::Internal("Failed to check permissions".to_string())
+        })
+}
+
+/// Require a permission, rejecting the request with 403 when it is missing
+fn authorize(
+    state: &AppState,
+    user: &User,
+    project_id: Option<ProjectId>,
+    resource: &str,
+    action: &str,
+) -> Result<(), AppError> {
+    state
+        .permission_checker
+        .require(user.id, project_id, resource, action)
+        .map_err(|e| match e {
+            AuthError::Forbidden(msg) => {
+                tracing::warn!("Permission denied for {}: {}", user.username, msg);
+                AppError::Forbidden(msg)
+            },
+            _ => {
+                tracing::error!("Permission check failed: {:?}", e);
+                AppError::Internal("Failed to check permissions".to_string())
+            },
+        })
+}
+
+/// Require that the user owns a resource or administers its project
+fn authorize_owner_or_admin(
+    state: &AppState,
+    user: &User,
+    owner_id: UserId,
+    project_id: ProjectId,
+) -> Result<(), AppError> {
+    if user.id == owner_id {
+        return Ok(());
+    }
+
+    let is_admin = state
+        .permission_checker
+        .is_admin(user.id, Some(project_id))
+        .map_err(|e| {
+            tracing::error!("Permission check failed: {:?}", e);
+            AppError::Internal("Failed to check permissions".to_string())
+        })?;
+
+    if is_admin {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Only the author or a project admin can do this".to_string(),
+        ))
+    }
+}
+
+/// Load a ticket, returning 404 when it does not exist
+fn load_ticket(state: &AppState, ticket_id: TicketId) -> Result<Ticket, AppError> {
+    state
+        .ticket_repo
+        .find_by_id(ticket_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get ticket: {:?}", e);
+            AppError::Internal("Failed to retrieve ticket".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Ticket not found".to_string()))
+}
+
+/// Load a ticket by key, returning 404 when no ticket has it
+///
+/// Keys made with a project's former key still resolve.
+fn load_ticket_by_key(state: &AppState, key: &TicketKey) -> Result<Ticket, AppError> {
+    find_ticket_by_key(state, key)?
+        .ok_or_else(|| AppError::NotFound("Ticket not found".to_string()))
+}
+
+/// Find a ticket by key, including keys made with a project's former key
+fn find_ticket_by_key(state: &AppState, key: &TicketKey) -> Result<Option<Ticket>, AppError> {
+    let project = state
+        .project_repo
+        .find_by_key(&key.project_key)
+        .map_err(|e| {
+            tracing::error!("Failed to get project by key: {:?}", e);
+            AppError::Internal("Failed to retrieve ticket".to_string())
+        })?;
+    let Some(project) = project else {
+        return Ok(None);
+    };
+
+    state
+        .ticket_repo
+        .find_by_number(project.id, key.number)
+        .map_err(|e| {
+            tracing::error!("Failed to get ticket: {:?}", e);
+            AppError::Internal("Failed to retrieve ticket".to_string())
+        })
+}
+
+/// Record a mutation in the activity log
+///
+/// `before` and `after` are snapshots of the resource; their field-level diff
+/// is stored as the entry's details. Failures are logged rather than returned
+/// since the mutation itself has already been applied.
+fn record_activity<T: Serialize>(
+    state: &AppState,
+    user: &User,
+    action: ActivityAction,
+    resource_type: &str,
+    resource_id: String,
+    before: Option<&T>,
+    after: Option<&T>,
+) {
+    let details = Activity::diff(before, after);
+    let activity = Activity::new(user.id, action, resource_type, resource_id, Some(details));
+
+    if let Err(e) = state.activity_repo.record(&activity) {
+        tracing::error!("Failed to record activity: {:?}", e);
+    }
+}
+
+/// Push a change to the clients subscribed to `/api/events` and queue it for
+/// the project's webhooks
+///
+/// `data` is the resource as the REST API returns it, `None` for deletions.
+fn publish_change<T: Serialize>(
+    state: &AppState,
+    user: &User,
+    project_id: ProjectId,
+    resource: ChangeResource,
+    action: ActivityAction,
+    resource_id: String,
+    data: Option<&T>,
+) {
+    let data = match data.map(serde_json::to_value).transpose() {
+        Ok(data) => data,
+        Err(e) => {
+            tracing::error!("Failed to serialize change: {:?}", e);
+            return;
+        },
+    };
+
+    let change = ChangeEvent {
+        project_id,
+        resource,
+        resource_id,
+        action,
+        actor_id: user.id,
+        data,
+    };
+    queue_webhooks(state, &change);
+
+    // Sending only fails when no client is subscribed
+    let _ = state.changes.send(change);
+}
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing
@@ -117,6 +498,15 @@ async fn main() {
     run_migrations(&mut pool.get().expect("Failed to get connection"))
         .expect("Failed to run migrations");
 
+    // `worknest-api rebuild-search-index` repairs the search index and exits
+    if std::env::args().nth(1).as_deref() == Some("rebuild-search-index") {
+        let indexed = TicketRepository::new(Arc::clone(&pool))
+            .rebuild_search_index()
+            .expect("Failed to rebuild search index");
+        tracing::info!("Rebuilt search index for {} tickets", indexed);
+        return;
+    }
+
     // Initialize services
     let secret_key = std::env::var("WORKNEST_SECRET_KEY").unwrap_or_else(|_| {
         tracing::warn!("Using default secret key - set WORKNEST_SECRET_KEY in production!");
@@ -127,35 +517,142 @@ async fn main() {
     let project_repo = Arc::new(ProjectRepository::new(Arc::clone(&pool)));
     let ticket_repo = Arc::new(TicketRepository::new(Arc::clone(&pool)));
     let comment_repo = Arc::new(CommentRepository::new(Arc::clone(&pool)));
+    let dependency_repo = Arc::new(DependencyRepository::new(Arc::clone(&pool)));
     let attachment_repo = Arc::new(AttachmentRepository::new(Arc::clone(&pool)));
+    let activity_repo = Arc::new(ActivityRepository::new(Arc::clone(&pool)));
+    let label_repo = Arc::new(LabelRepository::new(Arc::clone(&pool)));
+    let custom_field_repo = Arc::new(CustomFieldRepository::new(Arc::clone(&pool)));
+    let saved_view_repo = Arc::new(SavedViewRepository::new(Arc::clone(&pool)));
+    let workflow_repo = Arc::new(WorkflowRepository::new(Arc::clone(&pool)));
+    let sprint_repo = Arc::new(SprintRepository::new(Arc::clone(&pool)));
+    let worklog_repo = Arc::new(WorklogRepository::new(Arc::clone(&pool)));
+    let watcher_repo = Arc::new(WatcherRepository::new(Arc::clone(&pool)));
+    let notification_repo = Arc::new(NotificationRepository::new(Arc::clone(&pool)));
+    let notification_preference_repo =
+        Arc::new(NotificationPreferenceRepository::new(Arc::clone(&pool)));
+    let mailer = mailer::mailer_from_env().expect("Failed to configure email");
+    let webhook_client = WebhookClient::new().expect("Failed to build webhook client");
+    if mailer.is_none() {
+        tracing::info!("No mail transport configured - notification emails are disabled");
+    }
+    let app_url = std::env::var("WORKNEST_APP_URL").ok();
+    let webhook_repo = Arc::new(WebhookRepository::new(Arc::clone(&pool)));
+    let webhook_delivery_repo = Arc::new(WebhookDeliveryRepository::new(Arc::clone(&pool)));
+    let code_link_repo = Arc::new(CodeLinkRepository::new(Arc::clone(&pool)));
+    let git_webhook_secret = std::env::var("WORKNEST_GIT_WEBHOOK_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty());
+    if git_webhook_secret.is_none() {
+        tracing::info!("No Git webhook secret configured - the Git integration is disabled");
+    }
+    let git_user = std::env::var("WORKNEST_GIT_USER")
+        .ok()
+        .filter(|username| !username.is_empty());
+    if git_webhook_secret.is_some() && git_user.is_none() {
+        tracing::info!("No Git user configured - tickets are not resolved from Git");
+    }
+    let (changes, _) = broadcast::channel(CHANGE_BUFFER_SIZE);
+    let role_repo = Arc::new(RoleRepository::new(Arc::clone(&pool)));
+    let team_repo = Arc::new(TeamRepository::new(Arc::clone(&pool)));
+    let permission_repo = Arc::new(PermissionRepository::new(Arc::clone(&pool)));
+    let session_repo = Arc::new(SessionRepository::new(Arc::clone(&pool)));
+    let access_token_repo = Arc::new(AccessTokenRepository::new(Arc::clone(&pool)));
+    let permission_checker = Arc::new(PermissionChecker::new(
+        Arc::clone(&role_repo),
+        permission_repo,
+    ));
     let auth_service = Arc::new(AuthService::new(
         Arc::clone(&user_repo),
+        session_repo,
+        access_token_repo,
         secret_key,
-        Some(24), // 24 hour token expiration
+        Some(15), // 15 minute access tokens
+        Some(30), // 30 day refresh tokens
     ));
 
     let state = AppState {
         pool,
         auth_service,
+        permission_checker,
         user_repo,
         project_repo,
+        role_repo,
+        team_repo,
         ticket_repo,
         comment_repo,
+        dependency_repo,
         attachment_repo,
+        activity_repo,
+        label_repo,
+        custom_field_repo,
+        saved_view_repo,
+        workflow_repo,
+        sprint_repo,
+        worklog_repo,
+        watcher_repo,
+        notification_repo,
+        notification_preference_repo,
+        mailer,
+        app_url,
+        webhook_repo,
+        webhook_delivery_repo,
+        webhook_client,
+        webhook_wakeup: Arc::new(Notify::new()),
+        code_link_repo,
+        git_webhook_secret,
+        git_user,
+        changes,
     };
 
+    // Daily digests are only worth scheduling when they can be sent
+    if state.mailer.is_some() {
+        tokio::spawn(run_digest_scheduler(state.clone()));
+    }
+    tokio::spawn(run_webhook_worker(state.clone()));
+
     // Build router
     // Public routes (no auth required)
     let public_routes = Router::new()
         .route("/health", get(health_check))
         .route("/api/auth/register", post(register))
-        .route("/api/auth/login", post(login));
+        .route("/api/auth/login", post(login))
+        .route("/api/auth/refresh", post(refresh))
+        // EventSource cannot send headers, so the change stream takes a
+        // stream ticket from the query string instead
+        .route("/api/events", get(stream_changes))
+        // Git forges prove themselves with the shared secret instead
+        .route("/api/integrations/git/webhook", post(receive_git_webhook));
 
     // Protected routes (auth required)
     let protected_routes = Router::new()
+        // Sessions
+        .route("/api/auth/logout", post(logout))
+        .route("/api/auth/logout-all", post(logout_all))
+        .route("/api/auth/sessions", get(list_sessions))
+        .route("/api/auth/sessions/{id}", delete(revoke_session))
+        // Personal access tokens
+        .route(
+            "/api/tokens",
+            get(list_access_tokens).post(create_access_token),
+        )
+        .route("/api/tokens/{id}", delete(revoke_access_token))
+        // Activity
+        .route("/api/activity", get(list_activity))
+        // Live updates
+        .route("/api/events/ticket", post(create_stream_ticket))
+        // Search administration
+        .route("/api/search/rebuild", post(rebuild_search_index))
         // Users
         .route("/api/users", get(list_users))
-        .route("/api/users/me", get(get_current_user))
+        .route(
+            "/api/users/me/notification-preferences",
+            get(get_notification_preferences).put(update_notification_preferences),
+        )
+        .route(
+            "/api/users/me",
+            get(get_current_user).put(update_current_user),
+        )
+        .route("/api/users/me/password", post(change_password))
         // Projects
         .route("/api/projects", get(list_projects).post(create_project))
         .route(
@@ -163,6 +660,56 @@ async fn main() {
             get(get_project).put(update_project).delete(delete_project),
         )
         .route("/api/projects/{id}/archive", post(archive_project))
+        .route(
+            "/api/projects/{id}/members",
+            get(list_project_members).post(add_project_member),
+        )
+        .route(
+            "/api/projects/{id}/members/{user_id}",
+            put(update_project_member).delete(remove_project_member),
+        )
+        .route(
+            "/api/projects/{id}/teams",
+            get(list_project_teams).post(attach_project_team),
+        )
+        .route(
+            "/api/projects/{id}/teams/{team_id}",
+            delete(detach_project_team),
+        )
+        .route(
+            "/api/projects/{id}/labels",
+            get(list_project_labels).post(create_label),
+        )
+        .route(
+            "/api/projects/{id}/fields",
+            get(list_project_fields).post(create_custom_field),
+        )
+        .route(
+            "/api/projects/{id}/workflow",
+            get(get_project_workflow).put(update_project_workflow),
+        )
+        .route(
+            "/api/projects/{id}/sprints",
+            get(list_project_sprints).post(create_sprint),
+        )
+        .route(
+            "/api/projects/{id}/webhooks",
+            get(list_project_webhooks).post(create_webhook),
+        )
+        // Teams
+        .route("/api/teams", get(list_teams).post(create_team))
+        .route(
+            "/api/teams/{id}",
+            get(get_team).put(update_team).delete(delete_team),
+        )
+        .route(
+            "/api/teams/{id}/members",
+            get(list_team_members).post(add_team_member),
+        )
+        .route(
+            "/api/teams/{id}/members/{user_id}",
+            put(update_team_member).delete(remove_team_member),
+        )
         // Tickets
         .route("/api/tickets", get(list_tickets).post(create_ticket))
         .route("/api/tickets/search", get(search_tickets))
@@ -170,6 +717,49 @@ async fn main() {
             "/api/tickets/{id}",
             get(get_ticket).put(update_ticket).delete(delete_ticket),
         )
+        .route("/api/tickets/{id}/history", get(get_ticket_history))
+        .route("/api/tickets/{id}/children", get(list_ticket_children))
+        .route(
+            "/api/tickets/{id}/dependencies",
+            get(list_dependencies).post(add_dependency),
+        )
+        .route(
+            "/api/tickets/{id}/dependencies/{depends_on_id}",
+            delete(remove_dependency),
+        )
+        .route("/api/tickets/{id}/labels", put(set_ticket_labels))
+        .route("/api/tickets/{id}/links", get(list_ticket_links))
+        .route("/api/tickets/{id}/watchers", get(list_ticket_watchers))
+        .route(
+            "/api/tickets/{id}/watch",
+            post(watch_ticket).delete(unwatch_ticket),
+        )
+        // Labels
+        .route("/api/labels/{id}", put(update_label).delete(delete_label))
+        .route("/api/labels/{id}/merge", post(merge_label))
+        // Custom fields
+        .route(
+            "/api/fields/{id}",
+            put(update_custom_field).delete(delete_custom_field),
+        )
+        // Sprints
+        .route(
+            "/api/sprints/{id}",
+            get(get_sprint).put(update_sprint).delete(delete_sprint),
+        )
+        .route("/api/sprints/{id}/start", post(start_sprint))
+        .route("/api/sprints/{id}/close", post(close_sprint))
+        .route("/api/sprints/{id}/tickets", post(add_sprint_tickets))
+        .route(
+            "/api/sprints/{id}/tickets/{ticket_id}",
+            delete(remove_sprint_ticket),
+        )
+        // Saved views
+        .route("/api/views", get(list_views).post(create_view))
+        .route(
+            "/api/views/{id}",
+            get(get_view).put(update_view).delete(delete_view),
+        )
         // Comments
         .route(
             "/api/tickets/{ticket_id}/comments",
@@ -179,6 +769,37 @@ async fn main() {
             "/api/comments/{id}",
             put(update_comment).delete(delete_comment),
         )
+        // Worklogs
+        .route(
+            "/api/tickets/{ticket_id}/worklogs",
+            get(list_worklogs_for_ticket).post(create_worklog),
+        )
+        .route(
+            "/api/worklogs/{id}",
+            put(update_worklog).delete(delete_worklog),
+        )
+        .route("/api/timesheet", get(get_timesheet))
+        // Notifications
+        .route("/api/notifications", get(list_notifications))
+        .route(
+            "/api/notifications/unread-count",
+            get(get_unread_notification_count),
+        )
+        .route(
+            "/api/notifications/read-all",
+            post(mark_all_notifications_read),
+        )
+        .route("/api/notifications/{id}/read", post(mark_notification_read))
+        // Webhooks
+        .route(
+            "/api/webhooks/{id}",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route("/api/webhooks/{id}/deliveries", get(list_webhook_deliveries))
+        .route(
+            "/api/webhooks/{id}/deliveries/{delivery_id}/redeliver",
+            post(redeliver_webhook_delivery),
+        )
         // Attachments
         .route(
             "/api/tickets/{ticket_id}/attachments",
@@ -238,7 +859,27 @@ struct RegisterRequest {
 #[derive(Debug, Serialize)]
 struct AuthResponse {
     user: UserDto,
+    #[serde(flatten)]
+    tokens: TokenResponse,
+}
+
+#[derive(Debug, Serialize)]
+struct TokenResponse {
     token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    refresh_token: String,
+    refresh_expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<SessionTokens> for TokenResponse {
+    fn from(tokens: SessionTokens) -> Self {
+        Self {
+            token: tokens.access_token.token,
+            expires_at: tokens.access_token.expires_at,
+            refresh_token: tokens.refresh_token,
+            refresh_expires_at: tokens.refresh_expires_at,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -262,8 +903,16 @@ impl From<User> for UserDto {
     }
 }
 
+/// User agent of the client making a request, recorded on its session
+fn user_agent(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|h| h.to_str().ok())
+}
+
 async fn register(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<RegisterRequest>,
 ) -> Result<Json<AuthResponse>, AppError> {
     tracing::info!("Register request for username: {}", req.username);
@@ -277,10 +926,19 @@ async fn register(
             AppError::BadRequest(format!("Registration failed: {}", e))
         })?;
 
-    // Generate token
-    let token = state
+    // Grant the default global role
+    state
+        .permission_checker
+        .assign_default_roles(user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to assign default roles: {:?}", e);
+            AppError::Internal("Failed to assign default roles".to_string())
+        })?;
+
+    // Generate tokens
+    let tokens = state
         .auth_service
-        .login(&req.username, &req.password)
+        .login(&req.username, &req.password, user_agent(&headers))
         .map_err(|e| {
             tracing::error!("Login after registration failed: {:?}", e);
             AppError::Internal("Failed to generate token".to_string())
@@ -288,7 +946,7 @@ async fn register(
 
     Ok(Json(AuthResponse {
         user: user.into(),
-        token: token.token,
+        tokens: tokens.into(),
     }))
 }
 
@@ -300,14 +958,15 @@ struct LoginRequest {
 
 async fn login(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<LoginRequest>,
 ) -> Result<Json<AuthResponse>, AppError> {
     tracing::info!("Login request for username: {}", req.username);
 
     // Login
-    let token = state
+    let tokens = state
         .auth_service
-        .login(&req.username, &req.password)
+        .login(&req.username, &req.password, user_agent(&headers))
         .map_err(|e| {
             tracing::error!("Login failed: {:?}", e);
             AppError::Unauthorized("Invalid credentials".to_string())
@@ -316,7 +975,7 @@ async fn login(
     // Get user
     let user = state
         .auth_service
-        .get_user_from_token(&token.token)
+        .get_user_from_token(&tokens.access_token.token)
         .map_err(|e| {
             tracing::error!("Failed to get user from token: {:?}", e);
             AppError::Internal("Failed to retrieve user".to_string())
@@ -324,106 +983,421 @@ async fn login(
 
     Ok(Json(AuthResponse {
         user: user.into(),
-        token: token.token,
+        tokens: tokens.into(),
     }))
 }
 
-// ============================================================================
-// User Routes
-// ============================================================================
+#[derive(Debug, Deserialize)]
+struct RefreshRequest {
+    refresh_token: String,
+}
 
-async fn list_users(
-    AuthUser(_user): AuthUser,
+async fn refresh(
     State(state): State<AppState>,
-) -> Result<Json<Vec<UserDto>>, AppError> {
-    let users = state.user_repo.find_all().map_err(|e| {
-        tracing::error!("Failed to list users: {:?}", e);
-        AppError::Internal("Failed to retrieve users".to_string())
-    })?;
-
-    Ok(Json(users.into_iter().map(UserDto::from).collect()))
-}
+    Json(req): Json<RefreshRequest>,
+) -> Result<Json<TokenResponse>, AppError> {
+    let tokens = state
+        .auth_service
+        .refresh_token(&req.refresh_token)
+        .map_err(|e| {
+            tracing::warn!("Token refresh failed: {:?}", e);
+            match e {
+                AuthError::RefreshTokenReused => AppError::Unauthorized(
+                    "Refresh token was already used; the session has been revoked".to_string(),
+                ),
+                AuthError::SessionRevoked => {
+                    AppError::Unauthorized("Session has been revoked".to_string())
+                },
+                AuthError::TokenInvalid | AuthError::TokenExpired | AuthError::UserNotFound => {
+                    AppError::Unauthorized("Invalid or expired refresh token".to_string())
+                },
+                _ => AppError::Internal("Failed to refresh token".to_string()),
+            }
+        })?;
 
-async fn get_current_user(AuthUser(user): AuthUser) -> Result<Json<UserDto>, AppError> {
-    Ok(Json(user.into()))
+    Ok(Json(tokens.into()))
 }
 
-// ============================================================================
-// Project Routes
-// ============================================================================
-
 #[derive(Debug, Serialize)]
-struct ProjectDto {
+struct SessionDto {
     id: String,
-    name: String,
-    description: Option<String>,
-    color: Option<String>,
-    archived: bool,
-    created_by: String,
-    created_at: String,
-    updated_at: String,
+    user_agent: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    current: bool,
 }
 
-impl From<Project> for ProjectDto {
-    fn from(project: Project) -> Self {
+impl SessionDto {
+    fn new(session: Session, current: &Claims) -> Self {
         Self {
-            id: project.id.to_string(),
-            name: project.name,
-            description: project.description,
-            color: project.color,
-            archived: project.archived,
-            created_by: project.created_by.to_string(),
-            created_at: project.created_at.to_rfc3339(),
-            updated_at: project.updated_at.to_rfc3339(),
+            id: session.id.to_string(),
+            current: session.id.to_string() == current.sid,
+            user_agent: session.user_agent,
+            created_at: session.created_at,
+            expires_at: session.expires_at,
         }
     }
 }
 
-async fn list_projects(
-    AuthUser(_user): AuthUser,
+#[derive(Debug, Serialize)]
+struct LogoutAllResponse {
+    revoked: usize,
+}
+
+async fn logout(
+    AuthClaims(claims): AuthClaims,
     State(state): State<AppState>,
-) -> Result<Json<Vec<ProjectDto>>, AppError> {
-    let projects = state.project_repo.find_all().map_err(|e| {
-        tracing::error!("Failed to list projects: {:?}", e);
-        AppError::Internal("Failed to retrieve projects".to_string())
+) -> Result<StatusCode, AppError> {
+    state.auth_service.logout(&claims).map_err(|e| {
+        tracing::error!("Failed to logout: {:?}", e);
+        AppError::Internal("Failed to logout".to_string())
     })?;
 
-    Ok(Json(projects.into_iter().map(ProjectDto::from).collect()))
+    Ok(StatusCode::NO_CONTENT)
 }
 
-async fn get_project(
-    AuthUser(_user): AuthUser,
+async fn logout_all(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> Result<Json<ProjectDto>, AppError> {
-    let project_id = ProjectId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
-
-    let project = state
-        .project_repo
-        .find_by_id(project_id)
-        .map_err(|e| {
-            tracing::error!("Failed to get project: {:?}", e);
-            AppError::Internal("Failed to retrieve project".to_string())
-        })?
-        .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
-
-    Ok(Json(project.into()))
-}
+) -> Result<Json<LogoutAllResponse>, AppError> {
+    let revoked = state.auth_service.logout_all(user.id).map_err(|e| {
+        tracing::error!("Failed to logout everywhere: {:?}", e);
+        AppError::Internal("Failed to revoke sessions".to_string())
+    })?;
 
-#[derive(Debug, Deserialize)]
-struct CreateProjectRequest {
-    name: String,
-    description: Option<String>,
+    Ok(Json(LogoutAllResponse { revoked }))
 }
 
-async fn create_project(
+async fn list_sessions(
     AuthUser(user): AuthUser,
+    AuthClaims(claims): AuthClaims,
     State(state): State<AppState>,
-    Json(req): Json<CreateProjectRequest>,
-) -> Result<Json<ProjectDto>, AppError> {
-    let mut project = Project::new(req.name, user.id);
+) -> Result<Json<Vec<SessionDto>>, AppError> {
+    let sessions = state.auth_service.list_sessions(user.id).map_err(|e| {
+        tracing::error!("Failed to list sessions: {:?}", e);
+        AppError::Internal("Failed to retrieve sessions".to_string())
+    })?;
+
+    Ok(Json(
+        sessions
+            .into_iter()
+            .map(|session| SessionDto::new(session, &claims))
+            .collect(),
+    ))
+}
+
+async fn revoke_session(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let session_id = SessionId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid session ID".to_string()))?;
+
+    state
+        .auth_service
+        .revoke_session(user.id, session_id)
+        .map_err(|e| match e {
+            AuthError::SessionNotFound => AppError::NotFound("Session not found".to_string()),
+            e => {
+                tracing::error!("Failed to revoke session: {:?}", e);
+                AppError::Internal("Failed to revoke session".to_string())
+            },
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Personal Access Token Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct AccessTokenDto {
+    id: String,
+    name: String,
+    scopes: Vec<TokenScope>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<PersonalAccessToken> for AccessTokenDto {
+    fn from(pat: PersonalAccessToken) -> Self {
+        Self {
+            id: pat.id.to_string(),
+            name: pat.name,
+            scopes: pat.scopes,
+            expires_at: pat.expires_at,
+            last_used_at: pat.last_used_at,
+            created_at: pat.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CreatedAccessTokenResponse {
+    /// Plain text token; only returned once
+    token: String,
+    #[serde(flatten)]
+    details: AccessTokenDto,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateAccessTokenRequest {
+    name: String,
+    scopes: Vec<String>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+async fn list_access_tokens(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AccessTokenDto>>, AppError> {
+    let tokens = state
+        .auth_service
+        .list_personal_access_tokens(user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to list access tokens: {:?}", e);
+            AppError::Internal("Failed to retrieve access tokens".to_string())
+        })?;
+
+    Ok(Json(tokens.into_iter().map(AccessTokenDto::from).collect()))
+}
+
+async fn create_access_token(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<CreateAccessTokenRequest>,
+) -> Result<(StatusCode, Json<CreatedAccessTokenResponse>), AppError> {
+    let scopes = req
+        .scopes
+        .iter()
+        .map(|name| TokenScope::from_name(name))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let (pat, token) = state
+        .auth_service
+        .create_personal_access_token(user.id, &req.name, scopes, req.expires_at)
+        .map_err(|e| match e {
+            AuthError::Validation(msg) => AppError::BadRequest(msg),
+            e => {
+                tracing::error!("Failed to create access token: {:?}", e);
+                AppError::Internal("Failed to create access token".to_string())
+            },
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedAccessTokenResponse {
+            token,
+            details: pat.into(),
+        }),
+    ))
+}
+
+async fn revoke_access_token(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let token_id = AccessTokenId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid access token ID".to_string()))?;
+
+    state
+        .auth_service
+        .revoke_personal_access_token(user.id, token_id)
+        .map_err(|e| match e {
+            AuthError::AccessTokenNotFound => {
+                AppError::NotFound("Access token not found".to_string())
+            },
+            e => {
+                tracing::error!("Failed to revoke access token: {:?}", e);
+                AppError::Internal("Failed to revoke access token".to_string())
+            },
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// User Routes
+// ============================================================================
+
+async fn list_users(
+    AuthUser(_user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<UserDto>>, AppError> {
+    let users = state.user_repo.find_all().map_err(|e| {
+        tracing::error!("Failed to list users: {:?}", e);
+        AppError::Internal("Failed to retrieve users".to_string())
+    })?;
+
+    Ok(Json(users.into_iter().map(UserDto::from).collect()))
+}
+
+async fn get_current_user(AuthUser(user): AuthUser) -> Result<Json<UserDto>, AppError> {
+    Ok(Json(user.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateProfileRequest {
+    username: Option<String>,
+    email: Option<String>,
+}
+
+async fn update_current_user(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<UpdateProfileRequest>,
+) -> Result<Json<UserDto>, AppError> {
+    let updated = state
+        .auth_service
+        .update_profile(user.id, req.username.as_deref(), req.email.as_deref())
+        .map_err(|e| match e {
+            AuthError::UserExists => {
+                AppError::BadRequest("Username or email is already in use".to_string())
+            },
+            AuthError::Validation(msg) => AppError::BadRequest(msg),
+            e => {
+                tracing::error!("Failed to update profile: {:?}", e);
+                AppError::Internal("Failed to update profile".to_string())
+            },
+        })?;
+
+    Ok(Json(updated.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangePasswordRequest {
+    current_password: String,
+    new_password: String,
+}
+
+async fn change_password(
+    AuthUser(user): AuthUser,
+    AuthClaims(claims): AuthClaims,
+    State(state): State<AppState>,
+    Json(req): Json<ChangePasswordRequest>,
+) -> Result<StatusCode, AppError> {
+    let current_session = claims.session_id().ok();
+
+    state
+        .auth_service
+        .change_password(
+            user.id,
+            &req.current_password,
+            &req.new_password,
+            current_session,
+        )
+        .map_err(|e| match e {
+            AuthError::InvalidCredentials => {
+                AppError::BadRequest("Current password is incorrect".to_string())
+            },
+            AuthError::PasswordValidation(msg) => AppError::BadRequest(msg),
+            e => {
+                tracing::error!("Failed to change password: {:?}", e);
+                AppError::Internal("Failed to change password".to_string())
+            },
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Project Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct ProjectDto {
+    id: String,
+    name: String,
+    /// Prefix of the project's ticket keys
+    key: String,
+    description: Option<String>,
+    color: Option<String>,
+    archived: bool,
+    is_private: bool,
+    created_by: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Project> for ProjectDto {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id.to_string(),
+            name: project.name,
+            key: project.key,
+            description: project.description,
+            color: project.color,
+            archived: project.archived,
+            is_private: project.is_private,
+            created_by: project.created_by.to_string(),
+            created_at: project.created_at.to_rfc3339(),
+            updated_at: project.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+async fn list_projects(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectDto>>, AppError> {
+    let projects = state.project_repo.find_visible_to(user.id).map_err(|e| {
+        tracing::error!("Failed to list projects: {:?}", e);
+        AppError::Internal("Failed to retrieve projects".to_string())
+    })?;
+
+    Ok(Json(projects.into_iter().map(ProjectDto::from).collect()))
+}
+
+async fn get_project(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ProjectDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "read")?;
+
+    let project = state
+        .project_repo
+        .find_by_id(project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get project: {:?}", e);
+            AppError::Internal("Failed to retrieve project".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+    Ok(Json(project.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateProjectRequest {
+    name: String,
+    /// Defaults to a free key derived from the name
+    key: Option<String>,
+    description: Option<String>,
+    is_private: Option<bool>,
+}
+
+async fn create_project(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<CreateProjectRequest>,
+) -> Result<Json<ProjectDto>, AppError> {
+    authorize(&state, &user, None, "project", "create")?;
+
+    let mut project = Project::new(req.name, user.id);
     project.description = req.description;
+    project.is_private = req.is_private.unwrap_or(false);
+    project.key = match req.key {
+        Some(key) => key.trim().to_ascii_uppercase(),
+        None => free_project_key(&state, &project.key)?,
+    };
 
     // Validate
     project.validate().map_err(|e| {
@@ -433,20 +1407,45 @@ async fn create_project(
 
     let created_project = state.project_repo.create(&project).map_err(|e| {
         tracing::error!("Failed to create project: {:?}", e);
-        AppError::Internal("Failed to create project".to_string())
+        match e {
+            DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+            _ => AppError::Internal("Failed to create project".to_string()),
+        }
     })?;
 
+    // The creator administers the new project
+    state
+        .permission_checker
+        .grant_project_owner(user.id, created_project.id)
+        .map_err(|e| {
+            tracing::error!("Failed to grant project owner role: {:?}", e);
+            AppError::Internal("Failed to create project".to_string())
+        })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Created,
+        "project",
+        created_project.id.to_string(),
+        None,
+        Some(&created_project),
+    );
+
     Ok(Json(created_project.into()))
 }
 
 #[derive(Debug, Deserialize)]
 struct UpdateProjectRequest {
     name: Option<String>,
+    /// New key; tickets stay reachable under the old one
+    key: Option<String>,
     description: Option<String>,
+    is_private: Option<bool>,
 }
 
 async fn update_project(
-    AuthUser(_user): AuthUser,
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
     Json(req): Json<UpdateProjectRequest>,
@@ -454,6 +1453,8 @@ async fn update_project(
     let project_id = ProjectId::from_string(&id)
         .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
+    authorize(&state, &user, Some(project_id), "project", "update")?;
+
     let mut project = state
         .project_repo
         .find_by_id(project_id)
@@ -462,14 +1463,21 @@ async fn update_project(
             AppError::Internal("Failed to retrieve project".to_string())
         })?
         .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+    let before = project.clone();
 
     // Update fields if provided
     if let Some(name) = req.name {
         project.name = name;
     }
+    if let Some(key) = req.key {
+        project.key = key.trim().to_ascii_uppercase();
+    }
     if let Some(description) = req.description {
         project.description = Some(description);
     }
+    if let Some(is_private) = req.is_private {
+        project.is_private = is_private;
+    }
 
     // Validate
     project.validate().map_err(|e| {
@@ -479,20 +1487,61 @@ async fn update_project(
 
     let updated_project = state.project_repo.update(&project).map_err(|e| {
         tracing::error!("Failed to update project: {:?}", e);
-        AppError::Internal("Failed to update project".to_string())
+        match e {
+            DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+            _ => AppError::Internal("Failed to update project".to_string()),
+        }
     })?;
 
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "project",
+        updated_project.id.to_string(),
+        Some(&before),
+        Some(&updated_project),
+    );
+
     Ok(Json(updated_project.into()))
 }
 
+/// The first of `base`, `base2`, `base3` and so on that no project uses or
+/// used as its key
+fn free_project_key(state: &AppState, base: &str) -> Result<String, AppError> {
+    for suffix in std::iter::once(String::new()).chain((2..).map(|n: u32| n.to_string())) {
+        let key = format!("{}{}", base, suffix);
+        let in_use = state.project_repo.key_in_use(&key).map_err(|e| {
+            tracing::error!("Failed to check project key: {:?}", e);
+            AppError::Internal("Failed to create project".to_string())
+        })?;
+        if !in_use {
+            return Ok(key);
+        }
+    }
+
+    unreachable!("project key suffixes are unbounded")
+}
+
 async fn delete_project(
-    AuthUser(_user): AuthUser,
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, AppError> {
     let project_id = ProjectId::from_string(&id)
         .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
+    authorize(&state, &user, Some(project_id), "project", "delete")?;
+
+    let project = state
+        .project_repo
+        .find_by_id(project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get project: {:?}", e);
+            AppError::Internal("Failed to retrieve project".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
     state.project_repo.delete(project_id).map_err(|e| {
         tracing::error!("Failed to delete project: {:?}", e);
         match e {
@@ -501,17 +1550,34 @@ async fn delete_project(
         }
     })?;
 
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Deleted,
+        "project",
+        project_id.to_string(),
+        Some(&project),
+        None,
+    );
+
     Ok(StatusCode::NO_CONTENT)
 }
 
 async fn archive_project(
-    AuthUser(_user): AuthUser,
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<ProjectDto>, AppError> {
     let project_id = ProjectId::from_string(&id)
         .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
+    authorize(&state, &user, Some(project_id), "project", "update")?;
+
+    let before = state.project_repo.find_by_id(project_id).map_err(|e| {
+        tracing::error!("Failed to get project: {:?}", e);
+        AppError::Internal("Failed to retrieve project".to_string())
+    })?;
+
     let archived_project = state.project_repo.archive(project_id).map_err(|e| {
         tracing::error!("Failed to archive project: {:?}", e);
         match e {
@@ -520,695 +1586,5649 @@ async fn archive_project(
         }
     })?;
 
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "project",
+        project_id.to_string(),
+        before.as_ref(),
+        Some(&archived_project),
+    );
+
     Ok(Json(archived_project.into()))
 }
 
 // ============================================================================
-// Ticket Routes
+// Project Member Routes
 // ============================================================================
 
 #[derive(Debug, Serialize)]
-struct TicketDto {
-    id: String,
+struct ProjectMemberDto {
     project_id: String,
-    title: String,
-    description: Option<String>,
-    ticket_type: String,
-    status: String,
-    priority: String,
-    assignee_id: Option<String>,
-    created_by: String,
-    created_at: String,
-    updated_at: String,
+    user_id: String,
+    username: String,
+    role_id: String,
+    role: String,
+    joined_at: String,
 }
 
-impl From<Ticket> for TicketDto {
-    fn from(ticket: Ticket) -> Self {
-        Self {
-            id: ticket.id.to_string(),
-            project_id: ticket.project_id.to_string(),
-            title: ticket.title,
-            description: ticket.description,
-            ticket_type: format!("{:?}", ticket.ticket_type),
-            status: format!("{:?}", ticket.status),
-            priority: format!("{:?}", ticket.priority),
-            assignee_id: ticket.assignee_id.map(|id| id.to_string()),
-            created_by: ticket.created_by.to_string(),
-            created_at: ticket.created_at.to_rfc3339(),
-            updated_at: ticket.updated_at.to_rfc3339(),
-        }
-    }
+/// Resolve the username and role name shown for a project or team member
+fn member_names(
+    state: &AppState,
+    user_id: UserId,
+    role_id: &RoleId,
+) -> Result<(String, String), AppError> {
+    let username = state
+        .user_repo
+        .find_by_id(user_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get user: {:?}", e);
+            AppError::Internal("Failed to retrieve members".to_string())
+        })?
+        .map(|u| u.username)
+        .unwrap_or_default();
+
+    let role = state
+        .role_repo
+        .find_by_id(role_id.clone())
+        .map_err(|e| {
+            tracing::error!("Failed to get role: {:?}", e);
+            AppError::Internal("Failed to retrieve members".to_string())
+        })?
+        .map(|r| r.name)
+        .unwrap_or_default();
+
+    Ok((username, role))
 }
 
-async fn list_tickets(
-    AuthUser(user): AuthUser,
-    State(state): State<AppState>,
-    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<Vec<TicketDto>>, AppError> {
-    let mut tickets = state.ticket_repo.find_all().map_err(|e| {
-        tracing::error!("Failed to list tickets: {:?}", e);
-        AppError::Internal("Failed to retrieve tickets".to_string())
-    })?;
+/// Build a member DTO, resolving the user and role names
+fn member_dto(state: &AppState, member: ProjectMember) -> Result<ProjectMemberDto, AppError> {
+    let (username, role) = member_names(state, member.user_id, &member.role_id)?;
+
+    Ok(ProjectMemberDto {
+        project_id: member.project_id.to_string(),
+        user_id: member.user_id.to_string(),
+        username,
+        role_id: member.role_id.to_string(),
+        role,
+        joined_at: member.joined_at.to_rfc3339(),
+    })
+}
 
-    // Filter by project_id if provided
-    if let Some(project_id_str) = params.get("project_id") {
-        let project_id = ProjectId::from_string(project_id_str)
-            .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
-        tickets.retain(|t| t.project_id == project_id);
-    }
-
-    // Filter by status if provided
-    if let Some(status_str) = params.get("status") {
-        let status = match status_str.to_lowercase().as_str() {
-            "open" => TicketStatus::Open,
-            "inprogress" => TicketStatus::InProgress,
-            "review" => TicketStatus::Review,
-            "done" => TicketStatus::Done,
-            "closed" => TicketStatus::Closed,
-            _ => return Err(AppError::BadRequest("Invalid status".to_string())),
-        };
-        tickets.retain(|t| t.status == status);
+/// Resolve a role given by name (`admin`, `member`, `viewer`) or by ID
+fn resolve_role(state: &AppState, role: &str) -> Result<RoleId, AppError> {
+    let role_id = match role.to_lowercase().as_str() {
+        "admin" => RoleId::admin(),
+        "member" => RoleId::member(),
+        "viewer" => RoleId::viewer(),
+        _ => RoleId::from_string(role)
+            .map_err(|_| AppError::BadRequest("Invalid role".to_string()))?,
+    };
+
+    state
+        .role_repo
+        .find_by_id(role_id.clone())
+        .map_err(|e| {
+            tracing::error!("Failed to get role: {:?}", e);
+            AppError::Internal("Failed to retrieve role".to_string())
+        })?
+        .ok_or_else(|| AppError::BadRequest("Unknown role".to_string()))?;
+
+    Ok(role_id)
+}
+
+/// Require that the user administers the project
+fn authorize_project_admin(
+    state: &AppState,
+    user: &User,
+    project_id: ProjectId,
+) -> Result<(), AppError> {
+    let is_admin = state
+        .permission_checker
+        .is_admin(user.id, Some(project_id))
+        .map_err(|e| {
+            tracing::error!("Permission check failed: {:?}", e);
+            AppError::Internal("Failed to check permissions".to_string())
+        })?;
+
+    if is_admin {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Only a project admin can manage project access and webhooks".to_string(),
+        ))
     }
+}
 
-    // Filter by priority if provided
-    if let Some(priority_str) = params.get("priority") {
-        let priority = match priority_str.to_lowercase().as_str() {
-            "low" => Priority::Low,
-            "medium" => Priority::Medium,
-            "high" => Priority::High,
-            "critical" => Priority::Critical,
-            _ => return Err(AppError::BadRequest("Invalid priority".to_string())),
-        };
-        tickets.retain(|t| t.priority == priority);
+/// Reject changes that would leave the project without an admin
+fn ensure_other_admin_remains(
+    state: &AppState,
+    project_id: ProjectId,
+    user_id: UserId,
+) -> Result<(), AppError> {
+    let member = state
+        .project_repo
+        .find_member(project_id, user_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get project member: {:?}", e);
+            AppError::Internal("Failed to retrieve project member".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Project member not found".to_string()))?;
+
+    if member.role_id != RoleId::admin() {
+        return Ok(());
     }
 
-    // Filter by assignee_id if provided
-    if let Some(assignee_id_str) = params.get("assignee_id") {
-        use worknest_core::models::UserId;
-        if assignee_id_str == "me" {
-            tickets.retain(|t| t.assignee_id == Some(user.id));
-        } else {
-            let assignee_id = UserId::from_string(assignee_id_str)
-                .map_err(|_| AppError::BadRequest("Invalid assignee ID".to_string()))?;
-            tickets.retain(|t| t.assignee_id == Some(assignee_id));
-        }
-    }
-
-    // Sort by field if provided
-    if let Some(sort_by) = params.get("sort") {
-        match sort_by.as_str() {
-            "created_at" => tickets.sort_by(|a, b| b.created_at.cmp(&a.created_at)),
-            "updated_at" => tickets.sort_by(|a, b| b.updated_at.cmp(&a.updated_at)),
-            "priority" => tickets.sort_by(|a, b| {
-                let priority_order = |p: &Priority| match p {
-                    Priority::Critical => 0,
-                    Priority::High => 1,
-                    Priority::Medium => 2,
-                    Priority::Low => 3,
-                };
-                priority_order(&a.priority).cmp(&priority_order(&b.priority))
-            }),
-            _ => {}, // Keep default order if invalid sort field
-        }
+    let admins = state
+        .role_repo
+        .count_users_with_role(&RoleId::admin(), Some(project_id))
+        .map_err(|e| {
+            tracing::error!("Failed to count project admins: {:?}", e);
+            AppError::Internal("Failed to retrieve project members".to_string())
+        })?;
+
+    if admins <= 1 {
+        return Err(AppError::BadRequest(
+            "A project must keep at least one admin".to_string(),
+        ));
     }
 
-    // Apply pagination if provided
-    let limit = params
-        .get("limit")
-        .and_then(|l| l.parse::<usize>().ok())
-        .unwrap_or(usize::MAX);
+    Ok(())
+}
+
+async fn list_project_members(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ProjectMemberDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "read")?;
 
-    let offset = params
-        .get("offset")
-        .and_then(|o| o.parse::<usize>().ok())
-        .unwrap_or(0);
+    let members = state.project_repo.find_members(project_id).map_err(|e| {
+        tracing::error!("Failed to list project members: {:?}", e);
+        AppError::Internal("Failed to retrieve project members".to_string())
+    })?;
 
-    let paginated_tickets: Vec<Ticket> = tickets.into_iter().skip(offset).take(limit).collect();
+    let dtos = members
+        .into_iter()
+        .map(|m| member_dto(&state, m))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(Json(
-        paginated_tickets.into_iter().map(TicketDto::from).collect(),
-    ))
+    Ok(Json(dtos))
 }
 
-async fn get_ticket(
-    AuthUser(_user): AuthUser,
+#[derive(Debug, Deserialize)]
+struct AddProjectMemberRequest {
+    user_id: Option<String>,
+    username: Option<String>,
+    role: Option<String>,
+}
+
+async fn add_project_member(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<Json<TicketDto>, AppError> {
-    let ticket_id = TicketId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    Json(req): Json<AddProjectMemberRequest>,
+) -> Result<(StatusCode, Json<ProjectMemberDto>), AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
-    let ticket = state
-        .ticket_repo
-        .find_by_id(ticket_id)
+    authorize_project_admin(&state, &user, project_id)?;
+
+    state
+        .project_repo
+        .find_by_id(project_id)
         .map_err(|e| {
-            tracing::error!("Failed to get ticket: {:?}", e);
-            AppError::Internal("Failed to retrieve ticket".to_string())
+            tracing::error!("Failed to get project: {:?}", e);
+            AppError::Internal("Failed to retrieve project".to_string())
         })?
-        .ok_or_else(|| AppError::NotFound("Ticket not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+    // Resolve the invited user by ID or username
+    let invitee = match (req.user_id, req.username) {
+        (Some(user_id), _) => {
+            let user_id = UserId::from_string(&user_id)
+                .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+            state.user_repo.find_by_id(user_id)
+        },
+        (None, Some(username)) => state.user_repo.find_by_username(&username),
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "Either user_id or username is required".to_string(),
+            ))
+        },
+    }
+    .map_err(|e| {
+        tracing::error!("Failed to get user: {:?}", e);
+        AppError::Internal("Failed to retrieve user".to_string())
+    })?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
-    Ok(Json(ticket.into()))
+    let existing = state
+        .project_repo
+        .find_member(project_id, invitee.id)
+        .map_err(|e| {
+            tracing::error!("Failed to get project member: {:?}", e);
+            AppError::Internal("Failed to retrieve project member".to_string())
+        })?;
+    if existing.is_some() {
+        return Err(AppError::BadRequest(
+            "User is already a member of this project".to_string(),
+        ));
+    }
+
+    let role_id = resolve_role(&state, req.role.as_deref().unwrap_or("member"))?;
+
+    let member = state
+        .project_repo
+        .set_member_role(project_id, invitee.id, &role_id)
+        .map_err(|e| {
+            tracing::error!("Failed to add project member: {:?}", e);
+            AppError::Internal("Failed to add project member".to_string())
+        })?;
+
+    Ok((StatusCode::CREATED, Json(member_dto(&state, member)?)))
 }
 
 #[derive(Debug, Deserialize)]
-struct CreateTicketRequest {
-    project_id: String,
-    title: String,
-    description: Option<String>,
-    ticket_type: String,
-    priority: Option<String>,
+struct UpdateProjectMemberRequest {
+    role: String,
 }
 
-async fn create_ticket(
+async fn update_project_member(
     AuthUser(user): AuthUser,
     State(state): State<AppState>,
-    Json(req): Json<CreateTicketRequest>,
-) -> Result<Json<TicketDto>, AppError> {
-    let project_id = ProjectId::from_string(&req.project_id)
+    Path((id, user_id)): Path<(String, String)>,
+    Json(req): Json<UpdateProjectMemberRequest>,
+) -> Result<Json<ProjectMemberDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
         .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+    let member_id = UserId::from_string(&user_id)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    let ticket_type = match req.ticket_type.to_lowercase().as_str() {
-        "task" => TicketType::Task,
-        "bug" => TicketType::Bug,
-        "feature" => TicketType::Feature,
-        "epic" => TicketType::Epic,
-        _ => return Err(AppError::BadRequest("Invalid ticket type".to_string())),
-    };
+    authorize_project_admin(&state, &user, project_id)?;
 
-    let mut ticket = Ticket::new(project_id, req.title, ticket_type, user.id);
-    ticket.description = req.description;
+    let role_id = resolve_role(&state, &req.role)?;
+    if role_id != RoleId::admin() {
+        ensure_other_admin_remains(&state, project_id, member_id)?;
+    } else {
+        // Only existing members can be re-roled
+        state
+            .project_repo
+            .find_member(project_id, member_id)
+            .map_err(|e| {
+                tracing::error!("Failed to get project member: {:?}", e);
+                AppError::Internal("Failed to retrieve project member".to_string())
+            })?
+            .ok_or_else(|| AppError::NotFound("Project member not found".to_string()))?;
+    }
 
-    if let Some(priority_str) = req.priority {
-        ticket.priority = match priority_str.to_lowercase().as_str() {
-            "low" => Priority::Low,
-            "medium" => Priority::Medium,
-            "high" => Priority::High,
-            "critical" => Priority::Critical,
-            _ => return Err(AppError::BadRequest("Invalid priority".to_string())),
-        };
+    let member = state
+        .project_repo
+        .set_member_role(project_id, member_id, &role_id)
+        .map_err(|e| {
+            tracing::error!("Failed to update project member: {:?}", e);
+            AppError::Internal("Failed to update project member".to_string())
+        })?;
+
+    Ok(Json(member_dto(&state, member)?))
+}
+
+async fn remove_project_member(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, user_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+    let member_id = UserId::from_string(&user_id)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Members may always leave a project; removing others requires admin
+    if member_id != user.id {
+        authorize_project_admin(&state, &user, project_id)?;
     }
 
-    // Validate
-    ticket.validate().map_err(|e| {
-        tracing::error!("Ticket validation failed: {:?}", e);
-        AppError::BadRequest(e.to_string())
-    })?;
+    ensure_other_admin_remains(&state, project_id, member_id)?;
 
-    let created_ticket = state.ticket_repo.create(&ticket).map_err(|e| {
-        tracing::error!("Failed to create ticket: {:?}", e);
-        AppError::Internal("Failed to create ticket".to_string())
+    state
+        .project_repo
+        .remove_member(project_id, member_id)
+        .map_err(|e| {
+            tracing::error!("Failed to remove project member: {:?}", e);
+            match e {
+                DbError::NotFound(_) => AppError::NotFound("Project member not found".to_string()),
+                _ => AppError::Internal("Failed to remove project member".to_string()),
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_project_teams(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TeamDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "read")?;
+
+    let teams = state.team_repo.find_by_project(project_id).map_err(|e| {
+        tracing::error!("Failed to list project teams: {:?}", e);
+        AppError::Internal("Failed to retrieve project teams".to_string())
     })?;
 
-    Ok(Json(created_ticket.into()))
+    Ok(Json(teams.into_iter().map(TeamDto::from).collect()))
 }
 
 #[derive(Debug, Deserialize)]
-struct UpdateTicketRequest {
-    title: Option<String>,
-    description: Option<String>,
-    status: Option<String>,
-    priority: Option<String>,
-    assignee_id: Option<String>,
+struct AttachProjectTeamRequest {
+    team_id: String,
 }
 
-async fn update_ticket(
-    AuthUser(_user): AuthUser,
+async fn attach_project_team(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(req): Json<UpdateTicketRequest>,
-) -> Result<Json<TicketDto>, AppError> {
-    let ticket_id = TicketId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    Json(req): Json<AttachProjectTeamRequest>,
+) -> Result<Json<Vec<TeamDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+    let team_id = TeamId::from_string(&req.team_id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
 
-    let mut ticket = state
-        .ticket_repo
-        .find_by_id(ticket_id)
+    authorize_project_admin(&state, &user, project_id)?;
+
+    state
+        .team_repo
+        .attach_to_project(team_id, project_id)
         .map_err(|e| {
-            tracing::error!("Failed to get ticket: {:?}", e);
-            AppError::Internal("Failed to retrieve ticket".to_string())
+            tracing::error!("Failed to attach team to project: {:?}", e);
+            match e {
+                DbError::ConstraintViolation(_) => {
+                    AppError::NotFound("Team or project not found".to_string())
+                },
+                _ => AppError::Internal("Failed to attach team to project".to_string()),
+            }
+        })?;
+
+    let teams = state.team_repo.find_by_project(project_id).map_err(|e| {
+        tracing::error!("Failed to list project teams: {:?}", e);
+        AppError::Internal("Failed to retrieve project teams".to_string())
+    })?;
+
+    Ok(Json(teams.into_iter().map(TeamDto::from).collect()))
+}
+
+async fn detach_project_team(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, team_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+    let team_id = TeamId::from_string(&team_id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+
+    authorize_project_admin(&state, &user, project_id)?;
+
+    state
+        .team_repo
+        .detach_from_project(team_id, project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to detach team from project: {:?}", e);
+            match e {
+                DbError::NotFound(_) => {
+                    AppError::NotFound("Team is not attached to project".to_string())
+                },
+                _ => AppError::Internal("Failed to detach team from project".to_string()),
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Team Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct TeamDto {
+    id: String,
+    name: String,
+    description: Option<String>,
+    created_by: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Team> for TeamDto {
+    fn from(team: Team) -> Self {
+        Self {
+            id: team.id.to_string(),
+            name: team.name,
+            description: team.description,
+            created_by: team.created_by.to_string(),
+            created_at: team.created_at.to_rfc3339(),
+            updated_at: team.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TeamMemberDto {
+    team_id: String,
+    user_id: String,
+    username: String,
+    role_id: String,
+    role: String,
+    joined_at: String,
+}
+
+/// Build a team member DTO, resolving the user and role names
+fn team_member_dto(state: &AppState, member: TeamMember) -> Result<TeamMemberDto, AppError> {
+    let (username, role) = member_names(state, member.user_id, &member.role_id)?;
+
+    Ok(TeamMemberDto {
+        team_id: member.team_id.to_string(),
+        user_id: member.user_id.to_string(),
+        username,
+        role_id: member.role_id.to_string(),
+        role,
+        joined_at: member.joined_at.to_rfc3339(),
+    })
+}
+
+/// Check whether the user is a global admin
+fn is_global_admin(state: &AppState, user: &User) -> Result<bool, AppError> {
+    state
+        .permission_checker
+        .is_admin(user.id, None)
+        .map_err(|e| {
+            tracing::error!("Permission check failed: {:?}", e);
+            AppError::Internal("Failed to check permissions".to_string())
+        })
+}
+
+/// Load a team, returning 404 when it does not exist
+fn load_team(state: &AppState, team_id: TeamId) -> Result<Team, AppError> {
+    state
+        .team_repo
+        .find_by_id(team_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get team: {:?}", e);
+            AppError::Internal("Failed to retrieve team".to_string())
         })?
-        .ok_or_else(|| AppError::NotFound("Ticket not found".to_string()))?;
+        .ok_or_else(|| AppError::NotFound("Team not found".to_string()))
+}
 
-    // Update fields if provided
-    if let Some(title) = req.title {
-        ticket.title = title;
+/// Find the user's membership in a team
+fn find_team_member(
+    state: &AppState,
+    team_id: TeamId,
+    user_id: UserId,
+) -> Result<Option<TeamMember>, AppError> {
+    state.team_repo.find_member(team_id, user_id).map_err(|e| {
+        tracing::error!("Failed to get team member: {:?}", e);
+        AppError::Internal("Failed to retrieve team member".to_string())
+    })
+}
+
+/// Require that the user belongs to the team or is a global admin
+fn authorize_team_member(state: &AppState, user: &User, team_id: TeamId) -> Result<(), AppError> {
+    if find_team_member(state, team_id, user.id)?.is_some() || is_global_admin(state, user)? {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Only team members can view this team".to_string(),
+        ))
     }
-    if let Some(description) = req.description {
-        ticket.description = Some(description);
+}
+
+/// Require that the user administers the team or is a global admin
+fn authorize_team_admin(state: &AppState, user: &User, team_id: TeamId) -> Result<(), AppError> {
+    let is_team_admin = find_team_member(state, team_id, user.id)?
+        .map(|m| m.role_id == RoleId::admin())
+        .unwrap_or(false);
+
+    if is_team_admin || is_global_admin(state, user)? {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Only a team admin can manage this team".to_string(),
+        ))
     }
-    if let Some(status_str) = req.status {
-        ticket.status = match status_str.to_lowercase().as_str() {
-            "open" => TicketStatus::Open,
-            "inprogress" | "in progress" => TicketStatus::InProgress,
-            "review" => TicketStatus::Review,
-            "done" => TicketStatus::Done,
-            "closed" => TicketStatus::Closed,
-            _ => return Err(AppError::BadRequest("Invalid status".to_string())),
-        };
+}
+
+/// Reject changes that would leave the team without an admin
+fn ensure_other_team_admin_remains(
+    state: &AppState,
+    team_id: TeamId,
+    user_id: UserId,
+) -> Result<(), AppError> {
+    let member = find_team_member(state, team_id, user_id)?
+        .ok_or_else(|| AppError::NotFound("Team member not found".to_string()))?;
+
+    if member.role_id != RoleId::admin() {
+        return Ok(());
     }
-    if let Some(priority_str) = req.priority {
-        ticket.priority = match priority_str.to_lowercase().as_str() {
+
+    let admins = state
+        .team_repo
+        .count_members_with_role(team_id, &RoleId::admin())
+        .map_err(|e| {
+            tracing::error!("Failed to count team admins: {:?}", e);
+            AppError::Internal("Failed to retrieve team members".to_string())
+        })?;
+
+    if admins <= 1 {
+        return Err(AppError::BadRequest(
+            "A team must keep at least one admin".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn list_teams(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TeamDto>>, AppError> {
+    // Global admins see every team, everyone else the teams they belong to
+    let teams = if is_global_admin(&state, &user)? {
+        state.team_repo.find_all()
+    } else {
+        state.team_repo.find_by_member(user.id)
+    }
+    .map_err(|e| {
+        tracing::error!("Failed to list teams: {:?}", e);
+        AppError::Internal("Failed to retrieve teams".to_string())
+    })?;
+
+    Ok(Json(teams.into_iter().map(TeamDto::from).collect()))
+}
+
+async fn get_team(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<TeamDto>, AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+
+    let team = load_team(&state, team_id)?;
+    authorize_team_member(&state, &user, team_id)?;
+
+    Ok(Json(team.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateTeamRequest {
+    name: String,
+    description: Option<String>,
+}
+
+async fn create_team(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<CreateTeamRequest>,
+) -> Result<Json<TeamDto>, AppError> {
+    authorize(&state, &user, None, "project", "create")?;
+
+    let team = Team::new(req.name, req.description, user.id);
+
+    // Validate
+    team.validate().map_err(|e| {
+        tracing::error!("Team validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created_team = state.team_repo.create(&team).map_err(|e| {
+        tracing::error!("Failed to create team: {:?}", e);
+        AppError::Internal("Failed to create team".to_string())
+    })?;
+
+    // The creator administers the new team
+    state
+        .team_repo
+        .set_member_role(created_team.id, user.id, &RoleId::admin())
+        .map_err(|e| {
+            tracing::error!("Failed to add team creator: {:?}", e);
+            AppError::Internal("Failed to create team".to_string())
+        })?;
+
+    Ok(Json(created_team.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateTeamRequest {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+async fn update_team(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateTeamRequest>,
+) -> Result<Json<TeamDto>, AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+
+    let mut team = load_team(&state, team_id)?;
+    authorize_team_admin(&state, &user, team_id)?;
+
+    team.update(req.name, req.description).map_err(|e| {
+        tracing::error!("Team validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let updated_team = state.team_repo.update(&team).map_err(|e| {
+        tracing::error!("Failed to update team: {:?}", e);
+        AppError::Internal("Failed to update team".to_string())
+    })?;
+
+    Ok(Json(updated_team.into()))
+}
+
+async fn delete_team(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+
+    load_team(&state, team_id)?;
+    authorize_team_admin(&state, &user, team_id)?;
+
+    state.team_repo.delete(team_id).map_err(|e| {
+        tracing::error!("Failed to delete team: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Team not found".to_string()),
+            _ => AppError::Internal("Failed to delete team".to_string()),
+        }
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_team_members(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TeamMemberDto>>, AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+
+    load_team(&state, team_id)?;
+    authorize_team_member(&state, &user, team_id)?;
+
+    let members = state.team_repo.find_members(team_id).map_err(|e| {
+        tracing::error!("Failed to list team members: {:?}", e);
+        AppError::Internal("Failed to retrieve team members".to_string())
+    })?;
+
+    let dtos = members
+        .into_iter()
+        .map(|m| team_member_dto(&state, m))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Json(dtos))
+}
+
+#[derive(Debug, Deserialize)]
+struct AddTeamMemberRequest {
+    user_id: Option<String>,
+    username: Option<String>,
+    role: Option<String>,
+}
+
+async fn add_team_member(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<AddTeamMemberRequest>,
+) -> Result<(StatusCode, Json<TeamMemberDto>), AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+
+    load_team(&state, team_id)?;
+    authorize_team_admin(&state, &user, team_id)?;
+
+    // Resolve the new member by ID or username
+    let new_member = match (req.user_id, req.username) {
+        (Some(user_id), _) => {
+            let user_id = UserId::from_string(&user_id)
+                .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+            state.user_repo.find_by_id(user_id)
+        },
+        (None, Some(username)) => state.user_repo.find_by_username(&username),
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "Either user_id or username is required".to_string(),
+            ))
+        },
+    }
+    .map_err(|e| {
+        tracing::error!("Failed to get user: {:?}", e);
+        AppError::Internal("Failed to retrieve user".to_string())
+    })?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if find_team_member(&state, team_id, new_member.id)?.is_some() {
+        return Err(AppError::BadRequest(
+            "User is already a member of this team".to_string(),
+        ));
+    }
+
+    let role_id = resolve_role(&state, req.role.as_deref().unwrap_or("member"))?;
+
+    let member = state
+        .team_repo
+        .set_member_role(team_id, new_member.id, &role_id)
+        .map_err(|e| {
+            tracing::error!("Failed to add team member: {:?}", e);
+            AppError::Internal("Failed to add team member".to_string())
+        })?;
+
+    Ok((StatusCode::CREATED, Json(team_member_dto(&state, member)?)))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateTeamMemberRequest {
+    role: String,
+}
+
+async fn update_team_member(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, user_id)): Path<(String, String)>,
+    Json(req): Json<UpdateTeamMemberRequest>,
+) -> Result<Json<TeamMemberDto>, AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+    let member_id = UserId::from_string(&user_id)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    authorize_team_admin(&state, &user, team_id)?;
+
+    let role_id = resolve_role(&state, &req.role)?;
+    if role_id != RoleId::admin() {
+        ensure_other_team_admin_remains(&state, team_id, member_id)?;
+    } else if find_team_member(&state, team_id, member_id)?.is_none() {
+        // Only existing members can be re-roled
+        return Err(AppError::NotFound("Team member not found".to_string()));
+    }
+
+    let member = state
+        .team_repo
+        .set_member_role(team_id, member_id, &role_id)
+        .map_err(|e| {
+            tracing::error!("Failed to update team member: {:?}", e);
+            AppError::Internal("Failed to update team member".to_string())
+        })?;
+
+    Ok(Json(team_member_dto(&state, member)?))
+}
+
+async fn remove_team_member(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, user_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let team_id = TeamId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid team ID".to_string()))?;
+    let member_id = UserId::from_string(&user_id)
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    // Members may always leave a team; removing others requires admin
+    if member_id != user.id {
+        authorize_team_admin(&state, &user, team_id)?;
+    }
+
+    ensure_other_team_admin_remains(&state, team_id, member_id)?;
+
+    state
+        .team_repo
+        .remove_member(team_id, member_id)
+        .map_err(|e| {
+            tracing::error!("Failed to remove team member: {:?}", e);
+            match e {
+                DbError::NotFound(_) => AppError::NotFound("Team member not found".to_string()),
+                _ => AppError::Internal("Failed to remove team member".to_string()),
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Ticket Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct TicketDto {
+    id: String,
+    project_id: String,
+    /// Number of the ticket within its project
+    number: u32,
+    /// Human-readable reference such as `WN-123`
+    key: String,
+    title: String,
+    description: Option<String>,
+    ticket_type: String,
+    /// Key of the ticket's status in its project's workflow
+    status: String,
+    status_category: StatusCategory,
+    priority: String,
+    assignee_id: Option<String>,
+    created_by: String,
+    /// Sprint the ticket is planned into; `null` for the backlog
+    sprint_id: Option<String>,
+    /// Parent in the ticket hierarchy; `null` for top-level tickets
+    parent_id: Option<String>,
+    /// Progress over all descendants; `null` for tickets without children
+    rollup: Option<TicketRollup>,
+    estimate_hours: Option<f32>,
+    /// Minutes logged against the ticket by everyone
+    logged_minutes: u32,
+    /// Minutes of the estimate not yet logged; `null` without an estimate
+    remaining_minutes: Option<u32>,
+    created_at: String,
+    updated_at: String,
+    /// Tickets this ticket depends on
+    blocked_by: Vec<String>,
+    /// Tickets that depend on this ticket
+    blocks: Vec<String>,
+    labels: Vec<LabelDto>,
+    custom_fields: Vec<CustomFieldValueDto>,
+}
+
+impl From<Ticket> for TicketDto {
+    fn from(ticket: Ticket) -> Self {
+        let remaining_minutes = ticket.remaining_minutes();
+        Self {
+            id: ticket.id.to_string(),
+            project_id: ticket.project_id.to_string(),
+            number: ticket.number,
+            key: String::new(),
+            title: ticket.title,
+            description: ticket.description,
+            ticket_type: format!("{:?}", ticket.ticket_type),
+            status: ticket.status,
+            status_category: ticket.status_category,
+            priority: format!("{:?}", ticket.priority),
+            assignee_id: ticket.assignee_id.map(|id| id.to_string()),
+            created_by: ticket.created_by.to_string(),
+            sprint_id: ticket.sprint_id.map(|id| id.to_string()),
+            parent_id: ticket.parent_id.map(|id| id.to_string()),
+            rollup: ticket.rollup,
+            estimate_hours: ticket.estimate_hours,
+            logged_minutes: ticket.logged_minutes,
+            remaining_minutes,
+            created_at: ticket.created_at.to_rfc3339(),
+            updated_at: ticket.updated_at.to_rfc3339(),
+            blocked_by: Vec::new(),
+            blocks: Vec::new(),
+            labels: ticket.labels.into_iter().map(LabelDto::from).collect(),
+            custom_fields: ticket
+                .custom_fields
+                .into_iter()
+                .map(CustomFieldValueDto::from)
+                .collect(),
+        }
+    }
+}
+
+/// Fill in the labels and custom field values of several tickets
+fn hydrate_tickets(state: &AppState, mut tickets: Vec<Ticket>) -> Result<Vec<Ticket>, AppError> {
+    let ids: Vec<TicketId> = tickets.iter().map(|t| t.id).collect();
+    let labels = state.label_repo.find_for_tickets(&ids).map_err(|e| {
+        tracing::error!("Failed to get ticket labels: {:?}", e);
+        AppError::Internal("Failed to retrieve ticket labels".to_string())
+    })?;
+    let values = state
+        .custom_field_repo
+        .find_values_for_tickets(&ids)
+        .map_err(|e| {
+            tracing::error!("Failed to get custom field values: {:?}", e);
+            AppError::Internal("Failed to retrieve custom field values".to_string())
+        })?;
+
+    for ticket in &mut tickets {
+        ticket.labels = labels
+            .iter()
+            .filter(|(ticket_id, _)| *ticket_id == ticket.id)
+            .map(|(_, label)| label.clone())
+            .collect();
+        ticket.custom_fields = values
+            .iter()
+            .filter(|(ticket_id, _)| *ticket_id == ticket.id)
+            .map(|(_, value)| value.clone())
+            .collect();
+    }
+
+    Ok(tickets)
+}
+
+/// Fill in the labels and custom field values of a ticket
+fn hydrate_ticket(state: &AppState, ticket: Ticket) -> Result<Ticket, AppError> {
+    Ok(hydrate_tickets(state, vec![ticket])?.remove(0))
+}
+
+/// Build ticket DTOs with their keys, labels, custom fields, roll-ups,
+/// logged time and `blocked_by`/`blocks` lists filled in
+fn ticket_dtos(state: &AppState, tickets: Vec<Ticket>) -> Result<Vec<TicketDto>, AppError> {
+    let tickets = hydrate_tickets(state, tickets)?;
+    let ids: Vec<TicketId> = tickets.iter().map(|t| t.id).collect();
+    let dependencies = state.dependency_repo.find_for_tickets(&ids).map_err(|e| {
+        tracing::error!("Failed to get ticket dependencies: {:?}", e);
+        AppError::Internal("Failed to retrieve ticket dependencies".to_string())
+    })?;
+    let rollups = state.ticket_repo.find_rollups(&ids).map_err(|e| {
+        tracing::error!("Failed to get ticket roll-ups: {:?}", e);
+        AppError::Internal("Failed to retrieve ticket progress".to_string())
+    })?;
+    let logged = state.worklog_repo.logged_minutes(&ids).map_err(|e| {
+        tracing::error!("Failed to get logged time: {:?}", e);
+        AppError::Internal("Failed to retrieve logged time".to_string())
+    })?;
+
+    let mut project_keys: Vec<(ProjectId, String)> = Vec::new();
+    for ticket in &tickets {
+        if project_keys.iter().any(|(id, _)| *id == ticket.project_id) {
+            continue;
+        }
+        let project = state
+            .project_repo
+            .find_by_id(ticket.project_id)
+            .map_err(|e| {
+                tracing::error!("Failed to get project: {:?}", e);
+                AppError::Internal("Failed to retrieve project".to_string())
+            })?;
+        if let Some(project) = project {
+            project_keys.push((project.id, project.key));
+        }
+    }
+
+    Ok(tickets
+        .into_iter()
+        .map(|mut ticket| {
+            let id = ticket.id;
+            ticket.rollup = rollups
+                .iter()
+                .find(|(ticket_id, _)| *ticket_id == id)
+                .map(|(_, rollup)| *rollup);
+            ticket.logged_minutes = logged
+                .iter()
+                .find(|(ticket_id, _)| *ticket_id == id)
+                .map_or(0, |(_, minutes)| *minutes);
+            let project_key = project_keys
+                .iter()
+                .find(|(id, _)| *id == ticket.project_id)
+                .map(|(_, key)| key.clone())
+                .unwrap_or_default();
+            let mut dto = TicketDto::from(ticket);
+            dto.key = TicketKey::new(project_key, dto.number).to_string();
+            for dep in &dependencies {
+                if dep.ticket_id == id {
+                    dto.blocked_by.push(dep.depends_on_ticket_id.to_string());
+                }
+                if dep.depends_on_ticket_id == id {
+                    dto.blocks.push(dep.ticket_id.to_string());
+                }
+            }
+            dto
+        })
+        .collect())
+}
+
+/// Build a single ticket DTO with its labels, custom fields and
+/// dependencies filled in
+fn ticket_dto(state: &AppState, ticket: Ticket) -> Result<TicketDto, AppError> {
+    Ok(ticket_dtos(state, vec![ticket])?.remove(0))
+}
+
+/// Default and maximum page sizes for ticket listings
+const DEFAULT_TICKET_PAGE_SIZE: u32 = 50;
+const MAX_TICKET_PAGE_SIZE: u32 = 200;
+
+#[derive(Debug, Serialize)]
+struct TicketListResponse {
+    items: Vec<TicketDto>,
+    total: u64,
+    next_cursor: Option<String>,
+}
+
+/// Split a comma-separated query parameter into its non-empty values
+fn list_param<'a>(
+    params: &'a std::collections::HashMap<String, String>,
+    name: &str,
+) -> impl Iterator<Item = &'a str> {
+    params
+        .get(name)
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse one sort item, a field name optionally prefixed with `-` for
+/// descending order
+fn parse_sort(value: &str) -> Result<TicketSort, AppError> {
+    let (name, descending) = match value.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (value, false),
+    };
+    let field = TicketSortField::from_name(name)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid sort field '{}'", name)))?;
+    Ok(if descending {
+        TicketSort::desc(field)
+    } else {
+        TicketSort::asc(field)
+    })
+}
+
+/// Build a ticket query from the `GET /api/tickets` query parameters
+///
+/// `q` takes a query in the ticket query language. `project_id`, `status`,
+/// `category`, `priority`, `type`, `label`, `assignee_id` and `sprint_id` accept
+/// comma-separated lists; `status` takes workflow status keys, `label` takes
+/// label names, `assignee_id` also accepts `me` and `none`, and `sprint_id`
+/// also accepts `none` for the backlog.
+/// `field.<field_id>` filters on a custom field and accepts a comma-separated
+/// list of values. `sort` is a comma-separated list of fields, each
+/// optionally prefixed with `-` for descending order.
+fn ticket_query_from_params(
+    state: &AppState,
+    user: &User,
+    params: &std::collections::HashMap<String, String>,
+) -> Result<TicketQuery, AppError> {
+    let mut query = TicketQuery::new().visible_to(user.id);
+
+    if let Some(q) = params.get("q").filter(|q| !q.trim().is_empty()) {
+        let expr = worknest_core::query::parse(q).map_err(AppError::InvalidQuery)?;
+        query = query.matching(expr, user.id);
+    }
+
+    for value in list_param(params, "project_id") {
+        let project_id = ProjectId::from_string(value)
+            .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+        query = query.project(project_id);
+    }
+
+    for value in list_param(params, "status") {
+        query = query.status(value);
+    }
+
+    for value in list_param(params, "category") {
+        let category = StatusCategory::from_name(value)
+            .map_err(|_| AppError::BadRequest("Invalid status category".to_string()))?;
+        query = query.status_category(category);
+    }
+
+    for value in list_param(params, "priority") {
+        let priority = match value.to_lowercase().as_str() {
             "low" => Priority::Low,
             "medium" => Priority::Medium,
             "high" => Priority::High,
             "critical" => Priority::Critical,
             _ => return Err(AppError::BadRequest("Invalid priority".to_string())),
         };
+        query = query.priority(priority);
     }
-    if let Some(assignee_id_str) = req.assignee_id {
-        if assignee_id_str.is_empty() {
-            ticket.assignee_id = None;
-        } else {
-            use worknest_core::models::UserId;
-            ticket.assignee_id = Some(
-                UserId::from_string(&assignee_id_str)
-                    .map_err(|_| AppError::BadRequest("Invalid assignee ID".to_string()))?,
-            );
+
+    for value in list_param(params, "type") {
+        query = query.ticket_type(parse_ticket_type(value)?);
+    }
+
+    for value in list_param(params, "label") {
+        query = query.label(value);
+    }
+
+    for name in params.keys() {
+        let Some(field_id) = name.strip_prefix("field.") else {
+            continue;
+        };
+        let field_id = CustomFieldId::from_string(field_id)
+            .map_err(|_| AppError::BadRequest("Invalid custom field ID".to_string()))?;
+        let field = load_custom_field(state, field_id)?;
+        for value in list_param(params, name) {
+            query = query.custom_field(field_id, parse_field_filter(&field, value, user)?);
         }
     }
 
-    // Validate
-    ticket.validate().map_err(|e| {
-        tracing::error!("Ticket validation failed: {:?}", e);
-        AppError::BadRequest(e.to_string())
-    })?;
+    for value in list_param(params, "assignee_id") {
+        query = match value {
+            "me" => query.assignee(user.id),
+            "none" => query.unassigned(),
+            _ => query.assignee(
+                UserId::from_string(value)
+                    .map_err(|_| AppError::BadRequest("Invalid assignee ID".to_string()))?,
+            ),
+        };
+    }
 
-    let updated_ticket = state.ticket_repo.update(&ticket).map_err(|e| {
-        tracing::error!("Failed to update ticket: {:?}", e);
-        AppError::Internal("Failed to update ticket".to_string())
+    for value in list_param(params, "sprint_id") {
+        query = match value {
+            "none" => query.backlog(),
+            _ => query.sprint(
+                SprintId::from_string(value)
+                    .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?,
+            ),
+        };
+    }
+
+    if let Some(before) = parse_timestamp(params, "due_before")? {
+        query = query.due_before(before);
+    }
+    if let Some(after) = parse_timestamp(params, "due_after")? {
+        query = query.due_after(after);
+    }
+
+    for value in list_param(params, "sort") {
+        query = query.sort_by(parse_sort(value)?);
+    }
+
+    if let Some(cursor) = params.get("cursor") {
+        let cursor = TicketCursor::decode(cursor)
+            .ok_or_else(|| AppError::BadRequest("Invalid cursor".to_string()))?;
+        query = query.after(cursor);
+    }
+
+    let limit = match params.get("limit") {
+        Some(limit) => limit
+            .parse::<u32>()
+            .ok()
+            .filter(|limit| (1..=MAX_TICKET_PAGE_SIZE).contains(limit))
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "Limit must be between 1 and {}",
+                    MAX_TICKET_PAGE_SIZE
+                ))
+            })?,
+        None => DEFAULT_TICKET_PAGE_SIZE,
+    };
+
+    Ok(query.limit(limit))
+}
+
+async fn list_tickets(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<TicketListResponse>, AppError> {
+    let query = ticket_query_from_params(&state, &user, &params)?;
+
+    let page = state.ticket_repo.query(&query).map_err(|e| {
+        tracing::error!("Failed to list tickets: {:?}", e);
+        AppError::Internal("Failed to retrieve tickets".to_string())
     })?;
 
-    Ok(Json(updated_ticket.into()))
+    Ok(Json(TicketListResponse {
+        items: ticket_dtos(&state, page.tickets)?,
+        total: page.total,
+        next_cursor: page.next_cursor.map(|cursor| cursor.encode()),
+    }))
+}
+
+/// Get a ticket by ID or by key, such as `WN-123`
+async fn get_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<TicketDto>, AppError> {
+    let ticket = match TicketId::from_string(&id) {
+        Ok(ticket_id) => load_ticket(&state, ticket_id)?,
+        Err(_) => {
+            let key = TicketKey::parse(&id)
+                .ok_or_else(|| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+            load_ticket_by_key(&state, &key)?
+        },
+    };
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    Ok(Json(ticket_dto(&state, ticket)?))
+}
+
+/// List the direct children of a ticket, each with its own roll-up
+async fn list_ticket_children(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TicketDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let children = state.ticket_repo.find_children(ticket_id).map_err(|e| {
+        tracing::error!("Failed to list child tickets: {:?}", e);
+        AppError::Internal("Failed to retrieve child tickets".to_string())
+    })?;
+
+    Ok(Json(ticket_dtos(&state, children)?))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateTicketRequest {
+    project_id: String,
+    title: String,
+    description: Option<String>,
+    ticket_type: String,
+    priority: Option<String>,
+    assignee_id: Option<String>,
+    parent_id: Option<String>,
+    estimate_hours: Option<f32>,
+    #[serde(default)]
+    custom_fields: Vec<CustomFieldValue>,
+}
+
+async fn create_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<CreateTicketRequest>,
+) -> Result<Json<TicketDto>, AppError> {
+    let project_id = ProjectId::from_string(&req.project_id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "ticket", "create")?;
+
+    let ticket_type = parse_ticket_type(&req.ticket_type)?;
+
+    let mut ticket = Ticket::new(project_id, req.title, ticket_type, user.id);
+    ticket.description = req.description;
+    ticket.estimate_hours = req.estimate_hours;
+    ticket.custom_fields = req.custom_fields;
+    if let Some(assignee_id_str) = req.assignee_id.filter(|id| !id.is_empty()) {
+        ticket.assignee_id = Some(
+            UserId::from_string(&assignee_id_str)
+                .map_err(|_| AppError::BadRequest("Invalid assignee ID".to_string()))?,
+        );
+    }
+    if let Some(parent_id_str) = req.parent_id.filter(|id| !id.is_empty()) {
+        ticket.parent_id = Some(
+            TicketId::from_string(&parent_id_str)
+                .map_err(|_| AppError::BadRequest("Invalid parent ticket ID".to_string()))?,
+        );
+    }
+
+    // New tickets start in the first status of the project's workflow
+    let workflow = load_workflow(&state, project_id)?;
+    if let Some(status) = workflow.initial_status() {
+        ticket.update_status(status);
+    }
+
+    if let Some(priority_str) = req.priority {
+        ticket.priority = match priority_str.to_lowercase().as_str() {
+            "low" => Priority::Low,
+            "medium" => Priority::Medium,
+            "high" => Priority::High,
+            "critical" => Priority::Critical,
+            _ => return Err(AppError::BadRequest("Invalid priority".to_string())),
+        };
+    }
+
+    // Validate
+    ticket.validate().map_err(|e| {
+        tracing::error!("Ticket validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+    validate_ticket_fields(&state, &ticket)?;
+    validate_ticket_parent(&state, &ticket)?;
+    if let Some(assignee_id) = ticket.assignee_id {
+        validate_assignee(&state, project_id, assignee_id)?;
+    }
+
+    let created_ticket = state.ticket_repo.create(&ticket).map_err(|e| {
+        tracing::error!("Failed to create ticket: {:?}", e);
+        AppError::Internal("Failed to create ticket".to_string())
+    })?;
+    save_ticket_fields(&state, &created_ticket)?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Created,
+        "ticket",
+        created_ticket.id.to_string(),
+        None,
+        Some(&created_ticket),
+    );
+
+    add_watcher(&state, created_ticket.id, user.id);
+    let mut recipients = Vec::new();
+    if let Some(assignee_id) = created_ticket.assignee_id {
+        add_watcher(&state, created_ticket.id, assignee_id);
+        recipients.push((assignee_id, NotificationKind::Assigned));
+    }
+
+    let dto = ticket_dto(&state, created_ticket.clone())?;
+    notify_users(&state, &user, &created_ticket, &dto.key, &recipients);
+    publish_change(
+        &state,
+        &user,
+        project_id,
+        ChangeResource::Ticket,
+        ActivityAction::Created,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(Json(dto))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateTicketRequest {
+    title: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+    priority: Option<String>,
+    ticket_type: Option<String>,
+    assignee_id: Option<String>,
+    /// Parent ticket; an empty string makes the ticket top-level
+    parent_id: Option<String>,
+    estimate_hours: Option<f32>,
+    /// Replaces every custom field value of the ticket when present
+    custom_fields: Option<Vec<CustomFieldValue>>,
+}
+
+async fn update_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateTicketRequest>,
+) -> Result<Json<TicketDto>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+    let mut ticket = hydrate_ticket(&state, ticket)?;
+    let before = ticket.clone();
+
+    // Update fields if provided
+    if let Some(title) = req.title {
+        ticket.title = title;
+    }
+    if let Some(description) = req.description {
+        ticket.description = Some(description);
+    }
+    if let Some(status_str) = req.status {
+        let workflow = load_workflow(&state, ticket.project_id)?;
+        let status = workflow
+            .status(&status_str)
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown status '{}'", status_str)))?;
+        if !workflow.can_transition(&ticket.status, &status.key) {
+            return Err(AppError::BadRequest(format!(
+                "Cannot move ticket from '{}' to '{}'",
+                ticket.status, status.key
+            )));
+        }
+        ticket.update_status(status);
+    }
+    if let Some(priority_str) = req.priority {
+        ticket.priority = match priority_str.to_lowercase().as_str() {
+            "low" => Priority::Low,
+            "medium" => Priority::Medium,
+            "high" => Priority::High,
+            "critical" => Priority::Critical,
+            _ => return Err(AppError::BadRequest("Invalid priority".to_string())),
+        };
+    }
+    if let Some(assignee_id_str) = req.assignee_id {
+        if assignee_id_str.is_empty() {
+            ticket.assignee_id = None;
+        } else {
+            ticket.assignee_id = Some(
+                UserId::from_string(&assignee_id_str)
+                    .map_err(|_| AppError::BadRequest("Invalid assignee ID".to_string()))?,
+            );
+        }
+    }
+    if let Some(estimate_hours) = req.estimate_hours {
+        ticket.estimate_hours = Some(estimate_hours);
+    }
+    if let Some(ticket_type) = req.ticket_type {
+        ticket.ticket_type = parse_ticket_type(&ticket_type)?;
+    }
+    let type_changed = ticket.ticket_type != before.ticket_type;
+    let parent_changed = req.parent_id.is_some() || type_changed;
+    if let Some(parent_id_str) = req.parent_id {
+        if parent_id_str.is_empty() {
+            ticket.parent_id = None;
+        } else {
+            ticket.parent_id = Some(
+                TicketId::from_string(&parent_id_str)
+                    .map_err(|_| AppError::BadRequest("Invalid parent ticket ID".to_string()))?,
+            );
+        }
+    }
+    let fields_changed = req.custom_fields.is_some();
+    if let Some(custom_fields) = req.custom_fields {
+        ticket.custom_fields = custom_fields;
+    }
+
+    // Validate
+    ticket.validate().map_err(|e| {
+        tracing::error!("Ticket validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+    if fields_changed {
+        validate_ticket_fields(&state, &ticket)?;
+    }
+    if let Some(assignee_id) = ticket.assignee_id {
+        if before.assignee_id != Some(assignee_id) {
+            validate_assignee(&state, ticket.project_id, assignee_id)?;
+        }
+    }
+    if parent_changed {
+        validate_ticket_parent(&state, &ticket)?;
+    }
+    if type_changed {
+        let children = state.ticket_repo.find_children(ticket.id).map_err(|e| {
+            tracing::error!("Failed to list child tickets: {:?}", e);
+            AppError::Internal("Failed to retrieve child tickets".to_string())
+        })?;
+        ticket
+            .validate_children(&children)
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+    }
+
+    // A ticket cannot move to a done status while its blockers are still open
+    if ticket.is_resolved() && ticket.status != before.status {
+        let blockers = state
+            .dependency_repo
+            .find_blockers(ticket.id)
+            .map_err(|e| {
+                tracing::error!("Failed to get ticket blockers: {:?}", e);
+                AppError::Internal("Failed to retrieve ticket dependencies".to_string())
+            })?;
+        ticket
+            .validate_blockers(&blockers)
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+    }
+
+    let updated_ticket = state.ticket_repo.update(&ticket).map_err(|e| {
+        tracing::error!("Failed to update ticket: {:?}", e);
+        AppError::Internal("Failed to update ticket".to_string())
+    })?;
+    if fields_changed {
+        save_ticket_fields(&state, &updated_ticket)?;
+    }
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "ticket",
+        updated_ticket.id.to_string(),
+        Some(&before),
+        Some(&updated_ticket),
+    );
+
+    // A new assignee starts watching and hears about the assignment rather
+    // than about an update
+    let mut recipients = Vec::new();
+    if let Some(assignee_id) = updated_ticket.assignee_id {
+        if before.assignee_id != Some(assignee_id) {
+            add_watcher(&state, updated_ticket.id, assignee_id);
+            recipients.push((assignee_id, NotificationKind::Assigned));
+        }
+    }
+    let watcher_kind = if updated_ticket.status != before.status {
+        NotificationKind::StatusChanged
+    } else {
+        NotificationKind::Updated
+    };
+    recipients.extend(watcher_recipients(&state, updated_ticket.id, watcher_kind));
+
+    let dto = ticket_dto(&state, updated_ticket)?;
+    notify_users(&state, &user, &ticket, &dto.key, &recipients);
+    publish_change(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Ticket,
+        ActivityAction::Updated,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(Json(dto))
+}
+
+async fn delete_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "delete")?;
+
+    state.ticket_repo.delete(ticket_id).map_err(|e| {
+        tracing::error!("Failed to delete ticket: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Ticket not found".to_string()),
+            _ => AppError::Internal("Failed to delete ticket".to_string()),
+        }
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Deleted,
+        "ticket",
+        ticket_id.to_string(),
+        Some(&ticket),
+        None,
+    );
+    publish_change::<TicketDto>(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Ticket,
+        ActivityAction::Deleted,
+        ticket_id.to_string(),
+        None,
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Serialize)]
+struct SearchResultDto {
+    #[serde(flatten)]
+    ticket: TicketDto,
+    score: f64,
+    snippet: String,
+}
+
+async fn search_tickets(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<Vec<SearchResultDto>>, AppError> {
+    let text = params
+        .get("q")
+        .ok_or_else(|| AppError::BadRequest("Missing 'q' query parameter".to_string()))?;
+
+    let mut query = TicketQuery::new().visible_to(user.id);
+
+    // Optional project_id filter
+    if let Some(project_id_str) = params.get("project_id") {
+        let project_id = ProjectId::from_string(project_id_str)
+            .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+        query = query.project(project_id);
+    }
+
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_TICKET_PAGE_SIZE)
+        .clamp(1, MAX_TICKET_PAGE_SIZE);
+
+    let hits = state
+        .ticket_repo
+        .search(text, &query.limit(limit))
+        .map_err(|e| {
+            tracing::error!("Failed to search tickets: {:?}", e);
+            AppError::Internal("Failed to search tickets".to_string())
+        })?;
+
+    let (tickets, matches): (Vec<Ticket>, Vec<(f64, String)>) = hits
+        .into_iter()
+        .map(|hit| (hit.ticket, (hit.score, hit.snippet)))
+        .unzip();
+
+    Ok(Json(
+        ticket_dtos(&state, tickets)?
+            .into_iter()
+            .zip(matches)
+            .map(|(ticket, (score, snippet))| SearchResultDto {
+                ticket,
+                score,
+                snippet,
+            })
+            .collect(),
+    ))
+}
+
+#[derive(Debug, Serialize)]
+struct RebuildSearchIndexResponse {
+    indexed: usize,
+}
+
+async fn rebuild_search_index(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<RebuildSearchIndexResponse>, AppError> {
+    if !is_global_admin(&state, &user)? {
+        return Err(AppError::Forbidden(
+            "Only administrators can rebuild the search index".to_string(),
+        ));
+    }
+
+    let indexed = state.ticket_repo.rebuild_search_index().map_err(|e| {
+        tracing::error!("Failed to rebuild search index: {:?}", e);
+        AppError::Internal("Failed to rebuild search index".to_string())
+    })?;
+
+    tracing::info!("Rebuilt search index for {} tickets", indexed);
+
+    Ok(Json(RebuildSearchIndexResponse { indexed }))
+}
+
+// ============================================================================
+// Label Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct LabelDto {
+    id: String,
+    project_id: String,
+    name: String,
+    color: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Label> for LabelDto {
+    fn from(label: Label) -> Self {
+        Self {
+            id: label.id.to_string(),
+            project_id: label.project_id.to_string(),
+            name: label.name,
+            color: label.color,
+            created_at: label.created_at.to_rfc3339(),
+            updated_at: label.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Load a label, returning 404 when it does not exist
+fn load_label(state: &AppState, label_id: LabelId) -> Result<Label, AppError> {
+    state
+        .label_repo
+        .find_by_id(label_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get label: {:?}", e);
+            AppError::Internal("Failed to retrieve label".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Label not found".to_string()))
+}
+
+/// Map a label repository error, reporting duplicate names as 400
+fn label_error(e: DbError, action: &str) -> AppError {
+    tracing::error!("Failed to {} label: {:?}", action, e);
+    match e {
+        DbError::NotFound(_) => AppError::NotFound("Label not found".to_string()),
+        DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+        _ => AppError::Internal(format!("Failed to {} label", action)),
+    }
+}
+
+async fn list_project_labels(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<LabelDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "ticket", "read")?;
+
+    let labels = state.label_repo.find_by_project(project_id).map_err(|e| {
+        tracing::error!("Failed to list labels: {:?}", e);
+        AppError::Internal("Failed to retrieve labels".to_string())
+    })?;
+
+    Ok(Json(labels.into_iter().map(LabelDto::from).collect()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateLabelRequest {
+    name: String,
+    color: Option<String>,
+}
+
+async fn create_label(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateLabelRequest>,
+) -> Result<Json<LabelDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "update")?;
+
+    let label = Label::new(
+        project_id,
+        req.name,
+        req.color
+            .unwrap_or_else(|| Label::DEFAULT_COLOR.to_string()),
+    );
+
+    label.validate().map_err(|e| {
+        tracing::error!("Label validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created = state
+        .label_repo
+        .create(&label)
+        .map_err(|e| label_error(e, "create"))?;
+
+    Ok(Json(created.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateLabelRequest {
+    name: Option<String>,
+    color: Option<String>,
+}
+
+/// Rename or recolor a label; tickets refer to it by id, so the change shows
+/// on every ticket that carries it
+async fn update_label(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateLabelRequest>,
+) -> Result<Json<LabelDto>, AppError> {
+    let label_id = LabelId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid label ID".to_string()))?;
+
+    let mut label = load_label(&state, label_id)?;
+    authorize(&state, &user, Some(label.project_id), "project", "update")?;
+
+    if let Some(name) = req.name {
+        label.name = name;
+    }
+    if let Some(color) = req.color {
+        label.color = color;
+    }
+
+    label.validate().map_err(|e| {
+        tracing::error!("Label validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let updated = state
+        .label_repo
+        .update(&label)
+        .map_err(|e| label_error(e, "update"))?;
+
+    Ok(Json(updated.into()))
+}
+
+async fn delete_label(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let label_id = LabelId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid label ID".to_string()))?;
+
+    let label = load_label(&state, label_id)?;
+    authorize(&state, &user, Some(label.project_id), "project", "update")?;
+
+    state
+        .label_repo
+        .delete(label_id)
+        .map_err(|e| label_error(e, "delete"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeLabelRequest {
+    /// Label that replaces the merged one
+    into: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MergeLabelResponse {
+    label: LabelDto,
+    /// Number of tickets that carried the merged label
+    tickets: usize,
+}
+
+/// Merge a label into another label of the same project
+async fn merge_label(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<MergeLabelRequest>,
+) -> Result<Json<MergeLabelResponse>, AppError> {
+    let source_id = LabelId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid label ID".to_string()))?;
+    let target_id = LabelId::from_string(&req.into)
+        .map_err(|_| AppError::BadRequest("Invalid label ID".to_string()))?;
+
+    let source = load_label(&state, source_id)?;
+    let target = load_label(&state, target_id)?;
+    authorize(&state, &user, Some(source.project_id), "project", "update")?;
+
+    let tickets = state
+        .label_repo
+        .merge(source_id, target_id)
+        .map_err(|e| label_error(e, "merge"))?;
+
+    tracing::info!(
+        "Merged label '{}' into '{}' on {} tickets",
+        source.name,
+        target.name,
+        tickets
+    );
+
+    Ok(Json(MergeLabelResponse {
+        label: target.into(),
+        tickets,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SetTicketLabelsRequest {
+    label_ids: Vec<String>,
+}
+
+/// Replace the labels of a ticket
+async fn set_ticket_labels(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<SetTicketLabelsRequest>,
+) -> Result<Json<TicketDto>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+
+    let label_ids = req
+        .label_ids
+        .iter()
+        .map(|id| LabelId::from_string(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| AppError::BadRequest("Invalid label ID".to_string()))?;
+
+    let before = hydrate_ticket(&state, ticket.clone())?;
+
+    state
+        .label_repo
+        .set_ticket_labels(ticket_id, &label_ids)
+        .map_err(|e| label_error(e, "assign"))?;
+
+    let after = hydrate_ticket(&state, ticket)?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "ticket",
+        ticket_id.to_string(),
+        Some(&before),
+        Some(&after),
+    );
+
+    let dto = ticket_dto(&state, after)?;
+    publish_change(
+        &state,
+        &user,
+        before.project_id,
+        ChangeResource::Ticket,
+        ActivityAction::Updated,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(Json(dto))
+}
+
+// ============================================================================
+// Custom Field Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct CustomFieldDto {
+    id: String,
+    project_id: String,
+    name: String,
+    field_type: CustomFieldType,
+    options: Vec<String>,
+    required: bool,
+    position: i32,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<CustomField> for CustomFieldDto {
+    fn from(field: CustomField) -> Self {
+        Self {
+            id: field.id.to_string(),
+            project_id: field.project_id.to_string(),
+            name: field.name,
+            field_type: field.field_type,
+            options: field.options,
+            required: field.required,
+            position: field.position,
+            created_at: field.created_at.to_rfc3339(),
+            updated_at: field.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CustomFieldValueDto {
+    field_id: String,
+    #[serde(flatten)]
+    value: FieldValue,
+}
+
+impl From<CustomFieldValue> for CustomFieldValueDto {
+    fn from(value: CustomFieldValue) -> Self {
+        Self {
+            field_id: value.field_id.to_string(),
+            value: value.value,
+        }
+    }
+}
+
+/// Load a custom field, returning 404 when it does not exist
+fn load_custom_field(state: &AppState, field_id: CustomFieldId) -> Result<CustomField, AppError> {
+    state
+        .custom_field_repo
+        .find_by_id(field_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get custom field: {:?}", e);
+            AppError::Internal("Failed to retrieve custom field".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Custom field not found".to_string()))
+}
+
+/// Map a custom field repository error, reporting duplicate names as 400
+fn custom_field_error(e: DbError, action: &str) -> AppError {
+    tracing::error!("Failed to {} custom field: {:?}", action, e);
+    match e {
+        DbError::NotFound(_) => AppError::NotFound("Custom field not found".to_string()),
+        DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+        _ => AppError::Internal(format!("Failed to {} custom field", action)),
+    }
+}
+
+/// Check a ticket's parent against the ticket hierarchy: same project, a
+/// parent type one level up, and no cycles
+fn validate_ticket_parent(state: &AppState, ticket: &Ticket) -> Result<(), AppError> {
+    let Some(parent_id) = ticket.parent_id else {
+        return Ok(());
+    };
+
+    let parent = state
+        .ticket_repo
+        .find_by_id(parent_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get parent ticket: {:?}", e);
+            AppError::Internal("Failed to retrieve parent ticket".to_string())
+        })?
+        .ok_or_else(|| AppError::BadRequest("Parent ticket not found".to_string()))?;
+    let ancestors = state
+        .ticket_repo
+        .find_ancestor_ids(parent_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get ticket ancestors: {:?}", e);
+            AppError::Internal("Failed to retrieve parent ticket".to_string())
+        })?;
+
+    ticket
+        .validate_parent(&parent, &ancestors)
+        .map_err(|e| AppError::BadRequest(e.to_string()))
+}
+
+/// Check that a user exists and can read the project's tickets before they
+/// are assigned one, rejecting the request with 400 otherwise
+fn validate_assignee(
+    state: &AppState,
+    project_id: ProjectId,
+    assignee_id: UserId,
+) -> Result<(), AppError> {
+    let assignee = state
+        .user_repo
+        .find_by_id(assignee_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get assignee: {:?}", e);
+            AppError::Internal("Failed to retrieve assignee".to_string())
+        })?
+        .ok_or_else(|| AppError::BadRequest("Assignee not found".to_string()))?;
+
+    if !can(state, &assignee, Some(project_id), "ticket", "read")? {
+        return Err(AppError::BadRequest(
+            "Assignee cannot access this project".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parse a ticket type name, rejecting unknown ones with 400
+fn parse_ticket_type(value: &str) -> Result<TicketType, AppError> {
+    match value.to_lowercase().as_str() {
+        "task" => Ok(TicketType::Task),
+        "bug" => Ok(TicketType::Bug),
+        "feature" => Ok(TicketType::Feature),
+        "epic" => Ok(TicketType::Epic),
+        "story" => Ok(TicketType::Story),
+        "subtask" => Ok(TicketType::Subtask),
+        _ => Err(AppError::BadRequest("Invalid ticket type".to_string())),
+    }
+}
+
+/// Check a ticket's custom field values against its project's fields, and
+/// that user values refer to existing users
+fn validate_ticket_fields(state: &AppState, ticket: &Ticket) -> Result<(), AppError> {
+    let fields = state
+        .custom_field_repo
+        .find_by_project(ticket.project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to list custom fields: {:?}", e);
+            AppError::Internal("Failed to retrieve custom fields".to_string())
+        })?;
+
+    ticket
+        .validate_custom_fields(&fields)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    for field_value in &ticket.custom_fields {
+        if let FieldValue::User(user_id) = field_value.value {
+            let exists = state
+                .user_repo
+                .find_by_id(user_id)
+                .map_err(|e| {
+                    tracing::error!("Failed to get user: {:?}", e);
+                    AppError::Internal("Failed to retrieve user".to_string())
+                })?
+                .is_some();
+            if !exists {
+                return Err(AppError::BadRequest(format!("Unknown user {}", user_id)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Store a ticket's custom field values, replacing the previous ones
+fn save_ticket_fields(state: &AppState, ticket: &Ticket) -> Result<(), AppError> {
+    state
+        .custom_field_repo
+        .set_ticket_values(ticket.id, &ticket.custom_fields)
+        .map_err(|e| custom_field_error(e, "save"))
+}
+
+/// Parse a `field.<field_id>` filter value according to the field's type
+///
+/// Dates use `YYYY-MM-DD`; user fields take a user ID or `me`.
+fn parse_field_filter(
+    field: &CustomField,
+    value: &str,
+    user: &User,
+) -> Result<FieldValue, AppError> {
+    let invalid = || {
+        AppError::BadRequest(format!(
+            "Invalid {} value '{}' for field '{}'",
+            field.field_type.as_str(),
+            value,
+            field.name
+        ))
+    };
+
+    Ok(match field.field_type {
+        CustomFieldType::Text => FieldValue::Text(value.to_string()),
+        CustomFieldType::SingleSelect | CustomFieldType::MultiSelect => {
+            FieldValue::SingleSelect(value.to_string())
+        },
+        CustomFieldType::Number => FieldValue::Number(
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .ok_or_else(invalid)?,
+        ),
+        CustomFieldType::Date => FieldValue::Date(
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| invalid())?,
+        ),
+        CustomFieldType::User if value == "me" => FieldValue::User(user.id),
+        CustomFieldType::User => {
+            FieldValue::User(UserId::from_string(value).map_err(|_| invalid())?)
+        },
+    })
+}
+
+async fn list_project_fields(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<CustomFieldDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "ticket", "read")?;
+
+    let fields = state
+        .custom_field_repo
+        .find_by_project(project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to list custom fields: {:?}", e);
+            AppError::Internal("Failed to retrieve custom fields".to_string())
+        })?;
+
+    Ok(Json(fields.into_iter().map(CustomFieldDto::from).collect()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateCustomFieldRequest {
+    name: String,
+    field_type: CustomFieldType,
+    #[serde(default)]
+    options: Vec<String>,
+    #[serde(default)]
+    required: bool,
+    position: Option<i32>,
+}
+
+async fn create_custom_field(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateCustomFieldRequest>,
+) -> Result<Json<CustomFieldDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "update")?;
+
+    let mut field = CustomField::new(project_id, req.name, req.field_type);
+    field.options = req.options;
+    field.required = req.required;
+
+    // New fields go last unless a position is given
+    field.position = match req.position {
+        Some(position) => position,
+        None => state
+            .custom_field_repo
+            .find_by_project(project_id)
+            .map_err(|e| {
+                tracing::error!("Failed to list custom fields: {:?}", e);
+                AppError::Internal("Failed to retrieve custom fields".to_string())
+            })?
+            .iter()
+            .map(|f| f.position + 1)
+            .max()
+            .unwrap_or(0),
+    };
+
+    field.validate().map_err(|e| {
+        tracing::error!("Custom field validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created = state
+        .custom_field_repo
+        .create(&field)
+        .map_err(|e| custom_field_error(e, "create"))?;
+
+    Ok(Json(created.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateCustomFieldRequest {
+    name: Option<String>,
+    options: Option<Vec<String>>,
+    required: Option<bool>,
+    position: Option<i32>,
+}
+
+/// Update a custom field; its type cannot change, and ticket values holding
+/// a removed option are cleared
+async fn update_custom_field(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateCustomFieldRequest>,
+) -> Result<Json<CustomFieldDto>, AppError> {
+    let field_id = CustomFieldId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid custom field ID".to_string()))?;
+
+    let mut field = load_custom_field(&state, field_id)?;
+    authorize(&state, &user, Some(field.project_id), "project", "update")?;
+
+    if let Some(name) = req.name {
+        field.name = name;
+    }
+    if let Some(options) = req.options {
+        field.options = options;
+    }
+    if let Some(required) = req.required {
+        field.required = required;
+    }
+    if let Some(position) = req.position {
+        field.position = position;
+    }
+
+    field.validate().map_err(|e| {
+        tracing::error!("Custom field validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let updated = state
+        .custom_field_repo
+        .update(&field)
+        .map_err(|e| custom_field_error(e, "update"))?;
+
+    Ok(Json(updated.into()))
+}
+
+async fn delete_custom_field(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let field_id = CustomFieldId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid custom field ID".to_string()))?;
+
+    let field = load_custom_field(&state, field_id)?;
+    authorize(&state, &user, Some(field.project_id), "project", "update")?;
+
+    state
+        .custom_field_repo
+        .delete(field_id)
+        .map_err(|e| custom_field_error(e, "delete"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Workflow Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct WorkflowDto {
+    project_id: String,
+    statuses: Vec<WorkflowStatus>,
+    transitions: Vec<WorkflowTransition>,
+}
+
+impl From<Workflow> for WorkflowDto {
+    fn from(workflow: Workflow) -> Self {
+        Self {
+            project_id: workflow.project_id.to_string(),
+            statuses: workflow.statuses,
+            transitions: workflow.transitions,
+        }
+    }
+}
+
+/// Load the workflow of a project
+fn load_workflow(state: &AppState, project_id: ProjectId) -> Result<Workflow, AppError> {
+    state
+        .workflow_repo
+        .find_by_project(project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get workflow: {:?}", e);
+            AppError::Internal("Failed to retrieve workflow".to_string())
+        })
+}
+
+async fn get_project_workflow(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WorkflowDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "ticket", "read")?;
+
+    Ok(Json(load_workflow(&state, project_id)?.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateWorkflowRequest {
+    statuses: Vec<WorkflowStatus>,
+    #[serde(default)]
+    transitions: Vec<WorkflowTransition>,
+}
+
+/// Replace a project's workflow
+///
+/// Statuses that tickets are still in cannot be removed. Tickets in a status
+/// whose category changed are moved to the new category.
+async fn update_project_workflow(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateWorkflowRequest>,
+) -> Result<Json<WorkflowDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "update")?;
+
+    let before = load_workflow(&state, project_id)?;
+    let workflow = Workflow {
+        project_id,
+        statuses: req.statuses,
+        transitions: req.transitions,
+    };
+
+    workflow.validate().map_err(|e| {
+        tracing::error!("Workflow validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let saved = state.workflow_repo.save(&workflow).map_err(|e| {
+        tracing::error!("Failed to save workflow: {:?}", e);
+        match e {
+            DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+            _ => AppError::Internal("Failed to save workflow".to_string()),
+        }
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "workflow",
+        project_id.to_string(),
+        Some(&before),
+        Some(&saved),
+    );
+
+    Ok(Json(saved.into()))
+}
+
+// ============================================================================
+// Sprint Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct SprintDto {
+    id: String,
+    project_id: String,
+    name: String,
+    goal: Option<String>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    state: SprintState,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Sprint> for SprintDto {
+    fn from(sprint: Sprint) -> Self {
+        Self {
+            id: sprint.id.to_string(),
+            project_id: sprint.project_id.to_string(),
+            name: sprint.name,
+            goal: sprint.goal,
+            start_date: sprint.start_date,
+            end_date: sprint.end_date,
+            state: sprint.state,
+            created_at: sprint.created_at.to_rfc3339(),
+            updated_at: sprint.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Load a sprint, returning 404 when it does not exist
+fn load_sprint(state: &AppState, sprint_id: SprintId) -> Result<Sprint, AppError> {
+    state
+        .sprint_repo
+        .find_by_id(sprint_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get sprint: {:?}", e);
+            AppError::Internal("Failed to retrieve sprint".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Sprint not found".to_string()))
+}
+
+/// Map a sprint repository error, reporting rule violations as 400
+fn sprint_error(e: DbError, action: &str) -> AppError {
+    tracing::error!("Failed to {} sprint: {:?}", action, e);
+    match e {
+        DbError::NotFound(msg) => AppError::NotFound(msg),
+        DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+        _ => AppError::Internal(format!("Failed to {} sprint", action)),
+    }
+}
+
+/// Load the tickets planned into a sprint
+fn load_sprint_tickets(state: &AppState, sprint_id: SprintId) -> Result<Vec<Ticket>, AppError> {
+    state
+        .sprint_repo
+        .find_ticket_ids(sprint_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get sprint tickets: {:?}", e);
+            AppError::Internal("Failed to retrieve sprint tickets".to_string())
+        })?
+        .into_iter()
+        .map(|id| load_ticket(state, id))
+        .collect()
+}
+
+/// Record and publish an update of tickets a sprint change moved, given as
+/// they were before it, returning them as they are now
+fn record_sprint_moves(
+    state: &AppState,
+    user: &User,
+    project_id: ProjectId,
+    before: &[Ticket],
+) -> Result<Vec<TicketDto>, AppError> {
+    let mut after = Vec::with_capacity(before.len());
+    for ticket in before {
+        let updated = load_ticket(state, ticket.id)?;
+        record_activity(
+            state,
+            user,
+            ActivityAction::Updated,
+            "ticket",
+            ticket.id.to_string(),
+            Some(ticket),
+            Some(&updated),
+        );
+        after.push(updated);
+    }
+
+    let dtos = ticket_dtos(state, after)?;
+    for dto in &dtos {
+        publish_change(
+            state,
+            user,
+            project_id,
+            ChangeResource::Ticket,
+            ActivityAction::Updated,
+            dto.id.clone(),
+            Some(dto),
+        );
+    }
+
+    Ok(dtos)
+}
+
+async fn list_project_sprints(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<SprintDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "ticket", "read")?;
+
+    let sprints = state.sprint_repo.find_by_project(project_id).map_err(|e| {
+        tracing::error!("Failed to list sprints: {:?}", e);
+        AppError::Internal("Failed to retrieve sprints".to_string())
+    })?;
+
+    Ok(Json(sprints.into_iter().map(SprintDto::from).collect()))
+}
+
+async fn get_sprint(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SprintDto>, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+
+    let sprint = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(sprint.project_id), "ticket", "read")?;
+
+    Ok(Json(sprint.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateSprintRequest {
+    name: String,
+    goal: Option<String>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+
+async fn create_sprint(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateSprintRequest>,
+) -> Result<Json<SprintDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
+
+    authorize(&state, &user, Some(project_id), "project", "update")?;
+
+    let mut sprint = Sprint::new(project_id, req.name);
+    sprint.goal = req.goal.filter(|goal| !goal.trim().is_empty());
+    sprint.start_date = req.start_date;
+    sprint.end_date = req.end_date;
+
+    sprint.validate().map_err(|e| {
+        tracing::error!("Sprint validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created = state
+        .sprint_repo
+        .create(&sprint)
+        .map_err(|e| sprint_error(e, "create"))?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Created,
+        "sprint",
+        created.id.to_string(),
+        None,
+        Some(&created),
+    );
+
+    Ok(Json(created.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateSprintRequest {
+    name: Option<String>,
+    goal: Option<String>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+
+async fn update_sprint(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateSprintRequest>,
+) -> Result<Json<SprintDto>, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+
+    let mut sprint = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(sprint.project_id), "project", "update")?;
+    let before = sprint.clone();
+
+    if let Some(name) = req.name {
+        sprint.name = name;
+    }
+    if let Some(goal) = req.goal {
+        sprint.goal = Some(goal).filter(|goal| !goal.trim().is_empty());
+    }
+    if let Some(start_date) = req.start_date {
+        sprint.start_date = Some(start_date);
+    }
+    if let Some(end_date) = req.end_date {
+        sprint.end_date = Some(end_date);
+    }
+
+    sprint.validate().map_err(|e| {
+        tracing::error!("Sprint validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let updated = state
+        .sprint_repo
+        .update(&sprint)
+        .map_err(|e| sprint_error(e, "update"))?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "sprint",
+        sprint_id.to_string(),
+        Some(&before),
+        Some(&updated),
+    );
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete a sprint; its tickets go back to the backlog
+async fn delete_sprint(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+
+    let sprint = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(sprint.project_id), "project", "update")?;
+
+    let tickets = load_sprint_tickets(&state, sprint_id)?;
+
+    state
+        .sprint_repo
+        .delete(sprint_id)
+        .map_err(|e| sprint_error(e, "delete"))?;
+
+    record_sprint_moves(&state, &user, sprint.project_id, &tickets)?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Deleted,
+        "sprint",
+        sprint_id.to_string(),
+        Some(&sprint),
+        None,
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Start a planned sprint; it needs start and end dates and no other sprint
+/// of the project may be active
+async fn start_sprint(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SprintDto>, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+
+    let before = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(before.project_id), "project", "update")?;
+
+    let mut sprint = before.clone();
+    sprint.state = SprintState::Active;
+    sprint
+        .validate()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    state
+        .sprint_repo
+        .start(sprint_id)
+        .map_err(|e| sprint_error(e, "start"))?;
+
+    let started = load_sprint(&state, sprint_id)?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "sprint",
+        sprint_id.to_string(),
+        Some(&before),
+        Some(&started),
+    );
+
+    Ok(Json(started.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CloseSprintRequest {
+    /// Planned sprint that unfinished tickets move to; the backlog if absent
+    move_to: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CloseSprintResponse {
+    sprint: SprintDto,
+    /// Number of unfinished tickets moved out of the sprint
+    moved: usize,
+}
+
+/// Close the active sprint, moving its unfinished tickets to the next
+/// sprint or the backlog
+async fn close_sprint(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<CloseSprintRequest>,
+) -> Result<Json<CloseSprintResponse>, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+    let move_to = req
+        .move_to
+        .filter(|id| !id.is_empty())
+        .map(|id| SprintId::from_string(&id))
+        .transpose()
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+
+    let before = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(before.project_id), "project", "update")?;
+
+    let tickets = load_sprint_tickets(&state, sprint_id)?;
+
+    let moved = state
+        .sprint_repo
+        .close(sprint_id, move_to)
+        .map_err(|e| sprint_error(e, "close"))?;
+
+    let tickets: Vec<Ticket> = tickets
+        .into_iter()
+        .filter(|ticket| moved.contains(&ticket.id))
+        .collect();
+    record_sprint_moves(&state, &user, before.project_id, &tickets)?;
+
+    let closed = load_sprint(&state, sprint_id)?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "sprint",
+        sprint_id.to_string(),
+        Some(&before),
+        Some(&closed),
+    );
+
+    Ok(Json(CloseSprintResponse {
+        sprint: closed.into(),
+        moved: moved.len(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct AddSprintTicketsRequest {
+    ticket_ids: Vec<String>,
+}
+
+/// Plan tickets of the sprint's project into the sprint
+async fn add_sprint_tickets(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<AddSprintTicketsRequest>,
+) -> Result<Json<Vec<TicketDto>>, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+
+    let sprint = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(sprint.project_id), "ticket", "update")?;
+
+    let ticket_ids = req
+        .ticket_ids
+        .iter()
+        .map(|id| TicketId::from_string(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let before = ticket_ids
+        .iter()
+        .map(|id| load_ticket(&state, *id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    state
+        .sprint_repo
+        .add_tickets(sprint_id, &ticket_ids)
+        .map_err(|e| sprint_error(e, "plan"))?;
+
+    let dtos = record_sprint_moves(&state, &user, sprint.project_id, &before)?;
+
+    Ok(Json(dtos))
+}
+
+/// Move a ticket of the sprint back to the backlog
+async fn remove_sprint_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, ticket_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let sprint_id = SprintId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid sprint ID".to_string()))?;
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let sprint = load_sprint(&state, sprint_id)?;
+    authorize(&state, &user, Some(sprint.project_id), "ticket", "update")?;
+
+    let before = load_ticket(&state, ticket_id)?;
+
+    state
+        .sprint_repo
+        .remove_ticket(sprint_id, ticket_id)
+        .map_err(|e| sprint_error(e, "update"))?;
+
+    let after = load_ticket(&state, ticket_id)?;
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "ticket",
+        ticket_id.to_string(),
+        Some(&before),
+        Some(&after),
+    );
+
+    let dto = ticket_dto(&state, after)?;
+    publish_change(
+        &state,
+        &user,
+        sprint.project_id,
+        ChangeResource::Ticket,
+        ActivityAction::Updated,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Saved View Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct SavedViewDto {
+    id: String,
+    owner_id: String,
+    project_id: Option<String>,
+    name: String,
+    query: String,
+    sort: String,
+    display: ViewDisplay,
+    shared: bool,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<SavedView> for SavedViewDto {
+    fn from(view: SavedView) -> Self {
+        Self {
+            id: view.id.to_string(),
+            owner_id: view.owner_id.to_string(),
+            project_id: view.project_id.map(|id| id.to_string()),
+            name: view.name,
+            query: view.query,
+            sort: view.sort,
+            display: view.display,
+            shared: view.shared,
+            created_at: view.created_at.to_rfc3339(),
+            updated_at: view.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Load a saved view the user may open, returning 404 for missing views and
+/// for other users' personal views
+fn load_visible_view(
+    state: &AppState,
+    user: &User,
+    view_id: SavedViewId,
+) -> Result<SavedView, AppError> {
+    let view = state
+        .saved_view_repo
+        .find_by_id(view_id)
+        .map_err(|e| {
+            tracing::error!("Failed to load saved view: {:?}", e);
+            AppError::Internal("Failed to retrieve saved view".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Saved view not found".to_string()))?;
+
+    if view.owner_id == user.id {
+        return Ok(view);
+    }
+
+    match view.project_id {
+        Some(project_id) if view.shared => {
+            authorize(state, user, Some(project_id), "ticket", "read")?;
+            Ok(view)
+        },
+        _ => Err(AppError::NotFound("Saved view not found".to_string())),
+    }
+}
+
+/// Require that the user owns a view or, for shared views, administers its
+/// project
+fn authorize_view_owner(state: &AppState, user: &User, view: &SavedView) -> Result<(), AppError> {
+    match view.project_id {
+        Some(project_id) if view.shared => {
+            authorize_owner_or_admin(state, user, view.owner_id, project_id)
+        },
+        _ if view.owner_id == user.id => Ok(()),
+        _ => Err(AppError::Forbidden(
+            "Only the owner can change a personal view".to_string(),
+        )),
+    }
+}
+
+/// Check a view's sort list and the user's access to its project, then
+/// validate the view itself
+fn validate_view(state: &AppState, user: &User, view: &mut SavedView) -> Result<(), AppError> {
+    view.sort = view
+        .sort
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| parse_sort(item).map(|_| item))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(",");
+
+    view.validate().map_err(|e| {
+        tracing::error!("Saved view validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    if let Some(project_id) = view.project_id {
+        authorize(state, user, Some(project_id), "ticket", "read")?;
+    }
+
+    Ok(())
+}
+
+async fn list_views(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SavedViewDto>>, AppError> {
+    let views = state
+        .saved_view_repo
+        .find_visible_to(user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to list saved views: {:?}", e);
+            AppError::Internal("Failed to retrieve saved views".to_string())
+        })?;
+
+    Ok(Json(views.into_iter().map(SavedViewDto::from).collect()))
+}
+
+async fn get_view(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SavedViewDto>, AppError> {
+    let view_id = SavedViewId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid view ID".to_string()))?;
+
+    Ok(Json(load_visible_view(&state, &user, view_id)?.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateSavedViewRequest {
+    name: String,
+    #[serde(default)]
+    query: String,
+    #[serde(default)]
+    sort: String,
+    display: Option<ViewDisplay>,
+    project_id: Option<String>,
+    #[serde(default)]
+    shared: bool,
+}
+
+async fn create_view(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<CreateSavedViewRequest>,
+) -> Result<Json<SavedViewDto>, AppError> {
+    let mut view = SavedView::new(user.id, req.name, req.query);
+    view.sort = req.sort;
+    view.display = req.display.unwrap_or(ViewDisplay::List);
+    view.shared = req.shared;
+    view.project_id = req
+        .project_id
+        .map(|id| {
+            ProjectId::from_string(&id)
+                .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))
+        })
+        .transpose()?;
+
+    validate_view(&state, &user, &mut view)?;
+
+    let created = state.saved_view_repo.create(&view).map_err(|e| {
+        tracing::error!("Failed to create saved view: {:?}", e);
+        AppError::Internal("Failed to create saved view".to_string())
+    })?;
+
+    Ok(Json(created.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateSavedViewRequest {
+    name: Option<String>,
+    query: Option<String>,
+    sort: Option<String>,
+    display: Option<ViewDisplay>,
+    project_id: Option<String>,
+    shared: Option<bool>,
+}
+
+async fn update_view(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateSavedViewRequest>,
+) -> Result<Json<SavedViewDto>, AppError> {
+    let view_id = SavedViewId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid view ID".to_string()))?;
+
+    let mut view = load_visible_view(&state, &user, view_id)?;
+    authorize_view_owner(&state, &user, &view)?;
+
+    if let Some(name) = req.name {
+        view.name = name;
+    }
+    if let Some(query) = req.query {
+        view.query = query;
+    }
+    if let Some(sort) = req.sort {
+        view.sort = sort;
+    }
+    if let Some(display) = req.display {
+        view.display = display;
+    }
+    if let Some(project_id) = req.project_id {
+        view.project_id = Some(
+            ProjectId::from_string(&project_id)
+                .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?,
+        );
+    }
+    if let Some(shared) = req.shared {
+        view.shared = shared;
+    }
+
+    validate_view(&state, &user, &mut view)?;
+
+    let updated = state.saved_view_repo.update(&view).map_err(|e| {
+        tracing::error!("Failed to update saved view: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Saved view not found".to_string()),
+            _ => AppError::Internal("Failed to update saved view".to_string()),
+        }
+    })?;
+
+    Ok(Json(updated.into()))
+}
+
+async fn delete_view(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let view_id = SavedViewId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid view ID".to_string()))?;
+
+    let view = load_visible_view(&state, &user, view_id)?;
+    authorize_view_owner(&state, &user, &view)?;
+
+    state.saved_view_repo.delete(view_id).map_err(|e| {
+        tracing::error!("Failed to delete saved view: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Saved view not found".to_string()),
+            _ => AppError::Internal("Failed to delete saved view".to_string()),
+        }
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Ticket Dependency Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct DependenciesDto {
+    blocked_by: Vec<TicketDto>,
+    blocks: Vec<TicketDto>,
+}
+
+/// Load both sides of a ticket's dependencies, limited to readable tickets
+fn load_dependencies(
+    state: &AppState,
+    user: &User,
+    ticket_id: TicketId,
+) -> Result<DependenciesDto, AppError> {
+    let map_err = |e: DbError| {
+        tracing::error!("Failed to get ticket dependencies: {:?}", e);
+        AppError::Internal("Failed to retrieve ticket dependencies".to_string())
+    };
+
+    let mut blocked_by = Vec::new();
+    for ticket in state
+        .dependency_repo
+        .find_blockers(ticket_id)
+        .map_err(map_err)?
+    {
+        if can(state, user, Some(ticket.project_id), "ticket", "read")? {
+            blocked_by.push(ticket);
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for ticket in state
+        .dependency_repo
+        .find_blocked(ticket_id)
+        .map_err(map_err)?
+    {
+        if can(state, user, Some(ticket.project_id), "ticket", "read")? {
+            blocks.push(ticket);
+        }
+    }
+
+    Ok(DependenciesDto {
+        blocked_by: ticket_dtos(state, blocked_by)?,
+        blocks: ticket_dtos(state, blocks)?,
+    })
+}
+
+async fn list_dependencies(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<DependenciesDto>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    Ok(Json(load_dependencies(&state, &user, ticket_id)?))
+}
+
+#[derive(Debug, Deserialize)]
+struct AddDependencyRequest {
+    depends_on: String,
+}
+
+async fn add_dependency(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<AddDependencyRequest>,
+) -> Result<(StatusCode, Json<DependenciesDto>), AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    let depends_on_id = TicketId::from_string(&req.depends_on)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+
+    let blocker = load_ticket(&state, depends_on_id)?;
+    authorize(&state, &user, Some(blocker.project_id), "ticket", "read")?;
+
+    state
+        .dependency_repo
+        .create(&TicketDependency::new(ticket_id, depends_on_id))
+        .map_err(|e| {
+            tracing::error!("Failed to add ticket dependency: {:?}", e);
+            match e {
+                DbError::ConstraintViolation(msg) => AppError::BadRequest(msg),
+                _ => AppError::Internal("Failed to add ticket dependency".to_string()),
+            }
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(load_dependencies(&state, &user, ticket_id)?),
+    ))
+}
+
+async fn remove_dependency(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, depends_on_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    let depends_on_id = TicketId::from_string(&depends_on_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+
+    state
+        .dependency_repo
+        .delete(ticket_id, depends_on_id)
+        .map_err(|e| {
+            tracing::error!("Failed to remove ticket dependency: {:?}", e);
+            match e {
+                DbError::NotFound(_) => AppError::NotFound("Dependency not found".to_string()),
+                _ => AppError::Internal("Failed to remove ticket dependency".to_string()),
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Comment Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct CommentDto {
+    id: String,
+    ticket_id: String,
+    user_id: String,
+    content: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Comment> for CommentDto {
+    fn from(comment: Comment) -> Self {
+        Self {
+            id: comment.id.to_string(),
+            ticket_id: comment.ticket_id.to_string(),
+            user_id: comment.user_id.to_string(),
+            content: comment.content,
+            created_at: comment.created_at.to_rfc3339(),
+            updated_at: comment.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+async fn list_comments_for_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Vec<CommentDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let comments = state.comment_repo.find_by_ticket(ticket_id).map_err(|e| {
+        tracing::error!("Failed to list comments: {:?}", e);
+        AppError::Internal("Failed to retrieve comments".to_string())
+    })?;
+
+    Ok(Json(comments.into_iter().map(CommentDto::from).collect()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateCommentRequest {
+    content: String,
+}
+
+async fn create_comment(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(ticket_id): Path<String>,
+    Json(req): Json<CreateCommentRequest>,
+) -> Result<Json<CommentDto>, AppError> {
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "comment", "create")?;
+
+    let comment = Comment::new(ticket_id, user.id, req.content);
+
+    // Validate
+    comment.validate().map_err(|e| {
+        tracing::error!("Comment validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created_comment = state.comment_repo.create(&comment).map_err(|e| {
+        tracing::error!("Failed to create comment: {:?}", e);
+        AppError::Internal("Failed to create comment".to_string())
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Created,
+        "comment",
+        created_comment.id.to_string(),
+        None,
+        Some(&created_comment),
+    );
+
+    // Mentioned users hear about the mention rather than the comment
+    let mut recipients: Vec<_> = mentioned_users(&state, &created_comment.mentions())
+        .into_iter()
+        .map(|id| (id, NotificationKind::Mentioned))
+        .collect();
+    recipients.extend(watcher_recipients(
+        &state,
+        ticket_id,
+        NotificationKind::Commented,
+    ));
+    notify_users(
+        &state,
+        &user,
+        &ticket,
+        &ticket_key(&state, &ticket)?,
+        &recipients,
+    );
+    add_watcher(&state, ticket_id, user.id);
+
+    let dto = CommentDto::from(created_comment);
+    publish_change(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Comment,
+        ActivityAction::Created,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(Json(dto))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateCommentRequest {
+    content: String,
+}
+
+async fn update_comment(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateCommentRequest>,
+) -> Result<Json<CommentDto>, AppError> {
+    let comment_id = CommentId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid comment ID".to_string()))?;
+
+    let mut comment = state
+        .comment_repo
+        .find_by_id(comment_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get comment: {:?}", e);
+            AppError::Internal("Failed to retrieve comment".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+
+    let ticket = load_ticket(&state, comment.ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "comment", "update")?;
+    authorize_owner_or_admin(&state, &user, comment.user_id, ticket.project_id)?;
+    let before = comment.clone();
+
+    comment.content = req.content;
+
+    // Validate
+    comment.validate().map_err(|e| {
+        tracing::error!("Comment validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let updated_comment = state.comment_repo.update(&comment).map_err(|e| {
+        tracing::error!("Failed to update comment: {:?}", e);
+        AppError::Internal("Failed to update comment".to_string())
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "comment",
+        updated_comment.id.to_string(),
+        Some(&before),
+        Some(&updated_comment),
+    );
+
+    // Only users newly mentioned by the edit are notified
+    let mentioned_before = before.mentions();
+    let new_mentions: Vec<String> = updated_comment
+        .mentions()
+        .into_iter()
+        .filter(|m| !mentioned_before.contains(m))
+        .collect();
+    if !new_mentions.is_empty() {
+        let recipients: Vec<_> = mentioned_users(&state, &new_mentions)
+            .into_iter()
+            .map(|id| (id, NotificationKind::Mentioned))
+            .collect();
+        notify_users(
+            &state,
+            &user,
+            &ticket,
+            &ticket_key(&state, &ticket)?,
+            &recipients,
+        );
+    }
+
+    let dto = CommentDto::from(updated_comment);
+    publish_change(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Comment,
+        ActivityAction::Updated,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(Json(dto))
+}
+
+async fn delete_comment(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let comment_id = CommentId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid comment ID".to_string()))?;
+
+    let comment = state
+        .comment_repo
+        .find_by_id(comment_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get comment: {:?}", e);
+            AppError::Internal("Failed to retrieve comment".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+
+    let ticket = load_ticket(&state, comment.ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "comment", "delete")?;
+    authorize_owner_or_admin(&state, &user, comment.user_id, ticket.project_id)?;
+
+    state.comment_repo.delete(comment_id).map_err(|e| {
+        tracing::error!("Failed to delete comment: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Comment not found".to_string()),
+            _ => AppError::Internal("Failed to delete comment".to_string()),
+        }
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Deleted,
+        "comment",
+        comment_id.to_string(),
+        Some(&comment),
+        None,
+    );
+    publish_change::<CommentDto>(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Comment,
+        ActivityAction::Deleted,
+        comment_id.to_string(),
+        None,
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Watcher & Notification Routes
+// ============================================================================
+
+/// Default and maximum number of notifications returned at once
+const DEFAULT_NOTIFICATION_LIMIT: u32 = 50;
+const MAX_NOTIFICATION_LIMIT: u32 = 200;
+
+#[derive(Debug, Serialize)]
+struct NotificationDto {
+    id: String,
+    user_id: String,
+    actor_id: String,
+    ticket_id: String,
+    kind: NotificationKind,
+    message: String,
+    /// When the notification was read; `null` while unread
+    read_at: Option<String>,
+    created_at: String,
+}
+
+impl From<Notification> for NotificationDto {
+    fn from(notification: Notification) -> Self {
+        Self {
+            id: notification.id.to_string(),
+            user_id: notification.user_id.to_string(),
+            actor_id: notification.actor_id.to_string(),
+            ticket_id: notification.ticket_id.to_string(),
+            kind: notification.kind,
+            message: notification.message,
+            read_at: notification.read_at.map(|t| t.to_rfc3339()),
+            created_at: notification.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct UnreadCountResponse {
+    count: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct MarkAllReadResponse {
+    marked: usize,
+}
+
+/// Add a user to a ticket's watchers
+///
+/// Failures are logged rather than returned since the change that made the
+/// user a watcher has already been applied.
+fn add_watcher(state: &AppState, ticket_id: TicketId, user_id: UserId) {
+    if let Err(e) = state.watcher_repo.watch(ticket_id, user_id) {
+        tracing::error!("Failed to watch ticket: {:?}", e);
+    }
+}
+
+/// Notify users about a change `actor` made to a ticket
+///
+/// Each recipient is notified once, for the first reason listed for them.
+/// The actor and users who cannot read the ticket are skipped. Failures are
+/// logged rather than returned since the change itself has already been
+/// applied.
+fn notify_users(
+    state: &AppState,
+    actor: &User,
+    ticket: &Ticket,
+    ticket_key: &str,
+    recipients: &[(UserId, NotificationKind)],
+) {
+    // Status changes name the new status
+    let status_name = if recipients
+        .iter()
+        .any(|(_, kind)| *kind == NotificationKind::StatusChanged)
+    {
+        load_workflow(state, ticket.project_id)
+            .ok()
+            .and_then(|w| w.status(&ticket.status).map(|s| s.name.clone()))
+            .unwrap_or_else(|| ticket.status.clone())
+    } else {
+        String::new()
+    };
+
+    let mut notified = vec![actor.id];
+    for &(user_id, kind) in recipients {
+        if notified.contains(&user_id) {
+            continue;
+        }
+        notified.push(user_id);
+
+        match state.permission_checker.has_permission(
+            user_id,
+            Some(ticket.project_id),
+            "ticket",
+            "read",
+        ) {
+            Ok(true) => {},
+            Ok(false) => continue,
+            Err(e) => {
+                tracing::error!("Permission check failed: {:?}", e);
+                continue;
+            },
+        }
+
+        let message = match kind {
+            NotificationKind::Mentioned => {
+                format!("{} mentioned you on {}", actor.username, ticket_key)
+            },
+            NotificationKind::Commented => {
+                format!("{} commented on {}", actor.username, ticket_key)
+            },
+            NotificationKind::Assigned => {
+                format!("{} assigned {} to you", actor.username, ticket_key)
+            },
+            NotificationKind::StatusChanged => {
+                format!("{} moved {} to {}", actor.username, ticket_key, status_name)
+            },
+            NotificationKind::Updated => format!("{} updated {}", actor.username, ticket_key),
+        };
+        let notification = Notification::new(user_id, actor.id, ticket.id, kind, message);
+        match state.notification_repo.create(&notification) {
+            Ok(notification) => email_notification(state, &notification, ticket),
+            Err(e) => tracing::error!("Failed to create notification: {:?}", e),
+        }
+    }
+}
+
+/// Watchers of a ticket, each with the same notification kind
+fn watcher_recipients(
+    state: &AppState,
+    ticket_id: TicketId,
+    kind: NotificationKind,
+) -> Vec<(UserId, NotificationKind)> {
+    match state.watcher_repo.find_watchers(ticket_id) {
+        Ok(watchers) => watchers.into_iter().map(|id| (id, kind)).collect(),
+        Err(e) => {
+            tracing::error!("Failed to get ticket watchers: {:?}", e);
+            Vec::new()
+        },
+    }
+}
+
+/// Users mentioned in a comment; unknown usernames are ignored
+fn mentioned_users(state: &AppState, mentions: &[String]) -> Vec<UserId> {
+    mentions
+        .iter()
+        .filter_map(
+            |username| match state.user_repo.find_by_username(username) {
+                Ok(user) => user.map(|u| u.id),
+                Err(e) => {
+                    tracing::error!("Failed to look up mentioned user: {:?}", e);
+                    None
+                },
+            },
+        )
+        .collect()
+}
+
+/// Human-readable key of a ticket, such as `WN-12`
+fn ticket_key(state: &AppState, ticket: &Ticket) -> Result<String, AppError> {
+    let project = state
+        .project_repo
+        .find_by_id(ticket.project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get project: {:?}", e);
+            AppError::Internal("Failed to retrieve project".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+    Ok(TicketKey::new(project.key, ticket.number).to_string())
+}
+
+async fn list_notifications(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<Vec<NotificationDto>>, AppError> {
+    let unread_only = params.get("unread").is_some_and(|v| v == "true");
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(DEFAULT_NOTIFICATION_LIMIT)
+        .clamp(1, MAX_NOTIFICATION_LIMIT);
+
+    let notifications = state
+        .notification_repo
+        .find_for_user(user.id, unread_only, Some(limit))
+        .map_err(|e| {
+            tracing::error!("Failed to list notifications: {:?}", e);
+            AppError::Internal("Failed to retrieve notifications".to_string())
+        })?;
+
+    Ok(Json(
+        notifications
+            .into_iter()
+            .map(NotificationDto::from)
+            .collect(),
+    ))
+}
+
+async fn get_unread_notification_count(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<UnreadCountResponse>, AppError> {
+    let count = state.notification_repo.unread_count(user.id).map_err(|e| {
+        tracing::error!("Failed to count notifications: {:?}", e);
+        AppError::Internal("Failed to retrieve notifications".to_string())
+    })?;
+
+    Ok(Json(UnreadCountResponse { count }))
+}
+
+async fn mark_notification_read(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<NotificationDto>, AppError> {
+    let notification_id = NotificationId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid notification ID".to_string()))?;
+
+    let notification = state
+        .notification_repo
+        .mark_read(notification_id, user.id)
+        .map_err(|e| match e {
+            DbError::NotFound(_) => AppError::NotFound("Notification not found".to_string()),
+            _ => {
+                tracing::error!("Failed to mark notification read: {:?}", e);
+                AppError::Internal("Failed to update notification".to_string())
+            },
+        })?;
+
+    Ok(Json(NotificationDto::from(notification)))
+}
+
+async fn mark_all_notifications_read(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<MarkAllReadResponse>, AppError> {
+    let marked = state
+        .notification_repo
+        .mark_all_read(user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to mark notifications read: {:?}", e);
+            AppError::Internal("Failed to update notifications".to_string())
+        })?;
+
+    Ok(Json(MarkAllReadResponse { marked }))
+}
+
+async fn list_ticket_watchers(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<UserDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let watcher_ids = state.watcher_repo.find_watchers(ticket_id).map_err(|e| {
+        tracing::error!("Failed to get ticket watchers: {:?}", e);
+        AppError::Internal("Failed to retrieve watchers".to_string())
+    })?;
+
+    let mut watchers = Vec::with_capacity(watcher_ids.len());
+    for watcher_id in watcher_ids {
+        let watcher = state.user_repo.find_by_id(watcher_id).map_err(|e| {
+            tracing::error!("Failed to get user: {:?}", e);
+            AppError::Internal("Failed to retrieve watchers".to_string())
+        })?;
+        watchers.extend(watcher.map(UserDto::from));
+    }
+
+    Ok(Json(watchers))
+}
+
+async fn watch_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    state.watcher_repo.watch(ticket_id, user.id).map_err(|e| {
+        tracing::error!("Failed to watch ticket: {:?}", e);
+        AppError::Internal("Failed to watch ticket".to_string())
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn unwatch_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    load_ticket(&state, ticket_id)?;
+
+    state
+        .watcher_repo
+        .unwatch(ticket_id, user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to unwatch ticket: {:?}", e);
+            AppError::Internal("Failed to unwatch ticket".to_string())
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Email Notifications
+// ============================================================================
+
+/// How often the digest scheduler looks for digests that are due
+const DIGEST_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+#[derive(Debug, Serialize)]
+struct NotificationPreferencesDto {
+    user_id: String,
+    email_assigned: bool,
+    email_mentioned: bool,
+    email_status_changed: bool,
+    daily_digest: bool,
+    digest_hour: u8,
+    last_digest_at: Option<String>,
+}
+
+impl From<NotificationPreferences> for NotificationPreferencesDto {
+    fn from(preferences: NotificationPreferences) -> Self {
+        Self {
+            user_id: preferences.user_id.to_string(),
+            email_assigned: preferences.email_assigned,
+            email_mentioned: preferences.email_mentioned,
+            email_status_changed: preferences.email_status_changed,
+            daily_digest: preferences.daily_digest,
+            digest_hour: preferences.digest_hour,
+            last_digest_at: preferences.last_digest_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// Send an email in the background, logging failures
+fn send_email(mailer: Arc<dyn Mailer>, email: Email) {
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = mailer.send(&email) {
+            tracing::error!("Failed to send email to {}: {:?}", email.to, e);
+        }
+    });
+}
+
+/// Email a notification right away when its recipient asked for it
+fn email_notification(state: &AppState, notification: &Notification, ticket: &Ticket) {
+    let Some(mailer) = &state.mailer else {
+        return;
+    };
+
+    let wants_email = state
+        .notification_preference_repo
+        .find(notification.user_id)
+        .map(|preferences| preferences.emails(notification.kind));
+    match wants_email {
+        Ok(true) => {},
+        Ok(false) => return,
+        Err(e) => {
+            tracing::error!("Failed to get notification preferences: {:?}", e);
+            return;
+        },
+    }
+
+    let user = match state.user_repo.find_by_id(notification.user_id) {
+        Ok(Some(user)) => user,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to get user: {:?}", e);
+            return;
+        },
+    };
+
+    let email =
+        mailer::notification_email(&user, notification, &ticket.title, state.app_url.as_deref());
+    send_email(Arc::clone(mailer), email);
+}
+
+/// Send the daily digests as they come due, until the server stops
+async fn run_digest_scheduler(state: AppState) {
+    let mut interval = tokio::time::interval(DIGEST_CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_due_digests(&state).await {
+            tracing::error!("Failed to send digests: {:?}", e);
+        }
+    }
+}
+
+/// Send a digest of unread notifications to every subscriber whose digest
+/// is due
+///
+/// Subscribers without new unread notifications get no email, but still
+/// count as served for the day.
+async fn send_due_digests(state: &AppState) -> Result<(), DbError> {
+    let Some(mailer) = &state.mailer else {
+        return Ok(());
+    };
+
+    let now = chrono::Utc::now();
+    for preferences in state
+        .notification_preference_repo
+        .find_digest_subscribers()?
+    {
+        if !preferences.digest_due(now) {
+            continue;
+        }
+
+        let notifications = state
+            .notification_repo
+            .find_unread_since(preferences.user_id, preferences.last_digest_at)?;
+        let user = state.user_repo.find_by_id(preferences.user_id)?;
+
+        if let (Some(user), false) = (user, notifications.is_empty()) {
+            let email = mailer::digest_email(&user, &notifications, state.app_url.as_deref());
+            let mailer = Arc::clone(mailer);
+            let sent = tokio::task::spawn_blocking(move || mailer.send(&email)).await;
+            match sent {
+                Ok(Ok(())) => {},
+                // Retried at the next check
+                Ok(Err(e)) => {
+                    tracing::error!("Failed to send digest to {}: {:?}", user.username, e);
+                    continue;
+                },
+                Err(e) => {
+                    tracing::error!("Digest task failed: {:?}", e);
+                    continue;
+                },
+            }
+        }
+
+        state
+            .notification_preference_repo
+            .mark_digest_sent(preferences.user_id, now)?;
+    }
+
+    Ok(())
+}
+
+async fn get_notification_preferences(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<NotificationPreferencesDto>, AppError> {
+    let preferences = state
+        .notification_preference_repo
+        .find(user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to get notification preferences: {:?}", e);
+            AppError::Internal("Failed to retrieve notification preferences".to_string())
+        })?;
+
+    Ok(Json(preferences.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateNotificationPreferencesRequest {
+    email_assigned: Option<bool>,
+    email_mentioned: Option<bool>,
+    email_status_changed: Option<bool>,
+    daily_digest: Option<bool>,
+    digest_hour: Option<u8>,
+}
+
+async fn update_notification_preferences(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Json(req): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<Json<NotificationPreferencesDto>, AppError> {
+    let mut preferences = state
+        .notification_preference_repo
+        .find(user.id)
+        .map_err(|e| {
+            tracing::error!("Failed to get notification preferences: {:?}", e);
+            AppError::Internal("Failed to retrieve notification preferences".to_string())
+        })?;
+
+    if let Some(email_assigned) = req.email_assigned {
+        preferences.email_assigned = email_assigned;
+    }
+    if let Some(email_mentioned) = req.email_mentioned {
+        preferences.email_mentioned = email_mentioned;
+    }
+    if let Some(email_status_changed) = req.email_status_changed {
+        preferences.email_status_changed = email_status_changed;
+    }
+    if let Some(daily_digest) = req.daily_digest {
+        preferences.daily_digest = daily_digest;
+    }
+    if let Some(digest_hour) = req.digest_hour {
+        preferences.digest_hour = digest_hour;
+    }
+
+    preferences
+        .validate()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let saved = state
+        .notification_preference_repo
+        .save(&preferences)
+        .map_err(|e| {
+            tracing::error!("Failed to save notification preferences: {:?}", e);
+            AppError::Internal("Failed to save notification preferences".to_string())
+        })?;
+
+    Ok(Json(saved.into()))
+}
+
+// ============================================================================
+// Worklog Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct WorklogDto {
+    id: String,
+    ticket_id: String,
+    user_id: String,
+    started_at: String,
+    duration_minutes: u32,
+    note: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<Worklog> for WorklogDto {
+    fn from(worklog: Worklog) -> Self {
+        Self {
+            id: worklog.id.to_string(),
+            ticket_id: worklog.ticket_id.to_string(),
+            user_id: worklog.user_id.to_string(),
+            started_at: worklog.started_at.to_rfc3339(),
+            duration_minutes: worklog.duration_minutes,
+            note: worklog.note,
+            created_at: worklog.created_at.to_rfc3339(),
+            updated_at: worklog.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Load a worklog, returning 404 when it does not exist
+fn load_worklog(state: &AppState, worklog_id: WorklogId) -> Result<Worklog, AppError> {
+    state
+        .worklog_repo
+        .find_by_id(worklog_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get worklog: {:?}", e);
+            AppError::Internal("Failed to retrieve worklog".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Worklog not found".to_string()))
+}
+
+async fn list_worklogs_for_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Vec<WorklogDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let worklogs = state.worklog_repo.find_by_ticket(ticket_id).map_err(|e| {
+        tracing::error!("Failed to list worklogs: {:?}", e);
+        AppError::Internal("Failed to retrieve worklogs".to_string())
+    })?;
+
+    Ok(Json(worklogs.into_iter().map(WorklogDto::from).collect()))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateWorklogRequest {
+    /// Defaults to `duration_minutes` before now, for time just spent
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    duration_minutes: u32,
+    note: Option<String>,
+}
+
+/// Log time against a ticket; the time is always logged for the caller
+async fn create_worklog(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(ticket_id): Path<String>,
+    Json(req): Json<CreateWorklogRequest>,
+) -> Result<Json<WorklogDto>, AppError> {
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+
+    let started_at = req.started_at.unwrap_or_else(|| {
+        chrono::Utc::now() - chrono::Duration::minutes(i64::from(req.duration_minutes))
+    });
+    let mut worklog = Worklog::new(ticket_id, user.id, started_at, req.duration_minutes);
+    worklog.note = req.note.filter(|note| !note.trim().is_empty());
+
+    // Validate
+    worklog.validate().map_err(|e| {
+        tracing::error!("Worklog validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created_worklog = state.worklog_repo.create(&worklog).map_err(|e| {
+        tracing::error!("Failed to create worklog: {:?}", e);
+        AppError::Internal("Failed to create worklog".to_string())
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Created,
+        "worklog",
+        created_worklog.id.to_string(),
+        None,
+        Some(&created_worklog),
+    );
+
+    Ok(Json(created_worklog.into()))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateWorklogRequest {
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    duration_minutes: Option<u32>,
+    /// An empty note removes it
+    note: Option<String>,
+}
+
+async fn update_worklog(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateWorklogRequest>,
+) -> Result<Json<WorklogDto>, AppError> {
+    let worklog_id = WorklogId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid worklog ID".to_string()))?;
+
+    let mut worklog = load_worklog(&state, worklog_id)?;
+    let ticket = load_ticket(&state, worklog.ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+    authorize_owner_or_admin(&state, &user, worklog.user_id, ticket.project_id)?;
+    let before = worklog.clone();
+
+    if let Some(started_at) = req.started_at {
+        worklog.started_at = started_at;
+    }
+    if let Some(duration_minutes) = req.duration_minutes {
+        worklog.duration_minutes = duration_minutes;
+    }
+    if let Some(note) = req.note {
+        worklog.note = Some(note).filter(|note| !note.trim().is_empty());
+    }
+
+    // Validate
+    worklog.validate().map_err(|e| {
+        tracing::error!("Worklog validation failed: {:?}", e);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let updated_worklog = state.worklog_repo.update(&worklog).map_err(|e| {
+        tracing::error!("Failed to update worklog: {:?}", e);
+        AppError::Internal("Failed to update worklog".to_string())
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Updated,
+        "worklog",
+        updated_worklog.id.to_string(),
+        Some(&before),
+        Some(&updated_worklog),
+    );
+
+    Ok(Json(updated_worklog.into()))
+}
+
+async fn delete_worklog(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let worklog_id = WorklogId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid worklog ID".to_string()))?;
+
+    let worklog = load_worklog(&state, worklog_id)?;
+    let ticket = load_ticket(&state, worklog.ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+    authorize_owner_or_admin(&state, &user, worklog.user_id, ticket.project_id)?;
+
+    state.worklog_repo.delete(worklog_id).map_err(|e| {
+        tracing::error!("Failed to delete worklog: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Worklog not found".to_string()),
+            _ => AppError::Internal("Failed to delete worklog".to_string()),
+        }
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Deleted,
+        "worklog",
+        worklog_id.to_string(),
+        Some(&worklog),
+        None,
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Serialize)]
+struct TimesheetRowDto {
+    ticket_id: String,
+    /// Key of the ticket, such as `WN-123`
+    ticket_key: String,
+    ticket_title: String,
+    /// Minutes per day, Monday first
+    daily_minutes: [u32; 7],
+    total_minutes: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct TimesheetDto {
+    user_id: String,
+    week_start: NaiveDate,
+    rows: Vec<TimesheetRowDto>,
+    /// Minutes per day over all tickets, Monday first
+    daily_minutes: [u32; 7],
+    total_minutes: u32,
+}
+
+/// A user's logged time for one week, by ticket and day
+///
+/// `week` is any date in the week (default: today) and `user_id` defaults
+/// to the caller. Only global admins can see other users' timesheets.
+async fn get_timesheet(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<TimesheetDto>, AppError> {
+    let user_id = params
+        .get("user_id")
+        .map(|id| UserId::from_string(id))
+        .transpose()
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?
+        .unwrap_or(user.id);
+    if user_id != user.id && !is_global_admin(&state, &user)? {
+        return Err(AppError::Forbidden(
+            "Only administrators can view other users' timesheets".to_string(),
+        ));
+    }
+
+    let date = params
+        .get("week")
+        .map(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .transpose()
+        .map_err(|_| AppError::BadRequest("Invalid 'week' date".to_string()))?
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let week_start = Timesheet::week_start_of(date);
+    let (from, to) = Timesheet::week_bounds(week_start);
+
+    let worklogs = state
+        .worklog_repo
+        .find_by_user_between(user_id, from, to)
+        .map_err(|e| {
+            tracing::error!("Failed to list worklogs: {:?}", e);
+            AppError::Internal("Failed to retrieve worklogs".to_string())
+        })?;
+    let timesheet = Timesheet::build(user_id, week_start, &worklogs);
+
+    let mut tickets = Vec::with_capacity(timesheet.rows.len());
+    for row in &timesheet.rows {
+        tickets.push(load_ticket(&state, row.ticket_id)?);
+    }
+    let tickets = ticket_dtos(&state, tickets)?;
+
+    Ok(Json(TimesheetDto {
+        user_id: user_id.to_string(),
+        week_start,
+        rows: timesheet
+            .rows
+            .iter()
+            .zip(tickets)
+            .map(|(row, ticket)| TimesheetRowDto {
+                ticket_id: ticket.id,
+                ticket_key: ticket.key,
+                ticket_title: ticket.title,
+                daily_minutes: row.daily_minutes,
+                total_minutes: row.total_minutes,
+            })
+            .collect(),
+        daily_minutes: timesheet.daily_minutes,
+        total_minutes: timesheet.total_minutes,
+    }))
+}
+
+// ============================================================================
+// Attachment Routes
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct AttachmentDto {
+    id: String,
+    ticket_id: String,
+    filename: String,
+    file_size: i64,
+    mime_type: String,
+    uploaded_by: String,
+    created_at: String,
+}
+
+impl From<Attachment> for AttachmentDto {
+    fn from(attachment: Attachment) -> Self {
+        Self {
+            id: attachment.id.to_string(),
+            ticket_id: attachment.ticket_id.to_string(),
+            filename: attachment.filename,
+            file_size: attachment.file_size,
+            mime_type: attachment.mime_type,
+            uploaded_by: attachment.uploaded_by.to_string(),
+            created_at: attachment.created_at.to_rfc3339(),
+        }
+    }
+}
+
+async fn list_attachments_for_ticket(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(ticket_id): Path<String>,
+) -> Result<Json<Vec<AttachmentDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let attachments = state
+        .attachment_repo
+        .find_by_ticket(ticket_id)
+        .map_err(|e| {
+            tracing::error!("Failed to list attachments: {:?}", e);
+            AppError::Internal("Failed to retrieve attachments".to_string())
+        })?;
+
+    Ok(Json(
+        attachments.into_iter().map(AttachmentDto::from).collect(),
+    ))
+}
+
+async fn delete_attachment(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let attachment_id = AttachmentId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid attachment ID".to_string()))?;
+
+    // Get attachment to find file path
+    let attachment = state
+        .attachment_repo
+        .find_by_id(attachment_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get attachment: {:?}", e);
+            AppError::Internal("Failed to retrieve attachment".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))?;
+
+    let ticket = load_ticket(&state, attachment.ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+    authorize_owner_or_admin(&state, &user, attachment.uploaded_by, ticket.project_id)?;
+
+    // Delete from database
+    state.attachment_repo.delete(attachment_id).map_err(|e| {
+        tracing::error!("Failed to delete attachment: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Attachment not found".to_string()),
+            _ => AppError::Internal("Failed to delete attachment".to_string()),
+        }
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Deleted,
+        "attachment",
+        attachment_id.to_string(),
+        Some(&attachment),
+        None,
+    );
+    publish_change::<AttachmentDto>(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Attachment,
+        ActivityAction::Deleted,
+        attachment_id.to_string(),
+        None,
+    );
+
+    // Delete file from disk (ignore errors if file doesn't exist)
+    let _ = fs::remove_file(&attachment.file_path);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn upload_attachment(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(ticket_id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<AttachmentDto>, AppError> {
+    let ticket_id = TicketId::from_string(&ticket_id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    // Verify ticket exists and the user may modify it
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "update")?;
+
+    // Create uploads directory if it doesn't exist
+    let upload_dir = PathBuf::from("./uploads");
+    fs::create_dir_all(&upload_dir).map_err(|e| {
+        tracing::error!("Failed to create uploads directory: {:?}", e);
+        AppError::Internal("Failed to create uploads directory".to_string())
+    })?;
+
+    // Process multipart form
+    let mut filename: Option<String> = None;
+    let mut file_data: Option<Bytes> = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Invalid multipart data: {}", e)))?
+    {
+        let field_name = field.name().unwrap_or("").to_string();
+
+        if field_name == "file" {
+            filename = field.file_name().map(|s| s.to_string());
+            file_data =
+                Some(field.bytes().await.map_err(|e| {
+                    AppError::BadRequest(format!("Failed to read file data: {}", e))
+                })?);
+        }
+    }
+
+    let filename = filename.ok_or_else(|| AppError::BadRequest("No file provided".to_string()))?;
+    let file_data =
+        file_data.ok_or_else(|| AppError::BadRequest("No file data provided".to_string()))?;
+
+    // Generate unique filename
+    let file_ext = std::path::Path::new(&filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    let unique_filename = format!(
+        "{}_{}",
+        uuid::Uuid::new_v4(),
+        filename.replace(&['/', '\\', ':', '*', '?', '"', '<', '>', '|'][..], "_")
+    );
+
+    let file_path = upload_dir.join(&unique_filename);
+
+    // Write file to disk
+    fs::write(&file_path, &file_data).map_err(|e| {
+        tracing::error!("Failed to write file: {:?}", e);
+        AppError::Internal("Failed to save file".to_string())
+    })?;
+
+    // Detect MIME type based on extension
+    let mime_type = match file_ext.to_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "txt" => "text/plain",
+        _ => "application/octet-stream",
+    }
+    .to_string();
+
+    // Create attachment record
+    let attachment = Attachment::new(
+        ticket_id,
+        filename,
+        file_data.len() as i64,
+        mime_type,
+        file_path.to_string_lossy().to_string(),
+        user.id,
+    );
+
+    // Validate
+    attachment.validate().map_err(|e| {
+        tracing::error!("Attachment validation failed: {:?}", e);
+        // Clean up file on validation error
+        let _ = fs::remove_file(&file_path);
+        AppError::BadRequest(e.to_string())
+    })?;
+
+    let created_attachment = state.attachment_repo.create(&attachment).map_err(|e| {
+        tracing::error!("Failed to create attachment: {:?}", e);
+        // Clean up file on database error
+        let _ = fs::remove_file(&file_path);
+        AppError::Internal("Failed to create attachment".to_string())
+    })?;
+
+    record_activity(
+        &state,
+        &user,
+        ActivityAction::Created,
+        "attachment",
+        created_attachment.id.to_string(),
+        None,
+        Some(&created_attachment),
+    );
+
+    let dto = AttachmentDto::from(created_attachment);
+    publish_change(
+        &state,
+        &user,
+        ticket.project_id,
+        ChangeResource::Attachment,
+        ActivityAction::Created,
+        dto.id.clone(),
+        Some(&dto),
+    );
+
+    Ok(Json(dto))
+}
+
+async fn download_attachment(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let attachment_id = AttachmentId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid attachment ID".to_string()))?;
+
+    let attachment = state
+        .attachment_repo
+        .find_by_id(attachment_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get attachment: {:?}", e);
+            AppError::Internal("Failed to retrieve attachment".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))?;
+
+    let ticket = load_ticket(&state, attachment.ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    // Read file from disk
+    let file_data = fs::read(&attachment.file_path).map_err(|e| {
+        tracing::error!("Failed to read file: {:?}", e);
+        AppError::NotFound("File not found on disk".to_string())
+    })?;
+
+    // Return file with appropriate headers
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, attachment.mime_type.clone()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", attachment.filename),
+            ),
+            (header::CONTENT_LENGTH, file_data.len().to_string()),
+        ],
+        file_data,
+    ))
+}
+
+// ============================================================================
+// Live Update Routes
+// ============================================================================
+
+/// Number of changes buffered for each subscriber; a client that falls
+/// further behind is asked to resync
+const CHANGE_BUFFER_SIZE: usize = 256;
+
+/// How often an idle change stream sends a keep-alive comment, and checks
+/// that its session is still active
+const CHANGE_STREAM_KEEP_ALIVE: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(Debug, Serialize)]
+struct StreamTicketResponse {
+    ticket: String,
+    expires_in: i64,
+}
+
+/// Issue a single-use ticket for opening the change stream
+///
+/// Tickets keep access tokens out of the stream's URL, and with it out of
+/// server and proxy logs.
+async fn create_stream_ticket(
+    AuthClaims(claims): AuthClaims,
+    State(state): State<AppState>,
+) -> Result<Json<StreamTicketResponse>, AppError> {
+    let ticket = state
+        .auth_service
+        .issue_stream_ticket(&claims)
+        .map_err(|e| {
+            tracing::error!("Failed to issue stream ticket: {:?}", e);
+            AppError::Internal("Failed to issue stream ticket".to_string())
+        })?;
+
+    Ok(Json(StreamTicketResponse {
+        ticket,
+        expires_in: worknest_auth::STREAM_TICKET_SECONDS,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChangesQuery {
+    ticket: String,
 }
 
-async fn delete_ticket(
-    AuthUser(_user): AuthUser,
+/// Stream ticket, comment and attachment changes as Server-Sent Events
+///
+/// The stream is opened with a ticket from `POST /api/events/ticket`. Each
+/// `change` event carries a [`ChangeEvent`] of a project whose tickets the
+/// user can read. A `resync` event means changes were dropped because the
+/// client fell behind, so it should reload what it shows. The session is
+/// checked again on every event and keep-alive, and the stream ends once it
+/// is revoked or its access token expires.
+async fn stream_changes(
     State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> Result<StatusCode, AppError> {
-    let ticket_id = TicketId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    axum::extract::Query(query): axum::extract::Query<StreamChangesQuery>,
+) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>>>, AppError>
+{
+    let (user, claims) = state
+        .auth_service
+        .redeem_stream_ticket(&query.ticket)
+        .map_err(|e| {
+            tracing::warn!("Stream ticket verification failed: {:?}", e);
+            match e {
+                AuthError::SessionRevoked => {
+                    AppError::Unauthorized("Session has been revoked".to_string())
+                },
+                _ => AppError::Unauthorized("Invalid or expired stream ticket".to_string()),
+            }
+        })?;
 
-    state.ticket_repo.delete(ticket_id).map_err(|e| {
-        tracing::error!("Failed to delete ticket: {:?}", e);
-        match e {
-            DbError::NotFound(_) => AppError::NotFound("Ticket not found".to_string()),
-            _ => AppError::Internal("Failed to delete ticket".to_string()),
-        }
-    })?;
+    let receiver = state.changes.subscribe();
+    let expires_in = u64::try_from(claims.exp - chrono::Utc::now().timestamp()).unwrap_or(0);
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(expires_in);
+    let keep_alive = tokio::time::interval_at(
+        tokio::time::Instant::now() + CHANGE_STREAM_KEEP_ALIVE,
+        CHANGE_STREAM_KEEP_ALIVE,
+    );
 
-    Ok(StatusCode::NO_CONTENT)
-}
+    let stream = futures_util::stream::unfold(
+        (receiver, keep_alive, state, user, claims),
+        move |(mut receiver, mut keep_alive, state, user, claims)| async move {
+            loop {
+                let received = tokio::select! {
+                    received = receiver.recv() => Some(received),
+                    _ = keep_alive.tick() => None,
+                    _ = tokio::time::sleep_until(deadline) => return None,
+                };
 
-async fn search_tickets(
-    AuthUser(_user): AuthUser,
-    State(state): State<AppState>,
-    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<Vec<TicketDto>>, AppError> {
-    let query = params
-        .get("q")
-        .ok_or_else(|| AppError::BadRequest("Missing 'q' query parameter".to_string()))?;
+                // Logging out or revoking the session ends the stream
+                if let Err(e) = state.auth_service.check_session(&claims) {
+                    tracing::debug!("Ending change stream of {}: {:?}", user.username, e);
+                    return None;
+                }
 
-    // Optional project_id filter
-    let project_id = if let Some(project_id_str) = params.get("project_id") {
-        Some(
-            ProjectId::from_string(project_id_str)
-                .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?,
-        )
-    } else {
-        None
-    };
+                let Some(received) = received else {
+                    let event = Event::default().comment("keep-alive");
+                    return Some((Ok(event), (receiver, keep_alive, state, user, claims)));
+                };
 
-    let tickets = state.ticket_repo.search(query, project_id).map_err(|e| {
-        tracing::error!("Failed to search tickets: {:?}", e);
-        AppError::Internal("Failed to search tickets".to_string())
-    })?;
+                let event = match received {
+                    Ok(change) => {
+                        if !can(&state, &user, Some(change.project_id), "ticket", "read")
+                            .unwrap_or(false)
+                        {
+                            continue;
+                        }
+                        match Event::default().event("change").json_data(&change) {
+                            Ok(event) => event,
+                            Err(e) => {
+                                tracing::error!("Failed to encode change: {:?}", e);
+                                continue;
+                            },
+                        }
+                    },
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        tracing::warn!(
+                            "Change stream of {} fell behind by {} changes",
+                            user.username,
+                            missed
+                        );
+                        Event::default().event("resync").data(missed.to_string())
+                    },
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                };
+
+                return Some((Ok(event), (receiver, keep_alive, state, user, claims)));
+            }
+        },
+    );
 
-    Ok(Json(tickets.into_iter().map(TicketDto::from).collect()))
+    Ok(Sse::new(stream))
 }
 
 // ============================================================================
-// Comment Routes
+// Webhook Routes
 // ============================================================================
 
+/// How often the webhook worker looks for retries that are due; new
+/// deliveries wake it up right away
+const WEBHOOK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Number of due deliveries attempted per batch
+const WEBHOOK_BATCH_SIZE: u32 = 50;
+
+/// Number of webhooks delivered to at the same time
+const WEBHOOK_CONCURRENCY: usize = 8;
+
+/// Default and maximum number of deliveries returned at once
+const DEFAULT_DELIVERY_LIMIT: u32 = 50;
+const MAX_DELIVERY_LIMIT: u32 = 200;
+
+/// A webhook as the API returns it; the secret is never sent back
 #[derive(Debug, Serialize)]
-struct CommentDto {
+struct WebhookDto {
     id: String,
-    ticket_id: String,
-    user_id: String,
-    content: String,
+    project_id: String,
+    url: String,
+    events: Vec<WebhookEvent>,
+    active: bool,
+    created_by: String,
     created_at: String,
     updated_at: String,
 }
 
-impl From<Comment> for CommentDto {
-    fn from(comment: Comment) -> Self {
+impl From<Webhook> for WebhookDto {
+    fn from(webhook: Webhook) -> Self {
         Self {
-            id: comment.id.to_string(),
-            ticket_id: comment.ticket_id.to_string(),
-            user_id: comment.user_id.to_string(),
-            content: comment.content,
-            created_at: comment.created_at.to_rfc3339(),
-            updated_at: comment.updated_at.to_rfc3339(),
+            id: webhook.id.to_string(),
+            project_id: webhook.project_id.to_string(),
+            url: webhook.url,
+            events: webhook.events,
+            active: webhook.active,
+            created_by: webhook.created_by.to_string(),
+            created_at: webhook.created_at.to_rfc3339(),
+            updated_at: webhook.updated_at.to_rfc3339(),
         }
     }
 }
 
-async fn list_comments_for_ticket(
-    AuthUser(_user): AuthUser,
+#[derive(Debug, Serialize)]
+struct WebhookDeliveryDto {
+    id: String,
+    webhook_id: String,
+    event: WebhookEvent,
+    status: DeliveryStatus,
+    attempts: u32,
+    response_code: Option<u16>,
+    error: Option<String>,
+    /// The body as it was signed and sent
+    payload: serde_json::Value,
+    next_attempt_at: Option<String>,
+    last_attempt_at: Option<String>,
+    created_at: String,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDto {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.to_string(),
+            webhook_id: delivery.webhook_id.to_string(),
+            event: delivery.event,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            response_code: delivery.response_code,
+            error: delivery.error,
+            payload: serde_json::from_str(&delivery.payload)
+                .unwrap_or(serde_json::Value::String(delivery.payload)),
+            next_attempt_at: delivery.next_attempt_at.map(|t| t.to_rfc3339()),
+            last_attempt_at: delivery.last_attempt_at.map(|t| t.to_rfc3339()),
+            created_at: delivery.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Load a webhook, returning 404 when it does not exist
+fn load_webhook(state: &AppState, webhook_id: WebhookId) -> Result<Webhook, AppError> {
+    state
+        .webhook_repo
+        .find_by_id(webhook_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get webhook: {:?}", e);
+            AppError::Internal("Failed to retrieve webhook".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("Webhook not found".to_string()))
+}
+
+/// Parse event names, rejecting unknown ones with 400
+fn parse_webhook_events(names: &[String]) -> Result<Vec<WebhookEvent>, AppError> {
+    let mut events = Vec::with_capacity(names.len());
+    for name in names {
+        let event =
+            WebhookEvent::from_name(name).map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+/// Queue a delivery of a change for every webhook of its project that
+/// subscribes to it
+fn queue_webhooks(state: &AppState, change: &ChangeEvent) {
+    let Some(event) = WebhookEvent::from_change(change.resource, change.action) else {
+        return;
+    };
+
+    let webhooks = match state.webhook_repo.find_subscribed(change.project_id, event) {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            tracing::error!("Failed to find webhooks: {:?}", e);
+            return;
+        },
+    };
+    if webhooks.is_empty() {
+        return;
+    }
+
+    // A payload that cannot be built is logged as a failed delivery rather
+    // than sent empty
+    let payload = webhooks::payload(event.as_str(), change, chrono::Utc::now());
+    if let Err(e) = &payload {
+        tracing::error!("Failed to encode webhook payload: {:?}", e);
+    }
+    for webhook in webhooks {
+        let delivery = match &payload {
+            Ok(payload) => WebhookDelivery::new(webhook.id, event, payload.clone()),
+            Err(e) => {
+                let mut delivery = WebhookDelivery::new(webhook.id, event, String::new());
+                delivery.fail(format!("Failed to encode payload: {}", e));
+                delivery
+            },
+        };
+        if let Err(e) = state.webhook_delivery_repo.create(&delivery) {
+            tracing::error!("Failed to queue webhook delivery: {:?}", e);
+        }
+    }
+    state.webhook_wakeup.notify_one();
+}
+
+/// Deliver queued payloads as they come due, until the server stops
+async fn run_webhook_worker(state: AppState) {
+    let mut interval = tokio::time::interval(WEBHOOK_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = state.webhook_wakeup.notified() => {},
+        }
+        if let Err(e) = deliver_due_webhooks(&state).await {
+            tracing::error!("Failed to deliver webhooks: {:?}", e);
+        }
+    }
+}
+
+/// Attempt every delivery that is due, recording each outcome
+///
+/// Deliveries to different webhooks go out concurrently, so a slow
+/// receiver only holds up its own. Each webhook gets its deliveries in the
+/// order they were queued; after a failed attempt the rest wait for the
+/// next batch, and retries come after the newer deliveries already due.
+async fn deliver_due_webhooks(state: &AppState) -> Result<(), DbError> {
+    use futures_util::stream::{self, StreamExt, TryStreamExt};
+
+    loop {
+        let due = state
+            .webhook_delivery_repo
+            .find_due(chrono::Utc::now(), WEBHOOK_BATCH_SIZE)?;
+        let batch_size = due.len();
+
+        let mut by_webhook: Vec<(WebhookId, Vec<WebhookDelivery>)> = Vec::new();
+        for delivery in due {
+            match by_webhook
+                .iter_mut()
+                .find(|(webhook_id, _)| *webhook_id == delivery.webhook_id)
+            {
+                Some((_, deliveries)) => deliveries.push(delivery),
+                None => by_webhook.push((delivery.webhook_id, vec![delivery])),
+            }
+        }
+
+        stream::iter(by_webhook)
+            .map(|(webhook_id, deliveries)| deliver_to_webhook(state, webhook_id, deliveries))
+            .buffer_unordered(WEBHOOK_CONCURRENCY)
+            .try_collect::<()>()
+            .await?;
+
+        if batch_size < WEBHOOK_BATCH_SIZE as usize {
+            return Ok(());
+        }
+    }
+}
+
+/// Attempt a webhook's due deliveries in order, stopping at the first one
+/// that fails
+async fn deliver_to_webhook(
+    state: &AppState,
+    webhook_id: WebhookId,
+    deliveries: Vec<WebhookDelivery>,
+) -> Result<(), DbError> {
+    // Deleting a webhook deletes its deliveries
+    let Some(webhook) = state.webhook_repo.find_by_id(webhook_id)? else {
+        return Ok(());
+    };
+
+    for mut delivery in deliveries {
+        let attempt = state.webhook_client.deliver(&webhook, &delivery).await;
+        let failed = attempt.error.is_some();
+        if let Some(error) = &attempt.error {
+            tracing::warn!(
+                "Webhook delivery {} to {} failed: {}",
+                delivery.id,
+                webhook.url,
+                error
+            );
+        }
+
+        delivery.record_attempt(attempt.response_code, attempt.error, chrono::Utc::now());
+        state.webhook_delivery_repo.update(&delivery)?;
+
+        if failed {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_project_webhooks(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
-    Path(ticket_id): Path<String>,
-) -> Result<Json<Vec<CommentDto>>, AppError> {
-    let ticket_id = TicketId::from_string(&ticket_id)
-        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    Path(id): Path<String>,
+) -> Result<Json<Vec<WebhookDto>>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
-    let comments = state.comment_repo.find_by_ticket(ticket_id).map_err(|e| {
-        tracing::error!("Failed to list comments: {:?}", e);
-        AppError::Internal("Failed to retrieve comments".to_string())
-    })?;
+    authorize_project_admin(&state, &user, project_id)?;
 
-    Ok(Json(comments.into_iter().map(CommentDto::from).collect()))
+    let webhooks = state
+        .webhook_repo
+        .find_by_project(project_id)
+        .map_err(|e| {
+            tracing::error!("Failed to list webhooks: {:?}", e);
+            AppError::Internal("Failed to retrieve webhooks".to_string())
+        })?;
+
+    Ok(Json(webhooks.into_iter().map(WebhookDto::from).collect()))
 }
 
 #[derive(Debug, Deserialize)]
-struct CreateCommentRequest {
-    content: String,
+struct CreateWebhookRequest {
+    url: String,
+    secret: String,
+    events: Vec<String>,
+    active: Option<bool>,
 }
 
-async fn create_comment(
+async fn create_webhook(
     AuthUser(user): AuthUser,
     State(state): State<AppState>,
-    Path(ticket_id): Path<String>,
-    Json(req): Json<CreateCommentRequest>,
-) -> Result<Json<CommentDto>, AppError> {
-    let ticket_id = TicketId::from_string(&ticket_id)
-        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+    Path(id): Path<String>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookDto>, AppError> {
+    let project_id = ProjectId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
-    let comment = Comment::new(ticket_id, user.id, req.content);
+    authorize_project_admin(&state, &user, project_id)?;
 
-    // Validate
-    comment.validate().map_err(|e| {
-        tracing::error!("Comment validation failed: {:?}", e);
+    let mut webhook = Webhook::new(
+        project_id,
+        req.url.trim().to_string(),
+        req.secret,
+        parse_webhook_events(&req.events)?,
+        user.id,
+    );
+    if let Some(active) = req.active {
+        webhook.active = active;
+    }
+
+    webhook.validate().map_err(|e| {
+        tracing::error!("Webhook validation failed: {:?}", e);
         AppError::BadRequest(e.to_string())
     })?;
 
-    let created_comment = state.comment_repo.create(&comment).map_err(|e| {
-        tracing::error!("Failed to create comment: {:?}", e);
-        AppError::Internal("Failed to create comment".to_string())
+    let created = state.webhook_repo.create(&webhook).map_err(|e| {
+        tracing::error!("Failed to create webhook: {:?}", e);
+        AppError::Internal("Failed to create webhook".to_string())
     })?;
 
-    Ok(Json(created_comment.into()))
+    Ok(Json(created.into()))
+}
+
+async fn get_webhook(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookDto>, AppError> {
+    let webhook_id = WebhookId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid webhook ID".to_string()))?;
+
+    let webhook = load_webhook(&state, webhook_id)?;
+    authorize_project_admin(&state, &user, webhook.project_id)?;
+
+    Ok(Json(webhook.into()))
 }
 
 #[derive(Debug, Deserialize)]
-struct UpdateCommentRequest {
-    content: String,
+struct UpdateWebhookRequest {
+    url: Option<String>,
+    secret: Option<String>,
+    events: Option<Vec<String>>,
+    active: Option<bool>,
 }
 
-async fn update_comment(
-    AuthUser(_user): AuthUser,
+async fn update_webhook(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Json(req): Json<UpdateCommentRequest>,
-) -> Result<Json<CommentDto>, AppError> {
-    let comment_id = CommentId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid comment ID".to_string()))?;
+    Json(req): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookDto>, AppError> {
+    let webhook_id = WebhookId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid webhook ID".to_string()))?;
 
-    let mut comment = state
-        .comment_repo
-        .find_by_id(comment_id)
-        .map_err(|e| {
-            tracing::error!("Failed to get comment: {:?}", e);
-            AppError::Internal("Failed to retrieve comment".to_string())
-        })?
-        .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+    let mut webhook = load_webhook(&state, webhook_id)?;
+    authorize_project_admin(&state, &user, webhook.project_id)?;
 
-    comment.content = req.content;
+    if let Some(url) = req.url {
+        webhook.url = url.trim().to_string();
+    }
+    if let Some(secret) = req.secret {
+        webhook.secret = secret;
+    }
+    if let Some(events) = req.events {
+        webhook.events = parse_webhook_events(&events)?;
+    }
+    if let Some(active) = req.active {
+        webhook.active = active;
+    }
 
-    // Validate
-    comment.validate().map_err(|e| {
-        tracing::error!("Comment validation failed: {:?}", e);
+    webhook.validate().map_err(|e| {
+        tracing::error!("Webhook validation failed: {:?}", e);
         AppError::BadRequest(e.to_string())
     })?;
 
-    let updated_comment = state.comment_repo.update(&comment).map_err(|e| {
-        tracing::error!("Failed to update comment: {:?}", e);
-        AppError::Internal("Failed to update comment".to_string())
+    let updated = state.webhook_repo.update(&webhook).map_err(|e| {
+        tracing::error!("Failed to update webhook: {:?}", e);
+        match e {
+            DbError::NotFound(_) => AppError::NotFound("Webhook not found".to_string()),
+            _ => AppError::Internal("Failed to update webhook".to_string()),
+        }
     })?;
 
-    Ok(Json(updated_comment.into()))
+    // Deliveries held back while the webhook was disabled are due now
+    if updated.active {
+        state.webhook_wakeup.notify_one();
+    }
+
+    Ok(Json(updated.into()))
 }
 
-async fn delete_comment(
-    AuthUser(_user): AuthUser,
+async fn delete_webhook(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, AppError> {
-    let comment_id = CommentId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid comment ID".to_string()))?;
+    let webhook_id = WebhookId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid webhook ID".to_string()))?;
 
-    state.comment_repo.delete(comment_id).map_err(|e| {
-        tracing::error!("Failed to delete comment: {:?}", e);
+    let webhook = load_webhook(&state, webhook_id)?;
+    authorize_project_admin(&state, &user, webhook.project_id)?;
+
+    state.webhook_repo.delete(webhook_id).map_err(|e| {
+        tracing::error!("Failed to delete webhook: {:?}", e);
         match e {
-            DbError::NotFound(_) => AppError::NotFound("Comment not found".to_string()),
-            _ => AppError::Internal("Failed to delete comment".to_string()),
+            DbError::NotFound(_) => AppError::NotFound("Webhook not found".to_string()),
+            _ => AppError::Internal("Failed to delete webhook".to_string()),
         }
     })?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// The delivery log of a webhook, newest first
+async fn list_webhook_deliveries(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<Vec<WebhookDeliveryDto>>, AppError> {
+    let webhook_id = WebhookId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid webhook ID".to_string()))?;
+
+    let webhook = load_webhook(&state, webhook_id)?;
+    authorize_project_admin(&state, &user, webhook.project_id)?;
+
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+        .clamp(1, MAX_DELIVERY_LIMIT);
+
+    let deliveries = state
+        .webhook_delivery_repo
+        .find_by_webhook(webhook_id, Some(limit))
+        .map_err(|e| {
+            tracing::error!("Failed to list webhook deliveries: {:?}", e);
+            AppError::Internal("Failed to retrieve webhook deliveries".to_string())
+        })?;
+
+    Ok(Json(
+        deliveries
+            .into_iter()
+            .map(WebhookDeliveryDto::from)
+            .collect(),
+    ))
+}
+
+/// Send the payload of a failed delivery again as a new delivery, keeping
+/// the failed one in the log
+async fn redeliver_webhook_delivery(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path((id, delivery_id)): Path<(String, String)>,
+) -> Result<(StatusCode, Json<WebhookDeliveryDto>), AppError> {
+    let webhook_id = WebhookId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid webhook ID".to_string()))?;
+    let delivery_id = WebhookDeliveryId::from_string(&delivery_id)
+        .map_err(|_| AppError::BadRequest("Invalid delivery ID".to_string()))?;
+
+    let webhook = load_webhook(&state, webhook_id)?;
+    authorize_project_admin(&state, &user, webhook.project_id)?;
+
+    let delivery = state
+        .webhook_delivery_repo
+        .find_by_id(delivery_id)
+        .map_err(|e| {
+            tracing::error!("Failed to get webhook delivery: {:?}", e);
+            AppError::Internal("Failed to retrieve webhook delivery".to_string())
+        })?
+        .filter(|delivery| delivery.webhook_id == webhook_id)
+        .ok_or_else(|| AppError::NotFound("Webhook delivery not found".to_string()))?;
+
+    if delivery.status != DeliveryStatus::Failed {
+        return Err(AppError::BadRequest(
+            "Only failed deliveries can be redelivered".to_string(),
+        ));
+    }
+    if delivery.payload.is_empty() {
+        return Err(AppError::BadRequest(
+            "This delivery has no payload to send".to_string(),
+        ));
+    }
+
+    let redelivery = state
+        .webhook_delivery_repo
+        .create(&delivery.redelivery())
+        .map_err(|e| {
+            tracing::error!("Failed to queue webhook delivery: {:?}", e);
+            AppError::Internal("Failed to queue webhook delivery".to_string())
+        })?;
+    state.webhook_wakeup.notify_one();
+
+    Ok((StatusCode::ACCEPTED, Json(redelivery.into())))
+}
+
 // ============================================================================
-// Attachment Routes
+// Git Integration Routes
 // ============================================================================
 
 #[derive(Debug, Serialize)]
-struct AttachmentDto {
+struct CodeLinkDto {
     id: String,
     ticket_id: String,
-    filename: String,
-    file_size: i64,
-    mime_type: String,
-    uploaded_by: String,
+    kind: CodeLinkKind,
+    repository: String,
+    reference: String,
+    title: String,
+    url: String,
+    author: Option<String>,
+    state: Option<PullRequestState>,
     created_at: String,
+    updated_at: String,
 }
 
-impl From<Attachment> for AttachmentDto {
-    fn from(attachment: Attachment) -> Self {
+impl From<CodeLink> for CodeLinkDto {
+    fn from(link: CodeLink) -> Self {
         Self {
-            id: attachment.id.to_string(),
-            ticket_id: attachment.ticket_id.to_string(),
-            filename: attachment.filename,
-            file_size: attachment.file_size,
-            mime_type: attachment.mime_type,
-            uploaded_by: attachment.uploaded_by.to_string(),
-            created_at: attachment.created_at.to_rfc3339(),
+            id: link.id.to_string(),
+            ticket_id: link.ticket_id.to_string(),
+            kind: link.kind,
+            repository: link.repository,
+            reference: link.reference,
+            title: link.title,
+            url: link.url,
+            author: link.author,
+            state: link.state,
+            created_at: link.created_at.to_rfc3339(),
+            updated_at: link.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// What a Git forge webhook changed
+#[derive(Debug, Serialize)]
+struct GitWebhookResponse {
+    event: String,
+    /// Number of commit and pull request links created or refreshed
+    linked: usize,
+    /// Keys of the tickets moved to a resolved status
+    resolved: Vec<String>,
+}
+
+impl GitWebhookResponse {
+    fn new(event: &str) -> Self {
+        Self {
+            event: event.to_string(),
+            linked: 0,
+            resolved: Vec::new(),
         }
     }
 }
 
-async fn list_attachments_for_ticket(
-    AuthUser(_user): AuthUser,
+/// List the commits and pull requests linked to a ticket
+async fn list_ticket_links(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
-    Path(ticket_id): Path<String>,
-) -> Result<Json<Vec<AttachmentDto>>, AppError> {
-    let ticket_id = TicketId::from_string(&ticket_id)
+    Path(id): Path<String>,
+) -> Result<Json<Vec<CodeLinkDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
         .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
 
-    let attachments = state
-        .attachment_repo
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let links = state
+        .code_link_repo
         .find_by_ticket(ticket_id)
         .map_err(|e| {
-            tracing::error!("Failed to list attachments: {:?}", e);
-            AppError::Internal("Failed to retrieve attachments".to_string())
+            tracing::error!("Failed to list code links: {:?}", e);
+            AppError::Internal("Failed to retrieve code links".to_string())
         })?;
 
-    Ok(Json(
-        attachments.into_iter().map(AttachmentDto::from).collect(),
-    ))
+    Ok(Json(links.into_iter().map(CodeLinkDto::from).collect()))
 }
 
-async fn delete_attachment(
-    AuthUser(_user): AuthUser,
+/// Take a push or pull request event from GitHub, Gitea or GitLab
+///
+/// Commits and pull requests are linked to the tickets their branch,
+/// message, title or description refer to. Tickets named after a closing
+/// keyword (`fixes WN-12`) are resolved by commits pushed to the default
+/// branch and by pull requests once merged. Other events are acknowledged
+/// and ignored.
+async fn receive_git_webhook(
     State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> Result<StatusCode, AppError> {
-    let attachment_id = AttachmentId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid attachment ID".to_string()))?;
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<GitWebhookResponse>, AppError> {
+    let secret = state
+        .git_webhook_secret
+        .as_deref()
+        .ok_or_else(|| AppError::Forbidden("Git integration is not configured".to_string()))?;
+    let forge = Forge::detect(&headers)
+        .ok_or_else(|| AppError::BadRequest("Not a GitHub, Gitea or GitLab webhook".to_string()))?;
+    if !forge.verify(&headers, &body, secret) {
+        tracing::warn!("Rejected {} webhook with an invalid signature", forge);
+        return Err(AppError::Unauthorized(
+            "Invalid webhook signature".to_string(),
+        ));
+    }
 
-    // Get attachment to find file path
-    let attachment = state
-        .attachment_repo
-        .find_by_id(attachment_id)
-        .map_err(|e| {
-            tracing::error!("Failed to get attachment: {:?}", e);
-            AppError::Internal("Failed to retrieve attachment".to_string())
-        })?
-        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))?;
+    let event = forge
+        .parse(&headers, &body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid {} payload: {}", forge, e)))?;
 
-    // Delete from database
-    state.attachment_repo.delete(attachment_id).map_err(|e| {
-        tracing::error!("Failed to delete attachment: {:?}", e);
-        match e {
-            DbError::NotFound(_) => AppError::NotFound("Attachment not found".to_string()),
-            _ => AppError::Internal("Failed to delete attachment".to_string()),
-        }
-    })?;
+    let response = match event {
+        ForgeEvent::Push(push) => link_git_push(&state, forge, &push)?,
+        ForgeEvent::PullRequest(pr) => link_pull_request(&state, forge, &pr)?,
+        ForgeEvent::Other(name) => GitWebhookResponse::new(&name),
+    };
 
-    // Delete file from disk (ignore errors if file doesn't exist)
-    let _ = fs::remove_file(&attachment.file_path);
+    Ok(Json(response))
+}
 
-    Ok(StatusCode::NO_CONTENT)
+/// The change behind a ticket resolved from Git, recorded in the ticket's
+/// history since the Git user makes the change on its behalf
+#[derive(Debug, Serialize)]
+struct GitSource {
+    forge: &'static str,
+    repository: String,
+    /// Commit hash, or `#` and the pull request number
+    reference: String,
+    /// The commit's author as Git recorded it, or the forge account that
+    /// merged the pull request
+    author: Option<String>,
 }
 
-async fn upload_attachment(
-    AuthUser(user): AuthUser,
-    State(state): State<AppState>,
-    Path(ticket_id): Path<String>,
-    mut multipart: Multipart,
-) -> Result<Json<AttachmentDto>, AppError> {
-    let ticket_id = TicketId::from_string(&ticket_id)
-        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+/// Link pushed commits to their tickets; on the default branch, resolve the
+/// tickets they close as the Git user
+fn link_git_push(
+    state: &AppState,
+    forge: Forge,
+    push: &forge::Push,
+) -> Result<GitWebhookResponse, AppError> {
+    let mut response = GitWebhookResponse::new("push");
+    // Tags carry no new work
+    let Some(branch) = push.branch.as_deref() else {
+        return Ok(response);
+    };
 
-    // Verify ticket exists
-    state
-        .ticket_repo
-        .find_by_id(ticket_id)
-        .map_err(|e| {
-            tracing::error!("Failed to get ticket: {:?}", e);
-            AppError::Internal("Failed to verify ticket".to_string())
-        })?
-        .ok_or_else(|| AppError::NotFound("Ticket not found".to_string()))?;
+    // Closing keywords only count once the work reaches the default branch
+    let resolver = if push.on_default_branch() {
+        find_git_user(state)?
+    } else {
+        None
+    };
 
-    // Create uploads directory if it doesn't exist
-    let upload_dir = PathBuf::from("./uploads");
-    fs::create_dir_all(&upload_dir).map_err(|e| {
-        tracing::error!("Failed to create uploads directory: {:?}", e);
-        AppError::Internal("Failed to create uploads directory".to_string())
-    })?;
+    for commit in &push.commits {
+        let tickets = referenced_tickets(state, branch, &commit.message)?;
+        let source = GitSource {
+            forge: forge.as_str(),
+            repository: push.repository.clone(),
+            reference: commit.sha.clone(),
+            author: Some(match &commit.author_email {
+                Some(email) => format!("{} <{}>", commit.author_name, email),
+                None => commit.author_name.clone(),
+            }),
+        };
 
-    // Process multipart form
-    let mut filename: Option<String> = None;
-    let mut file_data: Option<Bytes> = None;
+        for (ticket, closes) in tickets {
+            let mut link = CodeLink::commit(
+                ticket.id,
+                push.repository.clone(),
+                commit.sha.clone(),
+                &commit.message,
+                commit.url.clone(),
+            );
+            link.author = Some(commit.author_name.clone());
+            save_code_link(state, &link)?;
+            response.linked += 1;
+
+            match &resolver {
+                Some(resolver) if closes => response.resolved.extend(resolve_ticket_from_git(
+                    state, resolver, ticket.id, &source,
+                )?),
+                _ => {},
+            }
+        }
+    }
 
-    while let Some(field) = multipart
-        .next_field()
-        .await
-        .map_err(|e| AppError::BadRequest(format!("Invalid multipart data: {}", e)))?
-    {
-        let field_name = field.name().unwrap_or("").to_string();
+    Ok(response)
+}
 
-        if field_name == "file" {
-            filename = field.file_name().map(|s| s.to_string());
-            file_data =
-                Some(field.bytes().await.map_err(|e| {
-                    AppError::BadRequest(format!("Failed to read file data: {}", e))
-                })?);
+/// Link a pull request to its tickets; once merged, resolve the tickets it
+/// closes as the Git user
+fn link_pull_request(
+    state: &AppState,
+    forge: Forge,
+    pr: &forge::PullRequest,
+) -> Result<GitWebhookResponse, AppError> {
+    let mut response = GitWebhookResponse::new("pull_request");
+    let text = format!("{}\n\n{}", pr.title, pr.body);
+    let tickets = referenced_tickets(state, &pr.branch, &text)?;
+
+    let resolver = if pr.state == PullRequestState::Merged && !tickets.is_empty() {
+        find_git_user(state)?
+    } else {
+        None
+    };
+    let source = GitSource {
+        forge: forge.as_str(),
+        repository: pr.repository.clone(),
+        reference: format!("#{}", pr.number),
+        author: pr.actor_username.clone(),
+    };
+
+    for (ticket, closes) in tickets {
+        let mut link = CodeLink::pull_request(
+            ticket.id,
+            pr.repository.clone(),
+            pr.number,
+            &pr.title,
+            pr.url.clone(),
+            pr.state,
+        );
+        link.author = pr.author.clone();
+        save_code_link(state, &link)?;
+        response.linked += 1;
+
+        match &resolver {
+            Some(resolver) if closes => response.resolved.extend(resolve_ticket_from_git(
+                state, resolver, ticket.id, &source,
+            )?),
+            _ => {},
         }
     }
 
-    let filename = filename.ok_or_else(|| AppError::BadRequest("No file provided".to_string()))?;
-    let file_data =
-        file_data.ok_or_else(|| AppError::BadRequest("No file data provided".to_string()))?;
+    Ok(response)
+}
 
-    // Generate unique filename
-    let file_ext = std::path::Path::new(&filename)
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-    let unique_filename = format!(
-        "{}_{}",
-        uuid::Uuid::new_v4(),
-        filename.replace(&['/', '\\', ':', '*', '?', '"', '<', '>', '|'][..], "_")
-    );
+/// Tickets a branch name and a commit message or pull request refer to,
+/// each with whether the text closes it
+///
+/// References to tickets that do not exist are skipped.
+fn referenced_tickets(
+    state: &AppState,
+    branch: &str,
+    text: &str,
+) -> Result<Vec<(Ticket, bool)>, AppError> {
+    let mut tickets: Vec<(Ticket, bool)> = Vec::new();
+    let mut add =
+        |ticket: Ticket, closes: bool| match tickets.iter_mut().find(|(t, _)| t.id == ticket.id) {
+            Some((_, existing)) => *existing |= closes,
+            None => tickets.push((ticket, closes)),
+        };
 
-    let file_path = upload_dir.join(&unique_filename);
+    for key in git::branch_keys(branch) {
+        if let Some(ticket) = find_ticket_by_key(state, &key)? {
+            add(ticket, false);
+        }
+    }
+    for prefix in git::branch_id_prefixes(branch) {
+        let ticket = state.ticket_repo.find_by_id_prefix(&prefix).map_err(|e| {
+            tracing::error!("Failed to get ticket by id prefix: {:?}", e);
+            AppError::Internal("Failed to retrieve ticket".to_string())
+        })?;
+        if let Some(ticket) = ticket {
+            add(ticket, false);
+        }
+    }
+    for reference in git::find_references(text) {
+        if let Some(ticket) = find_ticket_by_key(state, &reference.key)? {
+            add(ticket, reference.closes);
+        }
+    }
 
-    // Write file to disk
-    fs::write(&file_path, &file_data).map_err(|e| {
-        tracing::error!("Failed to write file: {:?}", e);
-        AppError::Internal("Failed to save file".to_string())
+    Ok(tickets)
+}
+
+/// The account configured to resolve tickets closed from Git
+///
+/// Commit authors and forge accounts are not Worknest identities: anyone
+/// who can push may write any name and email into a commit. Resolving as
+/// one dedicated account keeps Git from acting as the people it names.
+fn find_git_user(state: &AppState) -> Result<Option<User>, AppError> {
+    let Some(username) = state.git_user.as_deref() else {
+        return Ok(None);
+    };
+
+    let user = state.user_repo.find_by_username(username).map_err(|e| {
+        tracing::error!("Failed to get user: {:?}", e);
+        AppError::Internal("Failed to retrieve user".to_string())
     })?;
+    if user.is_none() {
+        tracing::warn!(
+            "Git user '{}' does not exist - tickets are not resolved from Git",
+            username
+        );
+    }
+    Ok(user)
+}
 
-    // Detect MIME type based on extension
-    let mime_type = match file_ext.to_lowercase().as_str() {
-        "jpg" | "jpeg" => "image/jpeg",
-        "png" => "image/png",
-        "gif" => "image/gif",
-        "pdf" => "application/pdf",
-        "doc" => "application/msword",
-        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
-        "txt" => "text/plain",
-        _ => "application/octet-stream",
+fn save_code_link(state: &AppState, link: &CodeLink) -> Result<CodeLink, AppError> {
+    state.code_link_repo.upsert(link).map_err(|e| {
+        tracing::error!("Failed to save code link: {:?}", e);
+        AppError::Internal("Failed to save code link".to_string())
+    })
+}
+
+/// Move a ticket to the first resolved status its workflow allows next, as
+/// `user`, returning its key when it moved
+///
+/// Tickets that are already resolved, still blocked, or that the user may
+/// not update are left as they are. The history entry names the Git change
+/// under `git`.
+fn resolve_ticket_from_git(
+    state: &AppState,
+    user: &User,
+    ticket_id: TicketId,
+    source: &GitSource,
+) -> Result<Option<String>, AppError> {
+    let ticket = load_ticket(state, ticket_id)?;
+    if ticket.is_resolved() || !can(state, user, Some(ticket.project_id), "ticket", "update")? {
+        return Ok(None);
     }
-    .to_string();
 
-    // Create attachment record
-    let attachment = Attachment::new(
-        ticket_id,
-        filename,
-        file_data.len() as i64,
-        mime_type,
-        file_path.to_string_lossy().to_string(),
+    let workflow = load_workflow(state, ticket.project_id)?;
+    let Some(status) = workflow
+        .next_statuses(&ticket.status)
+        .into_iter()
+        .find(|status| status.category.is_resolved())
+    else {
+        return Ok(None);
+    };
+
+    let before = hydrate_ticket(state, ticket)?;
+    let mut ticket = before.clone();
+    ticket.update_status(status);
+
+    let blockers = state
+        .dependency_repo
+        .find_blockers(ticket.id)
+        .map_err(|e| {
+            tracing::error!("Failed to get ticket blockers: {:?}", e);
+            AppError::Internal("Failed to retrieve ticket dependencies".to_string())
+        })?;
+    if ticket.validate_blockers(&blockers).is_err() {
+        return Ok(None);
+    }
+
+    let updated_ticket = state.ticket_repo.update(&ticket).map_err(|e| {
+        tracing::error!("Failed to update ticket: {:?}", e);
+        AppError::Internal("Failed to update ticket".to_string())
+    })?;
+
+    let mut details = Activity::diff(Some(&before), Some(&updated_ticket));
+    if let (Some(details), Ok(source)) = (details.as_object_mut(), serde_json::to_value(source)) {
+        details.insert("git".to_string(), source);
+    }
+    let activity = Activity::new(
         user.id,
+        ActivityAction::Updated,
+        "ticket",
+        updated_ticket.id.to_string(),
+        Some(details),
     );
+    if let Err(e) = state.activity_repo.record(&activity) {
+        tracing::error!("Failed to record activity: {:?}", e);
+    }
 
-    // Validate
-    attachment.validate().map_err(|e| {
-        tracing::error!("Attachment validation failed: {:?}", e);
-        // Clean up file on validation error
-        let _ = fs::remove_file(&file_path);
-        AppError::BadRequest(e.to_string())
-    })?;
+    let recipients = watcher_recipients(state, updated_ticket.id, NotificationKind::StatusChanged);
+    let dto = ticket_dto(state, updated_ticket)?;
+    notify_users(state, user, &ticket, &dto.key, &recipients);
+    publish_change(
+        state,
+        user,
+        ticket.project_id,
+        ChangeResource::Ticket,
+        ActivityAction::Updated,
+        dto.id.clone(),
+        Some(&dto),
+    );
 
-    let created_attachment = state.attachment_repo.create(&attachment).map_err(|e| {
-        tracing::error!("Failed to create attachment: {:?}", e);
-        // Clean up file on database error
-        let _ = fs::remove_file(&file_path);
-        AppError::Internal("Failed to create attachment".to_string())
-    })?;
+    Ok(Some(dto.key))
+}
+
+// ============================================================================
+// Activity Routes
+// ============================================================================
 
-    Ok(Json(created_attachment.into()))
+#[derive(Debug, Serialize)]
+struct ActivityDto {
+    id: String,
+    user_id: String,
+    action: String,
+    resource_type: String,
+    resource_id: String,
+    details: Option<serde_json::Value>,
+    created_at: String,
 }
 
-async fn download_attachment(
-    AuthUser(_user): AuthUser,
+impl From<Activity> for ActivityDto {
+    fn from(activity: Activity) -> Self {
+        Self {
+            id: activity.id.to_string(),
+            user_id: activity.user_id.to_string(),
+            action: activity.action.to_string(),
+            resource_type: activity.resource_type,
+            resource_id: activity.resource_id,
+            details: activity.details,
+            created_at: activity.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Parse an RFC 3339 timestamp query parameter
+fn parse_timestamp(
+    params: &std::collections::HashMap<String, String>,
+    name: &str,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, AppError> {
+    params
+        .get(name)
+        .map(|value| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .map_err(|_| AppError::BadRequest(format!("Invalid '{}' timestamp", name)))
+        })
+        .transpose()
+}
+
+async fn list_activity(
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> Result<impl IntoResponse, AppError> {
-    let attachment_id = AttachmentId::from_string(&id)
-        .map_err(|_| AppError::BadRequest("Invalid attachment ID".to_string()))?;
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Result<Json<Vec<ActivityDto>>, AppError> {
+    // The audit trail spans every project, so only global admins may read it
+    if !is_global_admin(&state, &user)? {
+        return Err(AppError::Forbidden(
+            "Only administrators can view the activity log".to_string(),
+        ));
+    }
 
-    let attachment = state
-        .attachment_repo
-        .find_by_id(attachment_id)
-        .map_err(|e| {
-            tracing::error!("Failed to get attachment: {:?}", e);
-            AppError::Internal("Failed to retrieve attachment".to_string())
-        })?
-        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))?;
+    let user_id = params
+        .get("user_id")
+        .map(|id| UserId::from_string(id))
+        .transpose()
+        .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
+
+    let filter = ActivityFilter {
+        resource_type: params.get("resource_type").cloned(),
+        resource_id: params.get("resource_id").cloned(),
+        user_id,
+        since: parse_timestamp(&params, "since")?,
+        until: parse_timestamp(&params, "until")?,
+        limit: params.get("limit").and_then(|l| l.parse().ok()),
+        offset: params.get("offset").and_then(|o| o.parse().ok()),
+    };
 
-    // Read file from disk
-    let file_data = fs::read(&attachment.file_path).map_err(|e| {
-        tracing::error!("Failed to read file: {:?}", e);
-        AppError::NotFound("File not found on disk".to_string())
+    let activities = state.activity_repo.find(&filter).map_err(|e| {
+        tracing::error!("Failed to list activity: {:?}", e);
+        AppError::Internal("Failed to retrieve activity".to_string())
     })?;
 
-    // Return file with appropriate headers
-    Ok((
-        StatusCode::OK,
-        [
-            (header::CONTENT_TYPE, attachment.mime_type.clone()),
-            (
-                header::CONTENT_DISPOSITION,
-                format!("attachment; filename=\"{}\"", attachment.filename),
-            ),
-            (header::CONTENT_LENGTH, file_data.len().to_string()),
-        ],
-        file_data,
+    Ok(Json(
+        activities.into_iter().map(ActivityDto::from).collect(),
     ))
 }
 
+async fn get_ticket_history(
+    AuthUser(user): AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ActivityDto>>, AppError> {
+    let ticket_id = TicketId::from_string(&id)
+        .map_err(|_| AppError::BadRequest("Invalid ticket ID".to_string()))?;
+
+    let ticket = load_ticket(&state, ticket_id)?;
+    authorize(&state, &user, Some(ticket.project_id), "ticket", "read")?;
+
+    let history = state
+        .activity_repo
+        .find_for_resource("ticket", &ticket_id.to_string())
+        .map_err(|e| {
+            tracing::error!("Failed to get ticket history: {:?}", e);
+            AppError::Internal("Failed to retrieve ticket history".to_string())
+        })?;
+
+    Ok(Json(history.into_iter().map(ActivityDto::from).collect()))
+}
+
 // ============================================================================
 // Error Handling
 // ============================================================================
@@ -1216,25 +7236,141 @@ async fn download_attachment(
 #[derive(Debug)]
 enum AppError {
     BadRequest(String),
+    /// A ticket query that failed to parse; the response carries its position
+    InvalidQuery(QueryError),
     Unauthorized(String),
+    Forbidden(String),
     NotFound(String),
     Internal(String),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+        let (status, message, position) = match self {
+            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg, None),
+            AppError::InvalidQuery(err) => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid query: {}", err),
+                Some(err.position),
+            ),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg, None),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg, None),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg, None),
+            AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg, None),
         };
 
         #[derive(Serialize)]
         struct ErrorResponse {
             error: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            position: Option<usize>,
+        }
+
+        (
+            status,
+            Json(ErrorResponse {
+                error: message,
+                position,
+            }),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `(method, path)` of every route behind the auth middleware, read
+    /// from the router built in `main`
+    fn protected_routes() -> Vec<(Method, String)> {
+        let source = include_str!("main.rs");
+        let start = source.find("let protected_routes = Router::new()").unwrap();
+        let end = start
+            + source[start..]
+                .find(".layer(middleware::from_fn_with_state(")
+                .unwrap();
+
+        let mut routes = Vec::new();
+        for route in source[start..end].split(".route(").skip(1) {
+            let path = route.split('"').nth(1).unwrap();
+            // Path parameters such as `{id}` become a sample id
+            let path = path
+                .split('/')
+                .map(|segment| {
+                    if segment.starts_with('{') {
+                        "00000000-0000-0000-0000-000000000000"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let handlers = route.split('"').nth(2).unwrap();
+            for (name, method) in [
+                ("get(", Method::GET),
+                ("post(", Method::POST),
+                ("put(", Method::PUT),
+                ("delete(", Method::DELETE),
+            ] {
+                let called = handlers
+                    .match_indices(name)
+                    .any(|(i, _)| i == 0 || !handlers.as_bytes()[i - 1].is_ascii_alphanumeric());
+                if called {
+                    routes.push((method, path.clone()));
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn test_every_route_has_token_access() {
+        let routes = protected_routes();
+        assert!(routes.len() > 100, "found only {} routes", routes.len());
+
+        for (method, path) in &routes {
+            assert!(
+                token_access(method, path).is_some(),
+                "{} {} is neither mapped to a scope nor denied to access tokens",
+                method,
+                path
+            );
         }
+    }
 
-        (status, Json(ErrorResponse { error: message })).into_response()
+    #[test]
+    fn test_token_access_matches_whole_segments() {
+        assert_eq!(
+            token_access(&Method::GET, "/api/tickets"),
+            Some(TokenAccess::Scope(TokenScope::TicketsRead))
+        );
+        assert_eq!(
+            token_access(&Method::PUT, "/api/worklogs/1"),
+            Some(TokenAccess::Scope(TokenScope::TicketsWrite))
+        );
+        assert_eq!(
+            token_access(&Method::GET, "/api/timesheet"),
+            Some(TokenAccess::Scope(TokenScope::TicketsRead))
+        );
+        assert_eq!(
+            token_access(&Method::POST, "/api/teams"),
+            Some(TokenAccess::Scope(TokenScope::ProjectsWrite))
+        );
+        assert_eq!(
+            token_access(&Method::GET, "/api/users/me"),
+            Some(TokenAccess::Open)
+        );
+        assert_eq!(
+            token_access(&Method::PUT, "/api/users/me"),
+            Some(TokenAccess::Denied)
+        );
+        assert_eq!(
+            token_access(&Method::GET, "/api/notifications"),
+            Some(TokenAccess::Denied)
+        );
+        assert_eq!(token_access(&Method::GET, "/api/ticketsx"), None);
+        assert_eq!(token_access(&Method::GET, "/api/tickets-admin/1"), None);
     }
 }
@@ -0,0 +1,269 @@
+//! Outgoing webhooks
+//!
+//! Every payload is posted as JSON with its HMAC-SHA256 signature in the
+//! [`SIGNATURE_HEADER`], computed over the raw body with the webhook's
+//! secret. Receivers recompute it to check that a payload comes from
+//! Worknest and was not altered.
+//!
+//! Payloads only go to public addresses. Host names are resolved by
+//! [`PublicResolver`], which drops internal addresses so a name cannot
+//! be pointed at the server's own network after the webhook was saved, and
+//! redirects are not followed.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use worknest_core::models::{
+    is_internal_address, ChangeEvent, ProjectId, UserId, Webhook, WebhookDelivery,
+};
+
+/// Header carrying `sha256=<hex digest>` of the body
+pub const SIGNATURE_HEADER: &str = "X-Worknest-Signature";
+
+/// Header carrying the event name, such as `ticket.created`
+pub const EVENT_HEADER: &str = "X-Worknest-Event";
+
+/// Header carrying the delivery id, which differs for every redelivery
+pub const DELIVERY_HEADER: &str = "X-Worknest-Delivery";
+
+/// How long a receiver may take to answer
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Body posted to webhooks
+#[derive(Debug, Serialize)]
+struct Payload<'a> {
+    event: &'a str,
+    project_id: ProjectId,
+    resource_id: &'a str,
+    actor_id: UserId,
+    occurred_at: DateTime<Utc>,
+    /// The resource after the change, as the REST API returns it; `null`
+    /// for deletions
+    data: &'a Option<Value>,
+}
+
+/// Build the JSON body for a change
+pub fn payload(
+    event_name: &str,
+    change: &ChangeEvent,
+    occurred_at: DateTime<Utc>,
+) -> Result<String, serde_json::Error> {
+    let payload = Payload {
+        event: event_name,
+        project_id: change.project_id,
+        resource_id: &change.resource_id,
+        actor_id: change.actor_id,
+        occurred_at,
+        data: &change.data,
+    };
+
+    serde_json::to_string(&payload)
+}
+
+/// Signature of a body: `sha256=` followed by the hex HMAC-SHA256 digest
+pub fn sign(secret: &str, body: &str) -> String {
+    // HMAC takes keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC key");
+    mac.update(body.as_bytes());
+
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Outcome of posting a payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// HTTP status of the response, if one came back
+    pub response_code: Option<u16>,
+    /// Why the attempt failed, if it did
+    pub error: Option<String>,
+}
+
+/// Resolves host names to their public addresses only, failing for names
+/// that have none
+pub struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| !is_internal_address(addr.ip()))
+                .collect();
+
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// HTTP client posting payloads to webhooks
+#[derive(Clone)]
+pub struct WebhookClient {
+    client: reqwest::Client,
+}
+
+impl WebhookClient {
+    /// Build the client; there is no fallback to a client without the
+    /// address checks, so failing to build it is an error
+    pub fn new() -> Result<Self, reqwest::Error> {
+        // A proxy would resolve host names itself, past the resolver
+        let client = reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("Worknest-Webhooks/", env!("CARGO_PKG_VERSION")))
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()?;
+        Ok(Self { client })
+    }
+
+    /// Post a delivery's payload to its webhook
+    ///
+    /// Only the status of the receiver's response is kept, never its body.
+    pub async fn deliver(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> Attempt {
+        // Addresses written into the URL are not resolved, so check them here
+        let host = reqwest::Url::parse(&webhook.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string));
+        let literal = host
+            .as_deref()
+            .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+            .and_then(|host| host.parse::<IpAddr>().ok());
+        if literal.is_some_and(is_internal_address) {
+            return Attempt {
+                response_code: None,
+                error: Some("Webhook URL points to an internal address".to_string()),
+            };
+        }
+
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event.as_str())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(SIGNATURE_HEADER, sign(&webhook.secret, &delivery.payload))
+            .body(delivery.payload.clone())
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => Attempt {
+                response_code: Some(response.status().as_u16()),
+                error: None,
+            },
+            Ok(response) => Attempt {
+                response_code: Some(response.status().as_u16()),
+                error: Some(format!(
+                    "Receiver answered with status {}",
+                    response.status().as_u16()
+                )),
+            },
+            Err(e) => Attempt {
+                response_code: None,
+                error: Some(describe_error(&e)),
+            },
+        }
+    }
+}
+
+/// An error with the causes behind it, which for connection failures hold
+/// the actual reason
+fn describe_error(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use worknest_core::models::{ActivityAction, ChangeResource};
+
+    #[test]
+    fn test_sign() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_ne!(sign("other", "body"), sign("secret", "body"));
+    }
+
+    #[tokio::test]
+    async fn test_internal_receivers_are_refused() {
+        let client = WebhookClient::new().unwrap();
+        let change = ChangeEvent {
+            project_id: ProjectId::new(),
+            resource: ChangeResource::Ticket,
+            resource_id: "t1".to_string(),
+            action: ActivityAction::Created,
+            actor_id: UserId::new(),
+            data: None,
+        };
+        let delivery = WebhookDelivery::new(
+            worknest_core::models::WebhookId::new(),
+            worknest_core::models::WebhookEvent::TicketCreated,
+            payload("ticket.created", &change, Utc::now()).unwrap(),
+        );
+
+        for url in [
+            "http://127.0.0.1:9/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]:9/hook",
+            "http://localhost:9/hook",
+        ] {
+            let webhook = Webhook::new(
+                change.project_id,
+                url.to_string(),
+                "s3cret".to_string(),
+                vec![delivery.event],
+                change.actor_id,
+            );
+            let attempt = client.deliver(&webhook, &delivery).await;
+            assert_eq!(attempt.response_code, None, "{}", url);
+            let error = attempt.error.unwrap();
+            assert!(
+                error.contains("internal address") || error.contains("public address"),
+                "{}: {}",
+                url,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_payload() {
+        let change = ChangeEvent {
+            project_id: ProjectId::new(),
+            resource: ChangeResource::Ticket,
+            resource_id: "t1".to_string(),
+            action: ActivityAction::Created,
+            actor_id: UserId::new(),
+            data: Some(json!({ "title": "Login crash" })),
+        };
+
+        let body: Value =
+            serde_json::from_str(&payload("ticket.created", &change, Utc::now()).unwrap()).unwrap();
+        assert_eq!(body["event"], json!("ticket.created"));
+        assert_eq!(body["project_id"], json!(change.project_id.to_string()));
+        assert_eq!(body["resource_id"], json!("t1"));
+        assert_eq!(body["data"]["title"], json!("Login crash"));
+    }
+}
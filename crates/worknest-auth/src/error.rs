@@ -17,6 +17,22 @@ pub enum AuthError {
     #[error("Invalid token")]
     TokenInvalid,
 
+    /// The session behind the token was revoked or has ended
+    #[error("Session revoked")]
+    SessionRevoked,
+
+    /// A refresh token was presented after it had already been exchanged
+    #[error("Refresh token reuse detected")]
+    RefreshTokenReused,
+
+    /// Session not found
+    #[error("Session not found")]
+    SessionNotFound,
+
+    /// Personal access token not found
+    #[error("Access token not found")]
+    AccessTokenNotFound,
+
     /// User already exists
     #[error("User already exists")]
     UserExists,
@@ -25,6 +41,14 @@ pub enum AuthError {
     #[error("User not found")]
     UserNotFound,
 
+    /// Authenticated user lacks the required permission
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// Invalid user data
+    #[error("Validation error: {0}")]
+    Validation(String),
+
     /// Password validation error
     #[error("Password validation error: {0}")]
     PasswordValidation(String),
@@ -6,9 +6,11 @@
 
 pub mod error;
 pub mod password;
+pub mod permission;
 pub mod service;
 pub mod token;
 
 pub use error::{AuthError, Result};
-pub use service::AuthService;
-pub use token::{AuthToken, Claims};
+pub use permission::PermissionChecker;
+pub use service::{AuthService, STREAM_TICKET_SECONDS};
+pub use token::{AuthToken, Claims, SessionTokens, PERSONAL_ACCESS_TOKEN_PREFIX};
@@ -0,0 +1,239 @@
+//! Role-based authorization
+
+use std::sync::Arc;
+
+use worknest_core::models::{ProjectId, RoleId, UserId};
+use worknest_db::{PermissionRepository, RoleRepository};
+
+use crate::{AuthError, Result};
+
+/// Resolves a user's roles and checks them against the seeded permissions
+///
+/// A user's effective permissions on a project are the union of the
+/// permissions of their global roles and of the roles assigned to them for
+/// that project. Checks without a project only consider global roles.
+pub struct PermissionChecker {
+    role_repo: Arc<RoleRepository>,
+    permission_repo: Arc<PermissionRepository>,
+}
+
+impl PermissionChecker {
+    /// Create a new permission checker
+    ///
+    /// # Arguments
+    /// * `role_repo` - Role repository for role assignments
+    /// * `permission_repo` - Permission repository for role permissions
+    pub fn new(role_repo: Arc<RoleRepository>, permission_repo: Arc<PermissionRepository>) -> Self {
+        Self {
+            role_repo,
+            permission_repo,
+        }
+    }
+
+    /// Check whether a user may perform an action on a resource
+    ///
+    /// # Arguments
+    /// * `user_id` - User performing the action
+    /// * `project_id` - Project the resource belongs to, if any
+    /// * `resource` - Resource name (e.g. `ticket`)
+    /// * `action` - Action name (e.g. `delete`)
+    pub fn has_permission(
+        &self,
+        user_id: UserId,
+        project_id: Option<ProjectId>,
+        resource: &str,
+        action: &str,
+    ) -> Result<bool> {
+        let permissions = self
+            .permission_repo
+            .find_for_user(user_id, project_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        Ok(permissions.iter().any(|p| p.matches(resource, action)))
+    }
+
+    /// Require a permission, returning `AuthError::Forbidden` when it is missing
+    ///
+    /// # Arguments
+    /// * `user_id` - User performing the action
+    /// * `project_id` - Project the resource belongs to, if any
+    /// * `resource` - Resource name (e.g. `ticket`)
+    /// * `action` - Action name (e.g. `delete`)
+    pub fn require(
+        &self,
+        user_id: UserId,
+        project_id: Option<ProjectId>,
+        resource: &str,
+        action: &str,
+    ) -> Result<()> {
+        if self.has_permission(user_id, project_id, resource, action)? {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden(format!(
+                "Missing permission {}:{}",
+                resource, action
+            )))
+        }
+    }
+
+    /// Check whether a user holds the Admin role globally or for a project
+    pub fn is_admin(&self, user_id: UserId, project_id: Option<ProjectId>) -> Result<bool> {
+        let roles = self
+            .role_repo
+            .find_for_user(user_id, project_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        Ok(roles.iter().any(|r| r.id.as_str() == RoleId::ADMIN))
+    }
+
+    /// Assign the default global role to a newly registered user
+    ///
+    /// The first user of a fresh installation becomes the global Admin;
+    /// everyone else starts as a Member.
+    pub fn assign_default_roles(&self, user_id: UserId) -> Result<()> {
+        self.role_repo
+            .assign_initial_global_role(user_id)
+            .map(|_| ())
+            .map_err(|e| AuthError::Internal(e.to_string()))
+    }
+
+    /// Make a user the administrator of a project they created
+    pub fn grant_project_owner(&self, user_id: UserId, project_id: ProjectId) -> Result<()> {
+        self.role_repo
+            .assign_to_user(user_id, &RoleId::admin(), Some(project_id))
+            .map_err(|e| AuthError::Internal(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use worknest_core::models::User;
+    use worknest_db::{init_memory_pool, run_migrations, UserRepository};
+
+    fn setup_checker() -> (PermissionChecker, Arc<RoleRepository>, UserRepository) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let role_repo = Arc::new(RoleRepository::new(Arc::clone(&pool)));
+        let permission_repo = Arc::new(PermissionRepository::new(Arc::clone(&pool)));
+        let checker = PermissionChecker::new(Arc::clone(&role_repo), permission_repo);
+
+        (checker, role_repo, UserRepository::new(pool))
+    }
+
+    fn create_user(user_repo: &UserRepository, username: &str) -> UserId {
+        let user = User::new(username.to_string(), format!("{}@example.com", username));
+        user_repo.create_with_password(&user, "hash").unwrap();
+        user.id
+    }
+
+    #[test]
+    fn test_user_without_roles_is_denied() {
+        let (checker, _, user_repo) = setup_checker();
+        let user_id = create_user(&user_repo, "nobody");
+
+        assert!(!checker
+            .has_permission(user_id, None, "project", "read")
+            .unwrap());
+
+        let result = checker.require(user_id, None, "project", "read");
+        assert!(matches!(result, Err(AuthError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_first_user_becomes_admin() {
+        let (checker, _, user_repo) = setup_checker();
+        let first = create_user(&user_repo, "first");
+        let second = create_user(&user_repo, "second");
+
+        checker.assign_default_roles(first).unwrap();
+        checker.assign_default_roles(second).unwrap();
+
+        assert!(checker.is_admin(first, None).unwrap());
+        assert!(!checker.is_admin(second, None).unwrap());
+        assert!(checker.require(first, None, "project", "delete").is_ok());
+        assert!(checker.require(second, None, "project", "delete").is_err());
+        assert!(checker.require(second, None, "ticket", "update").is_ok());
+    }
+
+    #[test]
+    fn test_project_owner_gets_project_scoped_admin() {
+        let (checker, _, user_repo) = setup_checker();
+        let admin = create_user(&user_repo, "admin");
+        checker.assign_default_roles(admin).unwrap();
+
+        let owner = create_user(&user_repo, "owner");
+        checker.assign_default_roles(owner).unwrap();
+
+        let project_id = ProjectId::new();
+        checker.grant_project_owner(owner, project_id).unwrap();
+
+        assert!(checker
+            .require(owner, Some(project_id), "ticket", "delete")
+            .is_ok());
+        assert!(checker
+            .require(owner, Some(ProjectId::new()), "ticket", "delete")
+            .is_err());
+        assert!(checker.is_admin(owner, Some(project_id)).unwrap());
+        assert!(!checker.is_admin(owner, None).unwrap());
+    }
+
+    #[test]
+    fn test_global_member_is_read_only_on_public_projects() {
+        let (checker, role_repo, user_repo) = setup_checker();
+        let admin = create_user(&user_repo, "admin");
+        checker.assign_default_roles(admin).unwrap();
+        let outsider = create_user(&user_repo, "outsider");
+        checker.assign_default_roles(outsider).unwrap();
+
+        let project_id = Some(ProjectId::new());
+        assert!(checker
+            .require(outsider, project_id, "project", "read")
+            .is_ok());
+        assert!(checker
+            .require(outsider, project_id, "ticket", "read")
+            .is_ok());
+        assert!(matches!(
+            checker.require(outsider, project_id, "project", "update"),
+            Err(AuthError::Forbidden(_))
+        ));
+        assert!(checker
+            .require(outsider, project_id, "ticket", "create")
+            .is_err());
+
+        // Global permissions are unaffected
+        assert!(checker.require(outsider, None, "project", "create").is_ok());
+
+        // Membership grants write access
+        role_repo
+            .assign_to_user(outsider, &RoleId::member(), project_id)
+            .unwrap();
+        assert!(checker
+            .require(outsider, project_id, "project", "update")
+            .is_ok());
+        assert!(checker.is_admin(admin, project_id).unwrap());
+    }
+
+    #[test]
+    fn test_viewer_is_read_only() {
+        let (checker, role_repo, user_repo) = setup_checker();
+        let viewer = create_user(&user_repo, "viewer");
+        role_repo
+            .assign_to_user(viewer, &RoleId::viewer(), None)
+            .unwrap();
+
+        let project_id = Some(ProjectId::new());
+        assert!(checker
+            .require(viewer, project_id, "ticket", "read")
+            .is_ok());
+        assert!(checker
+            .require(viewer, project_id, "ticket", "create")
+            .is_err());
+        assert!(checker
+            .require(viewer, project_id, "comment", "create")
+            .is_err());
+    }
+}
@@ -1,37 +1,75 @@
 //! Authentication service
 
-use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use worknest_core::models::{User, UserId};
-use worknest_db::{Repository, UserRepository};
+use worknest_core::models::{
+    AccessTokenId, PersonalAccessToken, RefreshToken, Session, SessionId, TokenScope, User, UserId,
+};
+use worknest_db::{AccessTokenRepository, DbError, Repository, SessionRepository, UserRepository};
 
 use crate::{
     password,
-    token::{AuthToken, Claims, TokenManager},
+    token::{self, Claims, SessionTokens, TokenManager},
     AuthError, Result,
 };
 
 /// Authentication service
+///
+/// Logging in starts a session, recorded in the `sessions` table, and issues
+/// a short-lived access token plus a rotating refresh token for it. Access
+/// tokens carry the session ID in their `sid` claim and only authenticate
+/// while the session is active.
+///
+/// Personal access tokens are an alternative for scripts: opaque, long-lived
+/// and limited to a set of scopes.
+///
+/// Stream tickets stand in for access tokens where a token would end up in
+/// a URL: opaque, single-use and redeemable for a few seconds only. They
+/// are kept in memory, so they only work on the server that issued them.
 pub struct AuthService {
     user_repo: Arc<UserRepository>,
+    session_repo: Arc<SessionRepository>,
+    access_token_repo: Arc<AccessTokenRepository>,
     token_manager: TokenManager,
+    refresh_token_lifetime: Duration,
+    /// Claims of the unredeemed stream tickets, by ticket hash, with their
+    /// expiration time
+    stream_tickets: Mutex<HashMap<String, (Claims, DateTime<Utc>)>>,
 }
 
+/// How long a stream ticket can be redeemed
+pub const STREAM_TICKET_SECONDS: i64 = 30;
+
 impl AuthService {
     /// Create a new authentication service
     ///
     /// # Arguments
     /// * `user_repo` - User repository for database access
+    /// * `session_repo` - Session repository for database access
+    /// * `access_token_repo` - Personal access token repository for database access
     /// * `secret_key` - Secret key for JWT signing
-    /// * `token_expires_hours` - Token expiration time in hours (default: 24)
+    /// * `access_token_minutes` - Access token lifetime in minutes (default: 15)
+    /// * `refresh_token_days` - Refresh token lifetime in days (default: 30)
     pub fn new(
         user_repo: Arc<UserRepository>,
+        session_repo: Arc<SessionRepository>,
+        access_token_repo: Arc<AccessTokenRepository>,
         secret_key: String,
-        token_expires_hours: Option<i64>,
+        access_token_minutes: Option<i64>,
+        refresh_token_days: Option<i64>,
     ) -> Self {
         Self {
             user_repo,
-            token_manager: TokenManager::new(secret_key, token_expires_hours),
+            session_repo,
+            access_token_repo,
+            token_manager: TokenManager::with_lifetime(
+                secret_key,
+                Duration::minutes(access_token_minutes.unwrap_or(15)),
+            ),
+            refresh_token_lifetime: Duration::days(refresh_token_days.unwrap_or(30)),
+            stream_tickets: Mutex::new(HashMap::new()),
         }
     }
 
@@ -98,10 +136,16 @@ impl AuthService {
     /// # Arguments
     /// * `username` - Username or email
     /// * `password` - Plain text password
+    /// * `user_agent` - User agent of the client signing in, if known
     ///
     /// # Returns
-    /// An authentication token for the user
-    pub fn login(&self, username: &str, password: &str) -> Result<AuthToken> {
+    /// Access and refresh tokens for a new session
+    pub fn login(
+        &self,
+        username: &str,
+        password: &str,
+        user_agent: Option<&str>,
+    ) -> Result<SessionTokens> {
         // Find user by username or email
         let user = self
             .user_repo
@@ -123,10 +167,64 @@ impl AuthService {
             return Err(AuthError::InvalidCredentials);
         }
 
-        // Generate token
-        let token = self.token_manager.generate_token(user.id, user.username)?;
+        self.start_session(user.id, user.username, user_agent.map(str::to_string))
+    }
+
+    /// Persist a new session and issue its first tokens
+    fn start_session(
+        &self,
+        user_id: UserId,
+        username: String,
+        user_agent: Option<String>,
+    ) -> Result<SessionTokens> {
+        let refresh_token = token::generate_refresh_token();
+        let refresh_expires_at = Utc::now() + self.refresh_token_lifetime;
+        let session = Session::new(SessionId::new(), user_id, user_agent, refresh_expires_at);
+
+        self.session_repo
+            .create(&session)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+        self.session_repo
+            .create_refresh_token(&RefreshToken::new(
+                token::hash_token(&refresh_token),
+                session.id,
+                refresh_expires_at,
+            ))
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        let access_token = self
+            .token_manager
+            .generate_session_token(user_id, username, session.id)?;
+
+        Ok(SessionTokens {
+            access_token,
+            refresh_token,
+            refresh_expires_at,
+            session_id: session.id,
+        })
+    }
+
+    /// Check that the session behind a set of claims is still active, for
+    /// connections that outlive the request that authenticated them
+    pub fn check_session(&self, claims: &Claims) -> Result<()> {
+        self.active_session(claims).map(|_| ())
+    }
+
+    /// Find the active session behind a set of claims
+    fn active_session(&self, claims: &Claims) -> Result<Session> {
+        let session_id = claims.session_id()?;
+
+        let session = self
+            .session_repo
+            .find_by_id(session_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::SessionRevoked)?;
+
+        if !session.is_active() || session.user_id != claims.user_id()? {
+            return Err(AuthError::SessionRevoked);
+        }
 
-        Ok(token)
+        Ok(session)
     }
 
     /// Verify a JWT token
@@ -148,24 +246,308 @@ impl AuthService {
     /// # Returns
     /// The user if token is valid
     pub fn get_user_from_token(&self, token: &str) -> Result<User> {
+        self.authenticate(token).map(|(user, _)| user)
+    }
+
+    /// Authenticate a token
+    ///
+    /// Unlike [`verify_token`](Self::verify_token), this also rejects tokens
+    /// whose session was revoked.
+    ///
+    /// # Arguments
+    /// * `token` - The JWT token string
+    ///
+    /// # Returns
+    /// The user and the decoded claims if the token is valid
+    pub fn authenticate(&self, token: &str) -> Result<(User, Claims)> {
         let claims = self.verify_token(token)?;
-        let user_id = claims.user_id()?;
+        self.active_session(&claims)?;
 
-        self.user_repo
-            .find_by_id(user_id)
+        let user = self
+            .user_repo
+            .find_by_id(claims.user_id()?)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::UserNotFound)?;
+
+        Ok((user, claims))
+    }
+
+    /// Issue a single-use ticket that authenticates as the session behind
+    /// the claims, for [`STREAM_TICKET_SECONDS`]
+    pub fn issue_stream_ticket(&self, claims: &Claims) -> Result<String> {
+        let ticket = token::generate_refresh_token();
+        let now = Utc::now();
+
+        let mut tickets = self
+            .stream_tickets
+            .lock()
+            .map_err(|_| AuthError::Internal("Stream ticket store unavailable".to_string()))?;
+        tickets.retain(|_, (_, expires_at)| *expires_at > now);
+        tickets.insert(
+            token::hash_token(&ticket),
+            (
+                claims.clone(),
+                now + Duration::seconds(STREAM_TICKET_SECONDS),
+            ),
+        );
+
+        Ok(ticket)
+    }
+
+    /// Redeem a stream ticket, which can only be done once
+    ///
+    /// # Returns
+    /// The user and the claims the ticket was issued for, if the ticket is
+    /// valid and its session still active
+    pub fn redeem_stream_ticket(&self, ticket: &str) -> Result<(User, Claims)> {
+        let (claims, expires_at) = self
+            .stream_tickets
+            .lock()
+            .map_err(|_| AuthError::Internal("Stream ticket store unavailable".to_string()))?
+            .remove(&token::hash_token(ticket))
+            .ok_or(AuthError::TokenInvalid)?;
+        if expires_at <= Utc::now() {
+            return Err(AuthError::TokenExpired);
+        }
+
+        self.active_session(&claims)?;
+        let user = self
+            .user_repo
+            .find_by_id(claims.user_id()?)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::UserNotFound)?;
+
+        Ok((user, claims))
+    }
+
+    /// Exchange a refresh token for new tokens
+    ///
+    /// Refresh tokens are single-use: the presented token is retired and a
+    /// new one is issued alongside a fresh access token. Presenting a retired
+    /// token again means it leaked, so the whole session is revoked.
+    ///
+    /// # Arguments
+    /// * `refresh_token` - The current refresh token
+    ///
+    /// # Returns
+    /// New access and refresh tokens for the same session
+    pub fn refresh_token(&self, refresh_token: &str) -> Result<SessionTokens> {
+        let token_hash = token::hash_token(refresh_token);
+
+        let stored = self
+            .session_repo
+            .find_refresh_token(&token_hash)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::TokenInvalid)?;
+
+        if stored.is_used() {
+            return Err(self.refresh_token_reused(stored.session_id));
+        }
+
+        let session = self
+            .session_repo
+            .find_by_id(stored.session_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .filter(Session::is_active)
+            .ok_or(AuthError::SessionRevoked)?;
+
+        if stored.is_expired() {
+            return Err(AuthError::TokenExpired);
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(session.user_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::UserNotFound)?;
+
+        let successor = token::generate_refresh_token();
+        let refresh_expires_at = Utc::now() + self.refresh_token_lifetime;
+        let rotated = self
+            .session_repo
+            .rotate_refresh_token(
+                &token_hash,
+                &RefreshToken::new(
+                    token::hash_token(&successor),
+                    session.id,
+                    refresh_expires_at,
+                ),
+            )
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        // Lost a race against another exchange of the same token
+        if !rotated {
+            return Err(self.refresh_token_reused(session.id));
+        }
+
+        let access_token =
+            self.token_manager
+                .generate_session_token(user.id, user.username, session.id)?;
+
+        Ok(SessionTokens {
+            access_token,
+            refresh_token: successor,
+            refresh_expires_at,
+            session_id: session.id,
+        })
+    }
+
+    /// Revoke a session whose refresh token was replayed
+    fn refresh_token_reused(&self, session_id: SessionId) -> AuthError {
+        tracing::warn!(
+            "Refresh token reuse detected, revoking session {}",
+            session_id
+        );
+
+        match self.revoke_session_by_id(session_id) {
+            Ok(()) => AuthError::RefreshTokenReused,
+            Err(e) => e,
+        }
+    }
+
+    /// Logout by revoking the session behind a token
+    ///
+    /// # Arguments
+    /// * `claims` - Claims of the token being logged out
+    pub fn logout(&self, claims: &Claims) -> Result<()> {
+        self.revoke_session_by_id(claims.session_id()?)
+    }
+
+    /// Logout everywhere by revoking every session of a user
+    ///
+    /// # Returns
+    /// The number of sessions revoked
+    pub fn logout_all(&self, user_id: UserId) -> Result<usize> {
+        self.session_repo
+            .revoke_all_for_user(user_id, None)
+            .map_err(|e| AuthError::Internal(e.to_string()))
+    }
+
+    /// List the active sessions of a user, newest first
+    pub fn list_sessions(&self, user_id: UserId) -> Result<Vec<Session>> {
+        self.session_repo
+            .find_active_for_user(user_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))
+    }
+
+    /// Revoke one of a user's sessions
+    ///
+    /// Sessions of other users are reported as not found.
+    pub fn revoke_session(&self, user_id: UserId, session_id: SessionId) -> Result<()> {
+        let session = self
+            .session_repo
+            .find_by_id(session_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        match session {
+            Some(session) if session.user_id == user_id => self.revoke_session_by_id(session_id),
+            _ => Err(AuthError::SessionNotFound),
+        }
+    }
+
+    fn revoke_session_by_id(&self, session_id: SessionId) -> Result<()> {
+        self.session_repo.revoke(session_id).map_err(|e| match e {
+            DbError::NotFound(_) => AuthError::SessionNotFound,
+            e => AuthError::Internal(e.to_string()),
+        })
+    }
+
+    /// Create a personal access token
+    ///
+    /// # Arguments
+    /// * `user_id` - Owner of the token
+    /// * `name` - Name to recognise the token by
+    /// * `scopes` - What the token may be used for
+    /// * `expires_at` - Expiration time, or `None` for a token that never expires
+    ///
+    /// # Returns
+    /// The stored token and its plain text value, which cannot be retrieved later
+    pub fn create_personal_access_token(
+        &self,
+        user_id: UserId,
+        name: &str,
+        scopes: Vec<TokenScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(PersonalAccessToken, String)> {
+        let secret = token::generate_personal_access_token();
+        let pat = PersonalAccessToken::new(
+            user_id,
+            name.trim().to_string(),
+            token::hash_token(&secret),
+            scopes,
+            expires_at,
+        );
+
+        pat.validate()
+            .map_err(|e| AuthError::Validation(e.to_string()))?;
+
+        let pat = self
+            .access_token_repo
+            .create(&pat)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        Ok((pat, secret))
+    }
+
+    /// List a user's personal access tokens that have not been revoked
+    pub fn list_personal_access_tokens(&self, user_id: UserId) -> Result<Vec<PersonalAccessToken>> {
+        self.access_token_repo
+            .find_by_user(user_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))
+    }
+
+    /// Revoke one of a user's personal access tokens
+    ///
+    /// Tokens of other users are reported as not found.
+    pub fn revoke_personal_access_token(&self, user_id: UserId, id: AccessTokenId) -> Result<()> {
+        let pat = self
+            .access_token_repo
+            .find_by_id(id)
             .map_err(|e| AuthError::Internal(e.to_string()))?
-            .ok_or(AuthError::UserNotFound)
+            .filter(|pat| pat.user_id == user_id)
+            .ok_or(AuthError::AccessTokenNotFound)?;
+
+        self.access_token_repo.revoke(pat.id).map_err(|e| match e {
+            DbError::NotFound(_) => AuthError::AccessTokenNotFound,
+            e => AuthError::Internal(e.to_string()),
+        })
     }
 
-    /// Refresh a token
+    /// Authenticate a personal access token
     ///
     /// # Arguments
-    /// * `token` - The current token
+    /// * `secret` - The plain text token, including its `wn_pat_` prefix
     ///
     /// # Returns
-    /// A new token with extended expiration
-    pub fn refresh_token(&self, token: &str) -> Result<AuthToken> {
-        self.token_manager.refresh_token(token)
+    /// The owner of the token and the token itself, for scope checks
+    pub fn authenticate_personal_access_token(
+        &self,
+        secret: &str,
+    ) -> Result<(User, PersonalAccessToken)> {
+        let pat = self
+            .access_token_repo
+            .find_by_hash(&token::hash_token(secret))
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::TokenInvalid)?;
+
+        if pat.revoked_at.is_some() {
+            return Err(AuthError::TokenInvalid);
+        }
+        if !pat.is_active() {
+            return Err(AuthError::TokenExpired);
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(pat.user_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::UserNotFound)?;
+
+        self.access_token_repo
+            .touch(pat.id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
+        Ok((user, pat))
     }
 
     /// Change user password
@@ -174,11 +556,14 @@ impl AuthService {
     /// * `user_id` - User ID
     /// * `old_password` - Current password
     /// * `new_password` - New password
+    /// * `current_session` - Session to keep signed in; every other session
+    ///   of the user is revoked
     pub fn change_password(
         &self,
         user_id: UserId,
         old_password: &str,
         new_password: &str,
+        current_session: Option<SessionId>,
     ) -> Result<()> {
         // Get current password hash
         let current_hash = self
@@ -201,8 +586,67 @@ impl AuthService {
             .update_password(user_id, &new_hash)
             .map_err(|e| AuthError::Internal(e.to_string()))?;
 
+        // Sign out everywhere else
+        self.session_repo
+            .revoke_all_for_user(user_id, current_session)
+            .map_err(|e| AuthError::Internal(e.to_string()))?;
+
         Ok(())
     }
+
+    /// Update a user's profile
+    ///
+    /// # Arguments
+    /// * `user_id` - User ID
+    /// * `username` - New username, if changing
+    /// * `email` - New email address, if changing
+    ///
+    /// # Returns
+    /// The updated user
+    pub fn update_profile(
+        &self,
+        user_id: UserId,
+        username: Option<&str>,
+        email: Option<&str>,
+    ) -> Result<User> {
+        let mut user = self
+            .user_repo
+            .find_by_id(user_id)
+            .map_err(|e| AuthError::Internal(e.to_string()))?
+            .ok_or(AuthError::UserNotFound)?;
+
+        if let Some(username) = username.filter(|u| *u != user.username) {
+            let taken = self
+                .user_repo
+                .find_by_username(username)
+                .map_err(|e| AuthError::Internal(e.to_string()))?
+                .is_some();
+            if taken {
+                return Err(AuthError::UserExists);
+            }
+            user.username = username.to_string();
+        }
+
+        if let Some(email) = email.filter(|e| *e != user.email) {
+            let taken = self
+                .user_repo
+                .find_by_email(email)
+                .map_err(|e| AuthError::Internal(e.to_string()))?
+                .is_some();
+            if taken {
+                return Err(AuthError::UserExists);
+            }
+            user.email = email.to_string();
+        }
+
+        user.validate()
+            .map_err(|e| AuthError::Validation(e.to_string()))?;
+
+        self.user_repo.update(&user).map_err(|e| match e {
+            DbError::ConstraintViolation(_) => AuthError::UserExists,
+            e => AuthError::Internal(e.to_string()),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -216,8 +660,17 @@ mod tests {
         run_migrations(&mut conn).unwrap();
         drop(conn);
 
-        let user_repo = Arc::new(UserRepository::new(pool));
-        AuthService::new(user_repo, "test_secret_key".to_string(), Some(24))
+        let user_repo = Arc::new(UserRepository::new(Arc::clone(&pool)));
+        let session_repo = Arc::new(SessionRepository::new(Arc::clone(&pool)));
+        let access_token_repo = Arc::new(AccessTokenRepository::new(pool));
+        AuthService::new(
+            user_repo,
+            session_repo,
+            access_token_repo,
+            "test_secret_key".to_string(),
+            None,
+            None,
+        )
     }
 
     #[test]
@@ -268,9 +721,9 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let token = service.login("testuser", "password123").unwrap();
+        let token = service.login("testuser", "password123", None).unwrap();
 
-        assert!(!token.token.is_empty());
+        assert!(!token.access_token.token.is_empty());
     }
 
     #[test]
@@ -281,9 +734,11 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let token = service.login("test@example.com", "password123").unwrap();
+        let token = service
+            .login("test@example.com", "password123", None)
+            .unwrap();
 
-        assert!(!token.token.is_empty());
+        assert!(!token.access_token.token.is_empty());
     }
 
     #[test]
@@ -294,7 +749,7 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let result = service.login("testuser", "wrongpassword");
+        let result = service.login("testuser", "wrongpassword", None);
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), AuthError::InvalidCredentials));
@@ -304,7 +759,7 @@ mod tests {
     fn test_login_nonexistent_user() {
         let service = setup_auth_service();
 
-        let result = service.login("nonexistent", "password123");
+        let result = service.login("nonexistent", "password123", None);
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), AuthError::InvalidCredentials));
@@ -318,8 +773,8 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let token = service.login("testuser", "password123").unwrap();
-        let claims = service.verify_token(&token.token).unwrap();
+        let token = service.login("testuser", "password123", None).unwrap();
+        let claims = service.verify_token(&token.access_token.token).unwrap();
 
         assert_eq!(claims.username, "testuser");
     }
@@ -332,8 +787,10 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let token = service.login("testuser", "password123").unwrap();
-        let user = service.get_user_from_token(&token.token).unwrap();
+        let token = service.login("testuser", "password123", None).unwrap();
+        let user = service
+            .get_user_from_token(&token.access_token.token)
+            .unwrap();
 
         assert_eq!(user.id, registered_user.id);
         assert_eq!(user.username, "testuser");
@@ -347,14 +804,163 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let token = service.login("testuser", "password123").unwrap();
-        let refreshed = service.refresh_token(&token.token).unwrap();
+        let tokens = service.login("testuser", "password123", None).unwrap();
+        let refreshed = service.refresh_token(&tokens.refresh_token).unwrap();
 
-        assert_ne!(token.token, refreshed.token);
+        assert_ne!(tokens.refresh_token, refreshed.refresh_token);
+        assert_ne!(tokens.access_token.token, refreshed.access_token.token);
+        assert_eq!(tokens.session_id, refreshed.session_id);
 
         // Verify new token works
-        let claims = service.verify_token(&refreshed.token).unwrap();
+        let claims = service.verify_token(&refreshed.access_token.token).unwrap();
         assert_eq!(claims.username, "testuser");
+        assert!(service
+            .get_user_from_token(&refreshed.access_token.token)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_refresh_token_reuse_revokes_session() {
+        let service = setup_auth_service();
+
+        service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+
+        let tokens = service.login("testuser", "password123", None).unwrap();
+        let refreshed = service.refresh_token(&tokens.refresh_token).unwrap();
+
+        // Replaying the retired refresh token revokes the session
+        assert!(matches!(
+            service.refresh_token(&tokens.refresh_token),
+            Err(AuthError::RefreshTokenReused)
+        ));
+        assert!(matches!(
+            service.get_user_from_token(&refreshed.access_token.token),
+            Err(AuthError::SessionRevoked)
+        ));
+        assert!(matches!(
+            service.refresh_token(&refreshed.refresh_token),
+            Err(AuthError::SessionRevoked)
+        ));
+    }
+
+    #[test]
+    fn test_refresh_with_unknown_token() {
+        let service = setup_auth_service();
+
+        assert!(matches!(
+            service.refresh_token("not-a-refresh-token"),
+            Err(AuthError::TokenInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_login_creates_session() {
+        let service = setup_auth_service();
+
+        let user = service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+
+        let token = service
+            .login("testuser", "password123", Some("test-agent"))
+            .unwrap();
+        let sessions = service.list_sessions(user.id).unwrap();
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, token.session_id);
+        assert_eq!(sessions[0].user_agent, Some("test-agent".to_string()));
+    }
+
+    #[test]
+    fn test_logout_revokes_token() {
+        let service = setup_auth_service();
+
+        service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+
+        let token = service.login("testuser", "password123", None).unwrap();
+        let (user, claims) = service.authenticate(&token.access_token.token).unwrap();
+
+        service.logout(&claims).unwrap();
+
+        assert!(matches!(
+            service.authenticate(&token.access_token.token),
+            Err(AuthError::SessionRevoked)
+        ));
+        assert!(service.list_sessions(user.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stream_ticket_is_single_use() {
+        let service = setup_auth_service();
+
+        service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+        let token = service.login("testuser", "password123", None).unwrap();
+        let (user, claims) = service.authenticate(&token.access_token.token).unwrap();
+
+        let ticket = service.issue_stream_ticket(&claims).unwrap();
+        assert_ne!(ticket, token.access_token.token);
+        let (redeemed, redeemed_claims) = service.redeem_stream_ticket(&ticket).unwrap();
+        assert_eq!(redeemed.id, user.id);
+        assert_eq!(redeemed_claims.sid, claims.sid);
+        assert!(matches!(
+            service.redeem_stream_ticket(&ticket),
+            Err(AuthError::TokenInvalid)
+        ));
+
+        // Tickets and open streams end with their session
+        let ticket = service.issue_stream_ticket(&claims).unwrap();
+        service.logout(&claims).unwrap();
+        assert!(matches!(
+            service.redeem_stream_ticket(&ticket),
+            Err(AuthError::SessionRevoked)
+        ));
+        assert!(matches!(
+            service.check_session(&claims),
+            Err(AuthError::SessionRevoked)
+        ));
+    }
+
+    #[test]
+    fn test_logout_all_and_revoke_session() {
+        let service = setup_auth_service();
+
+        let user = service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+        let other = service
+            .register("otheruser", "other@example.com", "password123")
+            .unwrap();
+
+        let first = service.login("testuser", "password123", None).unwrap();
+        let second = service.login("testuser", "password123", None).unwrap();
+        let foreign = service.login("otheruser", "password123", None).unwrap();
+
+        let second_id = second.session_id;
+        assert!(matches!(
+            service.revoke_session(other.id, second_id),
+            Err(AuthError::SessionNotFound)
+        ));
+        service.revoke_session(user.id, second_id).unwrap();
+        assert!(service
+            .get_user_from_token(&second.access_token.token)
+            .is_err());
+        assert!(service
+            .get_user_from_token(&first.access_token.token)
+            .is_ok());
+
+        assert_eq!(service.logout_all(user.id).unwrap(), 1);
+        assert!(service
+            .get_user_from_token(&first.access_token.token)
+            .is_err());
+        assert!(service
+            .get_user_from_token(&foreign.access_token.token)
+            .is_ok());
     }
 
     #[test]
@@ -367,16 +973,16 @@ mod tests {
 
         // Change password
         service
-            .change_password(user.id, "oldpassword", "newpassword")
+            .change_password(user.id, "oldpassword", "newpassword", None)
             .unwrap();
 
         // Old password should not work
-        let result = service.login("testuser", "oldpassword");
+        let result = service.login("testuser", "oldpassword", None);
         assert!(result.is_err());
 
         // New password should work
-        let token = service.login("testuser", "newpassword").unwrap();
-        assert!(!token.token.is_empty());
+        let token = service.login("testuser", "newpassword", None).unwrap();
+        assert!(!token.access_token.token.is_empty());
     }
 
     #[test]
@@ -387,7 +993,7 @@ mod tests {
             .register("testuser", "test@example.com", "password123")
             .unwrap();
 
-        let result = service.change_password(user.id, "wrongpassword", "newpassword");
+        let result = service.change_password(user.id, "wrongpassword", "newpassword", None);
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), AuthError::InvalidCredentials));
@@ -405,4 +1011,138 @@ mod tests {
             AuthError::PasswordValidation(_)
         ));
     }
+
+    #[test]
+    fn test_change_password_revokes_other_sessions() {
+        let service = setup_auth_service();
+
+        let user = service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+        let current = service.login("testuser", "password123", None).unwrap();
+        let other = service.login("testuser", "password123", None).unwrap();
+
+        service
+            .change_password(
+                user.id,
+                "password123",
+                "newpassword",
+                Some(current.session_id),
+            )
+            .unwrap();
+
+        assert!(service
+            .get_user_from_token(&current.access_token.token)
+            .is_ok());
+        assert!(matches!(
+            service.get_user_from_token(&other.access_token.token),
+            Err(AuthError::SessionRevoked)
+        ));
+    }
+
+    #[test]
+    fn test_update_profile() {
+        let service = setup_auth_service();
+
+        let user = service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+        service
+            .register("otheruser", "other@example.com", "password123")
+            .unwrap();
+
+        let updated = service
+            .update_profile(user.id, Some("renamed"), Some("renamed@example.com"))
+            .unwrap();
+        assert_eq!(updated.username, "renamed");
+        assert_eq!(updated.email, "renamed@example.com");
+
+        // Keeping the current values is not a conflict
+        assert!(service
+            .update_profile(user.id, Some("renamed"), None)
+            .is_ok());
+
+        assert!(matches!(
+            service.update_profile(user.id, Some("otheruser"), None),
+            Err(AuthError::UserExists)
+        ));
+        assert!(matches!(
+            service.update_profile(user.id, None, Some("other@example.com")),
+            Err(AuthError::UserExists)
+        ));
+        assert!(matches!(
+            service.update_profile(user.id, None, Some("not-an-email")),
+            Err(AuthError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_personal_access_token_lifecycle() {
+        let service = setup_auth_service();
+
+        let user = service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+        let (pat, secret) = service
+            .create_personal_access_token(user.id, "CI", vec![TokenScope::TicketsRead], None)
+            .unwrap();
+
+        assert!(secret.starts_with(token::PERSONAL_ACCESS_TOKEN_PREFIX));
+        assert_ne!(pat.token_hash, secret);
+
+        let (owner, found) = service.authenticate_personal_access_token(&secret).unwrap();
+        assert_eq!(owner.id, user.id);
+        assert!(found.allows(TokenScope::TicketsRead));
+        assert!(!found.allows(TokenScope::TicketsWrite));
+
+        assert_eq!(
+            service.list_personal_access_tokens(user.id).unwrap().len(),
+            1
+        );
+
+        service
+            .revoke_personal_access_token(user.id, pat.id)
+            .unwrap();
+        assert!(matches!(
+            service.authenticate_personal_access_token(&secret),
+            Err(AuthError::TokenInvalid)
+        ));
+        assert!(service
+            .list_personal_access_tokens(user.id)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_personal_access_token_validation() {
+        let service = setup_auth_service();
+
+        let user = service
+            .register("testuser", "test@example.com", "password123")
+            .unwrap();
+        let other = service
+            .register("otheruser", "other@example.com", "password123")
+            .unwrap();
+
+        assert!(matches!(
+            service.create_personal_access_token(user.id, " ", vec![TokenScope::TicketsRead], None),
+            Err(AuthError::Validation(_))
+        ));
+        assert!(matches!(
+            service.create_personal_access_token(user.id, "CI", Vec::new(), None),
+            Err(AuthError::Validation(_))
+        ));
+
+        let (pat, _) = service
+            .create_personal_access_token(user.id, "CI", vec![TokenScope::TicketsRead], None)
+            .unwrap();
+        assert!(matches!(
+            service.revoke_personal_access_token(other.id, pat.id),
+            Err(AuthError::AccessTokenNotFound)
+        ));
+        assert!(matches!(
+            service.authenticate_personal_access_token("wn_pat_unknown"),
+            Err(AuthError::TokenInvalid)
+        ));
+    }
 }
@@ -2,10 +2,12 @@
 
 use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
-use worknest_core::models::UserId;
+use worknest_core::models::{SessionId, UserId};
 
 use crate::{AuthError, Result};
 
@@ -20,15 +22,34 @@ pub struct Claims {
     pub exp: i64,
     /// Token issued at timestamp
     pub iat: i64,
-    /// Token ID (for revocation)
+    /// Token ID
     pub jti: String,
+    /// Session ID (for revocation)
+    pub sid: String,
 }
 
 impl Claims {
     /// Create new claims for a user
+    ///
+    /// The claims reference a fresh session ID that is not persisted.
     pub fn new(user_id: UserId, username: String, expires_in_hours: i64) -> Self {
+        Self::for_session(
+            user_id,
+            username,
+            SessionId::new(),
+            Duration::hours(expires_in_hours),
+        )
+    }
+
+    /// Create new claims for a token issued within a session
+    pub fn for_session(
+        user_id: UserId,
+        username: String,
+        session_id: SessionId,
+        expires_in: Duration,
+    ) -> Self {
         let now = Utc::now();
-        let expiration = now + Duration::hours(expires_in_hours);
+        let expiration = now + expires_in;
 
         Self {
             sub: user_id.0.to_string(),
@@ -36,6 +57,7 @@ impl Claims {
             exp: expiration.timestamp(),
             iat: now.timestamp(),
             jti: Uuid::new_v4().to_string(),
+            sid: session_id.0.to_string(),
         }
     }
 
@@ -45,6 +67,11 @@ impl Claims {
         Ok(UserId::from_uuid(uuid))
     }
 
+    /// Get the ID of the session behind the token
+    pub fn session_id(&self) -> Result<SessionId> {
+        SessionId::from_string(&self.sid).map_err(|_| AuthError::TokenInvalid)
+    }
+
     /// Check if token is expired
     pub fn is_expired(&self) -> bool {
         Utc::now().timestamp() > self.exp
@@ -78,10 +105,51 @@ impl AuthToken {
     }
 }
 
+/// Tokens issued when a session is started or refreshed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTokens {
+    /// Short-lived access token
+    pub access_token: AuthToken,
+    /// Opaque single-use refresh token
+    pub refresh_token: String,
+    /// Refresh token expiration time
+    pub refresh_expires_at: DateTime<Utc>,
+    /// Session the tokens belong to
+    pub session_id: SessionId,
+}
+
+/// Prefix identifying personal access tokens in `Authorization` headers
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "wn_pat_";
+
+/// Generate a new opaque refresh token
+pub fn generate_refresh_token() -> String {
+    random_hex()
+}
+
+/// Generate a new personal access token
+pub fn generate_personal_access_token() -> String {
+    format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, random_hex())
+}
+
+/// Hash an opaque token (refresh or personal access token) for storage
+pub fn hash_token(token: &str) -> String {
+    to_hex(&Sha256::digest(token.as_bytes()))
+}
+
+fn random_hex() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    to_hex(&bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /// Token manager for JWT operations
 pub struct TokenManager {
     secret: String,
-    expires_in_hours: i64,
+    expires_in: Duration,
 }
 
 impl TokenManager {
@@ -91,10 +159,12 @@ impl TokenManager {
     /// * `secret` - Secret key for signing tokens
     /// * `expires_in_hours` - Token expiration time in hours (default: 24)
     pub fn new(secret: String, expires_in_hours: Option<i64>) -> Self {
-        Self {
-            secret,
-            expires_in_hours: expires_in_hours.unwrap_or(24),
-        }
+        Self::with_lifetime(secret, Duration::hours(expires_in_hours.unwrap_or(24)))
+    }
+
+    /// Create a new token manager with an arbitrary token lifetime
+    pub fn with_lifetime(secret: String, expires_in: Duration) -> Self {
+        Self { secret, expires_in }
     }
 
     /// Generate a JWT token for a user
@@ -106,7 +176,22 @@ impl TokenManager {
     /// # Returns
     /// An AuthToken containing the JWT string and expiration info
     pub fn generate_token(&self, user_id: UserId, username: String) -> Result<AuthToken> {
-        let claims = Claims::new(user_id, username, self.expires_in_hours);
+        self.generate_session_token(user_id, username, SessionId::new())
+    }
+
+    /// Generate a JWT token for a user within a session
+    ///
+    /// # Arguments
+    /// * `user_id` - User ID
+    /// * `username` - Username
+    /// * `session_id` - Session the token is issued for
+    pub fn generate_session_token(
+        &self,
+        user_id: UserId,
+        username: String,
+        session_id: SessionId,
+    ) -> Result<AuthToken> {
+        let claims = Claims::for_session(user_id, username, session_id, self.expires_in);
 
         let token = encode(
             &Header::default(),
@@ -144,6 +229,8 @@ impl TokenManager {
 
     /// Refresh a token (generate a new one with updated expiration)
     ///
+    /// The new token belongs to the same session as the current one.
+    ///
     /// # Arguments
     /// * `token` - The current token to refresh
     ///
@@ -151,7 +238,8 @@ impl TokenManager {
     /// A new AuthToken with extended expiration
     pub fn refresh_token(&self, token: &str) -> Result<AuthToken> {
         let claims = self.verify_token(token)?;
-        self.generate_token(claims.user_id()?, claims.username)
+        let session_id = claims.session_id()?;
+        self.generate_session_token(claims.user_id()?, claims.username, session_id)
     }
 }
 
@@ -247,5 +335,38 @@ mod tests {
         let claims = manager.verify_token(&refreshed.token).unwrap();
         assert_eq!(claims.username, username);
         assert_eq!(claims.user_id().unwrap(), user_id);
+
+        // And stay in the same session
+        let original = manager.verify_token(&auth_token.token).unwrap();
+        assert_eq!(claims.sid, original.sid);
+        assert_ne!(claims.jti, original.jti);
+    }
+
+    #[test]
+    fn test_short_lived_token() {
+        let manager = TokenManager::with_lifetime("secret".to_string(), Duration::minutes(15));
+        let session_id = SessionId::new();
+
+        let auth_token = manager
+            .generate_session_token(UserId::new(), "testuser".to_string(), session_id)
+            .unwrap();
+        let claims = manager.verify_token(&auth_token.token).unwrap();
+
+        assert_eq!(claims.session_id().unwrap(), session_id);
+        assert!(auth_token.expires_at <= Utc::now() + Duration::minutes(15));
+    }
+
+    #[test]
+    fn test_refresh_token_hashing() {
+        let token = generate_refresh_token();
+
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_refresh_token());
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), token);
+
+        let pat = generate_personal_access_token();
+        assert!(pat.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX));
+        assert_ne!(hash_token(&pat), hash_token(&token));
     }
 }
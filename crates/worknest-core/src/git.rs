@@ -0,0 +1,200 @@
+//! Ticket references in Git branches, commit messages and pull requests
+//!
+//! Commit messages and pull request titles refer to tickets by key, such as
+//! `[WN-12] Fix login crash`. A key right after a closing keyword
+//! (`fixes WN-12`, `Closes: WN-3`) also marks the ticket as resolved by the
+//! change. Branch names carry the key (`feature/WN-12-login-crash`) or,
+//! for branches created before tickets had keys, the first eight hex
+//! digits of the ticket id (`feature/1a2b3c4d-login-crash`).
+
+use crate::models::TicketKey;
+
+/// Words that mark the ticket key after them as resolved
+pub const CLOSING_KEYWORDS: &[&str] = &[
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// A ticket mentioned in a commit message or pull request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketReference {
+    pub key: TicketKey,
+    /// Whether a closing keyword came before any mention of the key
+    pub closes: bool,
+}
+
+/// Find the tickets a commit message or pull request refers to, in order
+/// of their first mention
+///
+/// Keys must be upper case here so that words like `utf-8` are not taken
+/// for tickets.
+pub fn find_references(text: &str) -> Vec<TicketReference> {
+    let mut references: Vec<TicketReference> = Vec::new();
+
+    for (start, key) in scan_keys(text, false) {
+        let closes = follows_closing_keyword(&text[..start]);
+        match references.iter_mut().find(|r| r.key == key) {
+            Some(reference) => reference.closes |= closes,
+            None => references.push(TicketReference { key, closes }),
+        }
+    }
+
+    references
+}
+
+/// Find the ticket keys in a branch name, in either case
+pub fn branch_keys(branch: &str) -> Vec<TicketKey> {
+    let mut keys: Vec<TicketKey> = Vec::new();
+    for (_, key) in scan_keys(branch, true) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Find ticket id prefixes in a branch name: segments of exactly eight
+/// hex digits with at least one digit among them, in lower case
+pub fn branch_id_prefixes(branch: &str) -> Vec<String> {
+    let mut prefixes: Vec<String> = Vec::new();
+    for segment in branch.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let is_prefix = segment.len() == 8
+            && segment.bytes().all(|b| b.is_ascii_hexdigit())
+            && segment.bytes().any(|b| b.is_ascii_digit());
+        let segment = segment.to_ascii_lowercase();
+        if is_prefix && !prefixes.contains(&segment) {
+            prefixes.push(segment);
+        }
+    }
+    prefixes
+}
+
+/// Find keys standing as words of their own, with the byte offset where
+/// each starts
+fn scan_keys(text: &str, any_case: bool) -> Vec<(usize, TicketKey)> {
+    let bytes = text.as_bytes();
+    let mut keys = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if i > 0 && bytes[i - 1].is_ascii_alphanumeric() {
+            i += 1;
+            continue;
+        }
+        match key_at(&bytes[i..], any_case) {
+            Some((len, key)) => {
+                keys.push((i, key));
+                i += len;
+            },
+            None => i += 1,
+        }
+    }
+
+    keys
+}
+
+/// Parse a key at the start of `bytes`, returning its length
+fn key_at(bytes: &[u8], any_case: bool) -> Option<(usize, TicketKey)> {
+    let project_len = bytes
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric())
+        .count();
+    if bytes.get(project_len) != Some(&b'-') {
+        return None;
+    }
+
+    let digits = bytes[project_len + 1..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let end = project_len + 1 + digits;
+    if digits == 0 || bytes.get(end).is_some_and(|b| b.is_ascii_alphanumeric()) {
+        return None;
+    }
+    if !any_case && bytes[..project_len].iter().any(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+
+    // Everything up to `end` is ASCII
+    let key = TicketKey::parse(std::str::from_utf8(&bytes[..end]).ok()?)?;
+    Some((end, key))
+}
+
+/// Whether text ends with a closing keyword, optionally followed by a colon
+fn follows_closing_keyword(before: &str) -> bool {
+    let before = before.trim_end();
+    let before = before.strip_suffix(':').unwrap_or(before).trim_end();
+
+    let word = before
+        .rsplit(|c: char| !c.is_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    CLOSING_KEYWORDS.contains(&word.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(references: &[TicketReference]) -> Vec<(String, bool)> {
+        references
+            .iter()
+            .map(|r| (r.key.to_string(), r.closes))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_references() {
+        let references =
+            find_references("[WN-12] Fix login crash\n\nFixes WN-12, closes: API-3; see WN-4");
+        assert_eq!(
+            keys(&references),
+            vec![
+                ("WN-12".to_string(), true),
+                ("API-3".to_string(), true),
+                ("WN-4".to_string(), false),
+            ]
+        );
+
+        // Lower case keys and keys inside other words are not references
+        assert!(find_references("Switch to utf-8 and sha-256").is_empty());
+        assert!(find_references("aWN-1 WN-1a WN-0 W-1").is_empty());
+
+        // The keyword has to come right before the key
+        assert_eq!(
+            keys(&find_references("WN-5 fixes the prefix-WN-6 bug")),
+            vec![("WN-5".to_string(), false), ("WN-6".to_string(), false)]
+        );
+        assert_eq!(
+            keys(&find_references("Resolved WN-7. Fixed\tWN-8")),
+            vec![("WN-7".to_string(), true), ("WN-8".to_string(), true)]
+        );
+        assert_eq!(
+            keys(&find_references("préfixes WN-9")),
+            vec![("WN-9".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn test_branch_keys() {
+        assert_eq!(
+            branch_keys("feature/WN-12-login-crash"),
+            vec![TicketKey::new("WN", 12)]
+        );
+        assert_eq!(
+            branch_keys("fix/wn-3-and-wn-4"),
+            vec![TicketKey::new("WN", 3), TicketKey::new("WN", 4)]
+        );
+        assert!(branch_keys("main").is_empty());
+    }
+
+    #[test]
+    fn test_branch_id_prefixes() {
+        assert_eq!(
+            branch_id_prefixes("feature/1A2B3C4D-login-crash"),
+            vec!["1a2b3c4d".to_string()]
+        );
+        // Too short, too long, or a word that happens to be hex
+        assert!(branch_id_prefixes("feature/1a2b3c4-x/1a2b3c4d5/deadbeef").is_empty());
+    }
+}
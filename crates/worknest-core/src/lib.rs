@@ -4,6 +4,8 @@
 //! It defines the fundamental entities, types, and validation rules.
 
 pub mod error;
+pub mod git;
 pub mod models;
+pub mod query;
 
 pub use error::{CoreError, Result};
@@ -1,17 +1,50 @@
 //! Domain models for Worknest
 
+pub mod access_token;
+pub mod activity;
 pub mod attachment;
+pub mod change;
+pub mod code_link;
 pub mod comment;
+pub mod custom_field;
+pub mod dependency;
+pub mod label;
+pub mod notification;
 pub mod project;
 pub mod role;
+pub mod saved_view;
+pub mod session;
+pub mod sprint;
 pub mod team;
 pub mod ticket;
 pub mod user;
+pub mod webhook;
+pub mod workflow;
+pub mod worklog;
 
+pub use access_token::{AccessTokenId, PersonalAccessToken, TokenScope};
+pub use activity::{Activity, ActivityAction, ActivityId};
 pub use attachment::{Attachment, AttachmentId};
+pub use change::{ChangeEvent, ChangeResource};
+pub use code_link::{CodeLink, CodeLinkId, CodeLinkKind, PullRequestState};
 pub use comment::{Comment, CommentId};
-pub use project::{Project, ProjectId};
+pub use custom_field::{CustomField, CustomFieldId, CustomFieldType, CustomFieldValue, FieldValue};
+pub use dependency::{DependencyId, TicketDependency};
+pub use label::{Label, LabelId};
+pub use notification::{Notification, NotificationId, NotificationKind, NotificationPreferences};
+pub use project::{Project, ProjectId, ProjectMember};
 pub use role::{Permission, PermissionId, Role, RoleId};
-pub use team::{Team, TeamId};
-pub use ticket::{Priority, Ticket, TicketId, TicketStatus, TicketType};
+pub use saved_view::{SavedView, SavedViewId, ViewDisplay};
+pub use session::{RefreshToken, Session, SessionId};
+pub use sprint::{Sprint, SprintId, SprintState};
+pub use team::{Team, TeamId, TeamMember};
+pub use ticket::{Priority, Ticket, TicketId, TicketKey, TicketRollup, TicketType};
 pub use user::{User, UserId};
+pub use webhook::{
+    is_internal_address, DeliveryStatus, Webhook, WebhookDelivery, WebhookDeliveryId, WebhookEvent,
+    WebhookId,
+};
+pub use workflow::{
+    normalize_status_key, StatusCategory, Workflow, WorkflowStatus, WorkflowTransition,
+};
+pub use worklog::{Timesheet, TimesheetRow, Worklog, WorklogId};
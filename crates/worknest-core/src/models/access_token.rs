@@ -0,0 +1,233 @@
+//! Personal access token domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::UserId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for personal access tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AccessTokenId(pub Uuid);
+
+impl AccessTokenId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for AccessTokenId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for AccessTokenId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// What a personal access token may be used for
+///
+/// A `write` scope also grants the matching `read` scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenScope {
+    #[serde(rename = "projects:read")]
+    ProjectsRead,
+    #[serde(rename = "projects:write")]
+    ProjectsWrite,
+    #[serde(rename = "tickets:read")]
+    TicketsRead,
+    #[serde(rename = "tickets:write")]
+    TicketsWrite,
+}
+
+impl TokenScope {
+    /// Every scope, in display order
+    pub const ALL: [TokenScope; 4] = [
+        TokenScope::ProjectsRead,
+        TokenScope::ProjectsWrite,
+        TokenScope::TicketsRead,
+        TokenScope::TicketsWrite,
+    ];
+
+    /// Stable name used for storage and in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::ProjectsRead => "projects:read",
+            TokenScope::ProjectsWrite => "projects:write",
+            TokenScope::TicketsRead => "tickets:read",
+            TokenScope::TicketsWrite => "tickets:write",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| CoreError::Validation(format!("Unknown token scope: {}", s)))
+    }
+
+    /// Whether holding this scope satisfies `required`
+    pub fn grants(&self, required: TokenScope) -> bool {
+        *self == required
+            || matches!(
+                (self, required),
+                (TokenScope::ProjectsWrite, TokenScope::ProjectsRead)
+                    | (TokenScope::TicketsWrite, TokenScope::TicketsRead)
+            )
+    }
+}
+
+impl std::fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A named, long-lived token for scripts and integrations
+///
+/// Only a hash of the token is stored; the token itself is shown once, when
+/// it is created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonalAccessToken {
+    pub id: AccessTokenId,
+    pub user_id: UserId,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<TokenScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    /// Create a new personal access token record
+    pub fn new(
+        user_id: UserId,
+        name: String,
+        token_hash: String,
+        scopes: Vec<TokenScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id: AccessTokenId::new(),
+            user_id,
+            name,
+            token_hash,
+            scopes,
+            expires_at,
+            last_used_at: None,
+            created_at: Utc::now(),
+            revoked_at: None,
+        }
+    }
+
+    /// Validate the token
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(CoreError::Validation(
+                "Token name cannot be empty".to_string(),
+            ));
+        }
+
+        if self.name.len() > 100 {
+            return Err(CoreError::Validation(
+                "Token name cannot exceed 100 characters".to_string(),
+            ));
+        }
+
+        if self.scopes.is_empty() {
+            return Err(CoreError::Validation(
+                "Token must have at least one scope".to_string(),
+            ));
+        }
+
+        if self.expires_at.is_some_and(|at| at <= self.created_at) {
+            return Err(CoreError::Validation(
+                "Token expiration must be in the future".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the token can still authenticate requests
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|at| at > Utc::now())
+    }
+
+    /// Whether the token's scopes satisfy `required`
+    pub fn allows(&self, required: TokenScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn token(scopes: Vec<TokenScope>, expires_at: Option<DateTime<Utc>>) -> PersonalAccessToken {
+        PersonalAccessToken::new(
+            UserId::new(),
+            "CI".to_string(),
+            "hash".to_string(),
+            scopes,
+            expires_at,
+        )
+    }
+
+    #[test]
+    fn test_scope_names_roundtrip() {
+        for scope in TokenScope::ALL {
+            assert_eq!(TokenScope::from_name(scope.as_str()).unwrap(), scope);
+        }
+        assert!(TokenScope::from_name("tickets:delete").is_err());
+    }
+
+    #[test]
+    fn test_write_scope_grants_read() {
+        let pat = token(vec![TokenScope::TicketsWrite], None);
+
+        assert!(pat.allows(TokenScope::TicketsRead));
+        assert!(pat.allows(TokenScope::TicketsWrite));
+        assert!(!pat.allows(TokenScope::ProjectsRead));
+
+        let read_only = token(vec![TokenScope::TicketsRead], None);
+        assert!(!read_only.allows(TokenScope::TicketsWrite));
+    }
+
+    #[test]
+    fn test_validate_and_expiry() {
+        assert!(token(vec![TokenScope::TicketsRead], None)
+            .validate()
+            .is_ok());
+        assert!(token(Vec::new(), None).validate().is_err());
+        assert!(token(
+            vec![TokenScope::TicketsRead],
+            Some(Utc::now() - Duration::days(1))
+        )
+        .validate()
+        .is_err());
+
+        let mut pat = token(
+            vec![TokenScope::TicketsRead],
+            Some(Utc::now() + Duration::days(1)),
+        );
+        assert!(pat.is_active());
+
+        pat.revoked_at = Some(Utc::now());
+        assert!(!pat.is_active());
+    }
+}
@@ -0,0 +1,195 @@
+//! Activity log domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use super::UserId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for activity log entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ActivityId(pub Uuid);
+
+impl ActivityId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for ActivityId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for ActivityId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Kind of mutation recorded in the activity log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl ActivityAction {
+    /// Stable name used for storage and filtering
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityAction::Created => "created",
+            ActivityAction::Updated => "updated",
+            ActivityAction::Deleted => "deleted",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "created" => Ok(ActivityAction::Created),
+            "updated" => Ok(ActivityAction::Updated),
+            "deleted" => Ok(ActivityAction::Deleted),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown activity action: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for ActivityAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Audit trail entry recording who changed what
+///
+/// `details` holds a JSON object mapping each changed field to its
+/// `{"from": ..., "to": ...}` values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: ActivityId,
+    pub user_id: UserId,
+    pub action: ActivityAction,
+    pub resource_type: String,
+    pub resource_id: String,
+    pub details: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Activity {
+    /// Create a new activity entry
+    pub fn new(
+        user_id: UserId,
+        action: ActivityAction,
+        resource_type: &str,
+        resource_id: String,
+        details: Option<Value>,
+    ) -> Self {
+        Self {
+            id: ActivityId::new(),
+            user_id,
+            action,
+            resource_type: resource_type.to_string(),
+            resource_id,
+            details,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Compute the field-level diff between two snapshots of a resource
+    ///
+    /// A missing snapshot stands for a resource that does not exist yet
+    /// (creation) or anymore (deletion). `updated_at` is never reported since
+    /// it changes with every mutation.
+    pub fn diff<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Value {
+        let to_map = |value: Option<&T>| match value.map(serde_json::to_value) {
+            Some(Ok(Value::Object(map))) => map,
+            _ => Map::new(),
+        };
+        let before = to_map(before);
+        let after = to_map(after);
+
+        let mut changes = Map::new();
+        let fields = before
+            .keys()
+            .chain(after.keys().filter(|k| !before.contains_key(*k)));
+        for field in fields {
+            if field == "updated_at" {
+                continue;
+            }
+
+            let from = before.get(field).cloned().unwrap_or(Value::Null);
+            let to = after.get(field).cloned().unwrap_or(Value::Null);
+            if from != to {
+                let mut change = Map::new();
+                change.insert("from".to_string(), from);
+                change.insert("to".to_string(), to);
+                changes.insert(field.clone(), Value::Object(change));
+            }
+        }
+
+        Value::Object(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Project, Ticket, TicketType, Workflow};
+
+    #[test]
+    fn test_action_names() {
+        for action in [
+            ActivityAction::Created,
+            ActivityAction::Updated,
+            ActivityAction::Deleted,
+        ] {
+            assert_eq!(ActivityAction::from_name(action.as_str()).unwrap(), action);
+        }
+        assert!(ActivityAction::from_name("renamed").is_err());
+    }
+
+    #[test]
+    fn test_diff_reports_changed_fields_only() {
+        let user_id = UserId::new();
+        let project_id = crate::models::ProjectId::new();
+        let before = Ticket::new(project_id, "Title".to_string(), TicketType::Task, user_id);
+        let mut after = before.clone();
+        after.update_status(Workflow::default_for(project_id).status("done").unwrap());
+
+        let diff = Activity::diff(Some(&before), Some(&after));
+        let changes = diff.as_object().unwrap();
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes["status"]["from"], "open");
+        assert_eq!(changes["status"]["to"], "done");
+        assert_eq!(changes["status_category"]["to"], "done");
+    }
+
+    #[test]
+    fn test_diff_for_creation_and_deletion() {
+        let project = Project::new("Project".to_string(), UserId::new());
+
+        let created = Activity::diff(None, Some(&project));
+        assert_eq!(created["name"]["from"], Value::Null);
+        assert_eq!(created["name"]["to"], "Project");
+        assert!(created.get("updated_at").is_none());
+
+        let deleted = Activity::diff(Some(&project), None);
+        assert_eq!(deleted["name"]["to"], Value::Null);
+    }
+}
@@ -0,0 +1,56 @@
+//! Change event domain model
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{ActivityAction, ProjectId, UserId};
+
+/// Kind of resource a change event is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeResource {
+    Ticket,
+    Comment,
+    Attachment,
+}
+
+/// A change to a project's tickets, comments or attachments, pushed to the
+/// clients that can read the project's tickets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub project_id: ProjectId,
+    pub resource: ChangeResource,
+    pub resource_id: String,
+    pub action: ActivityAction,
+    /// User who made the change
+    pub actor_id: UserId,
+    /// The resource after the change, as the REST API returns it; `None` for
+    /// deletions
+    pub data: Option<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_change_event_format() {
+        let event = ChangeEvent {
+            project_id: ProjectId::new(),
+            resource: ChangeResource::Comment,
+            resource_id: "c1".to_string(),
+            action: ActivityAction::Deleted,
+            actor_id: UserId::new(),
+            data: None,
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["resource"], json!("comment"));
+        assert_eq!(value["action"], json!("deleted"));
+        assert_eq!(value["data"], Value::Null);
+
+        let parsed: ChangeEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
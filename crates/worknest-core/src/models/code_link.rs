@@ -0,0 +1,261 @@
+//! Code link domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::TicketId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for code links
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CodeLinkId(pub Uuid);
+
+impl CodeLinkId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for CodeLinkId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for CodeLinkId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// What a code link points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeLinkKind {
+    Commit,
+    /// A pull request, or a merge request on GitLab
+    PullRequest,
+}
+
+impl CodeLinkKind {
+    /// Stable name used for storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeLinkKind::Commit => "commit",
+            CodeLinkKind::PullRequest => "pull_request",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "commit" => Ok(CodeLinkKind::Commit),
+            "pull_request" => Ok(CodeLinkKind::PullRequest),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown code link kind: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// State of a linked pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PullRequestState {
+    Open,
+    Merged,
+    /// Closed without being merged
+    Closed,
+}
+
+impl PullRequestState {
+    /// Stable name used for storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PullRequestState::Open => "open",
+            PullRequestState::Merged => "merged",
+            PullRequestState::Closed => "closed",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "open" => Ok(PullRequestState::Open),
+            "merged" => Ok(PullRequestState::Merged),
+            "closed" => Ok(PullRequestState::Closed),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown pull request state: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A commit or pull request that refers to a ticket
+///
+/// A ticket has at most one link per commit or pull request; seeing the same
+/// one again refreshes its title, author and state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeLink {
+    pub id: CodeLinkId,
+    pub ticket_id: TicketId,
+    pub kind: CodeLinkKind,
+    /// Repository as the forge names it, such as `acme/worknest`
+    pub repository: String,
+    /// Commit hash, or pull request number
+    pub reference: String,
+    /// First line of the commit message, or the pull request title
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    /// `None` for commits
+    pub state: Option<PullRequestState>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CodeLink {
+    /// Longest title kept; longer ones are cut off
+    pub const MAX_TITLE_LENGTH: usize = 255;
+
+    /// Link a commit to a ticket
+    pub fn commit(
+        ticket_id: TicketId,
+        repository: String,
+        sha: String,
+        message: &str,
+        url: String,
+    ) -> Self {
+        let title = message.lines().next().unwrap_or_default();
+        Self::new(
+            ticket_id,
+            CodeLinkKind::Commit,
+            repository,
+            sha,
+            title,
+            url,
+            None,
+        )
+    }
+
+    /// Link a pull request to a ticket
+    pub fn pull_request(
+        ticket_id: TicketId,
+        repository: String,
+        number: u64,
+        title: &str,
+        url: String,
+        state: PullRequestState,
+    ) -> Self {
+        Self::new(
+            ticket_id,
+            CodeLinkKind::PullRequest,
+            repository,
+            number.to_string(),
+            title,
+            url,
+            Some(state),
+        )
+    }
+
+    fn new(
+        ticket_id: TicketId,
+        kind: CodeLinkKind,
+        repository: String,
+        reference: String,
+        title: &str,
+        url: String,
+        state: Option<PullRequestState>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: CodeLinkId::new(),
+            ticket_id,
+            kind,
+            repository,
+            reference,
+            title: title.trim().chars().take(Self::MAX_TITLE_LENGTH).collect(),
+            url,
+            author: None,
+            state,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Short form of the reference: the abbreviated hash of a commit, or
+    /// `#` and the number of a pull request
+    pub fn short_reference(&self) -> String {
+        match self.kind {
+            CodeLinkKind::Commit => self.reference.chars().take(7).collect(),
+            CodeLinkKind::PullRequest => format!("#{}", self.reference),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_link() {
+        let link = CodeLink::commit(
+            TicketId::new(),
+            "acme/worknest".to_string(),
+            "9fceb02d0ae598e95dc970b74767f19372d61af8".to_string(),
+            "[WN-12] Fix login crash\n\nFixes WN-12",
+            "https://git.example.com/acme/worknest/commit/9fceb02".to_string(),
+        );
+
+        assert_eq!(link.kind, CodeLinkKind::Commit);
+        assert_eq!(link.title, "[WN-12] Fix login crash");
+        assert_eq!(link.short_reference(), "9fceb02");
+        assert_eq!(link.state, None);
+    }
+
+    #[test]
+    fn test_pull_request_link() {
+        let long_title = "x".repeat(300);
+        let link = CodeLink::pull_request(
+            TicketId::new(),
+            "acme/worknest".to_string(),
+            42,
+            &long_title,
+            "https://git.example.com/acme/worknest/pull/42".to_string(),
+            PullRequestState::Open,
+        );
+
+        assert_eq!(link.reference, "42");
+        assert_eq!(link.short_reference(), "#42");
+        assert_eq!(link.title.len(), CodeLink::MAX_TITLE_LENGTH);
+        assert_eq!(link.state, Some(PullRequestState::Open));
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for kind in [CodeLinkKind::Commit, CodeLinkKind::PullRequest] {
+            assert_eq!(CodeLinkKind::from_name(kind.as_str()).unwrap(), kind);
+        }
+        for state in [
+            PullRequestState::Open,
+            PullRequestState::Merged,
+            PullRequestState::Closed,
+        ] {
+            assert_eq!(PullRequestState::from_name(state.as_str()).unwrap(), state);
+        }
+        assert!(CodeLinkKind::from_name("tag").is_err());
+    }
+}
@@ -77,6 +77,35 @@ impl Comment {
         Ok(())
     }
 
+    /// Usernames mentioned as `@username`, in order of first mention
+    ///
+    /// A mention only starts at an `@` that does not follow a letter or
+    /// digit, so email addresses are not taken for mentions. Trailing dots
+    /// and dashes end the sentence rather than the name.
+    pub fn mentions(&self) -> Vec<String> {
+        let mut mentions: Vec<String> = Vec::new();
+        let mut previous = None;
+
+        for (i, c) in self.content.char_indices() {
+            let starts_mention = c == '@' && !previous.is_some_and(char::is_alphanumeric);
+            previous = Some(c);
+            if !starts_mention {
+                continue;
+            }
+
+            let rest = &self.content[i + 1..];
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '.' | '-')))
+                .unwrap_or(rest.len());
+            let username = rest[..end].trim_end_matches(['.', '-']);
+            if !username.is_empty() && !mentions.iter().any(|m| m == username) {
+                mentions.push(username.to_string());
+            }
+        }
+
+        mentions
+    }
+
     /// Update comment content
     pub fn update_content(&mut self, content: String) -> Result<()> {
         self.content = content;
@@ -114,6 +143,24 @@ mod tests {
         assert!(comment.validate().is_err());
     }
 
+    #[test]
+    fn test_mentions() {
+        let comment = Comment::new(
+            TicketId::new(),
+            UserId::new(),
+            "@alice can you pair with @bob.smith? cc @alice, not bob@example.com. Thanks @carol."
+                .to_string(),
+        );
+        assert_eq!(comment.mentions(), vec!["alice", "bob.smith", "carol"]);
+
+        let comment = Comment::new(
+            TicketId::new(),
+            UserId::new(),
+            "Mail me@home or @ nobody".to_string(),
+        );
+        assert!(comment.mentions().is_empty());
+    }
+
     #[test]
     fn test_update_content() {
         let mut comment = Comment::new(TicketId::new(), UserId::new(), "Original".to_string());
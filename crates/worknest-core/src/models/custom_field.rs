@@ -0,0 +1,338 @@
+//! Custom field domain model
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ProjectId, UserId};
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for custom fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CustomFieldId(pub Uuid);
+
+impl CustomFieldId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for CustomFieldId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for CustomFieldId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Kind of value a custom field holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Date,
+    SingleSelect,
+    MultiSelect,
+    User,
+}
+
+impl CustomFieldType {
+    /// Stable name used for storage and in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CustomFieldType::Text => "text",
+            CustomFieldType::Number => "number",
+            CustomFieldType::Date => "date",
+            CustomFieldType::SingleSelect => "single_select",
+            CustomFieldType::MultiSelect => "multi_select",
+            CustomFieldType::User => "user",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(CustomFieldType::Text),
+            "number" => Ok(CustomFieldType::Number),
+            "date" => Ok(CustomFieldType::Date),
+            "single_select" => Ok(CustomFieldType::SingleSelect),
+            "multi_select" => Ok(CustomFieldType::MultiSelect),
+            "user" => Ok(CustomFieldType::User),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown custom field type: {}",
+                s
+            ))),
+        }
+    }
+
+    /// Whether values are picked from the field's options
+    pub fn has_options(&self) -> bool {
+        matches!(
+            self,
+            CustomFieldType::SingleSelect | CustomFieldType::MultiSelect
+        )
+    }
+}
+
+/// A field a project adds to its tickets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomField {
+    pub id: CustomFieldId,
+    pub project_id: ProjectId,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    /// Choices of a select field; empty for other types
+    pub options: Vec<String>,
+    pub required: bool,
+    /// Display order within the project
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CustomField {
+    /// Create a new optional custom field
+    pub fn new(project_id: ProjectId, name: String, field_type: CustomFieldType) -> Self {
+        let now = Utc::now();
+        Self {
+            id: CustomFieldId::new(),
+            project_id,
+            name,
+            field_type,
+            options: Vec::new(),
+            required: false,
+            position: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Validate the field definition
+    pub fn validate(&self) -> Result<()> {
+        let name = self.name.trim();
+
+        if name.is_empty() {
+            return Err(CoreError::Validation(
+                "Field name cannot be empty".to_string(),
+            ));
+        }
+
+        if name.len() > 50 {
+            return Err(CoreError::Validation(
+                "Field name cannot exceed 50 characters".to_string(),
+            ));
+        }
+
+        if !self.field_type.has_options() {
+            if !self.options.is_empty() {
+                return Err(CoreError::Validation(format!(
+                    "{} fields cannot have options",
+                    self.field_type.as_str()
+                )));
+            }
+            return Ok(());
+        }
+
+        if self.options.is_empty() {
+            return Err(CoreError::Validation(
+                "Select fields need at least one option".to_string(),
+            ));
+        }
+
+        for (i, option) in self.options.iter().enumerate() {
+            if option.trim().is_empty() || option.len() > 100 {
+                return Err(CoreError::Validation(
+                    "Options must be between 1 and 100 characters".to_string(),
+                ));
+            }
+            if self.options[..i].contains(option) {
+                return Err(CoreError::Validation(format!(
+                    "Duplicate option '{}'",
+                    option
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that a value fits this field's type and options
+    pub fn check_value(&self, value: &FieldValue) -> Result<()> {
+        if value.field_type() != self.field_type {
+            return Err(CoreError::Validation(format!(
+                "Field '{}' expects a {} value",
+                self.name,
+                self.field_type.as_str()
+            )));
+        }
+
+        let unknown = match value {
+            FieldValue::SingleSelect(option) => (!self.options.contains(option)).then_some(option),
+            FieldValue::MultiSelect(options) => {
+                options.iter().find(|option| !self.options.contains(option))
+            },
+            _ => None,
+        };
+
+        match unknown {
+            Some(option) => Err(CoreError::Validation(format!(
+                "'{}' is not an option of field '{}'",
+                option, self.name
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Value of a custom field, tagged with its type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum FieldValue {
+    Text(String),
+    Number(f64),
+    Date(NaiveDate),
+    SingleSelect(String),
+    MultiSelect(Vec<String>),
+    User(UserId),
+}
+
+impl FieldValue {
+    /// The field type this value belongs to
+    pub fn field_type(&self) -> CustomFieldType {
+        match self {
+            FieldValue::Text(_) => CustomFieldType::Text,
+            FieldValue::Number(_) => CustomFieldType::Number,
+            FieldValue::Date(_) => CustomFieldType::Date,
+            FieldValue::SingleSelect(_) => CustomFieldType::SingleSelect,
+            FieldValue::MultiSelect(_) => CustomFieldType::MultiSelect,
+            FieldValue::User(_) => CustomFieldType::User,
+        }
+    }
+
+    /// Check the value on its own, without its field definition
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            FieldValue::Text(text) if text.trim().is_empty() => Err(CoreError::Validation(
+                "Text values cannot be empty".to_string(),
+            )),
+            FieldValue::Text(text) if text.len() > 1000 => Err(CoreError::Validation(
+                "Text values must be 1000 characters or less".to_string(),
+            )),
+            FieldValue::Number(number) if !number.is_finite() => Err(CoreError::Validation(
+                "Number values must be finite".to_string(),
+            )),
+            FieldValue::SingleSelect(option) if option.is_empty() => Err(CoreError::Validation(
+                "Select values cannot be empty".to_string(),
+            )),
+            FieldValue::MultiSelect(options) if options.is_empty() => Err(CoreError::Validation(
+                "Select at least one option".to_string(),
+            )),
+            FieldValue::MultiSelect(options)
+                if options
+                    .iter()
+                    .enumerate()
+                    .any(|(i, option)| options[..i].contains(option)) =>
+            {
+                Err(CoreError::Validation(
+                    "Options cannot be selected twice".to_string(),
+                ))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A ticket's value for one custom field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomFieldValue {
+    pub field_id: CustomFieldId,
+    #[serde(flatten)]
+    pub value: FieldValue,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_field() {
+        let mut field = CustomField::new(
+            ProjectId::new(),
+            "Environment".to_string(),
+            CustomFieldType::SingleSelect,
+        );
+        assert!(field.validate().is_err());
+
+        field.options = vec!["staging".to_string(), "production".to_string()];
+        assert!(field.validate().is_ok());
+
+        field.options.push("staging".to_string());
+        assert!(field.validate().is_err());
+
+        let mut text = CustomField::new(
+            ProjectId::new(),
+            "Customer".to_string(),
+            CustomFieldType::Text,
+        );
+        assert!(text.validate().is_ok());
+        text.options = vec!["acme".to_string()];
+        assert!(text.validate().is_err());
+    }
+
+    #[test]
+    fn test_check_value() {
+        let mut field = CustomField::new(
+            ProjectId::new(),
+            "Platforms".to_string(),
+            CustomFieldType::MultiSelect,
+        );
+        field.options = vec!["web".to_string(), "ios".to_string()];
+
+        assert!(field
+            .check_value(&FieldValue::MultiSelect(vec!["web".to_string()]))
+            .is_ok());
+        assert!(field
+            .check_value(&FieldValue::MultiSelect(vec!["android".to_string()]))
+            .is_err());
+        assert!(field
+            .check_value(&FieldValue::Text("web".to_string()))
+            .is_err());
+
+        assert!(FieldValue::Number(f64::NAN).validate().is_err());
+        assert!(
+            FieldValue::MultiSelect(vec!["web".to_string(), "web".to_string()])
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_value_serialization() {
+        let value = CustomFieldValue {
+            field_id: CustomFieldId::new(),
+            value: FieldValue::Date(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()),
+        };
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["type"], "date");
+        assert_eq!(json["value"], "2025-01-31");
+
+        let back: CustomFieldValue = serde_json::from_value(json).unwrap();
+        assert_eq!(back, value);
+    }
+}
@@ -0,0 +1,94 @@
+//! Ticket dependency domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::TicketId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for ticket dependencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DependencyId(pub Uuid);
+
+impl DependencyId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for DependencyId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for DependencyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A "depends on" edge between two tickets
+///
+/// `ticket_id` is blocked by `depends_on_ticket_id` until the latter is done.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TicketDependency {
+    pub id: DependencyId,
+    pub ticket_id: TicketId,
+    pub depends_on_ticket_id: TicketId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TicketDependency {
+    /// Create a new dependency of `ticket_id` on `depends_on_ticket_id`
+    pub fn new(ticket_id: TicketId, depends_on_ticket_id: TicketId) -> Self {
+        Self {
+            id: DependencyId::new(),
+            ticket_id,
+            depends_on_ticket_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Validate the dependency
+    pub fn validate(&self) -> Result<()> {
+        if self.ticket_id == self.depends_on_ticket_id {
+            return Err(CoreError::Validation(
+                "A ticket cannot depend on itself".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_dependency() {
+        let ticket_id = TicketId::new();
+        let blocker_id = TicketId::new();
+        let dependency = TicketDependency::new(ticket_id, blocker_id);
+
+        assert_eq!(dependency.ticket_id, ticket_id);
+        assert_eq!(dependency.depends_on_ticket_id, blocker_id);
+        assert!(dependency.validate().is_ok());
+    }
+
+    #[test]
+    fn test_self_dependency_is_invalid() {
+        let ticket_id = TicketId::new();
+        let dependency = TicketDependency::new(ticket_id, ticket_id);
+
+        assert!(dependency.validate().is_err());
+    }
+}
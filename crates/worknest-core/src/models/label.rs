@@ -0,0 +1,147 @@
+//! Label domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ProjectId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LabelId(pub Uuid);
+
+impl LabelId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for LabelId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for LabelId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A colored tag that tickets of one project can carry
+///
+/// Tickets refer to labels by id, so renaming a label renames it on every
+/// ticket that uses it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Label {
+    pub id: LabelId,
+    pub project_id: ProjectId,
+    pub name: String,
+    /// Color as `#rrggbb`
+    pub color: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Label {
+    /// Color given to labels created without one
+    pub const DEFAULT_COLOR: &'static str = "#6b7280";
+
+    /// Create a new label
+    pub fn new(project_id: ProjectId, name: String, color: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: LabelId::new(),
+            project_id,
+            name,
+            color,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Validate the label
+    pub fn validate(&self) -> Result<()> {
+        let name = self.name.trim();
+
+        if name.is_empty() {
+            return Err(CoreError::Validation(
+                "Label name cannot be empty".to_string(),
+            ));
+        }
+
+        if name.len() > 50 {
+            return Err(CoreError::Validation(
+                "Label name cannot exceed 50 characters".to_string(),
+            ));
+        }
+
+        // Filters take comma-separated label names
+        if name.contains(',') {
+            return Err(CoreError::Validation(
+                "Label name cannot contain commas".to_string(),
+            ));
+        }
+
+        if Self::parse_color(&self.color).is_none() {
+            return Err(CoreError::Validation(format!(
+                "Invalid label color '{}'; use #rrggbb",
+                self.color
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// The label color as red, green and blue components
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        Self::parse_color(&self.color)
+    }
+
+    fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+        let hex = color.strip_prefix('#')?;
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some((component(0)?, component(2)?, component(4)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_label() {
+        let mut label = Label::new(
+            ProjectId::new(),
+            "frontend".to_string(),
+            "#1d76db".to_string(),
+        );
+        assert!(label.validate().is_ok());
+        assert_eq!(label.rgb(), Some((0x1d, 0x76, 0xdb)));
+
+        label.color = "blue".to_string();
+        assert!(label.validate().is_err());
+
+        label.color = Label::DEFAULT_COLOR.to_string();
+        label.name = "ui,ux".to_string();
+        assert!(label.validate().is_err());
+
+        label.name = "  ".to_string();
+        assert!(label.validate().is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! Notification domain model
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{TicketId, UserId};
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NotificationId(pub Uuid);
+
+impl NotificationId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for NotificationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for NotificationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Why a user was notified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Someone mentioned the user in a comment
+    Mentioned,
+    /// Someone commented on a watched ticket
+    Commented,
+    /// Someone assigned a ticket to the user
+    Assigned,
+    /// Someone moved a watched ticket to another status
+    StatusChanged,
+    /// Someone changed a watched ticket
+    Updated,
+}
+
+impl NotificationKind {
+    /// Stable name used for storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Mentioned => "mentioned",
+            NotificationKind::Commented => "commented",
+            NotificationKind::Assigned => "assigned",
+            NotificationKind::StatusChanged => "status_changed",
+            NotificationKind::Updated => "updated",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "mentioned" => Ok(NotificationKind::Mentioned),
+            "commented" => Ok(NotificationKind::Commented),
+            "assigned" => Ok(NotificationKind::Assigned),
+            "status_changed" => Ok(NotificationKind::StatusChanged),
+            "updated" => Ok(NotificationKind::Updated),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown notification kind: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Entry in a user's notification inbox about a ticket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: NotificationId,
+    /// User the notification is for
+    pub user_id: UserId,
+    /// User whose change caused the notification
+    pub actor_id: UserId,
+    pub ticket_id: TicketId,
+    pub kind: NotificationKind,
+    /// Human-readable summary, such as `alice commented on WN-12`
+    pub message: String,
+    /// When the user read the notification; `None` while unread
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    /// Create a new unread notification
+    pub fn new(
+        user_id: UserId,
+        actor_id: UserId,
+        ticket_id: TicketId,
+        kind: NotificationKind,
+        message: String,
+    ) -> Self {
+        Self {
+            id: NotificationId::new(),
+            user_id,
+            actor_id,
+            ticket_id,
+            kind,
+            message,
+            read_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Whether the user has read the notification
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
+
+/// How a user wants to hear about notifications besides the inbox
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    pub user_id: UserId,
+    /// Email when a ticket is assigned to the user
+    pub email_assigned: bool,
+    /// Email when the user is mentioned in a comment
+    pub email_mentioned: bool,
+    /// Email when a watched ticket changes status
+    pub email_status_changed: bool,
+    /// Email a daily digest of unread notifications
+    pub daily_digest: bool,
+    /// UTC hour of the day from which the digest is sent
+    pub digest_hour: u8,
+    /// When the last digest was sent
+    pub last_digest_at: Option<DateTime<Utc>>,
+}
+
+impl NotificationPreferences {
+    /// Preferences of users who never changed them: instant emails for
+    /// assignments, mentions and status changes, no digest
+    pub fn new(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            email_assigned: true,
+            email_mentioned: true,
+            email_status_changed: true,
+            daily_digest: false,
+            digest_hour: 8,
+            last_digest_at: None,
+        }
+    }
+
+    /// Validate the preferences
+    pub fn validate(&self) -> Result<()> {
+        if self.digest_hour > 23 {
+            return Err(CoreError::Validation(
+                "Digest hour must be between 0 and 23".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether a notification of this kind is also sent by email right away
+    ///
+    /// Comments and other changes only reach the inbox and the digest.
+    pub fn emails(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::Assigned => self.email_assigned,
+            NotificationKind::Mentioned => self.email_mentioned,
+            NotificationKind::StatusChanged => self.email_status_changed,
+            NotificationKind::Commented | NotificationKind::Updated => false,
+        }
+    }
+
+    /// Whether the daily digest is due at `now`: the digest hour has come
+    /// and no digest went out yet today
+    pub fn digest_due(&self, now: DateTime<Utc>) -> bool {
+        self.daily_digest
+            && now.hour() >= u32::from(self.digest_hour)
+            && self
+                .last_digest_at
+                .is_none_or(|last| last.date_naive() < now.date_naive())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_kind_names() {
+        for kind in [
+            NotificationKind::Mentioned,
+            NotificationKind::Commented,
+            NotificationKind::Assigned,
+            NotificationKind::StatusChanged,
+            NotificationKind::Updated,
+        ] {
+            assert_eq!(NotificationKind::from_name(kind.as_str()).unwrap(), kind);
+        }
+        assert!(NotificationKind::from_name("liked").is_err());
+    }
+
+    #[test]
+    fn test_new_notification_is_unread() {
+        let notification = Notification::new(
+            UserId::new(),
+            UserId::new(),
+            TicketId::new(),
+            NotificationKind::Mentioned,
+            "alice mentioned you on WN-1".to_string(),
+        );
+        assert!(!notification.is_read());
+    }
+
+    #[test]
+    fn test_preferences_pick_instant_emails() {
+        let mut preferences = NotificationPreferences::new(UserId::new());
+        assert!(preferences.validate().is_ok());
+        assert!(preferences.emails(NotificationKind::Mentioned));
+        assert!(!preferences.emails(NotificationKind::Commented));
+
+        preferences.email_mentioned = false;
+        assert!(!preferences.emails(NotificationKind::Mentioned));
+
+        preferences.digest_hour = 24;
+        assert!(preferences.validate().is_err());
+    }
+
+    #[test]
+    fn test_digest_due_once_a_day() {
+        use chrono::TimeZone;
+
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap();
+        let mut preferences = NotificationPreferences::new(UserId::new());
+        preferences.digest_hour = 8;
+        assert!(!preferences.digest_due(at(3, 9)));
+
+        preferences.daily_digest = true;
+        assert!(!preferences.digest_due(at(3, 7)));
+        assert!(preferences.digest_due(at(3, 9)));
+
+        preferences.last_digest_at = Some(at(3, 8));
+        assert!(!preferences.digest_due(at(3, 23)));
+        assert!(preferences.digest_due(at(4, 8)));
+    }
+}
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::user::UserId;
+use super::{role::RoleId, user::UserId};
 
 /// Project identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -46,9 +46,16 @@ impl std::fmt::Display for ProjectId {
 pub struct Project {
     pub id: ProjectId,
     pub name: String,
+    /// Short unique key that prefixes the project's ticket numbers, as in
+    /// `WN-123`
+    #[serde(default)]
+    pub key: String,
     pub description: Option<String>,
     pub color: Option<String>,
     pub archived: bool,
+    /// Private projects are only visible to their members
+    #[serde(default)]
+    pub is_private: bool,
     pub created_by: UserId,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -60,10 +67,12 @@ impl Project {
         let now = Utc::now();
         Self {
             id: ProjectId::new(),
+            key: Self::key_from_name(&name),
             name,
             description: None,
             color: None,
             archived: false,
+            is_private: false,
             created_by,
             created_at: now,
             updated_at: now,
@@ -92,9 +101,57 @@ impl Project {
             }
         }
 
+        Self::validate_key(&self.key)
+    }
+
+    /// Check that a project key is 2 to 10 uppercase letters or digits,
+    /// starting with a letter
+    pub fn validate_key(key: &str) -> crate::Result<()> {
+        let valid = (2..=10).contains(&key.len())
+            && key.starts_with(|c: char| c.is_ascii_uppercase())
+            && key
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+        if !valid {
+            return Err(crate::CoreError::Validation(
+                "Project key must be 2 to 10 uppercase letters or digits, starting with a letter"
+                    .to_string(),
+            ));
+        }
+
         Ok(())
     }
 
+    /// Suggest a key for a project name
+    ///
+    /// Uses the initials of multi-word names ("Work Nest" gives `WN`) and
+    /// the first three characters otherwise, falling back to `PRJ` when the
+    /// name has too few ASCII letters.
+    pub fn key_from_name(name: &str) -> String {
+        let words: Vec<&str> = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let key: String = if words.len() >= 2 {
+            words
+                .iter()
+                .take(4)
+                .filter_map(|word| word.chars().next())
+                .collect()
+        } else {
+            words.concat().chars().take(3).collect()
+        };
+        let key = key.to_ascii_uppercase();
+
+        if Self::validate_key(&key).is_ok() {
+            key
+        } else {
+            "PRJ".to_string()
+        }
+    }
+
     /// Archive this project
     pub fn archive(&mut self) {
         self.archived = true;
@@ -108,6 +165,27 @@ impl Project {
     }
 }
 
+/// A user's membership in a project, with the role they hold there
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectMember {
+    pub project_id: ProjectId,
+    pub user_id: UserId,
+    pub role_id: RoleId,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl ProjectMember {
+    /// Create a new project membership
+    pub fn new(project_id: ProjectId, user_id: UserId, role_id: RoleId) -> Self {
+        Self {
+            project_id,
+            user_id,
+            role_id,
+            joined_at: Utc::now(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,6 +196,24 @@ mod tests {
         let project = Project::new("Test Project".to_string(), user_id);
         assert_eq!(project.name, "Test Project");
         assert!(!project.archived);
+        assert!(!project.is_private);
+        assert_eq!(project.key, "TP");
+    }
+
+    #[test]
+    fn test_project_key() {
+        assert_eq!(Project::key_from_name("Worknest"), "WOR");
+        assert_eq!(Project::key_from_name("work nest api v2"), "WNAV");
+        assert_eq!(Project::key_from_name("2024 roadmap"), "PRJ");
+        assert_eq!(Project::key_from_name("X"), "PRJ");
+
+        assert!(Project::validate_key("WN").is_ok());
+        assert!(Project::validate_key("API2").is_ok());
+        assert!(Project::validate_key("W").is_err());
+        assert!(Project::validate_key("wn").is_err());
+        assert!(Project::validate_key("2WN").is_err());
+        assert!(Project::validate_key("WN-1").is_err());
+        assert!(Project::validate_key("ABCDEFGHIJK").is_err());
     }
 
     #[test]
@@ -7,18 +7,50 @@ use uuid::Uuid;
 use crate::error::{CoreError, Result};
 
 /// Unique identifier for roles
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct RoleId(Uuid);
+///
+/// Role identifiers are free-form strings so that the built-in roles seeded by
+/// the migrations (`role_admin`, `role_member`, `role_viewer`) can be referenced
+/// alongside generated ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RoleId(String);
 
 impl RoleId {
+    /// Identifier of the built-in Admin role
+    pub const ADMIN: &'static str = "role_admin";
+    /// Identifier of the built-in Member role
+    pub const MEMBER: &'static str = "role_member";
+    /// Identifier of the built-in Viewer role
+    pub const VIEWER: &'static str = "role_viewer";
+
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(format!("role_{}", Uuid::new_v4()))
     }
 
     pub fn from_string(s: &str) -> Result<Self> {
-        Ok(Self(
-            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
-        ))
+        if s.trim().is_empty() {
+            return Err(CoreError::InvalidId("Role ID cannot be empty".to_string()));
+        }
+        Ok(Self(s.to_string()))
+    }
+
+    /// The built-in Admin role
+    pub fn admin() -> Self {
+        Self(Self::ADMIN.to_string())
+    }
+
+    /// The built-in Member role
+    pub fn member() -> Self {
+        Self(Self::MEMBER.to_string())
+    }
+
+    /// The built-in Viewer role
+    pub fn viewer() -> Self {
+        Self(Self::VIEWER.to_string())
+    }
+
+    /// Get the identifier as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
@@ -35,18 +67,26 @@ impl std::fmt::Display for RoleId {
 }
 
 /// Unique identifier for permissions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct PermissionId(Uuid);
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PermissionId(String);
 
 impl PermissionId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(format!("perm_{}", Uuid::new_v4()))
     }
 
     pub fn from_string(s: &str) -> Result<Self> {
-        Ok(Self(
-            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
-        ))
+        if s.trim().is_empty() {
+            return Err(CoreError::InvalidId(
+                "Permission ID cannot be empty".to_string(),
+            ));
+        }
+        Ok(Self(s.to_string()))
+    }
+
+    /// Get the identifier as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
@@ -238,4 +278,15 @@ mod tests {
         assert!(!perm.matches("project", "delete"));
         assert!(!perm.matches("ticket", "create"));
     }
+
+    #[test]
+    fn test_builtin_role_ids() {
+        assert_eq!(RoleId::admin().as_str(), "role_admin");
+        assert_eq!(
+            RoleId::from_string("role_member").unwrap(),
+            RoleId::member()
+        );
+        assert!(RoleId::from_string("").is_err());
+        assert_ne!(RoleId::new(), RoleId::new());
+    }
 }
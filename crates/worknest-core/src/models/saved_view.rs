@@ -0,0 +1,177 @@
+//! Saved view domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ProjectId, UserId};
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for saved views
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SavedViewId(pub Uuid);
+
+impl SavedViewId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for SavedViewId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for SavedViewId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How a saved view shows its tickets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewDisplay {
+    List,
+    Board,
+}
+
+impl ViewDisplay {
+    /// Stable name used for storage and in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ViewDisplay::List => "list",
+            ViewDisplay::Board => "board",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "list" => Ok(ViewDisplay::List),
+            "board" => Ok(ViewDisplay::Board),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown display mode: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A named ticket query with its sort order and display mode
+///
+/// Personal views are visible to their owner only. Shared views belong to a
+/// project and are visible to everyone who can read its tickets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub id: SavedViewId,
+    pub owner_id: UserId,
+    pub project_id: Option<ProjectId>,
+    pub name: String,
+    /// Filter in the ticket query language; empty matches every ticket
+    pub query: String,
+    /// Comma-separated sort fields, each optionally prefixed with `-`
+    pub sort: String,
+    pub display: ViewDisplay,
+    pub shared: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SavedView {
+    /// Create a new personal list view
+    pub fn new(owner_id: UserId, name: String, query: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: SavedViewId::new(),
+            owner_id,
+            project_id: None,
+            name,
+            query,
+            sort: String::new(),
+            display: ViewDisplay::List,
+            shared: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Validate the view
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(CoreError::Validation(
+                "View name cannot be empty".to_string(),
+            ));
+        }
+
+        if self.name.len() > 100 {
+            return Err(CoreError::Validation(
+                "View name cannot exceed 100 characters".to_string(),
+            ));
+        }
+
+        if !self.query.trim().is_empty() {
+            crate::query::parse(&self.query)
+                .map_err(|e| CoreError::Validation(format!("Invalid query: {}", e)))?;
+        }
+
+        if self.shared && self.project_id.is_none() {
+            return Err(CoreError::Validation(
+                "Shared views must belong to a project".to_string(),
+            ));
+        }
+
+        if self.display == ViewDisplay::Board && self.project_id.is_none() {
+            return Err(CoreError::Validation(
+                "Board views must belong to a project".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_view() {
+        let mut view = SavedView::new(
+            UserId::new(),
+            "My open bugs".to_string(),
+            "type:bug assignee:me -status:done,closed".to_string(),
+        );
+        assert!(view.validate().is_ok());
+
+        view.query = "status:".to_string();
+        assert!(view.validate().is_err());
+
+        view.query = String::new();
+        view.shared = true;
+        assert!(view.validate().is_err());
+
+        view.project_id = Some(ProjectId::new());
+        view.display = ViewDisplay::Board;
+        assert!(view.validate().is_ok());
+    }
+
+    #[test]
+    fn test_display_names_roundtrip() {
+        for display in [ViewDisplay::List, ViewDisplay::Board] {
+            assert_eq!(ViewDisplay::from_name(display.as_str()).unwrap(), display);
+        }
+        assert!(ViewDisplay::from_name("calendar").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Session domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::UserId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for sessions
+///
+/// Carried in the `sid` claim of every access token issued for the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionId(pub Uuid);
+
+impl SessionId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for SessionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A signed-in device or client of a user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub id: SessionId,
+    pub user_id: UserId,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    /// Create a new session
+    pub fn new(
+        id: SessionId,
+        user_id: UserId,
+        user_agent: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            user_agent,
+            created_at: Utc::now(),
+            expires_at,
+            revoked_at: None,
+        }
+    }
+
+    /// Whether the session can still authenticate requests
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    /// Revoke the session
+    pub fn revoke(&mut self) {
+        if self.revoked_at.is_none() {
+            self.revoked_at = Some(Utc::now());
+        }
+    }
+}
+
+/// A single-use refresh token belonging to a session
+///
+/// Only a hash of the token is stored. Refreshing marks the token used and
+/// issues a successor; presenting a used token again is treated as theft and
+/// revokes the whole session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshToken {
+    pub token_hash: String,
+    pub session_id: SessionId,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshToken {
+    /// Create a new refresh token record from the hash of the token
+    pub fn new(token_hash: String, session_id: SessionId, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            token_hash,
+            session_id,
+            created_at: Utc::now(),
+            expires_at,
+            used_at: None,
+        }
+    }
+
+    /// Whether the token has already been exchanged
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+
+    /// Whether the token is past its expiration time
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_session_lifecycle() {
+        let mut session = Session::new(
+            SessionId::new(),
+            UserId::new(),
+            Some("Firefox".to_string()),
+            Utc::now() + Duration::hours(1),
+        );
+        assert!(session.is_active());
+
+        session.revoke();
+        assert!(!session.is_active());
+    }
+
+    #[test]
+    fn test_expired_session_is_inactive() {
+        let session = Session::new(
+            SessionId::new(),
+            UserId::new(),
+            None,
+            Utc::now() - Duration::seconds(1),
+        );
+        assert!(!session.is_active());
+    }
+
+    #[test]
+    fn test_refresh_token_state() {
+        let mut token = RefreshToken::new(
+            "hash".to_string(),
+            SessionId::new(),
+            Utc::now() + Duration::days(30),
+        );
+        assert!(!token.is_used());
+        assert!(!token.is_expired());
+
+        token.used_at = Some(Utc::now());
+        token.expires_at = Utc::now() - Duration::seconds(1);
+        assert!(token.is_used());
+        assert!(token.is_expired());
+    }
+}
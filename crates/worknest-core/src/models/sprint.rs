@@ -0,0 +1,191 @@
+//! Sprint domain model
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ProjectId;
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for sprints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SprintId(pub Uuid);
+
+impl SprintId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for SprintId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for SprintId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Lifecycle state of a sprint
+///
+/// Sprints move from planned to active to closed and never back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SprintState {
+    Planned,
+    Active,
+    Closed,
+}
+
+impl SprintState {
+    /// Stable name used for storage and in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SprintState::Planned => "planned",
+            SprintState::Active => "active",
+            SprintState::Closed => "closed",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "planned" => Ok(SprintState::Planned),
+            "active" => Ok(SprintState::Active),
+            "closed" => Ok(SprintState::Closed),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown sprint state: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for SprintState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SprintState::Planned => write!(f, "Planned"),
+            SprintState::Active => write!(f, "Active"),
+            SprintState::Closed => write!(f, "Closed"),
+        }
+    }
+}
+
+/// A time-boxed iteration of a project
+///
+/// Tickets are planned into at most one sprint at a time; tickets in no
+/// sprint make up the project's backlog. Only one sprint per project can be
+/// active.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sprint {
+    pub id: SprintId,
+    pub project_id: ProjectId,
+    pub name: String,
+    pub goal: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub state: SprintState,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Sprint {
+    /// Create a new planned sprint
+    pub fn new(project_id: ProjectId, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: SprintId::new(),
+            project_id,
+            name,
+            goal: None,
+            start_date: None,
+            end_date: None,
+            state: SprintState::Planned,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether tickets can still be added to or removed from the sprint
+    pub fn is_open(&self) -> bool {
+        self.state != SprintState::Closed
+    }
+
+    /// Validate the sprint
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(CoreError::Validation(
+                "Sprint name cannot be empty".to_string(),
+            ));
+        }
+
+        if self.name.len() > 100 {
+            return Err(CoreError::Validation(
+                "Sprint name cannot exceed 100 characters".to_string(),
+            ));
+        }
+
+        if self.goal.as_ref().is_some_and(|goal| goal.len() > 1000) {
+            return Err(CoreError::Validation(
+                "Sprint goal cannot exceed 1000 characters".to_string(),
+            ));
+        }
+
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            if end < start {
+                return Err(CoreError::Validation(
+                    "Sprint cannot end before it starts".to_string(),
+                ));
+            }
+        }
+
+        if self.state == SprintState::Active
+            && (self.start_date.is_none() || self.end_date.is_none())
+        {
+            return Err(CoreError::Validation(
+                "Active sprints need a start and end date".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_sprint() {
+        let mut sprint = Sprint::new(ProjectId::new(), "Sprint 1".to_string());
+        assert!(sprint.validate().is_ok());
+        assert!(sprint.is_open());
+
+        sprint.start_date = NaiveDate::from_ymd_opt(2025, 3, 3);
+        sprint.end_date = NaiveDate::from_ymd_opt(2025, 3, 1);
+        assert!(sprint.validate().is_err());
+
+        sprint.end_date = NaiveDate::from_ymd_opt(2025, 3, 14);
+        sprint.state = SprintState::Active;
+        assert!(sprint.validate().is_ok());
+
+        sprint.end_date = None;
+        assert!(sprint.validate().is_err());
+
+        sprint.name = " ".to_string();
+        assert!(sprint.validate().is_err());
+    }
+}
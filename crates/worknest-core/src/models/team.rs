@@ -4,18 +4,22 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::UserId;
+use super::{RoleId, UserId};
 use crate::error::{CoreError, Result};
 
 /// Unique identifier for teams
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct TeamId(Uuid);
+pub struct TeamId(pub Uuid);
 
 impl TeamId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
 
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
     pub fn from_string(s: &str) -> Result<Self> {
         Ok(Self(
             Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
@@ -104,6 +108,29 @@ impl Team {
     }
 }
 
+/// A user's membership in a team
+///
+/// The member's role applies to every project the team is attached to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub team_id: TeamId,
+    pub user_id: UserId,
+    pub role_id: RoleId,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl TeamMember {
+    /// Create a new team membership
+    pub fn new(team_id: TeamId, user_id: UserId, role_id: RoleId) -> Self {
+        Self {
+            team_id,
+            user_id,
+            role_id,
+            joined_at: Utc::now(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
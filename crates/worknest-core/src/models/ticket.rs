@@ -4,7 +4,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{project::ProjectId, user::UserId};
+use super::{
+    custom_field::{CustomField, CustomFieldValue},
+    label::Label,
+    project::{Project, ProjectId},
+    sprint::SprintId,
+    user::UserId,
+    workflow::{StatusCategory, Workflow, WorkflowStatus},
+};
 
 /// Ticket identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -42,43 +49,44 @@ impl std::fmt::Display for TicketId {
 }
 
 /// Ticket type
+///
+/// Types form the levels of the ticket hierarchy: epics at the top, then
+/// stories, tasks, bugs and features, then subtasks.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TicketType {
     Task,
     Bug,
     Feature,
     Epic,
+    Story,
+    Subtask,
 }
 
-impl std::fmt::Display for TicketType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl TicketType {
+    /// Depth of the type in the hierarchy, 0 for epics
+    pub fn level(&self) -> u8 {
         match self {
-            TicketType::Task => write!(f, "Task"),
-            TicketType::Bug => write!(f, "Bug"),
-            TicketType::Feature => write!(f, "Feature"),
-            TicketType::Epic => write!(f, "Epic"),
+            TicketType::Epic => 0,
+            TicketType::Story | TicketType::Task | TicketType::Bug | TicketType::Feature => 1,
+            TicketType::Subtask => 2,
         }
     }
-}
 
-/// Ticket status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum TicketStatus {
-    Open,
-    InProgress,
-    Review,
-    Done,
-    Closed,
+    /// Whether a ticket of this type may be the parent of one of `child`
+    pub fn can_parent(&self, child: TicketType) -> bool {
+        child.level() == self.level() + 1
+    }
 }
 
-impl std::fmt::Display for TicketStatus {
+impl std::fmt::Display for TicketType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TicketStatus::Open => write!(f, "Open"),
-            TicketStatus::InProgress => write!(f, "In Progress"),
-            TicketStatus::Review => write!(f, "Review"),
-            TicketStatus::Done => write!(f, "Done"),
-            TicketStatus::Closed => write!(f, "Closed"),
+            TicketType::Task => write!(f, "Task"),
+            TicketType::Bug => write!(f, "Bug"),
+            TicketType::Feature => write!(f, "Feature"),
+            TicketType::Epic => write!(f, "Epic"),
+            TicketType::Story => write!(f, "Story"),
+            TicketType::Subtask => write!(f, "Subtask"),
         }
     }
 }
@@ -103,22 +111,129 @@ impl std::fmt::Display for Priority {
     }
 }
 
+/// Progress of a ticket's descendants
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TicketRollup {
+    /// Number of direct children
+    pub child_count: u32,
+    /// Number of children, grandchildren and so on
+    pub descendant_count: u32,
+    /// Number of descendants whose status is in the done category
+    pub done_count: u32,
+    /// Sum of the estimates of every descendant
+    pub estimate_hours: f32,
+    /// Share of descendants that are done, from 0 to 100
+    pub percent_done: u8,
+}
+
+impl TicketRollup {
+    /// Build a roll-up from the counts over a ticket's descendants
+    pub fn new(
+        child_count: u32,
+        descendant_count: u32,
+        done_count: u32,
+        estimate_hours: f32,
+    ) -> Self {
+        let percent_done = (done_count * 100)
+            .checked_div(descendant_count)
+            .unwrap_or(0) as u8;
+        Self {
+            child_count,
+            descendant_count,
+            done_count,
+            estimate_hours,
+            percent_done,
+        }
+    }
+}
+
+/// Human-readable ticket reference made of the project key and the
+/// ticket's number, such as `WN-123`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TicketKey {
+    pub project_key: String,
+    pub number: u32,
+}
+
+impl TicketKey {
+    /// Create a key for a ticket number in a project
+    pub fn new(project_key: impl Into<String>, number: u32) -> Self {
+        Self {
+            project_key: project_key.into(),
+            number,
+        }
+    }
+
+    /// Parse a key such as `WN-123`; the project key is case-insensitive
+    pub fn parse(s: &str) -> Option<Self> {
+        let (project_key, number) = s.rsplit_once('-')?;
+        let project_key = project_key.to_ascii_uppercase();
+        Project::validate_key(&project_key).ok()?;
+
+        if !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let number: u32 = number.parse().ok()?;
+        if number == 0 {
+            return None;
+        }
+
+        Some(Self {
+            project_key,
+            number,
+        })
+    }
+}
+
+impl std::fmt::Display for TicketKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.project_key, self.number)
+    }
+}
+
 /// Ticket entity
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Ticket {
     pub id: TicketId,
     pub project_id: ProjectId,
+    /// Sequence number within the project, allocated when the ticket is
+    /// stored
+    #[serde(default)]
+    pub number: u32,
     pub title: String,
     pub description: Option<String>,
     pub ticket_type: TicketType,
-    pub status: TicketStatus,
+    /// Key of the ticket's status in its project's workflow
+    pub status: String,
+    /// Category of `status`, kept alongside it so resolved tickets can be
+    /// told apart without loading the workflow
+    pub status_category: StatusCategory,
     pub priority: Priority,
     pub assignee_id: Option<UserId>,
     pub created_by: UserId,
     pub due_date: Option<DateTime<Utc>>,
     pub estimate_hours: Option<f32>,
+    /// Sprint the ticket is planned into; `None` for the backlog
+    #[serde(default)]
+    pub sprint_id: Option<SprintId>,
+    /// Parent in the ticket hierarchy
+    #[serde(default)]
+    pub parent_id: Option<TicketId>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Labels on the ticket; stored separately and filled in by the API
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// Custom field values; stored separately and filled in by the API
+    #[serde(default)]
+    pub custom_fields: Vec<CustomFieldValue>,
+    /// Progress of the ticket's descendants; computed and filled in by the
+    /// API for tickets that have children
+    #[serde(default)]
+    pub rollup: Option<TicketRollup>,
+    /// Minutes logged against the ticket; computed and filled in by the API
+    #[serde(default)]
+    pub logged_minutes: u32,
 }
 
 impl Ticket {
@@ -133,20 +248,34 @@ impl Ticket {
         Self {
             id: TicketId::new(),
             project_id,
+            number: 0,
             title,
             description: None,
             ticket_type,
-            status: TicketStatus::Open,
+            status: Workflow::DEFAULT_INITIAL_STATUS.to_string(),
+            status_category: StatusCategory::Todo,
             priority: Priority::Medium,
             assignee_id: None,
             created_by,
             due_date: None,
             estimate_hours: None,
+            sprint_id: None,
+            parent_id: None,
             created_at: now,
             updated_at: now,
+            labels: Vec::new(),
+            custom_fields: Vec::new(),
+            rollup: None,
+            logged_minutes: 0,
         }
     }
 
+    /// Minutes of the estimate not yet logged; `None` without an estimate
+    pub fn remaining_minutes(&self) -> Option<u32> {
+        self.estimate_hours
+            .map(|hours| ((hours * 60.0).round() as u32).saturating_sub(self.logged_minutes))
+    }
+
     /// Validate ticket data
     pub fn validate(&self) -> crate::Result<()> {
         if self.title.is_empty() {
@@ -177,12 +306,145 @@ impl Ticket {
             }
         }
 
+        for (i, field_value) in self.custom_fields.iter().enumerate() {
+            field_value.value.validate()?;
+
+            if self.custom_fields[..i]
+                .iter()
+                .any(|other| other.field_id == field_value.field_id)
+            {
+                return Err(crate::CoreError::Validation(
+                    "Each custom field can only have one value".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the custom field values against the project's field definitions
+    ///
+    /// # Arguments
+    /// * `fields` - Custom fields defined by the ticket's project
+    pub fn validate_custom_fields(&self, fields: &[CustomField]) -> crate::Result<()> {
+        for field_value in &self.custom_fields {
+            let field = fields
+                .iter()
+                .find(|f| f.id == field_value.field_id && f.project_id == self.project_id)
+                .ok_or_else(|| {
+                    crate::CoreError::Validation(format!(
+                        "Unknown custom field {}",
+                        field_value.field_id
+                    ))
+                })?;
+            field.check_value(&field_value.value)?;
+        }
+
+        let missing: Vec<&str> = fields
+            .iter()
+            .filter(|f| f.required && f.project_id == self.project_id)
+            .filter(|f| !self.custom_fields.iter().any(|v| v.field_id == f.id))
+            .map(|f| f.name.as_str())
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(crate::CoreError::Validation(format!(
+                "Missing required fields: {}",
+                missing.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the ticket is not resolved while blockers are unresolved
+    ///
+    /// # Arguments
+    /// * `blockers` - Tickets this ticket depends on
+    pub fn validate_blockers(&self, blockers: &[Ticket]) -> crate::Result<()> {
+        if !self.is_resolved() {
+            return Ok(());
+        }
+
+        let open: Vec<&str> = blockers
+            .iter()
+            .filter(|b| !b.is_resolved())
+            .map(|b| b.title.as_str())
+            .collect();
+
+        if !open.is_empty() {
+            return Err(crate::CoreError::Validation(format!(
+                "Ticket cannot be marked done while it is blocked by open tickets: {}",
+                open.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Check that `parent` may be the ticket's parent
+    ///
+    /// The parent must be in the same project and one level up the type
+    /// hierarchy, and must not be the ticket itself or one of its
+    /// descendants.
+    ///
+    /// # Arguments
+    /// * `parent` - The proposed parent
+    /// * `parent_ancestors` - IDs of the parent's ancestors
+    pub fn validate_parent(
+        &self,
+        parent: &Ticket,
+        parent_ancestors: &[TicketId],
+    ) -> crate::Result<()> {
+        if parent.id == self.id || parent_ancestors.contains(&self.id) {
+            return Err(crate::CoreError::Validation(
+                "A ticket cannot be its own ancestor".to_string(),
+            ));
+        }
+
+        if parent.project_id != self.project_id {
+            return Err(crate::CoreError::Validation(
+                "Parent ticket must be in the same project".to_string(),
+            ));
+        }
+
+        if !parent.ticket_type.can_parent(self.ticket_type) {
+            return Err(crate::CoreError::Validation(format!(
+                "A {} cannot be a child of a {}",
+                self.ticket_type, parent.ticket_type
+            )));
+        }
+
         Ok(())
     }
 
-    /// Update ticket status
-    pub fn update_status(&mut self, status: TicketStatus) {
-        self.status = status;
+    /// Check that the ticket's type may parent its existing children, after
+    /// a change of type
+    pub fn validate_children(&self, children: &[Ticket]) -> crate::Result<()> {
+        match children
+            .iter()
+            .find(|child| !self.ticket_type.can_parent(child.ticket_type))
+        {
+            Some(child) => Err(crate::CoreError::Validation(format!(
+                "A {} cannot be a child of a {}",
+                child.ticket_type, self.ticket_type
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether work on the ticket is finished
+    pub fn is_resolved(&self) -> bool {
+        self.status_category.is_resolved()
+    }
+
+    /// Move the ticket to a workflow status
+    ///
+    /// Whether the move is allowed is up to the workflow; see
+    /// `Workflow::can_transition`.
+    pub fn update_status(&mut self, status: &WorkflowStatus) {
+        self.status = status.key.clone();
+        self.status_category = status.category;
         self.updated_at = Utc::now();
     }
 
@@ -214,10 +476,28 @@ mod tests {
             user_id,
         );
         assert_eq!(ticket.title, "Test Ticket");
-        assert_eq!(ticket.status, TicketStatus::Open);
+        assert_eq!(ticket.status, "open");
         assert_eq!(ticket.priority, Priority::Medium);
     }
 
+    #[test]
+    fn test_remaining_minutes() {
+        let mut ticket = Ticket::new(
+            ProjectId::new(),
+            "Test Ticket".to_string(),
+            TicketType::Task,
+            UserId::new(),
+        );
+        assert_eq!(ticket.remaining_minutes(), None);
+
+        ticket.estimate_hours = Some(2.5);
+        ticket.logged_minutes = 100;
+        assert_eq!(ticket.remaining_minutes(), Some(50));
+
+        ticket.logged_minutes = 200;
+        assert_eq!(ticket.remaining_minutes(), Some(0));
+    }
+
     #[test]
     fn test_ticket_validation_success() {
         let project_id = ProjectId::new();
@@ -231,6 +511,83 @@ mod tests {
         assert!(ticket.validate().is_ok());
     }
 
+    #[test]
+    fn test_ticket_key() {
+        let key = TicketKey::parse("wn-123").unwrap();
+        assert_eq!(key, TicketKey::new("WN", 123));
+        assert_eq!(key.to_string(), "WN-123");
+
+        assert!(TicketKey::parse("WN-0").is_none());
+        assert!(TicketKey::parse("WN-+1").is_none());
+        assert!(TicketKey::parse("WN123").is_none());
+        assert!(TicketKey::parse("W-1").is_none());
+        assert!(TicketKey::parse("WN-").is_none());
+    }
+
+    #[test]
+    fn test_validate_parent() {
+        let project_id = ProjectId::new();
+        let user_id = UserId::new();
+        let epic = Ticket::new(project_id, "Epic".to_string(), TicketType::Epic, user_id);
+        let story = Ticket::new(project_id, "Story".to_string(), TicketType::Story, user_id);
+        let subtask = Ticket::new(
+            project_id,
+            "Subtask".to_string(),
+            TicketType::Subtask,
+            user_id,
+        );
+
+        assert!(story.validate_parent(&epic, &[]).is_ok());
+        assert!(subtask.validate_parent(&story, &[epic.id]).is_ok());
+        assert!(subtask.validate_parent(&epic, &[]).is_err());
+        assert!(epic.validate_parent(&story, &[]).is_err());
+        assert!(story.validate_parent(&story, &[]).is_err());
+
+        // A ticket cannot move under one of its own descendants
+        let mut looped = story.clone();
+        looped.ticket_type = TicketType::Subtask;
+        assert!(looped.validate_parent(&subtask, &[story.id]).is_err());
+
+        let other = Ticket::new(
+            ProjectId::new(),
+            "Other".to_string(),
+            TicketType::Epic,
+            user_id,
+        );
+        assert!(story.validate_parent(&other, &[]).is_err());
+
+        let rollup = TicketRollup::new(2, 3, 1, 8.0);
+        assert_eq!(rollup.percent_done, 33);
+    }
+
+    #[test]
+    fn test_type_change_is_checked_against_parent_and_children() {
+        let project_id = ProjectId::new();
+        let user_id = UserId::new();
+        let mut epic = Ticket::new(project_id, "Epic".to_string(), TicketType::Epic, user_id);
+        let mut story = Ticket::new(project_id, "Story".to_string(), TicketType::Story, user_id);
+        story.parent_id = Some(epic.id);
+        let bug = Ticket::new(project_id, "Bug".to_string(), TicketType::Bug, user_id);
+
+        assert!(epic
+            .validate_children(&[story.clone(), bug.clone()])
+            .is_ok());
+
+        // An Epic with Stories cannot become a Task
+        epic.ticket_type = TicketType::Task;
+        assert!(epic.validate_children(&[story.clone()]).is_err());
+        assert!(epic.validate_children(&[]).is_ok());
+        epic.ticket_type = TicketType::Epic;
+
+        // A Story under an Epic cannot become a Subtask or an Epic
+        story.ticket_type = TicketType::Subtask;
+        assert!(story.validate_parent(&epic, &[]).is_err());
+        story.ticket_type = TicketType::Epic;
+        assert!(story.validate_parent(&epic, &[]).is_err());
+        story.ticket_type = TicketType::Bug;
+        assert!(story.validate_parent(&epic, &[]).is_ok());
+    }
+
     #[test]
     fn test_ticket_validation_empty_title() {
         let project_id = ProjectId::new();
@@ -250,8 +607,10 @@ mod tests {
             user_id,
         );
 
-        ticket.update_status(TicketStatus::InProgress);
-        assert_eq!(ticket.status, TicketStatus::InProgress);
+        let workflow = Workflow::default_for(project_id);
+        ticket.update_status(workflow.status("in_progress").unwrap());
+        assert_eq!(ticket.status, "in_progress");
+        assert_eq!(ticket.status_category, StatusCategory::InProgress);
     }
 
     #[test]
@@ -274,4 +633,60 @@ mod tests {
         ticket.unassign();
         assert!(ticket.assignee_id.is_none());
     }
+
+    #[test]
+    fn test_validate_blockers() {
+        let project_id = ProjectId::new();
+        let user_id = UserId::new();
+        let mut ticket = Ticket::new(project_id, "Ship".to_string(), TicketType::Task, user_id);
+        let mut blocker = Ticket::new(project_id, "Build".to_string(), TicketType::Task, user_id);
+        let workflow = Workflow::default_for(project_id);
+
+        ticket.update_status(workflow.status("done").unwrap());
+        assert!(ticket.validate_blockers(&[blocker.clone()]).is_err());
+
+        ticket.update_status(workflow.status("review").unwrap());
+        assert!(ticket.validate_blockers(&[blocker.clone()]).is_ok());
+
+        ticket.update_status(workflow.status("closed").unwrap());
+        blocker.update_status(workflow.status("closed").unwrap());
+        assert!(ticket.validate_blockers(&[blocker]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_custom_fields() {
+        use crate::models::{CustomFieldType, FieldValue};
+
+        let project_id = ProjectId::new();
+        let mut ticket = Ticket::new(
+            project_id,
+            "Crash on login".to_string(),
+            TicketType::Bug,
+            UserId::new(),
+        );
+
+        let mut severity = CustomField::new(
+            project_id,
+            "Severity".to_string(),
+            CustomFieldType::SingleSelect,
+        );
+        severity.options = vec!["minor".to_string(), "major".to_string()];
+        severity.required = true;
+        let fields = [severity.clone()];
+
+        assert!(ticket.validate_custom_fields(&fields).is_err());
+
+        ticket.custom_fields = vec![CustomFieldValue {
+            field_id: severity.id,
+            value: FieldValue::SingleSelect("blocker".to_string()),
+        }];
+        assert!(ticket.validate().is_ok());
+        assert!(ticket.validate_custom_fields(&fields).is_err());
+
+        ticket.custom_fields[0].value = FieldValue::SingleSelect("major".to_string());
+        assert!(ticket.validate_custom_fields(&fields).is_ok());
+
+        ticket.custom_fields.push(ticket.custom_fields[0].clone());
+        assert!(ticket.validate().is_err());
+    }
 }
@@ -0,0 +1,628 @@
+//! Webhook domain model
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use uuid::Uuid;
+
+use super::{ActivityAction, ChangeResource, ProjectId, UserId};
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for webhooks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WebhookId(pub Uuid);
+
+impl WebhookId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for WebhookId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for WebhookId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Unique identifier for webhook deliveries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WebhookDeliveryId(pub Uuid);
+
+impl WebhookDeliveryId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for WebhookDeliveryId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for WebhookDeliveryId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Kind of change a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "ticket.created")]
+    TicketCreated,
+    #[serde(rename = "ticket.updated")]
+    TicketUpdated,
+    #[serde(rename = "ticket.deleted")]
+    TicketDeleted,
+    #[serde(rename = "comment.created")]
+    CommentCreated,
+    #[serde(rename = "comment.updated")]
+    CommentUpdated,
+    #[serde(rename = "comment.deleted")]
+    CommentDeleted,
+    #[serde(rename = "attachment.created")]
+    AttachmentCreated,
+    #[serde(rename = "attachment.deleted")]
+    AttachmentDeleted,
+}
+
+impl WebhookEvent {
+    /// Every event, in display order
+    pub const ALL: [WebhookEvent; 8] = [
+        WebhookEvent::TicketCreated,
+        WebhookEvent::TicketUpdated,
+        WebhookEvent::TicketDeleted,
+        WebhookEvent::CommentCreated,
+        WebhookEvent::CommentUpdated,
+        WebhookEvent::CommentDeleted,
+        WebhookEvent::AttachmentCreated,
+        WebhookEvent::AttachmentDeleted,
+    ];
+
+    /// Stable name used for storage and in payloads
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::TicketCreated => "ticket.created",
+            WebhookEvent::TicketUpdated => "ticket.updated",
+            WebhookEvent::TicketDeleted => "ticket.deleted",
+            WebhookEvent::CommentCreated => "comment.created",
+            WebhookEvent::CommentUpdated => "comment.updated",
+            WebhookEvent::CommentDeleted => "comment.deleted",
+            WebhookEvent::AttachmentCreated => "attachment.created",
+            WebhookEvent::AttachmentDeleted => "attachment.deleted",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == s)
+            .ok_or_else(|| CoreError::Validation(format!("Unknown webhook event: {}", s)))
+    }
+
+    /// The event for a change pushed to live clients, if webhooks can
+    /// subscribe to it
+    pub fn from_change(resource: ChangeResource, action: ActivityAction) -> Option<Self> {
+        match (resource, action) {
+            (ChangeResource::Ticket, ActivityAction::Created) => Some(WebhookEvent::TicketCreated),
+            (ChangeResource::Ticket, ActivityAction::Updated) => Some(WebhookEvent::TicketUpdated),
+            (ChangeResource::Ticket, ActivityAction::Deleted) => Some(WebhookEvent::TicketDeleted),
+            (ChangeResource::Comment, ActivityAction::Created) => {
+                Some(WebhookEvent::CommentCreated)
+            },
+            (ChangeResource::Comment, ActivityAction::Updated) => {
+                Some(WebhookEvent::CommentUpdated)
+            },
+            (ChangeResource::Comment, ActivityAction::Deleted) => {
+                Some(WebhookEvent::CommentDeleted)
+            },
+            (ChangeResource::Attachment, ActivityAction::Created) => {
+                Some(WebhookEvent::AttachmentCreated)
+            },
+            (ChangeResource::Attachment, ActivityAction::Deleted) => {
+                Some(WebhookEvent::AttachmentDeleted)
+            },
+            (ChangeResource::Attachment, ActivityAction::Updated) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Subscription of an outside URL to changes in a project
+///
+/// Payloads are signed with the secret so receivers can check that they come
+/// from Worknest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub project_id: ProjectId,
+    /// URL the payloads are posted to
+    pub url: String,
+    /// Key of the HMAC-SHA256 payload signature
+    pub secret: String,
+    /// Events delivered to the URL
+    pub events: Vec<WebhookEvent>,
+    /// Inactive webhooks keep their settings and log but receive nothing
+    pub active: bool,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// Create a new active webhook
+    pub fn new(
+        project_id: ProjectId,
+        url: String,
+        secret: String,
+        events: Vec<WebhookEvent>,
+        created_by: UserId,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: WebhookId::new(),
+            project_id,
+            url,
+            secret,
+            events,
+            active: true,
+            created_by,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Validate the webhook
+    ///
+    /// URLs naming `localhost` or an internal address are rejected here;
+    /// host names are only resolved when a payload is delivered, which
+    /// checks the addresses again.
+    pub fn validate(&self) -> Result<()> {
+        let rest = self
+            .url
+            .strip_prefix("https://")
+            .or_else(|| self.url.strip_prefix("http://"))
+            .unwrap_or_default();
+        if rest.is_empty() || rest.starts_with('/') || self.url.contains(char::is_whitespace) {
+            return Err(CoreError::Validation(format!(
+                "Invalid webhook URL '{}'; use an http:// or https:// URL",
+                self.url
+            )));
+        }
+
+        let host = url_host(rest).to_ascii_lowercase();
+        let is_internal = host == "localhost"
+            || host.ends_with(".localhost")
+            || host.parse().is_ok_and(is_internal_address);
+        if is_internal {
+            return Err(CoreError::Validation(format!(
+                "Webhook URL '{}' points to an internal address",
+                self.url
+            )));
+        }
+
+        if self.url.len() > 2000 {
+            return Err(CoreError::Validation(
+                "Webhook URL cannot exceed 2000 characters".to_string(),
+            ));
+        }
+
+        if self.secret.is_empty() {
+            return Err(CoreError::Validation(
+                "Webhook secret cannot be empty".to_string(),
+            ));
+        }
+
+        if self.secret.len() > 255 {
+            return Err(CoreError::Validation(
+                "Webhook secret cannot exceed 255 characters".to_string(),
+            ));
+        }
+
+        if self.events.is_empty() {
+            return Err(CoreError::Validation(
+                "Webhook must subscribe to at least one event".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the webhook receives an event
+    pub fn subscribes_to(&self, event: WebhookEvent) -> bool {
+        self.active && self.events.contains(&event)
+    }
+}
+
+/// The host of a URL without its scheme: no user info, port or the
+/// brackets around an IPv6 address
+fn url_host(rest: &str) -> &str {
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    }
+}
+
+/// Whether an address belongs to this machine or a private network rather
+/// than the public internet, so webhooks may not be delivered to it
+///
+/// Covers loopback, private, shared (carrier-grade NAT), link-local
+/// (including cloud metadata services at 169.254.169.254), unspecified,
+/// multicast and reserved addresses, and IPv6 addresses that embed one.
+pub fn is_internal_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_internal_ipv4(ip),
+        IpAddr::V6(ip) => is_internal_ipv6(ip),
+    }
+}
+
+fn is_internal_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || a == 0
+        // Shared address space, 100.64.0.0/10
+        || (a == 100 && (b & 0xc0) == 64)
+        // IETF protocol assignments, 192.0.0.0/24
+        || ip.octets()[..3] == [192, 0, 0]
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved, 240.0.0.0/4
+        || a >= 240
+}
+
+fn is_internal_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_internal_ipv4(ipv4);
+    }
+
+    let segments = ip.segments();
+    // NAT64, 64:ff9b::/96, reaches the IPv4 address in its last 32 bits
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [.., high, low] = segments;
+        return is_internal_ipv4(Ipv4Addr::from((u32::from(high) << 16) | u32::from(low)));
+    }
+
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Unique local, fc00::/7
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (segments[0] & 0xffc0) == 0xfe80
+        // Site-local, deprecated but still routed on some networks, fec0::/10
+        || (segments[0] & 0xffc0) == 0xfec0
+}
+
+/// State of a webhook delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting for its first attempt or a retry
+    Pending,
+    /// The receiver answered with a 2xx status
+    Succeeded,
+    /// Every attempt failed
+    Failed,
+}
+
+impl DeliveryStatus {
+    /// Stable name used for storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Succeeded => "succeeded",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "succeeded" => Ok(DeliveryStatus::Succeeded),
+            "failed" => Ok(DeliveryStatus::Failed),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown delivery status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// One event sent to a webhook, with the outcome of its latest attempt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub webhook_id: WebhookId,
+    pub event: WebhookEvent,
+    /// JSON body exactly as it is signed and sent
+    pub payload: String,
+    pub status: DeliveryStatus,
+    /// Number of attempts made so far
+    pub attempts: u32,
+    /// HTTP status of the latest response
+    pub response_code: Option<u16>,
+    /// Why the latest attempt failed, when it got no 2xx response
+    pub error: Option<String>,
+    /// When the next attempt is due; `None` once the delivery is settled
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    /// Attempts made before a delivery fails for good
+    pub const MAX_ATTEMPTS: u32 = 6;
+
+    /// Wait before the first retry; it doubles with every further attempt
+    pub const RETRY_BASE_SECONDS: i64 = 30;
+
+    /// Create a new delivery, due right away
+    pub fn new(webhook_id: WebhookId, event: WebhookEvent, payload: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: WebhookDeliveryId::new(),
+            webhook_id,
+            event,
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            response_code: None,
+            error: None,
+            next_attempt_at: Some(now),
+            last_attempt_at: None,
+            created_at: now,
+        }
+    }
+
+    /// A fresh delivery of the same payload, leaving this one in the log
+    pub fn redelivery(&self) -> Self {
+        Self::new(self.webhook_id, self.event, self.payload.clone())
+    }
+
+    /// Wait after the given number of failed attempts: 30s, 1m, 2m, 4m, ...
+    pub fn retry_delay(attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        Duration::seconds(Self::RETRY_BASE_SECONDS << exponent)
+    }
+
+    /// Settle the delivery as failed without sending it, for a payload that
+    /// could not be built
+    pub fn fail(&mut self, error: String) {
+        self.status = DeliveryStatus::Failed;
+        self.error = Some(error);
+        self.next_attempt_at = None;
+    }
+
+    /// Record the outcome of an attempt made at `at`
+    ///
+    /// A 2xx response settles the delivery. Anything else schedules a retry
+    /// with exponential backoff until the attempts run out.
+    pub fn record_attempt(
+        &mut self,
+        response_code: Option<u16>,
+        error: Option<String>,
+        at: DateTime<Utc>,
+    ) {
+        self.attempts += 1;
+        self.last_attempt_at = Some(at);
+        self.response_code = response_code;
+
+        let succeeded = error.is_none() && response_code.is_some_and(|c| (200..300).contains(&c));
+        self.error = match (succeeded, error, response_code) {
+            (true, _, _) => None,
+            (false, Some(error), _) => Some(error),
+            (false, None, Some(code)) => Some(format!("Receiver answered with status {}", code)),
+            (false, None, None) => Some("No response".to_string()),
+        };
+
+        if succeeded {
+            self.status = DeliveryStatus::Succeeded;
+            self.next_attempt_at = None;
+        } else if self.attempts >= Self::MAX_ATTEMPTS {
+            self.status = DeliveryStatus::Failed;
+            self.next_attempt_at = None;
+        } else {
+            self.status = DeliveryStatus::Pending;
+            self.next_attempt_at = Some(at + Self::retry_delay(self.attempts));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook::new(
+            ProjectId::new(),
+            url.to_string(),
+            "s3cret".to_string(),
+            vec![WebhookEvent::TicketCreated],
+            UserId::new(),
+        )
+    }
+
+    #[test]
+    fn test_webhook_event_names() {
+        for event in WebhookEvent::ALL {
+            assert_eq!(WebhookEvent::from_name(event.as_str()).unwrap(), event);
+            assert_eq!(
+                serde_json::to_value(event).unwrap(),
+                serde_json::json!(event.as_str())
+            );
+        }
+        assert!(WebhookEvent::from_name("ticket.liked").is_err());
+
+        assert_eq!(
+            WebhookEvent::from_change(ChangeResource::Comment, ActivityAction::Updated),
+            Some(WebhookEvent::CommentUpdated)
+        );
+        assert_eq!(
+            WebhookEvent::from_change(ChangeResource::Attachment, ActivityAction::Updated),
+            None
+        );
+    }
+
+    #[test]
+    fn test_webhook_validation() {
+        assert!(webhook("https://chat.example.com/hooks/1")
+            .validate()
+            .is_ok());
+        assert!(webhook("http://hooks.example.com:8080/ci?x=1")
+            .validate()
+            .is_ok());
+        assert!(webhook("http://93.184.216.34/hook").validate().is_ok());
+        assert!(webhook("ftp://example.com").validate().is_err());
+        assert!(webhook("https://").validate().is_err());
+        assert!(webhook("https://exa mple.com").validate().is_err());
+
+        for url in [
+            "http://localhost:8080",
+            "http://api.LOCALHOST/hook",
+            "http://127.0.0.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://user@10.0.0.5:9000",
+            "http://[::1]:3000/hook",
+            "https://[::ffff:192.168.1.1]/hook",
+        ] {
+            assert!(webhook(url).validate().is_err(), "{}", url);
+        }
+
+        let mut hook = webhook("https://example.com");
+        hook.secret.clear();
+        assert!(hook.validate().is_err());
+
+        let mut hook = webhook("https://example.com");
+        hook.events.clear();
+        assert!(hook.validate().is_err());
+    }
+
+    #[test]
+    fn test_internal_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.0.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(is_internal_address(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:2800:220:1::1"] {
+            assert!(!is_internal_address(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_inactive_webhook_subscribes_to_nothing() {
+        let mut hook = webhook("https://example.com");
+        assert!(hook.subscribes_to(WebhookEvent::TicketCreated));
+        assert!(!hook.subscribes_to(WebhookEvent::TicketDeleted));
+
+        hook.active = false;
+        assert!(!hook.subscribes_to(WebhookEvent::TicketCreated));
+    }
+
+    #[test]
+    fn test_delivery_retries_with_backoff() {
+        let mut delivery =
+            WebhookDelivery::new(WebhookId::new(), WebhookEvent::TicketCreated, "{}".into());
+        let now = Utc::now();
+
+        delivery.record_attempt(Some(500), None, now);
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(30)));
+        assert_eq!(
+            delivery.error.as_deref(),
+            Some("Receiver answered with status 500")
+        );
+
+        delivery.record_attempt(None, Some("Connection refused".into()), now);
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(60)));
+        assert_eq!(delivery.response_code, None);
+
+        delivery.record_attempt(Some(204), None, now);
+        assert_eq!(delivery.status, DeliveryStatus::Succeeded);
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.error, None);
+        assert_eq!(delivery.next_attempt_at, None);
+    }
+
+    #[test]
+    fn test_delivery_fails_after_max_attempts() {
+        let mut delivery =
+            WebhookDelivery::new(WebhookId::new(), WebhookEvent::TicketCreated, "{}".into());
+        for _ in 0..WebhookDelivery::MAX_ATTEMPTS {
+            delivery.record_attempt(Some(502), None, Utc::now());
+        }
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(delivery.next_attempt_at, None);
+
+        let again = delivery.redelivery();
+        assert_ne!(again.id, delivery.id);
+        assert_eq!(again.payload, delivery.payload);
+        assert_eq!(again.status, DeliveryStatus::Pending);
+        assert_eq!(again.attempts, 0);
+    }
+
+    #[test]
+    fn test_delivery_fails_without_attempt() {
+        let mut delivery =
+            WebhookDelivery::new(WebhookId::new(), WebhookEvent::TicketCreated, String::new());
+        delivery.fail("Failed to encode payload".into());
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, 0);
+        assert_eq!(delivery.next_attempt_at, None);
+        assert_eq!(delivery.error.as_deref(), Some("Failed to encode payload"));
+    }
+}
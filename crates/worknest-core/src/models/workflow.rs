@@ -0,0 +1,298 @@
+//! Workflow domain model
+
+use serde::{Deserialize, Serialize};
+
+use super::ProjectId;
+use crate::error::{CoreError, Result};
+
+/// Broad stage a workflow status belongs to
+///
+/// Categories let code that does not know a project's workflow tell whether
+/// a ticket is still waiting, being worked on or finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl StatusCategory {
+    /// Stable name used for storage and in the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusCategory::Todo => "todo",
+            StatusCategory::InProgress => "in_progress",
+            StatusCategory::Done => "done",
+        }
+    }
+
+    /// Parse from the stable name
+    pub fn from_name(s: &str) -> Result<Self> {
+        match s {
+            "todo" => Ok(StatusCategory::Todo),
+            "in_progress" => Ok(StatusCategory::InProgress),
+            "done" => Ok(StatusCategory::Done),
+            _ => Err(CoreError::Validation(format!(
+                "Unknown status category: {}",
+                s
+            ))),
+        }
+    }
+
+    /// Whether work on a ticket in this category is finished
+    pub fn is_resolved(&self) -> bool {
+        *self == StatusCategory::Done
+    }
+}
+
+impl std::fmt::Display for StatusCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatusCategory::Todo => write!(f, "To Do"),
+            StatusCategory::InProgress => write!(f, "In Progress"),
+            StatusCategory::Done => write!(f, "Done"),
+        }
+    }
+}
+
+/// One status of a workflow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowStatus {
+    /// Stable identifier stored on tickets, e.g. `in_progress`
+    pub key: String,
+    /// Display name, e.g. `In Progress`
+    pub name: String,
+    pub category: StatusCategory,
+}
+
+impl WorkflowStatus {
+    pub fn new(key: &str, name: &str, category: StatusCategory) -> Self {
+        Self {
+            key: key.to_string(),
+            name: name.to_string(),
+            category,
+        }
+    }
+}
+
+/// A move a ticket may make from one status to another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowTransition {
+    pub from: String,
+    pub to: String,
+}
+
+/// The ordered statuses of a project and the transitions allowed between
+/// them
+///
+/// The first status is the one new tickets start in. A ticket may only move
+/// along a listed transition; staying in the same status is always allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workflow {
+    pub project_id: ProjectId,
+    pub statuses: Vec<WorkflowStatus>,
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+impl Workflow {
+    /// Key of the status new tickets start in under the default workflow
+    pub const DEFAULT_INITIAL_STATUS: &'static str = "open";
+
+    /// The workflow projects start with: open, in progress, review, done and
+    /// closed, with every transition allowed
+    pub fn default_for(project_id: ProjectId) -> Self {
+        let statuses = vec![
+            WorkflowStatus::new(Self::DEFAULT_INITIAL_STATUS, "Open", StatusCategory::Todo),
+            WorkflowStatus::new("in_progress", "In Progress", StatusCategory::InProgress),
+            WorkflowStatus::new("review", "Review", StatusCategory::InProgress),
+            WorkflowStatus::new("done", "Done", StatusCategory::Done),
+            WorkflowStatus::new("closed", "Closed", StatusCategory::Done),
+        ];
+
+        let transitions = statuses
+            .iter()
+            .flat_map(|from| {
+                statuses
+                    .iter()
+                    .filter(move |to| to.key != from.key)
+                    .map(move |to| WorkflowTransition {
+                        from: from.key.clone(),
+                        to: to.key.clone(),
+                    })
+            })
+            .collect();
+
+        Self {
+            project_id,
+            statuses,
+            transitions,
+        }
+    }
+
+    /// Find a status by key, ignoring case, `_`, `-` and spaces
+    pub fn status(&self, key: &str) -> Option<&WorkflowStatus> {
+        let key = normalize_status_key(key);
+        self.statuses
+            .iter()
+            .find(|s| normalize_status_key(&s.key) == key)
+    }
+
+    /// The status new tickets start in
+    pub fn initial_status(&self) -> Option<&WorkflowStatus> {
+        self.statuses.first()
+    }
+
+    /// Whether a ticket may move from `from` to `to`
+    pub fn can_transition(&self, from: &str, to: &str) -> bool {
+        from == to
+            || self
+                .transitions
+                .iter()
+                .any(|t| t.from == from && t.to == to)
+    }
+
+    /// The statuses a ticket in `from` may move to, in workflow order
+    pub fn next_statuses(&self, from: &str) -> Vec<&WorkflowStatus> {
+        self.statuses
+            .iter()
+            .filter(|s| s.key != from && self.can_transition(from, &s.key))
+            .collect()
+    }
+
+    /// Validate the workflow
+    pub fn validate(&self) -> Result<()> {
+        if self.statuses.is_empty() {
+            return Err(CoreError::Validation(
+                "A workflow needs at least one status".to_string(),
+            ));
+        }
+
+        for (i, status) in self.statuses.iter().enumerate() {
+            let valid_key = !status.key.is_empty()
+                && status.key.len() <= 50
+                && status
+                    .key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if !valid_key {
+                return Err(CoreError::Validation(format!(
+                    "Invalid status key '{}'; use up to 50 lowercase letters, digits and underscores",
+                    status.key
+                )));
+            }
+
+            if status.name.trim().is_empty() || status.name.len() > 50 {
+                return Err(CoreError::Validation(
+                    "Status names must be between 1 and 50 characters".to_string(),
+                ));
+            }
+
+            let key = normalize_status_key(&status.key);
+            if self.statuses[..i]
+                .iter()
+                .any(|other| normalize_status_key(&other.key) == key)
+            {
+                return Err(CoreError::Validation(format!(
+                    "Duplicate status '{}'",
+                    status.key
+                )));
+            }
+        }
+
+        for transition in &self.transitions {
+            for key in [&transition.from, &transition.to] {
+                if !self.statuses.iter().any(|s| &s.key == key) {
+                    return Err(CoreError::Validation(format!(
+                        "Transition refers to unknown status '{}'",
+                        key
+                    )));
+                }
+            }
+            if transition.from == transition.to {
+                return Err(CoreError::Validation(format!(
+                    "Status '{}' cannot transition to itself",
+                    transition.from
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Normalize a status key for comparison: lowercase, without `_`, `-` or
+/// spaces, so `in_progress`, `InProgress` and `in progress` are the same
+pub fn normalize_status_key(key: &str) -> String {
+    key.to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_workflow() {
+        let workflow = Workflow::default_for(ProjectId::new());
+        assert!(workflow.validate().is_ok());
+        assert_eq!(workflow.initial_status().unwrap().key, "open");
+        assert_eq!(workflow.status("InProgress").unwrap().key, "in_progress");
+        assert!(workflow.can_transition("open", "closed"));
+        assert_eq!(workflow.next_statuses("open").len(), 4);
+    }
+
+    #[test]
+    fn test_transitions() {
+        let mut workflow = Workflow {
+            project_id: ProjectId::new(),
+            statuses: vec![
+                WorkflowStatus::new("todo", "To Do", StatusCategory::Todo),
+                WorkflowStatus::new("doing", "Doing", StatusCategory::InProgress),
+                WorkflowStatus::new("shipped", "Shipped", StatusCategory::Done),
+            ],
+            transitions: vec![
+                WorkflowTransition {
+                    from: "todo".to_string(),
+                    to: "doing".to_string(),
+                },
+                WorkflowTransition {
+                    from: "doing".to_string(),
+                    to: "shipped".to_string(),
+                },
+            ],
+        };
+        assert!(workflow.validate().is_ok());
+        assert!(workflow.can_transition("todo", "doing"));
+        assert!(workflow.can_transition("todo", "todo"));
+        assert!(!workflow.can_transition("todo", "shipped"));
+        assert!(!workflow.can_transition("shipped", "todo"));
+
+        workflow.transitions.push(WorkflowTransition {
+            from: "shipped".to_string(),
+            to: "archived".to_string(),
+        });
+        assert!(workflow.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_keys() {
+        let mut workflow = Workflow::default_for(ProjectId::new());
+        workflow.statuses[1].key = "In Progress".to_string();
+        assert!(workflow.validate().is_err());
+
+        let mut workflow = Workflow::default_for(ProjectId::new());
+        workflow.statuses.push(WorkflowStatus::new(
+            "inprogress",
+            "Again",
+            StatusCategory::Done,
+        ));
+        assert!(workflow.validate().is_err());
+
+        workflow.statuses.clear();
+        workflow.transitions.clear();
+        assert!(workflow.validate().is_err());
+    }
+}
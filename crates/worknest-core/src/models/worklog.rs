@@ -0,0 +1,253 @@
+//! Worklog domain model
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{TicketId, UserId};
+use crate::error::{CoreError, Result};
+
+/// Unique identifier for worklogs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WorklogId(pub Uuid);
+
+impl WorklogId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    pub fn from_string(s: &str) -> Result<Self> {
+        Ok(Self(
+            Uuid::parse_str(s).map_err(|e| CoreError::InvalidId(e.to_string()))?,
+        ))
+    }
+}
+
+impl Default for WorklogId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for WorklogId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Time a user spent on a ticket
+///
+/// Durations are whole minutes so totals add up exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Worklog {
+    pub id: WorklogId,
+    pub ticket_id: TicketId,
+    pub user_id: UserId,
+    pub started_at: DateTime<Utc>,
+    pub duration_minutes: u32,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Worklog {
+    /// Longest single entry: a full day
+    pub const MAX_DURATION_MINUTES: u32 = 24 * 60;
+
+    /// Create a new worklog
+    pub fn new(
+        ticket_id: TicketId,
+        user_id: UserId,
+        started_at: DateTime<Utc>,
+        duration_minutes: u32,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: WorklogId::new(),
+            ticket_id,
+            user_id,
+            started_at,
+            duration_minutes,
+            note: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Validate the worklog
+    pub fn validate(&self) -> Result<()> {
+        if self.duration_minutes == 0 || self.duration_minutes > Self::MAX_DURATION_MINUTES {
+            return Err(CoreError::Validation(
+                "Worklog duration must be between 1 minute and 24 hours".to_string(),
+            ));
+        }
+
+        // A few minutes of leeway for clocks that run ahead
+        if self.started_at > Utc::now() + Duration::minutes(5) {
+            return Err(CoreError::Validation(
+                "Worklogs cannot start in the future".to_string(),
+            ));
+        }
+
+        if self.note.as_ref().is_some_and(|note| note.len() > 1000) {
+            return Err(CoreError::Validation(
+                "Worklog note cannot exceed 1000 characters".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Minutes a user logged against one ticket in a timesheet week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimesheetRow {
+    pub ticket_id: TicketId,
+    /// Minutes per day, Monday first
+    pub daily_minutes: [u32; 7],
+    pub total_minutes: u32,
+}
+
+/// A user's logged time for one week, by ticket and day
+///
+/// Days are UTC dates; a worklog counts towards the day it started on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timesheet {
+    pub user_id: UserId,
+    /// Monday of the week
+    pub week_start: NaiveDate,
+    pub rows: Vec<TimesheetRow>,
+    /// Minutes per day over all tickets, Monday first
+    pub daily_minutes: [u32; 7],
+    pub total_minutes: u32,
+}
+
+impl Timesheet {
+    /// Monday of the week containing a date
+    pub fn week_start_of(date: NaiveDate) -> NaiveDate {
+        date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+    }
+
+    /// First instant of the week starting on `week_start` and the first
+    /// instant after it
+    pub fn week_bounds(week_start: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let start = week_start
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc();
+        (start, start + Duration::days(7))
+    }
+
+    /// Sum up a user's worklogs for the week starting on `week_start`
+    ///
+    /// Worklogs of other users or outside the week are ignored. Rows keep
+    /// the order in which their tickets first appear.
+    pub fn build(user_id: UserId, week_start: NaiveDate, worklogs: &[Worklog]) -> Self {
+        let mut timesheet = Self {
+            user_id,
+            week_start,
+            rows: Vec::new(),
+            daily_minutes: [0; 7],
+            total_minutes: 0,
+        };
+
+        for worklog in worklogs.iter().filter(|w| w.user_id == user_id) {
+            let day = (worklog.started_at.date_naive() - week_start).num_days();
+            let Ok(day) = usize::try_from(day) else {
+                continue;
+            };
+            if day >= 7 {
+                continue;
+            }
+
+            let row_index = match timesheet
+                .rows
+                .iter()
+                .position(|row| row.ticket_id == worklog.ticket_id)
+            {
+                Some(index) => index,
+                None => {
+                    timesheet.rows.push(TimesheetRow {
+                        ticket_id: worklog.ticket_id,
+                        daily_minutes: [0; 7],
+                        total_minutes: 0,
+                    });
+                    timesheet.rows.len() - 1
+                },
+            };
+
+            let row = &mut timesheet.rows[row_index];
+            row.daily_minutes[day] += worklog.duration_minutes;
+            row.total_minutes += worklog.duration_minutes;
+            timesheet.daily_minutes[day] += worklog.duration_minutes;
+            timesheet.total_minutes += worklog.duration_minutes;
+        }
+
+        timesheet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_validate_worklog() {
+        let mut worklog = Worklog::new(TicketId::new(), UserId::new(), Utc::now(), 90);
+        assert!(worklog.validate().is_ok());
+
+        worklog.duration_minutes = 0;
+        assert!(worklog.validate().is_err());
+
+        worklog.duration_minutes = Worklog::MAX_DURATION_MINUTES + 1;
+        assert!(worklog.validate().is_err());
+
+        worklog.duration_minutes = 30;
+        worklog.started_at = Utc::now() + Duration::hours(1);
+        assert!(worklog.validate().is_err());
+
+        worklog.started_at = Utc::now();
+        worklog.note = Some("a".repeat(1001));
+        assert!(worklog.validate().is_err());
+    }
+
+    #[test]
+    fn test_build_timesheet() {
+        let user_id = UserId::new();
+        let ticket_a = TicketId::new();
+        let ticket_b = TicketId::new();
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap();
+
+        // Week of Monday 2025-03-03
+        let week_start = Timesheet::week_start_of(NaiveDate::from_ymd_opt(2025, 3, 6).unwrap());
+        assert_eq!(week_start, NaiveDate::from_ymd_opt(2025, 3, 3).unwrap());
+
+        let worklogs = vec![
+            Worklog::new(ticket_a, user_id, at(3, 9), 60),
+            Worklog::new(ticket_b, user_id, at(3, 13), 45),
+            Worklog::new(ticket_a, user_id, at(9, 23), 30),
+            // Before and after the week, and someone else's time
+            Worklog::new(ticket_a, user_id, at(2, 23), 15),
+            Worklog::new(ticket_a, user_id, at(10, 0), 15),
+            Worklog::new(ticket_a, UserId::new(), at(4, 9), 15),
+        ];
+
+        let timesheet = Timesheet::build(user_id, week_start, &worklogs);
+        assert_eq!(timesheet.rows.len(), 2);
+        assert_eq!(timesheet.rows[0].ticket_id, ticket_a);
+        assert_eq!(timesheet.rows[0].daily_minutes, [60, 0, 0, 0, 0, 0, 30]);
+        assert_eq!(timesheet.rows[0].total_minutes, 90);
+        assert_eq!(timesheet.rows[1].total_minutes, 45);
+        assert_eq!(timesheet.daily_minutes, [105, 0, 0, 0, 0, 0, 30]);
+        assert_eq!(timesheet.total_minutes, 135);
+
+        let (start, end) = Timesheet::week_bounds(week_start);
+        assert_eq!(start, at(3, 0));
+        assert_eq!(end, at(10, 0));
+    }
+}
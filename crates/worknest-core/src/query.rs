@@ -0,0 +1,860 @@
+//! Ticket query language
+//!
+//! A small JQL-like language for filtering tickets, for example
+//! `status:open,review assignee:me priority>=high due<7d "login crash"`.
+//!
+//! Statuses are keys of the project's workflow; `category` compares the
+//! workflow category (`todo`, `in_progress`, `done`) and works across
+//! projects.
+//!
+//! ```text
+//! query      = or
+//! or         = and ("OR" and)*
+//! and        = unary (["AND"] unary)*
+//! unary      = ("NOT" | "-") unary | primary
+//! primary    = "(" or ")" | comparison | text
+//! comparison = field op value ("," value)*
+//! op         = ":" | "=" | "!=" | "<" | "<=" | ">" | ">="
+//! ```
+//!
+//! Terms next to each other are AND-ed. Words and quoted strings that are
+//! not part of a comparison are free text. Keywords must be upper case.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::{
+    normalize_status_key, Priority, ProjectId, StatusCategory, TicketType, UserId,
+};
+
+/// A parsed query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Condition(Condition),
+    /// Free text to search for
+    Text(String),
+}
+
+/// A field comparison such as `priority>=high`
+///
+/// With several values (`status:open,review`) the condition holds when the
+/// field equals any of them, or, for `!=`, none of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub field: Field,
+    pub op: CompareOp,
+    pub values: Vec<Value>,
+}
+
+/// Ticket field that can be compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Status,
+    Category,
+    Priority,
+    Type,
+    Assignee,
+    Creator,
+    Project,
+    Label,
+    Due,
+    Created,
+    Updated,
+}
+
+impl Field {
+    /// Parse from the name used in queries
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "status" => Some(Field::Status),
+            "category" => Some(Field::Category),
+            "priority" => Some(Field::Priority),
+            "type" => Some(Field::Type),
+            "assignee" => Some(Field::Assignee),
+            "creator" | "reporter" => Some(Field::Creator),
+            "project" => Some(Field::Project),
+            "label" | "labels" => Some(Field::Label),
+            "due" => Some(Field::Due),
+            "created" => Some(Field::Created),
+            "updated" => Some(Field::Updated),
+            _ => None,
+        }
+    }
+
+    /// Name used in queries
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Field::Status => "status",
+            Field::Category => "category",
+            Field::Priority => "priority",
+            Field::Type => "type",
+            Field::Assignee => "assignee",
+            Field::Creator => "creator",
+            Field::Project => "project",
+            Field::Label => "label",
+            Field::Due => "due",
+            Field::Created => "created",
+            Field::Updated => "updated",
+        }
+    }
+
+    /// Whether `<`, `<=`, `>` and `>=` make sense for the field
+    fn is_ordered(&self) -> bool {
+        matches!(
+            self,
+            Field::Category | Field::Priority | Field::Due | Field::Created | Field::Updated
+        )
+    }
+}
+
+/// Comparison operator; `:` is an alias for `=`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    /// Whether the operator tests equality rather than order
+    pub fn is_equality(&self) -> bool {
+        matches!(self, CompareOp::Eq | CompareOp::Ne)
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "=",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+/// Value on the right-hand side of a comparison, typed by its field
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Workflow status key, normalized with `normalize_status_key`
+    Status(String),
+    Category(StatusCategory),
+    Priority(Priority),
+    Type(TicketType),
+    User(UserRef),
+    Project(ProjectRef),
+    /// Label name, matched case-insensitively
+    Label(String),
+    Date(DateSpec),
+    /// `none`: the field has no value
+    Empty,
+}
+
+/// User named in a query
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserRef {
+    /// `me`: the user running the query
+    Me,
+    Id(UserId),
+    Username(String),
+}
+
+/// Project named in a query
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectRef {
+    Id(ProjectId),
+    Name(String),
+}
+
+/// Date named in a query
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateSpec {
+    /// `today`
+    Today,
+    /// A whole UTC day, such as `2025-01-31`
+    Day(NaiveDate),
+    /// An offset from now, such as `7d`, `-2w` or `12h`
+    Relative(Duration),
+    /// An exact time, given as a quoted RFC 3339 timestamp
+    Instant(DateTime<Utc>),
+}
+
+impl DateSpec {
+    /// The half-open interval the date covers, relative to `now`
+    ///
+    /// Whole days cover the day; relative and exact times are a single
+    /// instant, with both bounds equal. Times past either end of the
+    /// calendar are clamped to it.
+    pub fn bounds(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        match self {
+            DateSpec::Today => day_bounds(now.date_naive()),
+            DateSpec::Day(day) => day_bounds(*day),
+            DateSpec::Relative(offset) => {
+                let at = now
+                    .checked_add_signed(*offset)
+                    .unwrap_or(if *offset < Duration::zero() {
+                        DateTime::<Utc>::MIN_UTC
+                    } else {
+                        DateTime::<Utc>::MAX_UTC
+                    });
+                (at, at)
+            },
+            DateSpec::Instant(at) => (*at, *at),
+        }
+    }
+
+    /// The UTC day containing the date, used for equality comparisons
+    pub fn day(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        day_bounds(self.bounds(now).0.date_naive())
+    }
+}
+
+fn day_bounds(day: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let end = start
+        .checked_add_signed(Duration::days(1))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    (start, end)
+}
+
+/// Query syntax error
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at position {position}")]
+pub struct QueryError {
+    pub message: String,
+    /// Character offset of the problem in the query, counting from 0
+    pub position: usize,
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+/// Parse a query
+pub fn parse(input: &str) -> Result<Expr, QueryError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, index: 0 };
+
+    if parser.peek().kind == TokenKind::End {
+        return Err(QueryError::new("Query is empty", 0));
+    }
+
+    let expr = parser.parse_or()?;
+    let token = parser.peek();
+    if token.kind != TokenKind::End {
+        return Err(QueryError::new(
+            format!("Unexpected {}", token.kind.describe()),
+            token.position,
+        ));
+    }
+
+    Ok(expr)
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Quoted(String),
+    Op(CompareOp),
+    LParen,
+    RParen,
+    Comma,
+    Minus,
+    End,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::Word(word) => format!("'{}'", word),
+            TokenKind::Quoted(text) => format!("\"{}\"", text),
+            TokenKind::Op(op) => format!("'{}'", op.as_str()),
+            TokenKind::LParen => "'('".to_string(),
+            TokenKind::RParen => "')'".to_string(),
+            TokenKind::Comma => "','".to_string(),
+            TokenKind::Minus => "'-'".to_string(),
+            TokenKind::End => "end of query".to_string(),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, TokenKind::Word(word) if word == keyword)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '(' | ')' | '"' | ',' | ':' | '=' | '!' | '<' | '>')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        let kind = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            },
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            ',' => TokenKind::Comma,
+            ':' | '=' => TokenKind::Op(CompareOp::Eq),
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                i += 1;
+                TokenKind::Op(CompareOp::Ne)
+            },
+            '!' => return Err(QueryError::new("Expected '=' after '!'", i)),
+            '<' | '>' => {
+                let or_equal = chars.get(i + 1) == Some(&'=');
+                if or_equal {
+                    i += 1;
+                }
+                TokenKind::Op(match (c, or_equal) {
+                    ('<', false) => CompareOp::Lt,
+                    ('<', true) => CompareOp::Le,
+                    (_, false) => CompareOp::Gt,
+                    (_, true) => CompareOp::Ge,
+                })
+            },
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(QueryError::new("Unterminated quoted text", start)),
+                        Some('"') => break,
+                        Some('\\') if chars.get(i + 1).is_some() => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        },
+                        Some(&c) => {
+                            text.push(c);
+                            i += 1;
+                        },
+                    }
+                }
+                TokenKind::Quoted(text)
+            },
+            // A leading '-' negates a term, except where a value is expected
+            '-' if chars
+                .get(i + 1)
+                .is_some_and(|&next| is_word_char(next) || next == '(' || next == '"')
+                && !matches!(
+                    tokens.last().map(|t| &t.kind),
+                    Some(TokenKind::Op(_)) | Some(TokenKind::Comma)
+                ) =>
+            {
+                TokenKind::Minus
+            },
+            _ => {
+                while i + 1 < chars.len() && is_word_char(chars[i + 1]) {
+                    i += 1;
+                }
+                TokenKind::Word(chars[start..=i].iter().collect())
+            },
+        };
+
+        tokens.push(Token {
+            kind,
+            position: start,
+        });
+        i += 1;
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        position: chars.len(),
+    });
+
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.index].clone();
+        if token.kind != TokenKind::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, QueryError> {
+        let mut left = self.parse_and()?;
+        while self.peek().kind.is_keyword("OR") {
+            self.next();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, QueryError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let kind = &self.peek().kind;
+            if kind.is_keyword("AND") {
+                self.next();
+            } else if kind.is_keyword("OR") || matches!(kind, TokenKind::RParen | TokenKind::End) {
+                break;
+            }
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, QueryError> {
+        let kind = &self.peek().kind;
+        if kind.is_keyword("NOT") || *kind == TokenKind::Minus {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, QueryError> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::LParen => {
+                let expr = self.parse_or()?;
+                if self.next().kind != TokenKind::RParen {
+                    return Err(QueryError::new("Unclosed '('", token.position));
+                }
+                Ok(expr)
+            },
+            TokenKind::Word(word) if word == "AND" || word == "OR" => Err(QueryError::new(
+                format!("Expected a term before '{}'", word),
+                token.position,
+            )),
+            TokenKind::Word(word) => {
+                if matches!(self.peek().kind, TokenKind::Op(_)) {
+                    self.parse_condition(&word, token.position)
+                } else {
+                    Ok(Expr::Text(word))
+                }
+            },
+            TokenKind::Quoted(text) => Ok(Expr::Text(text)),
+            TokenKind::End => Err(QueryError::new("Unexpected end of query", token.position)),
+            kind => Err(QueryError::new(
+                format!("Unexpected {}", kind.describe()),
+                token.position,
+            )),
+        }
+    }
+
+    fn parse_condition(&mut self, name: &str, position: usize) -> Result<Expr, QueryError> {
+        let field = Field::from_name(name)
+            .ok_or_else(|| QueryError::new(format!("Unknown field '{}'", name), position))?;
+
+        let op_token = self.next();
+        let op = match op_token.kind {
+            TokenKind::Op(op) => op,
+            _ => unreachable!("parse_condition is only called before an operator"),
+        };
+
+        if !op.is_equality() && !field.is_ordered() {
+            return Err(QueryError::new(
+                format!(
+                    "'{}' can only be compared with ':', '=' or '!='",
+                    field.as_str()
+                ),
+                op_token.position,
+            ));
+        }
+
+        let mut values = vec![self.parse_value(field, op)?];
+        while self.peek().kind == TokenKind::Comma {
+            let comma = self.next();
+            if !op.is_equality() {
+                return Err(QueryError::new(
+                    format!("'{}' does not accept a list of values", op.as_str()),
+                    comma.position,
+                ));
+            }
+            values.push(self.parse_value(field, op)?);
+        }
+
+        Ok(Expr::Condition(Condition { field, op, values }))
+    }
+
+    fn parse_value(&mut self, field: Field, op: CompareOp) -> Result<Value, QueryError> {
+        let token = self.next();
+        let text = match token.kind {
+            TokenKind::Word(text) | TokenKind::Quoted(text) => text,
+            kind => {
+                return Err(QueryError::new(
+                    format!(
+                        "Expected a value for '{}', found {}",
+                        field.as_str(),
+                        kind.describe()
+                    ),
+                    token.position,
+                ))
+            },
+        };
+
+        typed_value(field, op, &text).map_err(|message| QueryError::new(message, token.position))
+    }
+}
+
+/// Interpret `text` as a value of `field`
+fn typed_value(field: Field, op: CompareOp, text: &str) -> Result<Value, String> {
+    let normalized: String = text
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .collect();
+    let is_empty = normalized == "none" || normalized == "empty";
+
+    if is_empty {
+        return match field {
+            Field::Assignee | Field::Label | Field::Due if op.is_equality() => Ok(Value::Empty),
+            Field::Assignee | Field::Label | Field::Due => {
+                Err("'none' can only be compared with ':', '=' or '!='".to_string())
+            },
+            _ => Err(format!("'{}' always has a value", field.as_str())),
+        };
+    }
+
+    match field {
+        Field::Status => {
+            if normalized.chars().all(|c| c.is_ascii_alphanumeric()) {
+                Ok(Value::Status(normalize_status_key(text)))
+            } else {
+                Err(format!("Invalid status '{}'", text))
+            }
+        },
+        Field::Category => match normalized.as_str() {
+            "todo" => Ok(Value::Category(StatusCategory::Todo)),
+            "inprogress" => Ok(Value::Category(StatusCategory::InProgress)),
+            "done" => Ok(Value::Category(StatusCategory::Done)),
+            _ => Err(format!("Unknown status category '{}'", text)),
+        },
+        Field::Priority => match normalized.as_str() {
+            "low" => Ok(Value::Priority(Priority::Low)),
+            "medium" => Ok(Value::Priority(Priority::Medium)),
+            "high" => Ok(Value::Priority(Priority::High)),
+            "critical" => Ok(Value::Priority(Priority::Critical)),
+            _ => Err(format!("Unknown priority '{}'", text)),
+        },
+        Field::Type => match normalized.as_str() {
+            "task" => Ok(Value::Type(TicketType::Task)),
+            "bug" => Ok(Value::Type(TicketType::Bug)),
+            "feature" => Ok(Value::Type(TicketType::Feature)),
+            "epic" => Ok(Value::Type(TicketType::Epic)),
+            "story" => Ok(Value::Type(TicketType::Story)),
+            "subtask" => Ok(Value::Type(TicketType::Subtask)),
+            _ => Err(format!("Unknown ticket type '{}'", text)),
+        },
+        Field::Assignee | Field::Creator => Ok(Value::User(if normalized == "me" {
+            UserRef::Me
+        } else if let Ok(id) = Uuid::parse_str(text) {
+            UserRef::Id(UserId::from_uuid(id))
+        } else {
+            UserRef::Username(text.to_string())
+        })),
+        Field::Project => Ok(Value::Project(match Uuid::parse_str(text) {
+            Ok(id) => ProjectRef::Id(ProjectId::from_uuid(id)),
+            Err(_) => ProjectRef::Name(text.to_string()),
+        })),
+        Field::Label => Ok(Value::Label(text.to_string())),
+        Field::Due | Field::Created | Field::Updated => parse_date(text).map(Value::Date),
+    }
+}
+
+/// Longest relative offset accepted, about a hundred years either way
+const MAX_RELATIVE_DAYS: i64 = 36_525;
+
+/// Parse `today`, a relative offset (`7d`, `-2w`, `+12h`), a date
+/// (`2025-01-31`) or an RFC 3339 timestamp
+fn parse_date(text: &str) -> Result<DateSpec, String> {
+    let invalid = || {
+        format!(
+            "Invalid date '{}'; use e.g. today, 7d, -2w, 12h or 2025-01-31",
+            text
+        )
+    };
+
+    if text.eq_ignore_ascii_case("today") {
+        return Ok(DateSpec::Today);
+    }
+
+    if let Ok(day) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Ok(DateSpec::Day(day));
+    }
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Ok(DateSpec::Instant(at.with_timezone(&Utc)));
+    }
+
+    let unit = text.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = text[..text.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    let offset = match unit.to_ascii_lowercase() {
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => return Err(invalid()),
+    };
+
+    match offset {
+        Some(offset) if offset.num_days().abs() <= MAX_RELATIVE_DAYS => {
+            Ok(DateSpec::Relative(offset))
+        },
+        _ => Err(format!(
+            "Date offset '{}' is out of range; offsets go up to {} days",
+            text, MAX_RELATIVE_DAYS
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: Field, op: CompareOp, values: Vec<Value>) -> Expr {
+        Expr::Condition(Condition { field, op, values })
+    }
+
+    #[test]
+    fn test_parse_comparisons_with_implicit_and() {
+        let expr = parse("status:open assignee:me priority>=high").unwrap();
+
+        assert_eq!(
+            expr,
+            Expr::And(
+                Box::new(Expr::And(
+                    Box::new(condition(
+                        Field::Status,
+                        CompareOp::Eq,
+                        vec![Value::Status("open".to_string())]
+                    )),
+                    Box::new(condition(
+                        Field::Assignee,
+                        CompareOp::Eq,
+                        vec![Value::User(UserRef::Me)]
+                    )),
+                )),
+                Box::new(condition(
+                    Field::Priority,
+                    CompareOp::Ge,
+                    vec![Value::Priority(Priority::High)]
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_boolean_operators_and_precedence() {
+        let expr = parse("type:bug OR NOT (status:done,closed AND -\"flaky test\")").unwrap();
+
+        let Expr::Or(left, right) = expr else {
+            panic!("expected OR at the top level");
+        };
+        assert_eq!(
+            *left,
+            condition(
+                Field::Type,
+                CompareOp::Eq,
+                vec![Value::Type(TicketType::Bug)]
+            )
+        );
+
+        let Expr::Not(inner) = *right else {
+            panic!("expected NOT");
+        };
+        assert_eq!(
+            *inner,
+            Expr::And(
+                Box::new(condition(
+                    Field::Status,
+                    CompareOp::Eq,
+                    vec![
+                        Value::Status("done".to_string()),
+                        Value::Status("closed".to_string())
+                    ]
+                )),
+                Box::new(Expr::Not(Box::new(Expr::Text("flaky test".to_string())))),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_dates_and_empty_values() {
+        assert_eq!(
+            parse("due<7d").unwrap(),
+            condition(
+                Field::Due,
+                CompareOp::Lt,
+                vec![Value::Date(DateSpec::Relative(Duration::days(7)))]
+            )
+        );
+        assert_eq!(
+            parse("created>=-2w").unwrap(),
+            condition(
+                Field::Created,
+                CompareOp::Ge,
+                vec![Value::Date(DateSpec::Relative(Duration::weeks(-2)))]
+            )
+        );
+        assert_eq!(
+            parse("assignee!=none").unwrap(),
+            condition(Field::Assignee, CompareOp::Ne, vec![Value::Empty])
+        );
+
+        let day = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        assert_eq!(
+            parse("due:2025-01-31").unwrap(),
+            condition(
+                Field::Due,
+                CompareOp::Eq,
+                vec![Value::Date(DateSpec::Day(day))]
+            )
+        );
+
+        let now = Utc::now();
+        let (start, end) = DateSpec::Day(day).bounds(now);
+        assert_eq!(end - start, Duration::days(1));
+        let (start, end) = DateSpec::Relative(Duration::days(1)).bounds(now);
+        assert_eq!(start, end);
+
+        // Days at the end of the calendar do not overflow
+        let (start, end) = DateSpec::Day(NaiveDate::MAX).bounds(now);
+        assert!(start < end);
+    }
+
+    #[test]
+    fn test_oversized_date_offset_is_an_error() {
+        let err = parse("due<1000000000d").unwrap_err();
+        assert_eq!(err.position, 4);
+        assert!(err.message.contains("out of range"), "{}", err.message);
+
+        assert!(parse("created>-100000w").is_err());
+        assert!(parse("due<36525d").is_ok());
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        assert_eq!(
+            parse("label:frontend,\"needs review\"").unwrap(),
+            condition(
+                Field::Label,
+                CompareOp::Eq,
+                vec![
+                    Value::Label("frontend".to_string()),
+                    Value::Label("needs review".to_string())
+                ]
+            )
+        );
+        assert_eq!(
+            parse("-label:none").unwrap(),
+            Expr::Not(Box::new(condition(
+                Field::Label,
+                CompareOp::Eq,
+                vec![Value::Empty]
+            )))
+        );
+        assert!(parse("label>ux").is_err());
+    }
+
+    #[test]
+    fn test_parse_statuses_and_categories() {
+        assert_eq!(
+            parse("status:In_Progress").unwrap(),
+            condition(
+                Field::Status,
+                CompareOp::Eq,
+                vec![Value::Status("inprogress".to_string())]
+            )
+        );
+        assert_eq!(
+            parse("category<done").unwrap(),
+            condition(
+                Field::Category,
+                CompareOp::Lt,
+                vec![Value::Category(StatusCategory::Done)]
+            )
+        );
+        assert!(parse("status>open").is_err());
+    }
+
+    #[test]
+    fn test_free_text() {
+        assert_eq!(
+            parse("login crash").unwrap(),
+            Expr::And(
+                Box::new(Expr::Text("login".to_string())),
+                Box::new(Expr::Text("crash".to_string())),
+            )
+        );
+        assert_eq!(
+            parse("\"say \\\"hi\\\"\"").unwrap(),
+            Expr::Text("say \"hi\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_errors_report_positions() {
+        let cases = [
+            ("", 0),
+            ("colour:red", 0),
+            ("category:opne", 9),
+            ("status:\"in review?\"", 7),
+            ("type>bug", 4),
+            ("priority<high,low", 13),
+            ("(status:open", 0),
+            ("status:open)", 11),
+            ("due<soon", 4),
+            ("status:", 7),
+            ("a OR", 4),
+            ("\"unterminated", 0),
+            ("status!open", 6),
+        ];
+
+        for (query, position) in cases {
+            let err = parse(query).unwrap_err();
+            assert_eq!(err.position, position, "{}: {}", query, err);
+        }
+    }
+}
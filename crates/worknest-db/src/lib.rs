@@ -14,6 +14,13 @@ pub use connection::{init_memory_pool, init_pool, DbConnection, DbPool};
 pub use error::{DbError, Result};
 pub use migrations::run_migrations;
 pub use repositories::{
-    AttachmentRepository, CommentRepository, ProjectRepository, TicketRepository, UserRepository,
+    AccessTokenRepository, ActivityFilter, ActivityRepository, AttachmentRepository,
+    CodeLinkRepository, CommentRepository, CustomFieldRepository, DependencyRepository,
+    LabelRepository, NotificationPreferenceRepository, NotificationRepository,
+    PermissionRepository, ProjectRepository, RoleRepository, SavedViewRepository,
+    SessionRepository, SortDirection, SprintRepository, TeamRepository, TicketCursor, TicketPage,
+    TicketQuery, TicketRepository, TicketSearchHit, TicketSort, TicketSortField, UserRepository,
+    WatcherRepository, WebhookDeliveryRepository, WebhookRepository, WorkflowRepository,
+    WorklogRepository,
 };
 pub use repository::Repository;
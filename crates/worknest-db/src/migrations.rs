@@ -42,4 +42,106 @@ mod tests {
         assert!(tables.contains(&"comments".to_string()));
         assert!(tables.contains(&"sessions".to_string()));
     }
+
+    #[test]
+    fn test_workflow_migration_maps_existing_statuses() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+
+        migrations::runner()
+            .set_target(refinery::Target::Version(11))
+            .run(&mut conn)
+            .unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO users (id, username, email, password_hash, created_at, updated_at)
+             VALUES ('u1', 'alice', 'alice@example.com', 'hash', '2025-01-01', '2025-01-01');
+             INSERT INTO projects (id, name, created_by, created_at, updated_at)
+             VALUES ('p1', 'Legacy', 'u1', '2025-01-01', '2025-01-01');
+             INSERT INTO tickets (id, project_id, title, ticket_type, status, priority, created_by,
+                                  created_at, updated_at)
+             VALUES ('t1', 'p1', 'Waiting', 'Task', 'Review', 'Low', 'u1', '2025-01-01', '2025-01-01'),
+                    ('t2', 'p1', 'Finished', 'Task', 'Closed', 'Low', 'u1', '2025-01-01', '2025-01-01');",
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let tickets: Vec<(String, String)> = conn
+            .prepare("SELECT status, status_category FROM tickets ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            tickets,
+            [
+                ("review".to_string(), "in_progress".to_string()),
+                ("closed".to_string(), "done".to_string())
+            ]
+        );
+
+        let (statuses, transitions): (i64, i64) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM workflow_statuses WHERE project_id = 'p1'),
+                        (SELECT COUNT(*) FROM workflow_transitions WHERE project_id = 'p1')",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((statuses, transitions), (5, 20));
+    }
+
+    #[test]
+    fn test_ticket_number_migration_numbers_existing_tickets() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+
+        migrations::runner()
+            .set_target(refinery::Target::Version(14))
+            .run(&mut conn)
+            .unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO users (id, username, email, password_hash, created_at, updated_at)
+             VALUES ('u1', 'alice', 'alice@example.com', 'hash', '2025-01-01', '2025-01-01');
+             INSERT INTO projects (id, name, created_by, created_at, updated_at)
+             VALUES ('p1', 'Worknest', 'u1', '2025-01-01', '2025-01-01'),
+                    ('p2', 'work items', 'u1', '2025-01-01', '2025-01-01'),
+                    ('p3', '42', 'u1', '2025-01-01', '2025-01-01');
+             INSERT INTO tickets (id, project_id, title, ticket_type, status, priority, created_by,
+                                  status_category, created_at, updated_at)
+             VALUES ('t1', 'p1', 'Second', 'Task', 'open', 'Low', 'u1', 'todo', '2025-01-02', '2025-01-02'),
+                    ('t2', 'p1', 'First', 'Task', 'open', 'Low', 'u1', 'todo', '2025-01-01', '2025-01-01');",
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let projects: Vec<(String, i64)> = conn
+            .prepare("SELECT key, next_ticket_number FROM projects ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            projects,
+            [
+                ("WOR".to_string(), 3),
+                ("WOR2".to_string(), 1),
+                ("PRJ".to_string(), 1)
+            ]
+        );
+
+        let numbers: Vec<i64> = conn
+            .prepare("SELECT number FROM tickets ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(numbers, [2, 1]);
+    }
 }
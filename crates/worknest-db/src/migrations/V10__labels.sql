@@ -0,0 +1,23 @@
+-- Project-scoped ticket labels. Tickets refer to labels by id through
+-- ticket_labels, so renaming a label renames it on every ticket.
+
+CREATE TABLE labels (
+    id TEXT PRIMARY KEY NOT NULL,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    color TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (project_id, name),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE TABLE ticket_labels (
+    ticket_id TEXT NOT NULL,
+    label_id TEXT NOT NULL,
+    PRIMARY KEY (ticket_id, label_id),
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_ticket_labels_label_id ON ticket_labels(label_id);
@@ -0,0 +1,35 @@
+-- Project-defined custom fields. Values live in typed columns, one row per
+-- value (one per selected option for multi-select fields), so tickets can be
+-- filtered by them with plain indexed comparisons.
+
+CREATE TABLE custom_fields (
+    id TEXT PRIMARY KEY NOT NULL,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    field_type TEXT NOT NULL,
+    options TEXT NOT NULL DEFAULT '[]',
+    required INTEGER NOT NULL DEFAULT 0,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (project_id, name),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE TABLE ticket_field_values (
+    ticket_id TEXT NOT NULL,
+    field_id TEXT NOT NULL,
+    value_text TEXT,
+    value_number REAL,
+    value_date TEXT,
+    value_user TEXT,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (field_id) REFERENCES custom_fields(id) ON DELETE CASCADE,
+    FOREIGN KEY (value_user) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_ticket_field_values_ticket_id ON ticket_field_values(ticket_id);
+CREATE INDEX idx_ticket_field_values_text ON ticket_field_values(field_id, value_text);
+CREATE INDEX idx_ticket_field_values_number ON ticket_field_values(field_id, value_number);
+CREATE INDEX idx_ticket_field_values_date ON ticket_field_values(field_id, value_date);
+CREATE INDEX idx_ticket_field_values_user ON ticket_field_values(field_id, value_user);
@@ -0,0 +1,62 @@
+-- Per-project workflows. Tickets store the key of their workflow status plus
+-- a denormalized copy of its category, so boards and filters that only care
+-- whether work is finished do not need to join the workflow tables.
+
+CREATE TABLE workflow_statuses (
+    project_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (project_id, key),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE TABLE workflow_transitions (
+    project_id TEXT NOT NULL,
+    from_key TEXT NOT NULL,
+    to_key TEXT NOT NULL,
+    PRIMARY KEY (project_id, from_key, to_key),
+    FOREIGN KEY (project_id, from_key) REFERENCES workflow_statuses(project_id, key) ON DELETE CASCADE,
+    FOREIGN KEY (project_id, to_key) REFERENCES workflow_statuses(project_id, key) ON DELETE CASCADE
+);
+
+ALTER TABLE tickets ADD COLUMN status_category TEXT NOT NULL DEFAULT 'todo';
+
+-- Map the old fixed statuses onto the keys of the default workflow
+UPDATE tickets SET status = CASE status
+    WHEN 'Open' THEN 'open'
+    WHEN 'InProgress' THEN 'in_progress'
+    WHEN 'Review' THEN 'review'
+    WHEN 'Done' THEN 'done'
+    WHEN 'Closed' THEN 'closed'
+    ELSE 'open'
+END;
+
+UPDATE tickets SET status_category = CASE status
+    WHEN 'in_progress' THEN 'in_progress'
+    WHEN 'review' THEN 'in_progress'
+    WHEN 'done' THEN 'done'
+    WHEN 'closed' THEN 'done'
+    ELSE 'todo'
+END;
+
+CREATE INDEX idx_tickets_status_category ON tickets(project_id, status_category);
+
+-- Give every existing project the default workflow, with every transition
+-- allowed
+INSERT INTO workflow_statuses (project_id, key, name, category, position)
+SELECT p.id, s.key, s.name, s.category, s.position
+FROM projects p
+CROSS JOIN (
+    SELECT 'open' AS key, 'Open' AS name, 'todo' AS category, 0 AS position
+    UNION ALL SELECT 'in_progress', 'In Progress', 'in_progress', 1
+    UNION ALL SELECT 'review', 'Review', 'in_progress', 2
+    UNION ALL SELECT 'done', 'Done', 'done', 3
+    UNION ALL SELECT 'closed', 'Closed', 'done', 4
+) s;
+
+INSERT INTO workflow_transitions (project_id, from_key, to_key)
+SELECT a.project_id, a.key, b.key
+FROM workflow_statuses a
+JOIN workflow_statuses b ON b.project_id = a.project_id AND b.key <> a.key;
@@ -0,0 +1,24 @@
+-- Project sprints. Tickets point at the sprint they are planned into; tickets
+-- without one are the project's backlog.
+
+CREATE TABLE sprints (
+    id TEXT PRIMARY KEY NOT NULL,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    goal TEXT,
+    start_date TEXT,
+    end_date TEXT,
+    state TEXT NOT NULL DEFAULT 'planned',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_sprints_project_id ON sprints(project_id);
+
+-- At most one active sprint per project
+CREATE UNIQUE INDEX idx_sprints_one_active ON sprints(project_id) WHERE state = 'active';
+
+ALTER TABLE tickets ADD COLUMN sprint_id TEXT REFERENCES sprints(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tickets_sprint_id ON tickets(sprint_id);
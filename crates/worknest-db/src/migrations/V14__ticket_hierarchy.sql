@@ -0,0 +1,6 @@
+-- Parent-child links between tickets. Children of a deleted ticket become
+-- top-level tickets.
+
+ALTER TABLE tickets ADD COLUMN parent_id TEXT REFERENCES tickets(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tickets_parent_id ON tickets(parent_id);
@@ -0,0 +1,48 @@
+-- Human-readable ticket references: every project gets a unique key and every
+-- ticket a number within its project, as in WN-123.
+
+ALTER TABLE projects ADD COLUMN key TEXT;
+ALTER TABLE projects ADD COLUMN next_ticket_number INTEGER NOT NULL DEFAULT 1;
+
+-- Existing projects get the first three letters of their name, numbered when
+-- several projects share them
+UPDATE projects SET key = (
+    CASE WHEN name GLOB '[A-Za-z][A-Za-z0-9][A-Za-z0-9]*'
+         THEN upper(substr(name, 1, 3)) ELSE 'PRJ' END
+) || (
+    SELECT CASE WHEN COUNT(*) = 0 THEN '' ELSE COUNT(*) + 1 END
+    FROM projects p
+    WHERE p.rowid < projects.rowid
+      AND (CASE WHEN p.name GLOB '[A-Za-z][A-Za-z0-9][A-Za-z0-9]*'
+                THEN upper(substr(p.name, 1, 3)) ELSE 'PRJ' END)
+        = (CASE WHEN projects.name GLOB '[A-Za-z][A-Za-z0-9][A-Za-z0-9]*'
+                THEN upper(substr(projects.name, 1, 3)) ELSE 'PRJ' END)
+);
+
+CREATE UNIQUE INDEX idx_projects_key ON projects(key);
+
+-- Keys a project was known by before being renamed, so old references keep
+-- resolving
+CREATE TABLE project_key_aliases (
+    key TEXT PRIMARY KEY NOT NULL,
+    project_id TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_key_aliases_project_id ON project_key_aliases(project_id);
+
+ALTER TABLE tickets ADD COLUMN number INTEGER;
+
+-- Existing tickets are numbered in creation order
+UPDATE tickets SET number = (
+    SELECT COUNT(*) FROM tickets t
+    WHERE t.project_id = tickets.project_id
+      AND (t.created_at < tickets.created_at
+           OR (t.created_at = tickets.created_at AND t.rowid <= tickets.rowid))
+);
+
+UPDATE projects SET next_ticket_number = (
+    SELECT COUNT(*) + 1 FROM tickets WHERE tickets.project_id = projects.id
+);
+
+CREATE UNIQUE INDEX idx_tickets_project_number ON tickets(project_id, number);
@@ -0,0 +1,17 @@
+-- Time spent on tickets. Durations are whole minutes.
+
+CREATE TABLE worklogs (
+    id TEXT PRIMARY KEY NOT NULL,
+    ticket_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    duration_minutes INTEGER NOT NULL CHECK (duration_minutes > 0),
+    note TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_worklogs_ticket_id ON worklogs(ticket_id);
+CREATE INDEX idx_worklogs_user_started ON worklogs(user_id, started_at);
@@ -0,0 +1,16 @@
+-- Per-user choices of which notifications are also sent by email, and when
+-- the daily digest goes out. Users without a row get the defaults.
+
+CREATE TABLE notification_preferences (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    email_assigned INTEGER NOT NULL DEFAULT 1,
+    email_mentioned INTEGER NOT NULL DEFAULT 1,
+    email_status_changed INTEGER NOT NULL DEFAULT 1,
+    daily_digest INTEGER NOT NULL DEFAULT 0,
+    digest_hour INTEGER NOT NULL DEFAULT 8 CHECK (digest_hour BETWEEN 0 AND 23),
+    last_digest_at TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_notification_preferences_digest ON notification_preferences(daily_digest);
@@ -0,0 +1,19 @@
+-- Commits and pull requests that refer to tickets, reported by Git forge
+-- webhooks. A ticket keeps one row per commit or pull request, refreshed
+-- whenever the forge reports it again.
+
+CREATE TABLE code_links (
+    id TEXT PRIMARY KEY NOT NULL,
+    ticket_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('commit', 'pull_request')),
+    repository TEXT NOT NULL,
+    reference TEXT NOT NULL,
+    title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    author TEXT,
+    state TEXT CHECK (state IN ('open', 'merged', 'closed')),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    UNIQUE (ticket_id, kind, repository, reference)
+);
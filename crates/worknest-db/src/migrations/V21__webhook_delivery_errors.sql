@@ -0,0 +1,7 @@
+-- Webhook delivery errors no longer quote the receiver's response body.
+-- Drop the bodies already stored, keeping the status.
+
+UPDATE webhook_deliveries
+SET error = 'Receiver answered with status ' || response_code
+WHERE response_code IS NOT NULL
+  AND error LIKE 'Receiver answered with status %: %';
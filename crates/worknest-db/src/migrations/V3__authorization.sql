@@ -0,0 +1,15 @@
+-- Authorization: backfill role assignments for users and projects created
+-- before permissions were enforced by the API.
+
+-- Every existing user becomes a global Member
+INSERT OR IGNORE INTO user_roles (user_id, role_id, project_id, assigned_at)
+SELECT id, 'role_member', '', strftime('%Y-%m-%dT%H:%M:%SZ', 'now') FROM users;
+
+-- Project creators administer the projects they created
+INSERT OR IGNORE INTO user_roles (user_id, role_id, project_id, assigned_at)
+SELECT created_by, 'role_admin', id, strftime('%Y-%m-%dT%H:%M:%SZ', 'now') FROM projects;
+
+-- The earliest registered user becomes the global administrator
+INSERT OR IGNORE INTO user_roles (user_id, role_id, project_id, assigned_at)
+SELECT id, 'role_admin', '', strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+FROM users ORDER BY created_at LIMIT 1;
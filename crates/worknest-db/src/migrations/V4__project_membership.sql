@@ -0,0 +1,7 @@
+-- Project membership: private projects are only visible to their members.
+-- Members are the users holding a project-scoped role in user_roles.
+
+ALTER TABLE projects ADD COLUMN is_private INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_projects_is_private ON projects(is_private);
+CREATE INDEX idx_user_roles_project_user ON user_roles(project_id, user_id);
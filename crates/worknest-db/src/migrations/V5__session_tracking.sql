@@ -0,0 +1,5 @@
+-- Session tracking: sessions are created on login and can be revoked.
+-- The `token` column holds the `jti` of the token issued for the session.
+
+ALTER TABLE sessions ADD COLUMN user_agent TEXT;
+ALTER TABLE sessions ADD COLUMN revoked_at TEXT;
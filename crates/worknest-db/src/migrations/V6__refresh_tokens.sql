@@ -0,0 +1,14 @@
+-- Rotating refresh tokens. Each session owns a chain of single-use refresh
+-- tokens; only the SHA-256 hash of a token is stored. A token with `used_at`
+-- set has been exchanged and must not be accepted again.
+
+CREATE TABLE refresh_tokens (
+    token_hash TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    used_at TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_refresh_tokens_session_id ON refresh_tokens(session_id);
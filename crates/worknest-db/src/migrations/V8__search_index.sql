@@ -0,0 +1,76 @@
+-- Replace the external-content ticket search index with one that keeps its
+-- own copy of the text. Each row is keyed by the ticket's rowid and also
+-- covers the ticket's comment bodies and attachment filenames, which an
+-- external-content table over `tickets` cannot index.
+
+DROP TRIGGER IF EXISTS tickets_fts_insert;
+DROP TRIGGER IF EXISTS tickets_fts_update;
+DROP TRIGGER IF EXISTS tickets_fts_delete;
+DROP TABLE IF EXISTS tickets_fts;
+
+CREATE VIRTUAL TABLE tickets_fts USING fts5(
+    title,
+    description,
+    comments,
+    attachments,
+    tokenize = 'porter unicode61'
+);
+
+-- Index every existing ticket
+INSERT INTO tickets_fts(rowid, title, description, comments, attachments)
+SELECT t.rowid, t.title, COALESCE(t.description, ''),
+       COALESCE((SELECT group_concat(c.content, ' ') FROM comments c WHERE c.ticket_id = t.id), ''),
+       COALESCE((SELECT group_concat(a.filename, ' ') FROM attachments a WHERE a.ticket_id = t.id), '')
+FROM tickets t;
+
+-- Ticket triggers
+CREATE TRIGGER tickets_fts_insert AFTER INSERT ON tickets BEGIN
+    INSERT INTO tickets_fts(rowid, title, description, comments, attachments)
+    VALUES (new.rowid, new.title, COALESCE(new.description, ''), '', '');
+END;
+
+CREATE TRIGGER tickets_fts_update AFTER UPDATE OF title, description ON tickets BEGIN
+    UPDATE tickets_fts SET title = new.title, description = COALESCE(new.description, '')
+    WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER tickets_fts_delete AFTER DELETE ON tickets BEGIN
+    DELETE FROM tickets_fts WHERE rowid = old.rowid;
+END;
+
+-- Comment triggers re-collect all comment bodies of the affected ticket
+CREATE TRIGGER comments_fts_insert AFTER INSERT ON comments BEGIN
+    UPDATE tickets_fts
+    SET comments = COALESCE((SELECT group_concat(c.content, ' ') FROM comments c
+                             WHERE c.ticket_id = new.ticket_id), '')
+    WHERE rowid = (SELECT rowid FROM tickets WHERE id = new.ticket_id);
+END;
+
+CREATE TRIGGER comments_fts_update AFTER UPDATE OF content ON comments BEGIN
+    UPDATE tickets_fts
+    SET comments = COALESCE((SELECT group_concat(c.content, ' ') FROM comments c
+                             WHERE c.ticket_id = new.ticket_id), '')
+    WHERE rowid = (SELECT rowid FROM tickets WHERE id = new.ticket_id);
+END;
+
+CREATE TRIGGER comments_fts_delete AFTER DELETE ON comments BEGIN
+    UPDATE tickets_fts
+    SET comments = COALESCE((SELECT group_concat(c.content, ' ') FROM comments c
+                             WHERE c.ticket_id = old.ticket_id), '')
+    WHERE rowid = (SELECT rowid FROM tickets WHERE id = old.ticket_id);
+END;
+
+-- Attachment triggers re-collect all filenames of the affected ticket
+CREATE TRIGGER attachments_fts_insert AFTER INSERT ON attachments BEGIN
+    UPDATE tickets_fts
+    SET attachments = COALESCE((SELECT group_concat(a.filename, ' ') FROM attachments a
+                                WHERE a.ticket_id = new.ticket_id), '')
+    WHERE rowid = (SELECT rowid FROM tickets WHERE id = new.ticket_id);
+END;
+
+CREATE TRIGGER attachments_fts_delete AFTER DELETE ON attachments BEGIN
+    UPDATE tickets_fts
+    SET attachments = COALESCE((SELECT group_concat(a.filename, ' ') FROM attachments a
+                                WHERE a.ticket_id = old.ticket_id), '')
+    WHERE rowid = (SELECT rowid FROM tickets WHERE id = old.ticket_id);
+END;
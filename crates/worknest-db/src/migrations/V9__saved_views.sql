@@ -0,0 +1,21 @@
+-- Saved ticket views: a named query with its sort order and display mode.
+-- Personal views are visible to their owner; shared views to everyone who
+-- can read the tickets of their project.
+
+CREATE TABLE saved_views (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL,
+    project_id TEXT,
+    name TEXT NOT NULL,
+    query TEXT NOT NULL DEFAULT '',
+    sort TEXT NOT NULL DEFAULT '',
+    display TEXT NOT NULL DEFAULT 'list',
+    shared INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (owner_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_saved_views_owner_id ON saved_views(owner_id);
+CREATE INDEX idx_saved_views_project_id ON saved_views(project_id);
@@ -0,0 +1,258 @@
+//! Personal access token repository implementation
+
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{AccessTokenId, PersonalAccessToken, TokenScope, UserId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for the `personal_access_tokens` table
+pub struct AccessTokenRepository {
+    pool: Arc<DbPool>,
+}
+
+impl AccessTokenRepository {
+    /// Create a new AccessTokenRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Store a new personal access token
+    pub fn create(&self, token: &PersonalAccessToken) -> Result<PersonalAccessToken> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO personal_access_tokens
+                 (id, user_id, name, token_hash, scopes, expires_at, last_used_at, created_at, revoked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                token.id.0.to_string(),
+                token.user_id.0.to_string(),
+                token.name,
+                token.token_hash,
+                scopes_to_string(&token.scopes),
+                token.expires_at.map(|t| t.to_rfc3339()),
+                token.last_used_at.map(|t| t.to_rfc3339()),
+                token.created_at.to_rfc3339(),
+                token.revoked_at.map(|t| t.to_rfc3339()),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(token.clone())
+    }
+
+    /// Find a token by ID
+    pub fn find_by_id(&self, id: AccessTokenId) -> Result<Option<PersonalAccessToken>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, name, token_hash, scopes, expires_at, last_used_at, created_at, revoked_at
+                 FROM personal_access_tokens WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let token = stmt
+            .query_row(params![id.0.to_string()], row_to_token)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(token)
+    }
+
+    /// Find a token by the hash of its value
+    pub fn find_by_hash(&self, token_hash: &str) -> Result<Option<PersonalAccessToken>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, name, token_hash, scopes, expires_at, last_used_at, created_at, revoked_at
+                 FROM personal_access_tokens WHERE token_hash = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let token = stmt
+            .query_row(params![token_hash], row_to_token)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(token)
+    }
+
+    /// Find the tokens of a user that have not been revoked, newest first
+    pub fn find_by_user(&self, user_id: UserId) -> Result<Vec<PersonalAccessToken>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, name, token_hash, scopes, expires_at, last_used_at, created_at, revoked_at
+                 FROM personal_access_tokens
+                 WHERE user_id = ?1 AND revoked_at IS NULL
+                 ORDER BY created_at DESC",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let tokens = stmt
+            .query_map(params![user_id.0.to_string()], row_to_token)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(tokens)
+    }
+
+    /// Record that a token was just used
+    pub fn touch(&self, id: AccessTokenId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "UPDATE personal_access_tokens SET last_used_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Revoke a token
+    pub fn revoke(&self, id: AccessTokenId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE personal_access_tokens SET revoked_at = ?1
+                 WHERE id = ?2 AND revoked_at IS NULL",
+                params![Utc::now().to_rfc3339(), id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Access token not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Serialize scopes as a space-separated list
+fn scopes_to_string(scopes: &[TokenScope]) -> String {
+    scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Convert a database row to a PersonalAccessToken
+fn row_to_token(row: &Row) -> rusqlite::Result<PersonalAccessToken> {
+    let id_str: String = row.get(0)?;
+    let user_id_str: String = row.get(1)?;
+    let scopes_str: String = row.get(4)?;
+
+    Ok(PersonalAccessToken {
+        id: AccessTokenId(Uuid::parse_str(&id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        name: row.get(2)?,
+        token_hash: row.get(3)?,
+        scopes: scopes_str
+            .split_whitespace()
+            .filter_map(|name| TokenScope::from_name(name).ok())
+            .collect(),
+        expires_at: row.get(5)?,
+        last_used_at: row.get(6)?,
+        created_at: row.get(7)?,
+        revoked_at: row.get(8)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool, migrations::run_migrations,
+        repositories::user_repository::UserRepository,
+    };
+    use worknest_core::models::User;
+
+    fn setup_test_repo() -> (AccessTokenRepository, UserId) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        user_repo.create_with_password(&user, "hash").unwrap();
+
+        (AccessTokenRepository::new(pool), user.id)
+    }
+
+    #[test]
+    fn test_create_and_find_token() {
+        let (repo, user_id) = setup_test_repo();
+        let token = PersonalAccessToken::new(
+            user_id,
+            "CI".to_string(),
+            "hash".to_string(),
+            vec![TokenScope::TicketsRead, TokenScope::TicketsWrite],
+            None,
+        );
+
+        repo.create(&token).unwrap();
+
+        let found = repo.find_by_hash("hash").unwrap().unwrap();
+        assert_eq!(found.id, token.id);
+        assert_eq!(found.scopes, token.scopes);
+        assert!(repo.find_by_hash("other").unwrap().is_none());
+
+        repo.touch(token.id).unwrap();
+        assert!(repo
+            .find_by_id(token.id)
+            .unwrap()
+            .unwrap()
+            .last_used_at
+            .is_some());
+    }
+
+    #[test]
+    fn test_revoke_token() {
+        let (repo, user_id) = setup_test_repo();
+        let token = PersonalAccessToken::new(
+            user_id,
+            "CI".to_string(),
+            "hash".to_string(),
+            vec![TokenScope::TicketsRead],
+            None,
+        );
+        repo.create(&token).unwrap();
+        assert_eq!(repo.find_by_user(user_id).unwrap().len(), 1);
+
+        repo.revoke(token.id).unwrap();
+
+        assert!(repo.find_by_user(user_id).unwrap().is_empty());
+        assert!(!repo.find_by_hash("hash").unwrap().unwrap().is_active());
+        assert!(repo.revoke(token.id).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Activity log repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{Activity, ActivityAction, ActivityId, UserId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Filters for querying the activity log
+///
+/// Unset fields do not restrict the result. Entries are returned newest
+/// first.
+#[derive(Debug, Clone, Default)]
+pub struct ActivityFilter {
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+    pub user_id: Option<UserId>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Activity repository for the append-only `activity_log` table
+pub struct ActivityRepository {
+    pool: Arc<DbPool>,
+}
+
+impl ActivityRepository {
+    /// Create a new ActivityRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Record an activity entry
+    pub fn record(&self, activity: &Activity) -> Result<Activity> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO activity_log (id, user_id, action, resource_type, resource_id, details, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                activity.id.0.to_string(),
+                activity.user_id.0.to_string(),
+                activity.action.as_str(),
+                activity.resource_type,
+                activity.resource_id,
+                activity.details.as_ref().map(|d| d.to_string()),
+                activity.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(activity.clone())
+    }
+
+    /// Find activity entries matching a filter
+    pub fn find(&self, filter: &ActivityFilter) -> Result<Vec<Activity>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut conditions = Vec::new();
+        let mut values: Vec<SqlValue> = Vec::new();
+
+        if let Some(resource_type) = &filter.resource_type {
+            conditions.push("resource_type = ?");
+            values.push(SqlValue::Text(resource_type.clone()));
+        }
+        if let Some(resource_id) = &filter.resource_id {
+            conditions.push("resource_id = ?");
+            values.push(SqlValue::Text(resource_id.clone()));
+        }
+        if let Some(user_id) = filter.user_id {
+            conditions.push("user_id = ?");
+            values.push(SqlValue::Text(user_id.0.to_string()));
+        }
+        if let Some(since) = filter.since {
+            conditions.push("created_at >= ?");
+            values.push(SqlValue::Text(since.to_rfc3339()));
+        }
+        if let Some(until) = filter.until {
+            conditions.push("created_at < ?");
+            values.push(SqlValue::Text(until.to_rfc3339()));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        values.push(SqlValue::Integer(filter.limit.map(i64::from).unwrap_or(-1)));
+        values.push(SqlValue::Integer(filter.offset.map(i64::from).unwrap_or(0)));
+
+        let sql = format!(
+            "SELECT id, user_id, action, resource_type, resource_id, details, created_at
+             FROM activity_log {}
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ? OFFSET ?",
+            where_clause
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let activities = stmt
+            .query_map(params_from_iter(values), row_to_activity)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(activities)
+    }
+
+    /// Find the history of a single resource, newest first
+    pub fn find_for_resource(
+        &self,
+        resource_type: &str,
+        resource_id: &str,
+    ) -> Result<Vec<Activity>> {
+        self.find(&ActivityFilter {
+            resource_type: Some(resource_type.to_string()),
+            resource_id: Some(resource_id.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+/// Convert a database row to an Activity
+fn row_to_activity(row: &Row) -> rusqlite::Result<Activity> {
+    let id_str: String = row.get(0)?;
+    let user_id_str: String = row.get(1)?;
+    let action_str: String = row.get(2)?;
+    let details: Option<String> = row.get(5)?;
+
+    Ok(Activity {
+        id: ActivityId(Uuid::parse_str(&id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        action: ActivityAction::from_name(&action_str).unwrap_or(ActivityAction::Updated),
+        resource_type: row.get(3)?,
+        resource_id: row.get(4)?,
+        details: details.and_then(|d| serde_json::from_str(&d).ok()),
+        created_at: row.get(6)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool, migrations::run_migrations,
+        repositories::user_repository::UserRepository,
+    };
+    use chrono::Duration;
+    use worknest_core::models::User;
+
+    fn setup_test_repo() -> (ActivityRepository, UserId, UserId) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let alice = User::new("alice".to_string(), "alice@example.com".to_string());
+        let bob = User::new("bob".to_string(), "bob@example.com".to_string());
+        user_repo.create_with_password(&alice, "hash").unwrap();
+        user_repo.create_with_password(&bob, "hash").unwrap();
+
+        (ActivityRepository::new(pool), alice.id, bob.id)
+    }
+
+    #[test]
+    fn test_record_and_find_for_resource() {
+        let (repo, alice, _) = setup_test_repo();
+        let details = serde_json::json!({"status": {"from": "Open", "to": "Done"}});
+
+        repo.record(&Activity::new(
+            alice,
+            ActivityAction::Created,
+            "ticket",
+            "t1".to_string(),
+            None,
+        ))
+        .unwrap();
+        repo.record(&Activity::new(
+            alice,
+            ActivityAction::Updated,
+            "ticket",
+            "t1".to_string(),
+            Some(details.clone()),
+        ))
+        .unwrap();
+
+        let history = repo.find_for_resource("ticket", "t1").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].action, ActivityAction::Updated);
+        assert_eq!(history[0].details, Some(details));
+        assert_eq!(history[1].action, ActivityAction::Created);
+
+        assert!(repo.find_for_resource("ticket", "t2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_with_filters() {
+        let (repo, alice, bob) = setup_test_repo();
+
+        let mut old = Activity::new(
+            alice,
+            ActivityAction::Created,
+            "project",
+            "p1".to_string(),
+            None,
+        );
+        old.created_at = Utc::now() - Duration::days(10);
+        repo.record(&old).unwrap();
+        repo.record(&Activity::new(
+            bob,
+            ActivityAction::Deleted,
+            "comment",
+            "c1".to_string(),
+            None,
+        ))
+        .unwrap();
+
+        assert_eq!(repo.find(&ActivityFilter::default()).unwrap().len(), 2);
+
+        let by_user = ActivityFilter {
+            user_id: Some(bob),
+            ..Default::default()
+        };
+        assert_eq!(repo.find(&by_user).unwrap()[0].resource_type, "comment");
+
+        let recent = ActivityFilter {
+            since: Some(Utc::now() - Duration::days(1)),
+            ..Default::default()
+        };
+        assert_eq!(repo.find(&recent).unwrap().len(), 1);
+
+        let by_type = ActivityFilter {
+            resource_type: Some("project".to_string()),
+            until: Some(Utc::now()),
+            ..Default::default()
+        };
+        assert_eq!(repo.find(&by_type).unwrap()[0].resource_id, "p1");
+
+        let paged = ActivityFilter {
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(repo.find(&paged).unwrap()[0].resource_id, "p1");
+    }
+}
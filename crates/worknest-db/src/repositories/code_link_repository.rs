@@ -0,0 +1,238 @@
+//! Code link repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{CodeLink, CodeLinkId, CodeLinkKind, PullRequestState, TicketId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for the commits and pull requests linked to tickets
+pub struct CodeLinkRepository {
+    pool: Arc<DbPool>,
+}
+
+impl CodeLinkRepository {
+    /// Create a new CodeLinkRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Store a link, or refresh the title, url, author and state of the
+    /// ticket's existing link to the same commit or pull request
+    ///
+    /// A link without an author keeps the one already stored. Returns the
+    /// stored link, which keeps the id and creation time of an existing one.
+    pub fn upsert(&self, link: &CodeLink) -> Result<CodeLink> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO code_links (id, ticket_id, kind, repository, reference, title, url,
+                 author, state, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT (ticket_id, kind, repository, reference) DO UPDATE SET
+                 title = excluded.title, url = excluded.url,
+                 author = COALESCE(excluded.author, author),
+                 state = excluded.state, updated_at = excluded.updated_at",
+            params![
+                link.id.0.to_string(),
+                link.ticket_id.0.to_string(),
+                link.kind.as_str(),
+                link.repository,
+                link.reference,
+                link.title,
+                link.url,
+                link.author,
+                link.state.map(|s| s.as_str()),
+                link.created_at.to_rfc3339(),
+                link.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+
+        conn.query_row(
+            "SELECT id, ticket_id, kind, repository, reference, title, url, author, state,
+                    created_at, updated_at
+             FROM code_links
+             WHERE ticket_id = ?1 AND kind = ?2 AND repository = ?3 AND reference = ?4",
+            params![
+                link.ticket_id.0.to_string(),
+                link.kind.as_str(),
+                link.repository,
+                link.reference,
+            ],
+            row_to_code_link,
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+
+    /// Find the links of a ticket: pull requests first, then commits, the
+    /// newest first within each
+    pub fn find_by_ticket(&self, ticket_id: TicketId) -> Result<Vec<CodeLink>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, ticket_id, kind, repository, reference, title, url, author, state,
+                        created_at, updated_at
+                 FROM code_links
+                 WHERE ticket_id = ?1
+                 ORDER BY kind = 'commit', created_at DESC, rowid DESC",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let links = stmt
+            .query_map(params![ticket_id.0.to_string()], row_to_code_link)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(links)
+    }
+}
+
+/// Convert a database row to a CodeLink
+fn row_to_code_link(row: &Row) -> rusqlite::Result<CodeLink> {
+    let id_str: String = row.get(0)?;
+    let ticket_id_str: String = row.get(1)?;
+    let kind_str: String = row.get(2)?;
+    let state_str: Option<String> = row.get(8)?;
+    let created_at: String = row.get(9)?;
+    let updated_at: String = row.get(10)?;
+
+    let parse_time = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+    Ok(CodeLink {
+        id: CodeLinkId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        ticket_id: TicketId::from_uuid(Uuid::parse_str(&ticket_id_str).unwrap()),
+        kind: CodeLinkKind::from_name(&kind_str).unwrap_or(CodeLinkKind::Commit),
+        repository: row.get(3)?,
+        reference: row.get(4)?,
+        title: row.get(5)?,
+        url: row.get(6)?,
+        author: row.get(7)?,
+        state: state_str.and_then(|s| PullRequestState::from_name(&s).ok()),
+        created_at: parse_time(&created_at),
+        updated_at: parse_time(&updated_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+        Repository,
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (CodeLinkRepository, TicketRepository, Ticket) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+        let ticket = tickets
+            .create(&Ticket::new(
+                project.id,
+                "Login crash".to_string(),
+                TicketType::Bug,
+                user.id,
+            ))
+            .unwrap();
+
+        (CodeLinkRepository::new(pool), tickets, ticket)
+    }
+
+    fn pull_request(ticket: &Ticket, state: PullRequestState) -> CodeLink {
+        CodeLink::pull_request(
+            ticket.id,
+            "acme/worknest".to_string(),
+            42,
+            "Fix login crash",
+            "https://git.example.com/acme/worknest/pull/42".to_string(),
+            state,
+        )
+    }
+
+    #[test]
+    fn test_upsert_refreshes_existing_link() {
+        let (repo, _, ticket) = setup();
+
+        let opened = repo
+            .upsert(&pull_request(&ticket, PullRequestState::Open))
+            .unwrap();
+
+        let mut merged = pull_request(&ticket, PullRequestState::Merged);
+        merged.author = Some("octocat".to_string());
+        let stored = repo.upsert(&merged).unwrap();
+
+        assert_eq!(stored.id, opened.id);
+        assert_eq!(stored.created_at, opened.created_at);
+        assert_eq!(stored.state, Some(PullRequestState::Merged));
+        assert_eq!(stored.author.as_deref(), Some("octocat"));
+
+        let closed = pull_request(&ticket, PullRequestState::Closed);
+        let stored = repo.upsert(&closed).unwrap();
+        assert_eq!(stored.author.as_deref(), Some("octocat"));
+        assert_eq!(repo.find_by_ticket(ticket.id).unwrap(), vec![stored]);
+    }
+
+    #[test]
+    fn test_find_by_ticket_lists_pull_requests_first() {
+        let (repo, _, ticket) = setup();
+
+        let commit = repo
+            .upsert(&CodeLink::commit(
+                ticket.id,
+                "acme/worknest".to_string(),
+                "9fceb02d0ae598e95dc970b74767f19372d61af8".to_string(),
+                "Fix login crash",
+                "https://git.example.com/acme/worknest/commit/9fceb02".to_string(),
+            ))
+            .unwrap();
+        let pr = repo
+            .upsert(&pull_request(&ticket, PullRequestState::Open))
+            .unwrap();
+
+        let links = repo.find_by_ticket(ticket.id).unwrap();
+        assert_eq!(
+            links.iter().map(|l| l.id).collect::<Vec<_>>(),
+            vec![pr.id, commit.id]
+        );
+    }
+
+    #[test]
+    fn test_links_go_with_their_ticket() {
+        let (repo, tickets, ticket) = setup();
+        repo.upsert(&pull_request(&ticket, PullRequestState::Open))
+            .unwrap();
+
+        tickets.delete(ticket.id).unwrap();
+        assert!(repo.find_by_ticket(ticket.id).unwrap().is_empty());
+    }
+}
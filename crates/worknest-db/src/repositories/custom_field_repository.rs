@@ -0,0 +1,585 @@
+//! Custom field repository implementation
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{
+    CustomField, CustomFieldId, CustomFieldType, CustomFieldValue, FieldValue, ProjectId, TicketId,
+    UserId,
+};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Format used for date values in `ticket_field_values.value_date`
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Repository for the `custom_fields` table and the `ticket_field_values`
+/// they hold
+pub struct CustomFieldRepository {
+    pool: Arc<DbPool>,
+}
+
+impl CustomFieldRepository {
+    /// Create a new CustomFieldRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find all custom fields of a project, in display order
+    pub fn find_by_project(&self, project_id: ProjectId) -> Result<Vec<CustomField>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, field_type, options, required, position,
+                        created_at, updated_at
+                 FROM custom_fields WHERE project_id = ?1 ORDER BY position, name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let fields = stmt
+            .query_map(params![project_id.0.to_string()], row_to_field)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(fields)
+    }
+
+    /// Find the custom field values of several tickets, as `(ticket, value)`
+    /// pairs in field display order
+    pub fn find_values_for_tickets(
+        &self,
+        ticket_ids: &[TicketId],
+    ) -> Result<Vec<(TicketId, CustomFieldValue)>> {
+        if ticket_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let placeholders = vec!["?"; ticket_ids.len()].join(", ");
+        let sql = format!(
+            "SELECT v.ticket_id, v.field_id, f.field_type, v.value_text, v.value_number,
+                    v.value_date, v.value_user
+             FROM ticket_field_values v
+             JOIN custom_fields f ON f.id = v.field_id
+             WHERE v.ticket_id IN ({})
+             ORDER BY v.ticket_id, f.position, f.name, v.rowid",
+            placeholders
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let rows = stmt
+            .query_map(
+                params_from_iter(ticket_ids.iter().map(|id| id.0.to_string())),
+                row_to_value,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        // Multi-select fields store one row per option; fold them together
+        let mut values: Vec<(TicketId, CustomFieldValue)> = Vec::new();
+        for (ticket_id, value) in rows.into_iter().flatten() {
+            if let Some((last_ticket, last)) = values.last_mut() {
+                if let (FieldValue::MultiSelect(options), FieldValue::MultiSelect(more)) =
+                    (&mut last.value, &value.value)
+                {
+                    if *last_ticket == ticket_id && last.field_id == value.field_id {
+                        options.extend(more.iter().cloned());
+                        continue;
+                    }
+                }
+            }
+            values.push((ticket_id, value));
+        }
+
+        Ok(values)
+    }
+
+    /// Replace the custom field values of a ticket
+    ///
+    /// Every field must belong to the ticket's project. Values are not
+    /// checked against the field definitions here; see
+    /// `Ticket::validate_custom_fields`.
+    pub fn set_ticket_values(
+        &self,
+        ticket_id: TicketId,
+        values: &[CustomFieldValue],
+    ) -> Result<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        tx.execute(
+            "DELETE FROM ticket_field_values WHERE ticket_id = ?1",
+            params![ticket_id.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        for field_value in values {
+            for (column, value) in value_rows(&field_value.value) {
+                let sql = format!(
+                    "INSERT INTO ticket_field_values (ticket_id, field_id, {})
+                     SELECT t.id, f.id, ?3 FROM tickets t
+                     JOIN custom_fields f ON f.project_id = t.project_id
+                     WHERE t.id = ?1 AND f.id = ?2",
+                    column
+                );
+
+                let inserted = tx
+                    .execute(
+                        &sql,
+                        params![
+                            ticket_id.0.to_string(),
+                            field_value.field_id.0.to_string(),
+                            value,
+                        ],
+                    )
+                    .map_err(|e| {
+                        if e.to_string().contains("FOREIGN KEY constraint failed") {
+                            DbError::ConstraintViolation("Unknown user in custom field".to_string())
+                        } else {
+                            DbError::Query(e.to_string())
+                        }
+                    })?;
+
+                if inserted == 0 {
+                    return Err(DbError::ConstraintViolation(format!(
+                        "Custom field {} does not belong to the ticket's project",
+                        field_value.field_id
+                    )));
+                }
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+impl Repository<CustomField, CustomFieldId> for CustomFieldRepository {
+    fn find_by_id(&self, id: CustomFieldId) -> Result<Option<CustomField>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, field_type, options, required, position,
+                        created_at, updated_at
+                 FROM custom_fields WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let field = stmt
+            .query_row(params![id.0.to_string()], row_to_field)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(field)
+    }
+
+    fn find_all(&self) -> Result<Vec<CustomField>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, field_type, options, required, position,
+                        created_at, updated_at
+                 FROM custom_fields ORDER BY position, name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let fields = stmt
+            .query_map([], row_to_field)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(fields)
+    }
+
+    fn create(&self, entity: &CustomField) -> Result<CustomField> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO custom_fields
+                 (id, project_id, name, field_type, options, required, position,
+                  created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entity.id.0.to_string(),
+                entity.project_id.0.to_string(),
+                entity.name.trim(),
+                entity.field_type.as_str(),
+                options_to_json(&entity.options),
+                entity.required,
+                entity.position,
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(map_unique_name)?;
+
+        let mut created = entity.clone();
+        created.name = entity.name.trim().to_string();
+        Ok(created)
+    }
+
+    /// Update a field's name, options, required flag and position
+    ///
+    /// The field type cannot change. Ticket values holding an option that
+    /// was removed are deleted.
+    fn update(&self, entity: &CustomField) -> Result<CustomField> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let updated_at = Utc::now();
+        let rows_affected = tx
+            .execute(
+                "UPDATE custom_fields
+                 SET name = ?1, options = ?2, required = ?3, position = ?4, updated_at = ?5
+                 WHERE id = ?6",
+                params![
+                    entity.name.trim(),
+                    options_to_json(&entity.options),
+                    entity.required,
+                    entity.position,
+                    updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(map_unique_name)?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Custom field not found".to_string()));
+        }
+
+        if entity.field_type.has_options() {
+            let sql = format!(
+                "DELETE FROM ticket_field_values WHERE field_id = ? AND value_text NOT IN ({})",
+                vec!["?"; entity.options.len()].join(", ")
+            );
+            let mut values = vec![entity.id.0.to_string()];
+            values.extend(entity.options.iter().cloned());
+
+            tx.execute(&sql, params_from_iter(values))
+                .map_err(|e| DbError::Query(e.to_string()))?;
+        }
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let mut updated = entity.clone();
+        updated.name = entity.name.trim().to_string();
+        updated.updated_at = updated_at;
+        Ok(updated)
+    }
+
+    fn delete(&self, id: CustomFieldId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM custom_fields WHERE id = ?1",
+                params![id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Custom field not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// The `ticket_field_values` column and value of each row storing `value`
+///
+/// Multi-select values take one row per selected option.
+pub(crate) fn value_rows(value: &FieldValue) -> Vec<(&'static str, SqlValue)> {
+    match value {
+        FieldValue::Text(text) | FieldValue::SingleSelect(text) => {
+            vec![("value_text", SqlValue::Text(text.clone()))]
+        },
+        FieldValue::MultiSelect(options) => options
+            .iter()
+            .map(|option| ("value_text", SqlValue::Text(option.clone())))
+            .collect(),
+        FieldValue::Number(number) => vec![("value_number", SqlValue::Real(*number))],
+        FieldValue::Date(date) => vec![(
+            "value_date",
+            SqlValue::Text(date.format(DATE_FORMAT).to_string()),
+        )],
+        FieldValue::User(user_id) => vec![("value_user", SqlValue::Text(user_id.0.to_string()))],
+    }
+}
+
+/// Report a duplicate field name as a constraint violation
+fn map_unique_name(e: rusqlite::Error) -> DbError {
+    if e.to_string().contains("UNIQUE constraint failed") {
+        DbError::ConstraintViolation("A field with this name already exists".to_string())
+    } else {
+        DbError::Query(e.to_string())
+    }
+}
+
+fn options_to_json(options: &[String]) -> String {
+    serde_json::to_string(options).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Convert a database row to a CustomField
+fn row_to_field(row: &Row) -> rusqlite::Result<CustomField> {
+    let id_str: String = row.get(0)?;
+    let project_id_str: String = row.get(1)?;
+    let field_type_str: String = row.get(3)?;
+    let options_json: String = row.get(4)?;
+    let created_at: DateTime<Utc> = row.get(7)?;
+    let updated_at: DateTime<Utc> = row.get(8)?;
+
+    Ok(CustomField {
+        id: CustomFieldId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        project_id: ProjectId::from_uuid(Uuid::parse_str(&project_id_str).unwrap()),
+        name: row.get(2)?,
+        field_type: CustomFieldType::from_name(&field_type_str).unwrap_or(CustomFieldType::Text),
+        options: serde_json::from_str(&options_json).unwrap_or_default(),
+        required: row.get(5)?,
+        position: row.get(6)?,
+        created_at,
+        updated_at,
+    })
+}
+
+/// Convert a `ticket_field_values` row to a ticket's value
+///
+/// Rows whose stored value does not match the field type are skipped.
+fn row_to_value(row: &Row) -> rusqlite::Result<Option<(TicketId, CustomFieldValue)>> {
+    let ticket_id_str: String = row.get(0)?;
+    let field_id_str: String = row.get(1)?;
+    let field_type_str: String = row.get(2)?;
+    let text: Option<String> = row.get(3)?;
+    let number: Option<f64> = row.get(4)?;
+    let date: Option<String> = row.get(5)?;
+    let user: Option<String> = row.get(6)?;
+
+    let value = match CustomFieldType::from_name(&field_type_str) {
+        Ok(CustomFieldType::Text) => text.map(FieldValue::Text),
+        Ok(CustomFieldType::SingleSelect) => text.map(FieldValue::SingleSelect),
+        Ok(CustomFieldType::MultiSelect) => {
+            text.map(|option| FieldValue::MultiSelect(vec![option]))
+        },
+        Ok(CustomFieldType::Number) => number.map(FieldValue::Number),
+        Ok(CustomFieldType::Date) => date
+            .and_then(|s| NaiveDate::parse_from_str(&s, DATE_FORMAT).ok())
+            .map(FieldValue::Date),
+        Ok(CustomFieldType::User) => user
+            .and_then(|s| Uuid::parse_str(&s).ok())
+            .map(|id| FieldValue::User(UserId::from_uuid(id))),
+        Err(_) => None,
+    };
+
+    Ok(value.map(|value| {
+        (
+            TicketId::from_uuid(Uuid::parse_str(&ticket_id_str).unwrap()),
+            CustomFieldValue {
+                field_id: CustomFieldId::from_uuid(Uuid::parse_str(&field_id_str).unwrap()),
+                value,
+            },
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (Arc<DbPool>, Project, User, Ticket) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let ticket = Ticket::new(project.id, "First".to_string(), TicketType::Bug, user.id);
+        TicketRepository::new(Arc::clone(&pool))
+            .create(&ticket)
+            .unwrap();
+
+        (pool, project, user, ticket)
+    }
+
+    #[test]
+    fn test_set_and_find_values_of_every_type() {
+        let (pool, project, user, ticket) = setup();
+        let repo = CustomFieldRepository::new(pool);
+
+        let customer = CustomField::new(project.id, "Customer".to_string(), CustomFieldType::Text);
+        let points = CustomField::new(project.id, "Points".to_string(), CustomFieldType::Number);
+        let found_on = CustomField::new(project.id, "Found on".to_string(), CustomFieldType::Date);
+        let reviewer = CustomField::new(project.id, "Reviewer".to_string(), CustomFieldType::User);
+        let mut platforms = CustomField::new(
+            project.id,
+            "Platforms".to_string(),
+            CustomFieldType::MultiSelect,
+        );
+        platforms.options = vec!["web".to_string(), "ios".to_string(), "android".to_string()];
+        for field in [&customer, &points, &found_on, &reviewer, &platforms] {
+            repo.create(field).unwrap();
+        }
+
+        let values = vec![
+            CustomFieldValue {
+                field_id: customer.id,
+                value: FieldValue::Text("Acme".to_string()),
+            },
+            CustomFieldValue {
+                field_id: found_on.id,
+                value: FieldValue::Date(NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()),
+            },
+            CustomFieldValue {
+                field_id: platforms.id,
+                value: FieldValue::MultiSelect(vec!["ios".to_string(), "web".to_string()]),
+            },
+            CustomFieldValue {
+                field_id: points.id,
+                value: FieldValue::Number(3.5),
+            },
+            CustomFieldValue {
+                field_id: reviewer.id,
+                value: FieldValue::User(user.id),
+            },
+        ];
+        repo.set_ticket_values(ticket.id, &values).unwrap();
+
+        let found: Vec<CustomFieldValue> = repo
+            .find_values_for_tickets(&[ticket.id])
+            .unwrap()
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(found.len(), values.len());
+        for value in &values {
+            assert!(found.contains(value), "missing {:?}", value);
+        }
+
+        // Replacing clears the previous values
+        repo.set_ticket_values(ticket.id, &values[..1]).unwrap();
+        assert_eq!(repo.find_values_for_tickets(&[ticket.id]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_removing_an_option_drops_its_values() {
+        let (pool, project, _, ticket) = setup();
+        let repo = CustomFieldRepository::new(pool);
+
+        let mut severity = CustomField::new(
+            project.id,
+            "Severity".to_string(),
+            CustomFieldType::SingleSelect,
+        );
+        severity.options = vec!["minor".to_string(), "major".to_string()];
+        repo.create(&severity).unwrap();
+
+        repo.set_ticket_values(
+            ticket.id,
+            &[CustomFieldValue {
+                field_id: severity.id,
+                value: FieldValue::SingleSelect("major".to_string()),
+            }],
+        )
+        .unwrap();
+
+        severity.options = vec!["minor".to_string(), "critical".to_string()];
+        repo.update(&severity).unwrap();
+        assert!(repo
+            .find_values_for_tickets(&[ticket.id])
+            .unwrap()
+            .is_empty());
+
+        let duplicate = CustomField::new(project.id, "severity".to_string(), CustomFieldType::Text);
+        assert!(matches!(
+            repo.create(&duplicate),
+            Err(DbError::ConstraintViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_ticket_values_rejects_other_projects() {
+        let (pool, project, user, ticket) = setup();
+        let repo = CustomFieldRepository::new(Arc::clone(&pool));
+
+        let other = Project::new("Other".to_string(), user.id);
+        ProjectRepository::new(pool).create(&other).unwrap();
+        let foreign = CustomField::new(other.id, "Customer".to_string(), CustomFieldType::Text);
+        repo.create(&foreign).unwrap();
+
+        let result = repo.set_ticket_values(
+            ticket.id,
+            &[CustomFieldValue {
+                field_id: foreign.id,
+                value: FieldValue::Text("Acme".to_string()),
+            }],
+        );
+        assert!(matches!(result, Err(DbError::ConstraintViolation(_))));
+        assert!(repo
+            .find_values_for_tickets(&[ticket.id])
+            .unwrap()
+            .is_empty());
+        assert!(repo.find_by_project(project.id).unwrap().is_empty());
+        assert_eq!(repo.find_by_project(other.id).unwrap().len(), 1);
+    }
+}
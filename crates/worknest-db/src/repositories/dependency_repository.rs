@@ -0,0 +1,341 @@
+//! Ticket dependency repository implementation
+
+use rusqlite::{params, params_from_iter, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{DependencyId, Ticket, TicketDependency, TicketId};
+
+use crate::{connection::DbPool, repositories::ticket_repository::row_to_ticket, DbError, Result};
+
+/// Dependency repository for the `ticket_dependencies` graph
+///
+/// An edge `ticket_id -> depends_on_ticket_id` means `ticket_id` is blocked
+/// by `depends_on_ticket_id`. The graph is kept acyclic.
+pub struct DependencyRepository {
+    pool: Arc<DbPool>,
+}
+
+impl DependencyRepository {
+    /// Create a new DependencyRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find the tickets a ticket depends on (its blockers)
+    pub fn find_blockers(&self, ticket_id: TicketId) -> Result<Vec<Ticket>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
+                        t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at,
+                    t.status_category, t.sprint_id, t.parent_id, t.number
+                 FROM tickets t
+                 JOIN ticket_dependencies d ON d.depends_on_ticket_id = t.id
+                 WHERE d.ticket_id = ?1
+                 ORDER BY d.created_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let tickets = stmt
+            .query_map(params![ticket_id.0.to_string()], row_to_ticket)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(tickets)
+    }
+
+    /// Find the tickets that depend on a ticket (the tickets it blocks)
+    pub fn find_blocked(&self, ticket_id: TicketId) -> Result<Vec<Ticket>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
+                        t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at,
+                    t.status_category, t.sprint_id, t.parent_id, t.number
+                 FROM tickets t
+                 JOIN ticket_dependencies d ON d.ticket_id = t.id
+                 WHERE d.depends_on_ticket_id = ?1
+                 ORDER BY d.created_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let tickets = stmt
+            .query_map(params![ticket_id.0.to_string()], row_to_ticket)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(tickets)
+    }
+
+    /// Find every dependency touching any of the given tickets
+    pub fn find_for_tickets(&self, ticket_ids: &[TicketId]) -> Result<Vec<TicketDependency>> {
+        if ticket_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let placeholders = vec!["?"; ticket_ids.len()].join(", ");
+        let sql = format!(
+            "SELECT id, ticket_id, depends_on_ticket_id, created_at
+             FROM ticket_dependencies
+             WHERE ticket_id IN ({0}) OR depends_on_ticket_id IN ({0})
+             ORDER BY created_at",
+            placeholders
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let ids = ticket_ids.iter().map(|id| id.0.to_string());
+        let params: Vec<String> = ids.clone().chain(ids).collect();
+
+        let dependencies = stmt
+            .query_map(params_from_iter(params), row_to_dependency)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(dependencies)
+    }
+
+    /// Check whether adding `ticket_id -> depends_on_ticket_id` would close a cycle
+    ///
+    /// That is the case when `ticket_id` is already reachable from
+    /// `depends_on_ticket_id` by following existing dependencies.
+    pub fn would_create_cycle(
+        &self,
+        ticket_id: TicketId,
+        depends_on_ticket_id: TicketId,
+    ) -> Result<bool> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        would_create_cycle(&conn, ticket_id, depends_on_ticket_id)
+    }
+
+    /// Add a dependency, rejecting self-dependencies, duplicates and cycles
+    pub fn create(&self, dependency: &TicketDependency) -> Result<TicketDependency> {
+        dependency
+            .validate()
+            .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        if would_create_cycle(&tx, dependency.ticket_id, dependency.depends_on_ticket_id)? {
+            return Err(DbError::ConstraintViolation(
+                "Dependency would create a cycle".to_string(),
+            ));
+        }
+
+        tx.execute(
+            "INSERT INTO ticket_dependencies (id, ticket_id, depends_on_ticket_id, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                dependency.id.0.to_string(),
+                dependency.ticket_id.0.to_string(),
+                dependency.depends_on_ticket_id.0.to_string(),
+                dependency.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            let msg = e.to_string();
+            if msg.contains("UNIQUE constraint failed") {
+                DbError::ConstraintViolation("Dependency already exists".to_string())
+            } else if msg.contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown ticket".to_string())
+            } else {
+                DbError::Query(msg)
+            }
+        })?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(dependency.clone())
+    }
+
+    /// Remove the dependency of `ticket_id` on `depends_on_ticket_id`
+    pub fn delete(&self, ticket_id: TicketId, depends_on_ticket_id: TicketId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM ticket_dependencies WHERE ticket_id = ?1 AND depends_on_ticket_id = ?2",
+                params![
+                    ticket_id.0.to_string(),
+                    depends_on_ticket_id.0.to_string()
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Dependency not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Walk the dependency graph from `depends_on_ticket_id` looking for `ticket_id`
+fn would_create_cycle(
+    conn: &rusqlite::Connection,
+    ticket_id: TicketId,
+    depends_on_ticket_id: TicketId,
+) -> Result<bool> {
+    conn.query_row(
+        "WITH RECURSIVE reachable(id) AS (
+             SELECT ?1
+             UNION
+             SELECT d.depends_on_ticket_id
+             FROM ticket_dependencies d
+             JOIN reachable r ON d.ticket_id = r.id
+         )
+         SELECT EXISTS (SELECT 1 FROM reachable WHERE id = ?2)",
+        params![depends_on_ticket_id.0.to_string(), ticket_id.0.to_string()],
+        |row| row.get(0),
+    )
+    .map_err(|e| DbError::Query(e.to_string()))
+}
+
+/// Convert a database row to a TicketDependency
+fn row_to_dependency(row: &Row) -> rusqlite::Result<TicketDependency> {
+    let id_str: String = row.get(0)?;
+    let ticket_id_str: String = row.get(1)?;
+    let depends_on_str: String = row.get(2)?;
+
+    Ok(TicketDependency {
+        id: DependencyId(Uuid::parse_str(&id_str).unwrap()),
+        ticket_id: TicketId::from_uuid(Uuid::parse_str(&ticket_id_str).unwrap()),
+        depends_on_ticket_id: TicketId::from_uuid(Uuid::parse_str(&depends_on_str).unwrap()),
+        created_at: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+        repository::Repository,
+    };
+    use worknest_core::models::{Project, TicketType, User};
+
+    fn setup_test_repo() -> (DependencyRepository, Vec<TicketId>) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        user_repo.create_with_password(&user, "hash").unwrap();
+
+        let project_repo = ProjectRepository::new(Arc::clone(&pool));
+        let project = Project::new("Test Project".to_string(), user.id);
+        project_repo.create(&project).unwrap();
+
+        let ticket_repo = TicketRepository::new(Arc::clone(&pool));
+        let ids = ["A", "B", "C"]
+            .iter()
+            .map(|title| {
+                let ticket = Ticket::new(project.id, title.to_string(), TicketType::Task, user.id);
+                ticket_repo.create(&ticket).unwrap();
+                ticket.id
+            })
+            .collect();
+
+        (DependencyRepository::new(pool), ids)
+    }
+
+    #[test]
+    fn test_create_and_find_dependencies() {
+        let (repo, ids) = setup_test_repo();
+
+        repo.create(&TicketDependency::new(ids[0], ids[1])).unwrap();
+
+        let blockers = repo.find_blockers(ids[0]).unwrap();
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].id, ids[1]);
+
+        let blocked = repo.find_blocked(ids[1]).unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].id, ids[0]);
+
+        assert_eq!(repo.find_for_tickets(&[ids[1]]).unwrap().len(), 1);
+        assert!(repo.find_for_tickets(&[ids[2]]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_duplicates_and_self_dependencies() {
+        let (repo, ids) = setup_test_repo();
+
+        repo.create(&TicketDependency::new(ids[0], ids[1])).unwrap();
+        assert!(matches!(
+            repo.create(&TicketDependency::new(ids[0], ids[1])),
+            Err(DbError::ConstraintViolation(_))
+        ));
+        assert!(matches!(
+            repo.create(&TicketDependency::new(ids[2], ids[2])),
+            Err(DbError::ConstraintViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_cycles() {
+        let (repo, ids) = setup_test_repo();
+
+        repo.create(&TicketDependency::new(ids[0], ids[1])).unwrap();
+        repo.create(&TicketDependency::new(ids[1], ids[2])).unwrap();
+
+        assert!(repo.would_create_cycle(ids[2], ids[0]).unwrap());
+        assert!(!repo.would_create_cycle(ids[0], ids[2]).unwrap());
+        assert!(matches!(
+            repo.create(&TicketDependency::new(ids[2], ids[0])),
+            Err(DbError::ConstraintViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_delete_dependency() {
+        let (repo, ids) = setup_test_repo();
+
+        repo.create(&TicketDependency::new(ids[0], ids[1])).unwrap();
+        repo.delete(ids[0], ids[1]).unwrap();
+
+        assert!(repo.find_blockers(ids[0]).unwrap().is_empty());
+        assert!(repo.delete(ids[0], ids[1]).is_err());
+    }
+}
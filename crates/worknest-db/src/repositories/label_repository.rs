@@ -0,0 +1,456 @@
+//! Label repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{Label, LabelId, ProjectId, TicketId};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Repository for the `labels` table and the `ticket_labels` links
+pub struct LabelRepository {
+    pool: Arc<DbPool>,
+}
+
+impl LabelRepository {
+    /// Create a new LabelRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find all labels of a project
+    pub fn find_by_project(&self, project_id: ProjectId) -> Result<Vec<Label>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, color, created_at, updated_at
+                 FROM labels WHERE project_id = ?1 ORDER BY name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let labels = stmt
+            .query_map(params![project_id.0.to_string()], row_to_label)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(labels)
+    }
+
+    /// Find the labels of several tickets, as `(ticket, label)` pairs
+    pub fn find_for_tickets(&self, ticket_ids: &[TicketId]) -> Result<Vec<(TicketId, Label)>> {
+        if ticket_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let placeholders = vec!["?"; ticket_ids.len()].join(", ");
+        let sql = format!(
+            "SELECT l.id, l.project_id, l.name, l.color, l.created_at, l.updated_at, tl.ticket_id
+             FROM ticket_labels tl
+             JOIN labels l ON l.id = tl.label_id
+             WHERE tl.ticket_id IN ({})
+             ORDER BY l.name",
+            placeholders
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let labels = stmt
+            .query_map(
+                params_from_iter(ticket_ids.iter().map(|id| id.0.to_string())),
+                |row| {
+                    let ticket_id: String = row.get(6)?;
+                    Ok((
+                        TicketId::from_uuid(Uuid::parse_str(&ticket_id).unwrap()),
+                        row_to_label(row)?,
+                    ))
+                },
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(labels)
+    }
+
+    /// Replace the labels of a ticket
+    ///
+    /// Every label must belong to the ticket's project.
+    pub fn set_ticket_labels(&self, ticket_id: TicketId, label_ids: &[LabelId]) -> Result<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        tx.execute(
+            "DELETE FROM ticket_labels WHERE ticket_id = ?1",
+            params![ticket_id.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let mut seen = Vec::with_capacity(label_ids.len());
+        for label_id in label_ids {
+            if seen.contains(label_id) {
+                continue;
+            }
+            seen.push(*label_id);
+
+            let inserted = tx
+                .execute(
+                    "INSERT OR IGNORE INTO ticket_labels (ticket_id, label_id)
+                     SELECT t.id, l.id FROM tickets t
+                     JOIN labels l ON l.project_id = t.project_id
+                     WHERE t.id = ?1 AND l.id = ?2",
+                    params![ticket_id.0.to_string(), label_id.0.to_string()],
+                )
+                .map_err(|e| DbError::Query(e.to_string()))?;
+
+            if inserted == 0 {
+                return Err(DbError::ConstraintViolation(format!(
+                    "Label {} does not belong to the ticket's project",
+                    label_id
+                )));
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Merge `source` into `target`: every ticket labelled `source` gets
+    /// `target` instead, and `source` is deleted
+    ///
+    /// Both labels must belong to the same project. Returns the number of
+    /// tickets that carried `source`.
+    pub fn merge(&self, source: LabelId, target: LabelId) -> Result<usize> {
+        if source == target {
+            return Err(DbError::ConstraintViolation(
+                "Cannot merge a label into itself".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let same_project: Option<bool> = tx
+            .query_row(
+                "SELECT s.project_id = t.project_id FROM labels s, labels t
+                 WHERE s.id = ?1 AND t.id = ?2",
+                params![source.0.to_string(), target.0.to_string()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        match same_project {
+            None => return Err(DbError::NotFound("Label not found".to_string())),
+            Some(false) => {
+                return Err(DbError::ConstraintViolation(
+                    "Labels belong to different projects".to_string(),
+                ))
+            },
+            Some(true) => {},
+        }
+
+        let tickets = tx
+            .query_row(
+                "SELECT COUNT(*) FROM ticket_labels WHERE label_id = ?1",
+                params![source.0.to_string()],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.execute(
+            "INSERT OR IGNORE INTO ticket_labels (ticket_id, label_id)
+             SELECT ticket_id, ?2 FROM ticket_labels WHERE label_id = ?1",
+            params![source.0.to_string(), target.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        // Removes the remaining links to the source through the cascade
+        tx.execute(
+            "DELETE FROM labels WHERE id = ?1",
+            params![source.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(tickets as usize)
+    }
+}
+
+impl Repository<Label, LabelId> for LabelRepository {
+    fn find_by_id(&self, id: LabelId) -> Result<Option<Label>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, color, created_at, updated_at
+                 FROM labels WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let label = stmt
+            .query_row(params![id.0.to_string()], row_to_label)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(label)
+    }
+
+    fn find_all(&self) -> Result<Vec<Label>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, color, created_at, updated_at
+                 FROM labels ORDER BY name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let labels = stmt
+            .query_map([], row_to_label)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(labels)
+    }
+
+    fn create(&self, entity: &Label) -> Result<Label> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO labels (id, project_id, name, color, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entity.id.0.to_string(),
+                entity.project_id.0.to_string(),
+                entity.name.trim(),
+                entity.color,
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(map_unique_name)?;
+
+        let mut created = entity.clone();
+        created.name = entity.name.trim().to_string();
+        Ok(created)
+    }
+
+    fn update(&self, entity: &Label) -> Result<Label> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let updated_at = Utc::now();
+        let rows_affected = conn
+            .execute(
+                "UPDATE labels SET name = ?1, color = ?2, updated_at = ?3 WHERE id = ?4",
+                params![
+                    entity.name.trim(),
+                    entity.color,
+                    updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(map_unique_name)?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Label not found".to_string()));
+        }
+
+        let mut updated = entity.clone();
+        updated.name = entity.name.trim().to_string();
+        updated.updated_at = updated_at;
+        Ok(updated)
+    }
+
+    fn delete(&self, id: LabelId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM labels WHERE id = ?1",
+                params![id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Label not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Report a duplicate label name as a constraint violation
+fn map_unique_name(e: rusqlite::Error) -> DbError {
+    if e.to_string().contains("UNIQUE constraint failed") {
+        DbError::ConstraintViolation("A label with this name already exists".to_string())
+    } else {
+        DbError::Query(e.to_string())
+    }
+}
+
+/// Convert a database row to a Label
+fn row_to_label(row: &Row) -> rusqlite::Result<Label> {
+    let id_str: String = row.get(0)?;
+    let project_id_str: String = row.get(1)?;
+    let created_at: DateTime<Utc> = row.get(4)?;
+    let updated_at: DateTime<Utc> = row.get(5)?;
+
+    Ok(Label {
+        id: LabelId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        project_id: ProjectId::from_uuid(Uuid::parse_str(&project_id_str).unwrap()),
+        name: row.get(2)?,
+        color: row.get(3)?,
+        created_at,
+        updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (Arc<DbPool>, Project, Ticket, Ticket) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let ticket_repo = TicketRepository::new(Arc::clone(&pool));
+        let first = Ticket::new(project.id, "First".to_string(), TicketType::Task, user.id);
+        let second = Ticket::new(project.id, "Second".to_string(), TicketType::Bug, user.id);
+        ticket_repo.create(&first).unwrap();
+        ticket_repo.create(&second).unwrap();
+
+        (pool, project, first, second)
+    }
+
+    #[test]
+    fn test_rename_applies_to_every_ticket() {
+        let (pool, project, first, second) = setup();
+        let repo = LabelRepository::new(pool);
+
+        let mut label = Label::new(project.id, "ui".to_string(), "#1d76db".to_string());
+        repo.create(&label).unwrap();
+        repo.set_ticket_labels(first.id, &[label.id]).unwrap();
+        repo.set_ticket_labels(second.id, &[label.id]).unwrap();
+
+        label.name = "frontend".to_string();
+        repo.update(&label).unwrap();
+
+        let labels = repo.find_for_tickets(&[first.id, second.id]).unwrap();
+        assert_eq!(labels.len(), 2);
+        assert!(labels.iter().all(|(_, l)| l.name == "frontend"));
+
+        // Names are unique per project, ignoring case
+        let duplicate = Label::new(project.id, "Frontend".to_string(), "#000000".to_string());
+        assert!(matches!(
+            repo.create(&duplicate),
+            Err(DbError::ConstraintViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_moves_tickets_to_target() {
+        let (pool, project, first, second) = setup();
+        let repo = LabelRepository::new(pool);
+
+        let bug = Label::new(project.id, "bug".to_string(), "#d73a4a".to_string());
+        let defect = Label::new(project.id, "defect".to_string(), "#b60205".to_string());
+        repo.create(&bug).unwrap();
+        repo.create(&defect).unwrap();
+        repo.set_ticket_labels(first.id, &[bug.id, defect.id])
+            .unwrap();
+        repo.set_ticket_labels(second.id, &[defect.id]).unwrap();
+
+        assert_eq!(repo.merge(defect.id, bug.id).unwrap(), 2);
+        assert!(repo.find_by_id(defect.id).unwrap().is_none());
+
+        let labels = repo.find_for_tickets(&[first.id, second.id]).unwrap();
+        assert_eq!(labels.len(), 2);
+        assert!(labels.iter().all(|(_, l)| l.id == bug.id));
+
+        assert!(repo.merge(bug.id, bug.id).is_err());
+    }
+
+    #[test]
+    fn test_set_ticket_labels_rejects_other_projects() {
+        let (pool, project, first, _) = setup();
+        let repo = LabelRepository::new(Arc::clone(&pool));
+
+        let other = Project::new("Other".to_string(), project.created_by);
+        ProjectRepository::new(pool).create(&other).unwrap();
+        let foreign = Label::new(other.id, "ops".to_string(), "#0e8a16".to_string());
+        repo.create(&foreign).unwrap();
+
+        assert!(matches!(
+            repo.set_ticket_labels(first.id, &[foreign.id]),
+            Err(DbError::ConstraintViolation(_))
+        ));
+        assert!(repo.find_for_tickets(&[first.id]).unwrap().is_empty());
+    }
+}
@@ -1,13 +1,56 @@
 //! Repository implementations
 
+pub mod access_token_repository;
+pub mod activity_repository;
 pub mod attachment_repository;
+pub mod code_link_repository;
 pub mod comment_repository;
+pub mod custom_field_repository;
+pub mod dependency_repository;
+pub mod label_repository;
+pub mod notification_preference_repository;
+pub mod notification_repository;
+pub mod permission_repository;
 pub mod project_repository;
+pub mod role_repository;
+pub mod saved_view_repository;
+pub mod session_repository;
+pub mod sprint_repository;
+pub mod team_repository;
+pub mod ticket_query;
 pub mod ticket_repository;
 pub mod user_repository;
+pub mod watcher_repository;
+pub mod webhook_delivery_repository;
+pub mod webhook_repository;
+pub mod workflow_repository;
+pub mod worklog_repository;
 
+pub use access_token_repository::AccessTokenRepository;
+pub use activity_repository::{ActivityFilter, ActivityRepository};
 pub use attachment_repository::AttachmentRepository;
+pub use code_link_repository::CodeLinkRepository;
 pub use comment_repository::CommentRepository;
+pub use custom_field_repository::CustomFieldRepository;
+pub use dependency_repository::DependencyRepository;
+pub use label_repository::LabelRepository;
+pub use notification_preference_repository::NotificationPreferenceRepository;
+pub use notification_repository::NotificationRepository;
+pub use permission_repository::PermissionRepository;
 pub use project_repository::ProjectRepository;
+pub use role_repository::RoleRepository;
+pub use saved_view_repository::SavedViewRepository;
+pub use session_repository::SessionRepository;
+pub use sprint_repository::SprintRepository;
+pub use team_repository::TeamRepository;
+pub use ticket_query::{
+    SortDirection, TicketCursor, TicketPage, TicketQuery, TicketSearchHit, TicketSort,
+    TicketSortField,
+};
 pub use ticket_repository::TicketRepository;
 pub use user_repository::UserRepository;
+pub use watcher_repository::WatcherRepository;
+pub use webhook_delivery_repository::WebhookDeliveryRepository;
+pub use webhook_repository::WebhookRepository;
+pub use workflow_repository::WorkflowRepository;
+pub use worklog_repository::WorklogRepository;
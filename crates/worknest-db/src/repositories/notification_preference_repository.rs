@@ -0,0 +1,218 @@
+//! Notification preference repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{NotificationPreferences, UserId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for users' notification preferences
+///
+/// Users who never saved preferences get the defaults of
+/// [`NotificationPreferences::new`].
+pub struct NotificationPreferenceRepository {
+    pool: Arc<DbPool>,
+}
+
+impl NotificationPreferenceRepository {
+    /// Create a new NotificationPreferenceRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find a user's preferences, falling back to the defaults
+    pub fn find(&self, user_id: UserId) -> Result<NotificationPreferences> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let preferences = conn
+            .query_row(
+                "SELECT user_id, email_assigned, email_mentioned, email_status_changed,
+                        daily_digest, digest_hour, last_digest_at
+                 FROM notification_preferences WHERE user_id = ?1",
+                params![user_id.0.to_string()],
+                row_to_preferences,
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(preferences.unwrap_or_else(|| NotificationPreferences::new(user_id)))
+    }
+
+    /// Save a user's preferences
+    ///
+    /// The time of the last digest is kept; it only changes through
+    /// [`Self::mark_digest_sent`].
+    pub fn save(&self, preferences: &NotificationPreferences) -> Result<NotificationPreferences> {
+        {
+            let conn = self
+                .pool
+                .get()
+                .map_err(|e| DbError::Connection(e.to_string()))?;
+
+            conn.execute(
+                "INSERT INTO notification_preferences (user_id, email_assigned, email_mentioned,
+                     email_status_changed, daily_digest, digest_hour, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(user_id) DO UPDATE SET
+                     email_assigned = excluded.email_assigned,
+                     email_mentioned = excluded.email_mentioned,
+                     email_status_changed = excluded.email_status_changed,
+                     daily_digest = excluded.daily_digest,
+                     digest_hour = excluded.digest_hour,
+                     updated_at = excluded.updated_at",
+                params![
+                    preferences.user_id.0.to_string(),
+                    preferences.email_assigned,
+                    preferences.email_mentioned,
+                    preferences.email_status_changed,
+                    preferences.daily_digest,
+                    preferences.digest_hour,
+                    Utc::now().to_rfc3339(),
+                ],
+            )
+            .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+        }
+
+        self.find(preferences.user_id)
+    }
+
+    /// Find the preferences of every user who subscribed to the daily digest
+    pub fn find_digest_subscribers(&self) -> Result<Vec<NotificationPreferences>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT user_id, email_assigned, email_mentioned, email_status_changed,
+                        daily_digest, digest_hour, last_digest_at
+                 FROM notification_preferences WHERE daily_digest = 1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let subscribers = stmt
+            .query_map([], row_to_preferences)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(subscribers)
+    }
+
+    /// Remember when a user's digest was sent
+    pub fn mark_digest_sent(&self, user_id: UserId, sent_at: DateTime<Utc>) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE notification_preferences SET last_digest_at = ?1 WHERE user_id = ?2",
+                params![sent_at.to_rfc3339(), user_id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(
+                "Notification preferences not found".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a database row to NotificationPreferences
+fn row_to_preferences(row: &Row) -> rusqlite::Result<NotificationPreferences> {
+    let user_id_str: String = row.get(0)?;
+    let last_digest_at: Option<String> = row.get(6)?;
+
+    Ok(NotificationPreferences {
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        email_assigned: row.get(1)?,
+        email_mentioned: row.get(2)?,
+        email_status_changed: row.get(3)?,
+        daily_digest: row.get(4)?,
+        digest_hour: row.get(5)?,
+        last_digest_at: last_digest_at.map(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .unwrap()
+                .with_timezone(&Utc)
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool, migrations::run_migrations,
+        repositories::user_repository::UserRepository,
+    };
+    use worknest_core::models::User;
+
+    fn setup() -> (NotificationPreferenceRepository, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("alice".to_string(), "alice@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        (NotificationPreferenceRepository::new(pool), user)
+    }
+
+    #[test]
+    fn test_defaults_until_saved() {
+        let (repo, user) = setup();
+
+        assert_eq!(
+            repo.find(user.id).unwrap(),
+            NotificationPreferences::new(user.id)
+        );
+        assert!(repo.find_digest_subscribers().unwrap().is_empty());
+        assert!(matches!(
+            repo.mark_digest_sent(user.id, Utc::now()),
+            Err(DbError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_and_track_digests() {
+        let (repo, user) = setup();
+
+        let mut preferences = NotificationPreferences::new(user.id);
+        preferences.email_status_changed = false;
+        preferences.daily_digest = true;
+        preferences.digest_hour = 7;
+        let saved = repo.save(&preferences).unwrap();
+        assert_eq!(saved, preferences);
+
+        let sent_at = Utc::now();
+        repo.mark_digest_sent(user.id, sent_at).unwrap();
+        let subscribers = repo.find_digest_subscribers().unwrap();
+        assert_eq!(subscribers.len(), 1);
+        assert_eq!(subscribers[0].digest_hour, 7);
+        assert_eq!(
+            subscribers[0].last_digest_at.map(|t| t.timestamp()),
+            Some(sent_at.timestamp())
+        );
+
+        // Saving again keeps the digest history
+        preferences.daily_digest = false;
+        let saved = repo.save(&preferences).unwrap();
+        assert!(saved.last_digest_at.is_some());
+        assert!(repo.find_digest_subscribers().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+//! Notification repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{Notification, NotificationId, NotificationKind, TicketId, UserId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for users' notification inboxes
+///
+/// Every lookup is scoped to the recipient, so users can only see and change
+/// their own notifications.
+pub struct NotificationRepository {
+    pool: Arc<DbPool>,
+}
+
+impl NotificationRepository {
+    /// Create a new NotificationRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Store a new notification
+    pub fn create(&self, notification: &Notification) -> Result<Notification> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO notifications (id, user_id, actor_id, ticket_id, kind, message, read_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                notification.id.0.to_string(),
+                notification.user_id.0.to_string(),
+                notification.actor_id.0.to_string(),
+                notification.ticket_id.0.to_string(),
+                notification.kind.as_str(),
+                notification.message,
+                notification.read_at.map(|t| t.to_rfc3339()),
+                notification.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+
+        Ok(notification.clone())
+    }
+
+    /// Find one of a user's notifications
+    pub fn find_by_id(&self, id: NotificationId, user_id: UserId) -> Result<Option<Notification>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.query_row(
+            "SELECT id, user_id, actor_id, ticket_id, kind, message, read_at, created_at
+             FROM notifications WHERE id = ?1 AND user_id = ?2",
+            params![id.0.to_string(), user_id.0.to_string()],
+            row_to_notification,
+        )
+        .optional()
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+
+    /// Find a user's notifications, newest first
+    pub fn find_for_user(
+        &self,
+        user_id: UserId,
+        unread_only: bool,
+        limit: Option<u32>,
+    ) -> Result<Vec<Notification>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, actor_id, ticket_id, kind, message, read_at, created_at
+                 FROM notifications
+                 WHERE user_id = ?1 AND (?2 = 0 OR read_at IS NULL)
+                 ORDER BY created_at DESC, rowid DESC
+                 LIMIT ?3",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let notifications = stmt
+            .query_map(
+                params![
+                    user_id.0.to_string(),
+                    unread_only,
+                    limit.map(i64::from).unwrap_or(-1),
+                ],
+                row_to_notification,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(notifications)
+    }
+
+    /// Find a user's unread notifications created after `since`, oldest
+    /// first; all of them without `since`
+    pub fn find_unread_since(
+        &self,
+        user_id: UserId,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Notification>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, actor_id, ticket_id, kind, message, read_at, created_at
+                 FROM notifications
+                 WHERE user_id = ?1 AND read_at IS NULL AND (?2 IS NULL OR created_at > ?2)
+                 ORDER BY created_at, rowid",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let notifications = stmt
+            .query_map(
+                params![user_id.0.to_string(), since.map(|t| t.to_rfc3339())],
+                row_to_notification,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(notifications)
+    }
+
+    /// Count a user's unread notifications
+    pub fn unread_count(&self, user_id: UserId) -> Result<u32> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.query_row(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = ?1 AND read_at IS NULL",
+            params![user_id.0.to_string()],
+            |row| row.get(0),
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+
+    /// Mark one of a user's notifications as read
+    ///
+    /// Reading an already read notification keeps its original read time.
+    pub fn mark_read(&self, id: NotificationId, user_id: UserId) -> Result<Notification> {
+        {
+            let conn = self
+                .pool
+                .get()
+                .map_err(|e| DbError::Connection(e.to_string()))?;
+
+            conn.execute(
+                "UPDATE notifications SET read_at = COALESCE(read_at, ?1)
+                 WHERE id = ?2 AND user_id = ?3",
+                params![
+                    Utc::now().to_rfc3339(),
+                    id.0.to_string(),
+                    user_id.0.to_string()
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+        }
+
+        self.find_by_id(id, user_id)?
+            .ok_or_else(|| DbError::NotFound("Notification not found".to_string()))
+    }
+
+    /// Mark all of a user's notifications as read, returning how many were
+    /// unread
+    pub fn mark_all_read(&self, user_id: UserId) -> Result<usize> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "UPDATE notifications SET read_at = ?1 WHERE user_id = ?2 AND read_at IS NULL",
+            params![Utc::now().to_rfc3339(), user_id.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+}
+
+/// Convert a database row to a Notification
+fn row_to_notification(row: &Row) -> rusqlite::Result<Notification> {
+    let id_str: String = row.get(0)?;
+    let user_id_str: String = row.get(1)?;
+    let actor_id_str: String = row.get(2)?;
+    let ticket_id_str: String = row.get(3)?;
+    let kind_str: String = row.get(4)?;
+    let read_at: Option<String> = row.get(6)?;
+    let created_at: String = row.get(7)?;
+
+    let parse_time = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+    Ok(Notification {
+        id: NotificationId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        actor_id: UserId::from_uuid(Uuid::parse_str(&actor_id_str).unwrap()),
+        ticket_id: TicketId::from_uuid(Uuid::parse_str(&ticket_id_str).unwrap()),
+        kind: NotificationKind::from_name(&kind_str).unwrap_or(NotificationKind::Updated),
+        message: row.get(5)?,
+        read_at: read_at.as_deref().map(parse_time),
+        created_at: parse_time(&created_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+        Repository,
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (NotificationRepository, TicketRepository, Ticket, User, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let users = UserRepository::new(Arc::clone(&pool));
+        let alice = User::new("alice".to_string(), "alice@example.com".to_string());
+        let bob = User::new("bob".to_string(), "bob@example.com".to_string());
+        users.create_with_password(&alice, "hash").unwrap();
+        users.create_with_password(&bob, "hash").unwrap();
+
+        let project = Project::new("Test Project".to_string(), alice.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+        let ticket = tickets
+            .create(&Ticket::new(
+                project.id,
+                "Notify me".to_string(),
+                TicketType::Task,
+                alice.id,
+            ))
+            .unwrap();
+
+        (
+            NotificationRepository::new(pool),
+            tickets,
+            ticket,
+            alice,
+            bob,
+        )
+    }
+
+    fn notify(
+        repo: &NotificationRepository,
+        ticket: &Ticket,
+        to: &User,
+        from: &User,
+        kind: NotificationKind,
+    ) -> Notification {
+        repo.create(&Notification::new(
+            to.id,
+            from.id,
+            ticket.id,
+            kind,
+            format!("{} {}", from.username, kind.as_str()),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_inbox_and_read_state() {
+        let (repo, _, ticket, alice, bob) = setup();
+        let first = notify(&repo, &ticket, &bob, &alice, NotificationKind::Assigned);
+        let second = notify(&repo, &ticket, &bob, &alice, NotificationKind::Mentioned);
+        notify(&repo, &ticket, &alice, &bob, NotificationKind::Commented);
+
+        let inbox = repo.find_for_user(bob.id, false, None).unwrap();
+        assert_eq!(inbox.len(), 2);
+        assert_eq!(inbox[0].id, second.id);
+        assert_eq!(inbox[0].kind, NotificationKind::Mentioned);
+        assert_eq!(repo.unread_count(bob.id).unwrap(), 2);
+        assert_eq!(repo.find_for_user(bob.id, false, Some(1)).unwrap().len(), 1);
+
+        let read = repo.mark_read(first.id, bob.id).unwrap();
+        assert!(read.is_read());
+        assert_eq!(
+            repo.mark_read(first.id, bob.id).unwrap().read_at,
+            read.read_at
+        );
+        assert_eq!(repo.unread_count(bob.id).unwrap(), 1);
+        assert_eq!(
+            repo.find_for_user(bob.id, true, None).unwrap()[0].id,
+            second.id
+        );
+
+        // Other users' notifications are out of reach
+        assert!(repo.find_by_id(first.id, alice.id).unwrap().is_none());
+        assert!(matches!(
+            repo.mark_read(first.id, alice.id),
+            Err(DbError::NotFound(_))
+        ));
+
+        assert_eq!(repo.mark_all_read(bob.id).unwrap(), 1);
+        assert_eq!(repo.unread_count(bob.id).unwrap(), 0);
+        assert_eq!(repo.unread_count(alice.id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_find_unread_since() {
+        let (repo, _, ticket, alice, bob) = setup();
+        let mut old = Notification::new(
+            bob.id,
+            alice.id,
+            ticket.id,
+            NotificationKind::Updated,
+            "alice updated it".to_string(),
+        );
+        old.created_at = Utc::now() - chrono::Duration::days(2);
+        repo.create(&old).unwrap();
+        let recent = notify(&repo, &ticket, &bob, &alice, NotificationKind::Mentioned);
+        let read = notify(&repo, &ticket, &bob, &alice, NotificationKind::Assigned);
+        repo.mark_read(read.id, bob.id).unwrap();
+
+        let all = repo.find_unread_since(bob.id, None).unwrap();
+        assert_eq!(
+            all.iter().map(|n| n.id).collect::<Vec<_>>(),
+            vec![old.id, recent.id]
+        );
+
+        let since = Utc::now() - chrono::Duration::days(1);
+        let fresh = repo.find_unread_since(bob.id, Some(since)).unwrap();
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].id, recent.id);
+    }
+
+    #[test]
+    fn test_notifications_go_with_their_ticket() {
+        let (repo, tickets, ticket, alice, bob) = setup();
+        notify(&repo, &ticket, &bob, &alice, NotificationKind::Updated);
+
+        tickets.delete(ticket.id).unwrap();
+        assert!(repo.find_for_user(bob.id, false, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+//! Permission repository implementation
+
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+
+use worknest_core::models::{Permission, PermissionId, ProjectId, RoleId, UserId};
+
+use crate::{
+    connection::DbPool,
+    repositories::role_repository::{project_scope, EFFECTIVE_ROLE_IDS},
+    DbError, Result,
+};
+
+/// Permission repository for database operations
+///
+/// Permissions are seeded by the migrations; this repository resolves them
+/// for roles and users and maintains the `role_permissions` mapping.
+pub struct PermissionRepository {
+    pool: Arc<DbPool>,
+}
+
+impl PermissionRepository {
+    /// Create a new PermissionRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find all permissions
+    pub fn find_all(&self) -> Result<Vec<Permission>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, resource, action, description, created_at
+                 FROM permissions ORDER BY name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let permissions = stmt
+            .query_map([], row_to_permission)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(permissions)
+    }
+
+    /// Find a permission by its name (e.g. `ticket:delete`)
+    pub fn find_by_name(&self, name: &str) -> Result<Option<Permission>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, resource, action, description, created_at
+                 FROM permissions WHERE name = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let permission = stmt
+            .query_row(params![name], row_to_permission)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(permission)
+    }
+
+    /// Find the permissions granted to a role
+    pub fn find_by_role(&self, role_id: &RoleId) -> Result<Vec<Permission>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT p.id, p.name, p.resource, p.action, p.description, p.created_at
+                 FROM permissions p
+                 JOIN role_permissions rp ON rp.permission_id = p.id
+                 WHERE rp.role_id = ?1
+                 ORDER BY p.name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let permissions = stmt
+            .query_map(params![role_id.as_str()], row_to_permission)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(permissions)
+    }
+
+    /// Find the effective permissions of a user
+    ///
+    /// Combines the permissions of the user's global roles with those of the
+    /// roles assigned for `project_id`, when given, and of the roles the user
+    /// holds in teams attached to that project. Global roles other than Admin
+    /// do not apply to private projects.
+    pub fn find_for_user(
+        &self,
+        user_id: UserId,
+        project_id: Option<ProjectId>,
+    ) -> Result<Vec<Permission>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT DISTINCT p.id, p.name, p.resource, p.action, p.description, p.created_at
+                     FROM permissions p
+                     JOIN role_permissions rp ON rp.permission_id = p.id
+                     WHERE rp.role_id IN ({})
+                     ORDER BY p.name",
+                EFFECTIVE_ROLE_IDS
+            ))
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let permissions = stmt
+            .query_map(
+                params![user_id.0.to_string(), project_scope(project_id)],
+                row_to_permission,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(permissions)
+    }
+
+    /// Grant a permission to a role
+    pub fn grant_to_role(&self, role_id: &RoleId, permission_id: &PermissionId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO role_permissions (role_id, permission_id) VALUES (?1, ?2)",
+            params![role_id.as_str(), permission_id.as_str()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Revoke a permission from a role
+    pub fn revoke_from_role(&self, role_id: &RoleId, permission_id: &PermissionId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM role_permissions WHERE role_id = ?1 AND permission_id = ?2",
+                params![role_id.as_str(), permission_id.as_str()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(
+                "Permission not granted to role".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a database row to a Permission
+fn row_to_permission(row: &Row) -> rusqlite::Result<Permission> {
+    let id_str: String = row.get(0)?;
+
+    Ok(Permission {
+        id: PermissionId::from_string(&id_str).unwrap(),
+        name: row.get(1)?,
+        resource: row.get(2)?,
+        action: row.get(3)?,
+        description: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{role_repository::RoleRepository, user_repository::UserRepository},
+    };
+    use worknest_core::models::User;
+
+    fn setup_test_repo() -> (PermissionRepository, RoleRepository, UserId) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        user_repo.create_with_password(&user, "hash").unwrap();
+
+        (
+            PermissionRepository::new(Arc::clone(&pool)),
+            RoleRepository::new(pool),
+            user.id,
+        )
+    }
+
+    #[test]
+    fn test_seeded_permissions() {
+        let (repo, _, _) = setup_test_repo();
+
+        assert_eq!(repo.find_all().unwrap().len(), 12);
+
+        let perm = repo.find_by_name("ticket:delete").unwrap().unwrap();
+        assert!(perm.matches("ticket", "delete"));
+        assert_eq!(perm.id.as_str(), "perm_ticket_delete");
+    }
+
+    #[test]
+    fn test_find_by_role() {
+        let (repo, _, _) = setup_test_repo();
+
+        let viewer = repo.find_by_role(&RoleId::viewer()).unwrap();
+        assert_eq!(viewer.len(), 2);
+        assert!(viewer.iter().all(|p| p.action == "read"));
+
+        let admin = repo.find_by_role(&RoleId::admin()).unwrap();
+        assert_eq!(admin.len(), 12);
+    }
+
+    #[test]
+    fn test_find_for_user_combines_scopes() {
+        let (repo, roles, user_id) = setup_test_repo();
+        let project_id = ProjectId::new();
+
+        assert!(repo.find_for_user(user_id, None).unwrap().is_empty());
+
+        roles
+            .assign_to_user(user_id, &RoleId::viewer(), None)
+            .unwrap();
+        roles
+            .assign_to_user(user_id, &RoleId::member(), Some(project_id))
+            .unwrap();
+
+        let global = repo.find_for_user(user_id, None).unwrap();
+        assert!(!global.iter().any(|p| p.matches("ticket", "update")));
+
+        let scoped = repo.find_for_user(user_id, Some(project_id)).unwrap();
+        assert!(scoped.iter().any(|p| p.matches("ticket", "update")));
+        assert!(!scoped.iter().any(|p| p.matches("ticket", "delete")));
+    }
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let (repo, _, _) = setup_test_repo();
+        let perm = repo.find_by_name("ticket:delete").unwrap().unwrap();
+
+        repo.grant_to_role(&RoleId::member(), &perm.id).unwrap();
+        assert!(repo
+            .find_by_role(&RoleId::member())
+            .unwrap()
+            .iter()
+            .any(|p| p.name == "ticket:delete"));
+
+        repo.revoke_from_role(&RoleId::member(), &perm.id).unwrap();
+        assert!(repo.revoke_from_role(&RoleId::member(), &perm.id).is_err());
+    }
+}
@@ -1,13 +1,20 @@
 //! Project repository implementation
 
 use chrono::{DateTime, Utc};
-use rusqlite::{params, OptionalExtension, Row};
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use worknest_core::models::{Project, ProjectId, UserId};
+use worknest_core::models::{Project, ProjectId, ProjectMember, RoleId, UserId, Workflow};
 
-use crate::{connection::DbPool, repository::Repository, DbError, Result};
+use crate::{
+    connection::DbPool,
+    repositories::{
+        role_repository::project_access_predicate, workflow_repository::insert_workflow,
+    },
+    repository::Repository,
+    DbError, Result,
+};
 
 /// Project repository for database operations
 pub struct ProjectRepository {
@@ -29,7 +36,8 @@ impl ProjectRepository {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, color, archived, created_by, created_at, updated_at
+                "SELECT id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                        key
                  FROM projects WHERE created_by = ?1 ORDER BY name",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -43,6 +51,209 @@ impl ProjectRepository {
         Ok(projects)
     }
 
+    /// Find all projects a user can see
+    ///
+    /// Public projects are visible to anyone with a global role granting
+    /// `project:read`; private projects only to their members and global admins.
+    pub fn find_visible_to(&self, user_id: UserId) -> Result<Vec<Project>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let sql = format!(
+            "SELECT p.id, p.name, p.description, p.color, p.archived, p.created_by, p.created_at,
+                    p.updated_at, p.is_private, p.key
+             FROM projects p WHERE {} ORDER BY p.name",
+            project_access_predicate("perm_project_read")
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let projects = stmt
+            .query_map(params![user_id.0.to_string()], row_to_project)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(projects)
+    }
+
+    /// Find the members of a project
+    ///
+    /// Members are the users holding a role scoped to the project.
+    pub fn find_members(&self, project_id: ProjectId) -> Result<Vec<ProjectMember>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT ur.project_id, ur.user_id, ur.role_id, ur.assigned_at
+                 FROM user_roles ur
+                 JOIN users u ON u.id = ur.user_id
+                 WHERE ur.project_id = ?1
+                 ORDER BY u.username",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let members = stmt
+            .query_map(params![project_id.0.to_string()], row_to_member)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(members)
+    }
+
+    /// Find a single member of a project
+    pub fn find_member(
+        &self,
+        project_id: ProjectId,
+        user_id: UserId,
+    ) -> Result<Option<ProjectMember>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT project_id, user_id, role_id, assigned_at
+                 FROM user_roles WHERE project_id = ?1 AND user_id = ?2
+                 ORDER BY assigned_at DESC LIMIT 1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let member = stmt
+            .query_row(
+                params![project_id.0.to_string(), user_id.0.to_string()],
+                row_to_member,
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(member)
+    }
+
+    /// Add a member to a project or change the role of an existing member
+    ///
+    /// A member holds exactly one role per project; any previous project
+    /// role is replaced.
+    pub fn set_member_role(
+        &self,
+        project_id: ProjectId,
+        user_id: UserId,
+        role_id: &RoleId,
+    ) -> Result<ProjectMember> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        tx.execute(
+            "DELETE FROM user_roles WHERE project_id = ?1 AND user_id = ?2",
+            params![project_id.0.to_string(), user_id.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let member = ProjectMember::new(project_id, user_id, role_id.clone());
+        tx.execute(
+            "INSERT INTO user_roles (user_id, role_id, project_id, assigned_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                user_id.0.to_string(),
+                role_id.as_str(),
+                project_id.0.to_string(),
+                member.joined_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown user or role".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(member)
+    }
+
+    /// Remove a member from a project
+    pub fn remove_member(&self, project_id: ProjectId, user_id: UserId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM user_roles WHERE project_id = ?1 AND user_id = ?2",
+                params![project_id.0.to_string(), user_id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Project member not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Find a project by its key or by a key it had before being renamed
+    ///
+    /// Keys are matched case-insensitively.
+    pub fn find_by_key(&self, key: &str) -> Result<Option<Project>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                        key
+                 FROM projects
+                 WHERE key = ?1
+                    OR id = (SELECT project_id FROM project_key_aliases WHERE key = ?1)",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let project = stmt
+            .query_row(params![key.to_ascii_uppercase()], row_to_project)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(project)
+    }
+
+    /// Whether a key is taken by a project, either as its current key or as
+    /// one it had before
+    pub fn key_in_use(&self, key: &str) -> Result<bool> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM projects WHERE key = ?1)
+                 OR EXISTS (SELECT 1 FROM project_key_aliases WHERE key = ?1)",
+            params![key],
+            |row| row.get(0),
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+
     /// Find all active (non-archived) projects
     pub fn find_active(&self) -> Result<Vec<Project>> {
         let conn = self
@@ -52,7 +263,8 @@ impl ProjectRepository {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, color, archived, created_by, created_at, updated_at
+                "SELECT id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                        key
                  FROM projects WHERE archived = 0 ORDER BY name",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -75,7 +287,8 @@ impl ProjectRepository {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, color, archived, created_by, created_at, updated_at
+                "SELECT id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                        key
                  FROM projects WHERE archived = 1 ORDER BY name",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -143,7 +356,8 @@ impl Repository<Project, ProjectId> for ProjectRepository {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, color, archived, created_by, created_at, updated_at
+                "SELECT id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                        key
                  FROM projects WHERE id = ?1",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -164,7 +378,8 @@ impl Repository<Project, ProjectId> for ProjectRepository {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, description, color, archived, created_by, created_at, updated_at
+                "SELECT id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                        key
                  FROM projects ORDER BY name",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -179,14 +394,23 @@ impl Repository<Project, ProjectId> for ProjectRepository {
     }
 
     fn create(&self, entity: &Project) -> Result<Project> {
-        let conn = self
+        let mut conn = self
             .pool
             .get()
             .map_err(|e| DbError::Connection(e.to_string()))?;
 
-        conn.execute(
-            "INSERT INTO projects (id, name, description, color, archived, created_by, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        if alias_owner(&tx, &entity.key)?.is_some() {
+            return Err(key_in_use(&entity.key));
+        }
+
+        tx.execute(
+            "INSERT INTO projects (id, name, description, color, archived, created_by, created_at, updated_at, is_private,
+                                   key)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 entity.id.0.to_string(),
                 entity.name,
@@ -196,38 +420,85 @@ impl Repository<Project, ProjectId> for ProjectRepository {
                 entity.created_by.0.to_string(),
                 entity.created_at.to_rfc3339(),
                 entity.updated_at.to_rfc3339(),
+                if entity.is_private { 1 } else { 0 },
+                entity.key,
             ],
         )
-        .map_err(|e| DbError::Query(e.to_string()))?;
+        .map_err(map_key_error(&entity.key))?;
+
+        insert_workflow(&tx, &Workflow::default_for(entity.id))?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
 
         Ok(entity.clone())
     }
 
+    /// Update a project
+    ///
+    /// Changing the key keeps the old one as an alias, so references made
+    /// with it still resolve. Keys in use by another project, current or
+    /// old, are refused with a constraint violation.
     fn update(&self, entity: &Project) -> Result<Project> {
-        let conn = self
+        let mut conn = self
             .pool
             .get()
             .map_err(|e| DbError::Connection(e.to_string()))?;
 
-        let rows_affected = conn
-            .execute(
-                "UPDATE projects SET name = ?1, description = ?2, color = ?3, archived = ?4, updated_at = ?5
-                 WHERE id = ?6",
-                params![
-                    entity.name,
-                    entity.description,
-                    entity.color,
-                    if entity.archived { 1 } else { 0 },
-                    Utc::now().to_rfc3339(),
-                    entity.id.0.to_string(),
-                ],
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let project_id = entity.id.0.to_string();
+        let old_key: String = tx
+            .query_row(
+                "SELECT key FROM projects WHERE id = ?1",
+                params![project_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .ok_or_else(|| DbError::NotFound("Project not found".to_string()))?;
+
+        if old_key != entity.key {
+            match alias_owner(&tx, &entity.key)? {
+                Some(owner) if owner != project_id => return Err(key_in_use(&entity.key)),
+                _ => {},
+            }
+
+            // Taking back an old key of the project makes it current again
+            tx.execute(
+                "DELETE FROM project_key_aliases WHERE key = ?1",
+                params![entity.key],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+            tx.execute(
+                "INSERT INTO project_key_aliases (key, project_id) VALUES (?1, ?2)",
+                params![old_key, project_id],
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
-
-        if rows_affected == 0 {
-            return Err(DbError::NotFound("Project not found".to_string()));
         }
 
+        tx.execute(
+            "UPDATE projects SET name = ?1, description = ?2, color = ?3, archived = ?4, updated_at = ?5,
+                                 is_private = ?6, key = ?7
+             WHERE id = ?8",
+            params![
+                entity.name,
+                entity.description,
+                entity.color,
+                if entity.archived { 1 } else { 0 },
+                Utc::now().to_rfc3339(),
+                if entity.is_private { 1 } else { 0 },
+                entity.key,
+                project_id,
+            ],
+        )
+        .map_err(map_key_error(&entity.key))?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
         Ok(entity.clone())
     }
 
@@ -252,6 +523,35 @@ impl Repository<Project, ProjectId> for ProjectRepository {
     }
 }
 
+/// The project an old key now points to, if any
+fn alias_owner(conn: &Connection, key: &str) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT project_id FROM project_key_aliases WHERE key = ?1",
+        params![key],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| DbError::Query(e.to_string()))
+}
+
+fn key_in_use(key: &str) -> DbError {
+    DbError::ConstraintViolation(format!("Project key '{}' is already in use", key))
+}
+
+/// Map a failed project write, reporting a duplicate key as a constraint
+/// violation
+fn map_key_error(key: &str) -> impl Fn(rusqlite::Error) -> DbError + '_ {
+    move |e| {
+        if e.to_string()
+            .contains("UNIQUE constraint failed: projects.key")
+        {
+            key_in_use(key)
+        } else {
+            DbError::Query(e.to_string())
+        }
+    }
+}
+
 /// Convert a database row to a Project
 fn row_to_project(row: &Row) -> rusqlite::Result<Project> {
     let id_str: String = row.get(0)?;
@@ -271,25 +571,43 @@ fn row_to_project(row: &Row) -> rusqlite::Result<Project> {
         .with_timezone(&Utc);
 
     let archived: i32 = row.get(4)?;
+    let is_private: i32 = row.get(8)?;
 
     Ok(Project {
         id,
         name: row.get(1)?,
+        key: row.get(9)?,
         description: row.get(2)?,
         color: row.get(3)?,
         archived: archived == 1,
+        is_private: is_private == 1,
         created_by,
         created_at,
         updated_at,
     })
 }
 
+/// Convert a `user_roles` row to a ProjectMember
+fn row_to_member(row: &Row) -> rusqlite::Result<ProjectMember> {
+    let project_id_str: String = row.get(0)?;
+    let user_id_str: String = row.get(1)?;
+    let role_id_str: String = row.get(2)?;
+
+    Ok(ProjectMember {
+        project_id: ProjectId::from_uuid(Uuid::parse_str(&project_id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        role_id: RoleId::from_string(&role_id_str).unwrap(),
+        joined_at: row.get(3)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        connection::init_memory_pool, migrations::run_migrations,
-        repositories::user_repository::UserRepository,
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{role_repository::RoleRepository, user_repository::UserRepository},
     };
     use worknest_core::models::User;
 
@@ -352,11 +670,43 @@ mod tests {
         assert!(!found.archived);
     }
 
+    #[test]
+    fn test_project_keys_and_aliases() {
+        let (repo, user_id) = setup_test_repo();
+        let mut project = Project::new("Work Nest".to_string(), user_id);
+        repo.create(&project).unwrap();
+
+        let duplicate = Project::new("Web Notes".to_string(), user_id);
+        assert!(matches!(
+            repo.create(&duplicate),
+            Err(DbError::ConstraintViolation(_))
+        ));
+
+        project.key = "NEST".to_string();
+        repo.update(&project).unwrap();
+
+        assert_eq!(repo.find_by_key("nest").unwrap().unwrap().id, project.id);
+        assert_eq!(repo.find_by_key("WN").unwrap().unwrap().id, project.id);
+        assert!(repo.find_by_key("XX").unwrap().is_none());
+        assert!(repo.key_in_use("WN").unwrap());
+
+        // The old key stays reserved for the project that had it
+        assert!(matches!(
+            repo.create(&duplicate),
+            Err(DbError::ConstraintViolation(_))
+        ));
+
+        project.key = "WN".to_string();
+        repo.update(&project).unwrap();
+        assert_eq!(repo.find_by_key("NEST").unwrap().unwrap().key, "WN");
+    }
+
     #[test]
     fn test_find_active_and_archived() {
         let (repo, user_id) = setup_test_repo();
         let project1 = Project::new("Active Project".to_string(), user_id);
-        let project2 = Project::new("Archived Project".to_string(), user_id);
+        let mut project2 = Project::new("Archived Project".to_string(), user_id);
+        project2.key = "ARC".to_string();
 
         repo.create(&project1).unwrap();
         repo.create(&project2).unwrap();
@@ -395,4 +745,77 @@ mod tests {
         let found = repo.find_by_id(project.id).unwrap();
         assert!(found.is_none());
     }
+
+    #[test]
+    fn test_private_flag_roundtrip() {
+        let (repo, user_id) = setup_test_repo();
+        let mut project = Project::new("Secret".to_string(), user_id);
+        project.is_private = true;
+
+        repo.create(&project).unwrap();
+        assert!(repo.find_by_id(project.id).unwrap().unwrap().is_private);
+
+        project.is_private = false;
+        repo.update(&project).unwrap();
+        assert!(!repo.find_by_id(project.id).unwrap().unwrap().is_private);
+    }
+
+    #[test]
+    fn test_membership() {
+        let (repo, user_id) = setup_test_repo();
+        let project = Project::new("Test Project".to_string(), user_id);
+        repo.create(&project).unwrap();
+
+        assert!(repo.find_members(project.id).unwrap().is_empty());
+
+        repo.set_member_role(project.id, user_id, &RoleId::member())
+            .unwrap();
+        repo.set_member_role(project.id, user_id, &RoleId::admin())
+            .unwrap();
+
+        let members = repo.find_members(project.id).unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].role_id, RoleId::admin());
+
+        let member = repo.find_member(project.id, user_id).unwrap().unwrap();
+        assert_eq!(member.user_id, user_id);
+
+        repo.remove_member(project.id, user_id).unwrap();
+        assert!(repo.find_member(project.id, user_id).unwrap().is_none());
+        assert!(repo.remove_member(project.id, user_id).is_err());
+    }
+
+    #[test]
+    fn test_find_visible_to() {
+        let (repo, owner_id) = setup_test_repo();
+        let pool = Arc::clone(&repo.pool);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let outsider = User::new("outsider".to_string(), "outsider@example.com".to_string());
+        user_repo.create_with_password(&outsider, "hash").unwrap();
+
+        let role_repo = RoleRepository::new(pool);
+        role_repo
+            .assign_to_user(outsider.id, &RoleId::member(), None)
+            .unwrap();
+        role_repo
+            .assign_to_user(owner_id, &RoleId::admin(), None)
+            .unwrap();
+
+        let public = Project::new("Public".to_string(), owner_id);
+        let mut private = Project::new("Private".to_string(), owner_id);
+        private.is_private = true;
+        repo.create(&public).unwrap();
+        repo.create(&private).unwrap();
+
+        assert_eq!(repo.find_visible_to(owner_id).unwrap().len(), 2);
+
+        let visible = repo.find_visible_to(outsider.id).unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].name, "Public");
+
+        repo.set_member_role(private.id, outsider.id, &RoleId::viewer())
+            .unwrap();
+        assert_eq!(repo.find_visible_to(outsider.id).unwrap().len(), 2);
+    }
 }
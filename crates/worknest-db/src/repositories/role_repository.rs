@@ -0,0 +1,581 @@
+//! Role repository implementation
+
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+
+use worknest_core::models::{ProjectId, Role, RoleId, UserId};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Role repository for database operations
+///
+/// Besides role CRUD, this repository manages the `user_roles` assignments.
+/// A role assigned without a project is global and applies to every project;
+/// project-scoped assignments are stored with the project's ID.
+pub struct RoleRepository {
+    pool: Arc<DbPool>,
+}
+
+impl RoleRepository {
+    /// Create a new RoleRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find a role by its name
+    pub fn find_by_name(&self, name: &str) -> Result<Option<Role>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT id, name, description, created_at FROM roles WHERE name = ?1")
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let role = stmt
+            .query_row(params![name], row_to_role)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(role)
+    }
+
+    /// Find the roles that apply to a user
+    ///
+    /// Always includes the user's global roles, except non-Admin roles on
+    /// private projects. When `project_id` is given, the roles assigned to
+    /// the user for that project and those inherited through teams attached
+    /// to it are included as well.
+    pub fn find_for_user(
+        &self,
+        user_id: UserId,
+        project_id: Option<ProjectId>,
+    ) -> Result<Vec<Role>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT r.id, r.name, r.description, r.created_at
+                     FROM roles r
+                     WHERE r.id IN ({})
+                     ORDER BY r.name",
+                EFFECTIVE_ROLE_IDS
+            ))
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let roles = stmt
+            .query_map(
+                params![user_id.0.to_string(), project_scope(project_id)],
+                row_to_role,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(roles)
+    }
+
+    /// Assign a role to a user, globally or for a single project
+    ///
+    /// Assigning a role the user already holds is a no-op.
+    pub fn assign_to_user(
+        &self,
+        user_id: UserId,
+        role_id: &RoleId,
+        project_id: Option<ProjectId>,
+    ) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO user_roles (user_id, role_id, project_id, assigned_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                user_id.0.to_string(),
+                role_id.as_str(),
+                project_scope(project_id),
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown user or role".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        Ok(())
+    }
+
+    /// Revoke a role from a user, globally or for a single project
+    pub fn revoke_from_user(
+        &self,
+        user_id: UserId,
+        role_id: &RoleId,
+        project_id: Option<ProjectId>,
+    ) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM user_roles WHERE user_id = ?1 AND role_id = ?2 AND project_id = ?3",
+                params![
+                    user_id.0.to_string(),
+                    role_id.as_str(),
+                    project_scope(project_id)
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Role assignment not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Assign a user their first global role: Admin when nobody holds the
+    /// global Admin role yet, Member otherwise
+    ///
+    /// The check and the insert are one statement, so two users registering
+    /// at once cannot both become Admin.
+    ///
+    /// # Returns
+    /// The role the user was given
+    pub fn assign_initial_global_role(&self, user_id: UserId) -> Result<RoleId> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO user_roles (user_id, role_id, project_id, assigned_at)
+             SELECT ?1,
+                    CASE WHEN EXISTS (SELECT 1 FROM user_roles
+                                      WHERE role_id = 'role_admin' AND project_id = '')
+                         THEN 'role_member' ELSE 'role_admin' END,
+                    '', ?2",
+            params![user_id.0.to_string(), Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown user or role".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        let is_admin: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM user_roles
+                                WHERE user_id = ?1 AND role_id = 'role_admin' AND project_id = '')",
+                params![user_id.0.to_string()],
+                |row| row.get(0),
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(if is_admin {
+            RoleId::admin()
+        } else {
+            RoleId::member()
+        })
+    }
+
+    /// Count the users holding a role, globally or for a single project
+    pub fn count_users_with_role(
+        &self,
+        role_id: &RoleId,
+        project_id: Option<ProjectId>,
+    ) -> Result<i64> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.query_row(
+            "SELECT COUNT(*) FROM user_roles WHERE role_id = ?1 AND project_id = ?2",
+            params![role_id.as_str(), project_scope(project_id)],
+            |row| row.get(0),
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+}
+
+impl Repository<Role, RoleId> for RoleRepository {
+    fn find_by_id(&self, id: RoleId) -> Result<Option<Role>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT id, name, description, created_at FROM roles WHERE id = ?1")
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let role = stmt
+            .query_row(params![id.as_str()], row_to_role)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(role)
+    }
+
+    fn find_all(&self) -> Result<Vec<Role>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT id, name, description, created_at FROM roles ORDER BY name")
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let roles = stmt
+            .query_map([], row_to_role)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(roles)
+    }
+
+    fn create(&self, entity: &Role) -> Result<Role> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO roles (id, name, description, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                entity.id.as_str(),
+                entity.name,
+                entity.description,
+                entity.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE constraint failed") {
+                DbError::ConstraintViolation("Role already exists".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        Ok(entity.clone())
+    }
+
+    fn update(&self, entity: &Role) -> Result<Role> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE roles SET name = ?1, description = ?2 WHERE id = ?3",
+                params![entity.name, entity.description, entity.id.as_str()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Role not found".to_string()));
+        }
+
+        Ok(entity.clone())
+    }
+
+    fn delete(&self, id: RoleId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM roles WHERE id = ?1", params![id.as_str()])
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Role not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// SQL expression for a role capped at another one: `$role` itself, unless
+/// it has any permission that `$cap` lacks, in which case `$cap`
+macro_rules! capped_role {
+    ($role:literal, $cap:literal) => {
+        concat!(
+            "(CASE WHEN EXISTS (
+                SELECT 1 FROM role_permissions crp
+                WHERE crp.role_id = ",
+            $role,
+            " AND crp.permission_id NOT IN (SELECT cap.permission_id FROM role_permissions cap
+                                               WHERE cap.role_id = '",
+            $cap,
+            "'))
+            THEN '",
+            $cap,
+            "' ELSE ",
+            $role,
+            " END)"
+        )
+    };
+}
+
+/// SQL expression for the role a user role assignment (aliased `ur`) grants
+/// on a project, when `$global_on_project` holds for global assignments
+///
+/// Global roles other than Admin are capped at Viewer there, so writing to a
+/// public project takes a project or team membership.
+macro_rules! user_role_id {
+    ($global_on_project:literal) => {
+        concat!(
+            "(CASE WHEN ur.project_id = '' AND ur.role_id <> 'role_admin' AND ",
+            $global_on_project,
+            " THEN ",
+            capped_role!("ur.role_id", "role_viewer"),
+            " ELSE ur.role_id END)"
+        )
+    };
+}
+
+/// SQL expression for the role a team member (aliased `tm`) holds on the
+/// projects the team is attached to
+///
+/// Team roles are capped at Member, so managing a team never makes anyone a
+/// project admin.
+macro_rules! team_role_id {
+    () => {
+        capped_role!("tm.role_id", "role_member")
+    };
+}
+
+/// SQL predicate matching the projects (aliased `p`) on which the user bound
+/// to `?1` holds `permission_id`
+///
+/// Project-scoped roles always apply, as do the roles of teams attached to the
+/// project, capped at Member. Global roles apply to public projects, capped at
+/// Viewer; on private projects only a global Admin keeps access without
+/// membership.
+pub(crate) fn project_access_predicate(permission_id: &str) -> String {
+    format!(
+        "(EXISTS (
+            SELECT 1 FROM user_roles ur
+            JOIN role_permissions rp ON rp.role_id = {user_role}
+            WHERE ur.user_id = ?1 AND rp.permission_id = '{perm}'
+              AND (ur.project_id = p.id
+                   OR (ur.project_id = '' AND (p.is_private = 0 OR ur.role_id = 'role_admin')))
+        ) OR EXISTS (
+            SELECT 1 FROM project_teams pt
+            JOIN team_members tm ON tm.team_id = pt.team_id
+            JOIN role_permissions rp ON rp.role_id = {team_role}
+            WHERE pt.project_id = p.id AND tm.user_id = ?1 AND rp.permission_id = '{perm}'
+        ))",
+        perm = permission_id,
+        user_role = user_role_id!("1"),
+        team_role = team_role_id!()
+    )
+}
+
+/// SQL subquery selecting the IDs of the roles that apply to the user bound
+/// to `?1` on the project bound to `?2` (empty for global checks)
+///
+/// Combines global roles, project-scoped roles and the roles the user holds
+/// in teams attached to the project, capped at Member. On a project, global
+/// roles other than Admin are capped at Viewer and do not apply at all when
+/// the project is private.
+pub(crate) const EFFECTIVE_ROLE_IDS: &str = concat!(
+    "
+    SELECT ",
+    user_role_id!("?2 <> ''"),
+    " FROM user_roles ur
+    WHERE ur.user_id = ?1
+      AND (ur.project_id = ?2
+           OR (ur.project_id = ''
+               AND (ur.role_id = 'role_admin'
+                    OR NOT EXISTS (SELECT 1 FROM projects pr
+                                   WHERE pr.id = ?2 AND pr.is_private = 1))))
+    UNION
+    SELECT ",
+    team_role_id!(),
+    " FROM team_members tm
+    JOIN project_teams pt ON pt.team_id = tm.team_id
+    WHERE tm.user_id = ?1 AND pt.project_id = ?2"
+);
+
+/// Convert an optional project into the `user_roles.project_id` column value
+///
+/// Global assignments are stored with an empty project ID.
+pub(crate) fn project_scope(project_id: Option<ProjectId>) -> String {
+    project_id.map(|id| id.0.to_string()).unwrap_or_default()
+}
+
+/// Convert a database row to a Role
+fn row_to_role(row: &Row) -> rusqlite::Result<Role> {
+    let id_str: String = row.get(0)?;
+
+    Ok(Role {
+        id: RoleId::from_string(&id_str).unwrap(),
+        name: row.get(1)?,
+        description: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{project_repository::ProjectRepository, user_repository::UserRepository},
+    };
+    use worknest_core::models::{Project, User};
+
+    fn setup_test_repo() -> (RoleRepository, UserId, ProjectId) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        user_repo.create_with_password(&user, "hash").unwrap();
+
+        let project_repo = ProjectRepository::new(Arc::clone(&pool));
+        let project = Project::new("Test Project".to_string(), user.id);
+        project_repo.create(&project).unwrap();
+
+        (RoleRepository::new(pool), user.id, project.id)
+    }
+
+    #[test]
+    fn test_seeded_roles() {
+        let (repo, _, _) = setup_test_repo();
+
+        let roles = repo.find_all().unwrap();
+        assert_eq!(roles.len(), 3);
+
+        let admin = repo.find_by_id(RoleId::admin()).unwrap().unwrap();
+        assert_eq!(admin.name, "Admin");
+
+        let viewer = repo.find_by_name("Viewer").unwrap().unwrap();
+        assert_eq!(viewer.id, RoleId::viewer());
+    }
+
+    #[test]
+    fn test_create_update_delete_role() {
+        let (repo, _, _) = setup_test_repo();
+        let mut role = Role::new("Reporter".to_string(), None);
+
+        repo.create(&role).unwrap();
+        assert!(repo.create(&role).is_err());
+
+        role.description = Some("Files bugs".to_string());
+        repo.update(&role).unwrap();
+        let found = repo.find_by_id(role.id.clone()).unwrap().unwrap();
+        assert_eq!(found.description, Some("Files bugs".to_string()));
+
+        repo.delete(role.id.clone()).unwrap();
+        assert!(repo.find_by_id(role.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_global_and_project_assignments() {
+        let (repo, user_id, project_id) = setup_test_repo();
+
+        repo.assign_to_user(user_id, &RoleId::viewer(), None)
+            .unwrap();
+        repo.assign_to_user(user_id, &RoleId::admin(), Some(project_id))
+            .unwrap();
+
+        let global = repo.find_for_user(user_id, None).unwrap();
+        assert_eq!(global.len(), 1);
+        assert_eq!(global[0].id, RoleId::viewer());
+
+        let scoped = repo.find_for_user(user_id, Some(project_id)).unwrap();
+        assert_eq!(scoped.len(), 2);
+
+        let other = repo.find_for_user(user_id, Some(ProjectId::new())).unwrap();
+        assert_eq!(other.len(), 1);
+    }
+
+    #[test]
+    fn test_only_one_user_becomes_initial_admin() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = Arc::new(crate::connection::init_pool(dir.path().join("test.db")).unwrap());
+        run_migrations(&mut pool.get().unwrap()).unwrap();
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user_ids: Vec<UserId> = (0..8)
+            .map(|i| {
+                let user = User::new(format!("user{}", i), format!("user{}@example.com", i));
+                user_repo.create_with_password(&user, "hash").unwrap();
+                user.id
+            })
+            .collect();
+
+        // Register everyone at once
+        let repo = Arc::new(RoleRepository::new(pool));
+        let handles: Vec<_> = user_ids
+            .into_iter()
+            .map(|user_id| {
+                let repo = Arc::clone(&repo);
+                std::thread::spawn(move || repo.assign_initial_global_role(user_id).unwrap())
+            })
+            .collect();
+        let roles: Vec<RoleId> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(roles.iter().filter(|r| **r == RoleId::admin()).count(), 1);
+        assert_eq!(
+            repo.count_users_with_role(&RoleId::admin(), None).unwrap(),
+            1
+        );
+        assert_eq!(
+            repo.count_users_with_role(&RoleId::member(), None).unwrap(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_assign_is_idempotent_and_revoke() {
+        let (repo, user_id, project_id) = setup_test_repo();
+
+        repo.assign_to_user(user_id, &RoleId::member(), Some(project_id))
+            .unwrap();
+        repo.assign_to_user(user_id, &RoleId::member(), Some(project_id))
+            .unwrap();
+        assert_eq!(
+            repo.count_users_with_role(&RoleId::member(), Some(project_id))
+                .unwrap(),
+            1
+        );
+
+        repo.revoke_from_user(user_id, &RoleId::member(), Some(project_id))
+            .unwrap();
+        assert!(repo
+            .revoke_from_user(user_id, &RoleId::member(), Some(project_id))
+            .is_err());
+    }
+
+    #[test]
+    fn test_assign_unknown_role() {
+        let (repo, user_id, _) = setup_test_repo();
+        let result = repo.assign_to_user(user_id, &RoleId::new(), None);
+        assert!(matches!(result, Err(DbError::ConstraintViolation(_))));
+    }
+}
@@ -0,0 +1,292 @@
+//! Saved view repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{ProjectId, SavedView, SavedViewId, UserId, ViewDisplay};
+
+use crate::{
+    connection::DbPool, repositories::role_repository::project_access_predicate,
+    repository::Repository, DbError, Result,
+};
+
+/// Repository for the `saved_views` table
+pub struct SavedViewRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SavedViewRepository {
+    /// Create a new SavedViewRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find the views a user can open: their own, and the shared views of
+    /// projects where they can read tickets
+    pub fn find_visible_to(&self, user_id: UserId) -> Result<Vec<SavedView>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let sql = format!(
+            "SELECT v.id, v.owner_id, v.project_id, v.name, v.query, v.sort, v.display, v.shared,
+                    v.created_at, v.updated_at
+             FROM saved_views v
+             LEFT JOIN projects p ON p.id = v.project_id
+             WHERE v.owner_id = ?1 OR (v.shared = 1 AND p.id IS NOT NULL AND {})
+             ORDER BY v.name COLLATE NOCASE",
+            project_access_predicate("perm_ticket_read")
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let views = stmt
+            .query_map(params![user_id.0.to_string()], row_to_view)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(views)
+    }
+}
+
+impl Repository<SavedView, SavedViewId> for SavedViewRepository {
+    fn find_by_id(&self, id: SavedViewId) -> Result<Option<SavedView>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, owner_id, project_id, name, query, sort, display, shared,
+                        created_at, updated_at
+                 FROM saved_views WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let view = stmt
+            .query_row(params![id.0.to_string()], row_to_view)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(view)
+    }
+
+    fn find_all(&self) -> Result<Vec<SavedView>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, owner_id, project_id, name, query, sort, display, shared,
+                        created_at, updated_at
+                 FROM saved_views ORDER BY name COLLATE NOCASE",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let views = stmt
+            .query_map([], row_to_view)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(views)
+    }
+
+    fn create(&self, entity: &SavedView) -> Result<SavedView> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO saved_views
+                 (id, owner_id, project_id, name, query, sort, display, shared, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                entity.id.0.to_string(),
+                entity.owner_id.0.to_string(),
+                entity.project_id.map(|id| id.0.to_string()),
+                entity.name,
+                entity.query,
+                entity.sort,
+                entity.display.as_str(),
+                entity.shared,
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(entity.clone())
+    }
+
+    fn update(&self, entity: &SavedView) -> Result<SavedView> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let updated_at = Utc::now();
+        let rows_affected = conn
+            .execute(
+                "UPDATE saved_views
+                 SET project_id = ?1, name = ?2, query = ?3, sort = ?4, display = ?5, shared = ?6,
+                     updated_at = ?7
+                 WHERE id = ?8",
+                params![
+                    entity.project_id.map(|id| id.0.to_string()),
+                    entity.name,
+                    entity.query,
+                    entity.sort,
+                    entity.display.as_str(),
+                    entity.shared,
+                    updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Saved view not found".to_string()));
+        }
+
+        let mut updated = entity.clone();
+        updated.updated_at = updated_at;
+        Ok(updated)
+    }
+
+    fn delete(&self, id: SavedViewId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM saved_views WHERE id = ?1",
+                params![id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Saved view not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a database row to a SavedView
+fn row_to_view(row: &Row) -> rusqlite::Result<SavedView> {
+    let id_str: String = row.get(0)?;
+    let owner_id_str: String = row.get(1)?;
+    let project_id: Option<String> = row.get(2)?;
+    let display_str: String = row.get(6)?;
+    let created_at: DateTime<Utc> = row.get(8)?;
+    let updated_at: DateTime<Utc> = row.get(9)?;
+
+    Ok(SavedView {
+        id: SavedViewId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        owner_id: UserId::from_uuid(Uuid::parse_str(&owner_id_str).unwrap()),
+        project_id: project_id.map(|s| ProjectId::from_uuid(Uuid::parse_str(&s).unwrap())),
+        name: row.get(3)?,
+        query: row.get(4)?,
+        sort: row.get(5)?,
+        display: ViewDisplay::from_name(&display_str).unwrap_or(ViewDisplay::List),
+        shared: row.get(7)?,
+        created_at,
+        updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, role_repository::RoleRepository,
+            user_repository::UserRepository,
+        },
+    };
+    use worknest_core::models::{Project, RoleId, User};
+
+    #[test]
+    fn test_create_update_and_delete_view() {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        user_repo.create_with_password(&user, "hash").unwrap();
+
+        let repo = SavedViewRepository::new(pool);
+        let mut view = SavedView::new(user.id, "Mine".to_string(), "assignee:me".to_string());
+        view.sort = "-priority".to_string();
+        repo.create(&view).unwrap();
+
+        let found = repo.find_by_id(view.id).unwrap().unwrap();
+        assert_eq!(found, view);
+
+        view.name = "Assigned to me".to_string();
+        repo.update(&view).unwrap();
+        assert_eq!(
+            repo.find_by_id(view.id).unwrap().unwrap().name,
+            "Assigned to me"
+        );
+
+        repo.delete(view.id).unwrap();
+        assert!(repo.find_by_id(view.id).unwrap().is_none());
+        assert!(repo.delete(view.id).is_err());
+    }
+
+    #[test]
+    fn test_find_visible_to_includes_shared_project_views() {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let owner = User::new("owner".to_string(), "owner@example.com".to_string());
+        let other = User::new("other".to_string(), "other@example.com".to_string());
+        user_repo.create_with_password(&owner, "hash").unwrap();
+        user_repo.create_with_password(&other, "hash").unwrap();
+
+        let project_repo = ProjectRepository::new(Arc::clone(&pool));
+        let mut project = Project::new("Private".to_string(), owner.id);
+        project.is_private = true;
+        project_repo.create(&project).unwrap();
+
+        let repo = SavedViewRepository::new(Arc::clone(&pool));
+        let personal = SavedView::new(owner.id, "Personal".to_string(), String::new());
+        let mut shared = SavedView::new(owner.id, "Team bugs".to_string(), "type:bug".to_string());
+        shared.project_id = Some(project.id);
+        shared.shared = true;
+        repo.create(&personal).unwrap();
+        repo.create(&shared).unwrap();
+
+        assert_eq!(repo.find_visible_to(owner.id).unwrap().len(), 2);
+        assert!(repo.find_visible_to(other.id).unwrap().is_empty());
+
+        RoleRepository::new(Arc::clone(&pool))
+            .assign_to_user(other.id, &RoleId::viewer(), Some(project.id))
+            .unwrap();
+
+        let visible = repo.find_visible_to(other.id).unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id, shared.id);
+    }
+}
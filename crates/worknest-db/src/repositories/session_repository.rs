@@ -0,0 +1,407 @@
+//! Session repository implementation
+
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{RefreshToken, Session, SessionId, UserId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Session repository for database operations
+pub struct SessionRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SessionRepository {
+    /// Create a new SessionRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Persist a new session
+    pub fn create(&self, session: &Session) -> Result<Session> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO sessions (id, user_id, token, user_agent, expires_at, created_at, revoked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                session.id.0.to_string(),
+                session.user_id.0.to_string(),
+                session.id.0.to_string(),
+                session.user_agent,
+                session.expires_at.to_rfc3339(),
+                session.created_at.to_rfc3339(),
+                session.revoked_at.map(|t| t.to_rfc3339()),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(session.clone())
+    }
+
+    /// Find a session by ID
+    pub fn find_by_id(&self, id: SessionId) -> Result<Option<Session>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, user_agent, created_at, expires_at, revoked_at
+                 FROM sessions WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let session = stmt
+            .query_row(params![id.0.to_string()], row_to_session)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(session)
+    }
+
+    /// Find the sessions of a user that are neither revoked nor expired
+    pub fn find_active_for_user(&self, user_id: UserId) -> Result<Vec<Session>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, user_agent, created_at, expires_at, revoked_at
+                 FROM sessions
+                 WHERE user_id = ?1 AND revoked_at IS NULL AND expires_at > ?2
+                 ORDER BY created_at DESC",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let sessions = stmt
+            .query_map(
+                params![user_id.0.to_string(), Utc::now().to_rfc3339()],
+                row_to_session,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(sessions)
+    }
+
+    /// Revoke a session
+    ///
+    /// Revoking an already revoked session is a no-op.
+    pub fn revoke(&self, id: SessionId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE sessions SET revoked_at = COALESCE(revoked_at, ?1) WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Session not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Revoke every active session of a user, optionally keeping one
+    ///
+    /// Returns the number of sessions revoked.
+    pub fn revoke_all_for_user(&self, user_id: UserId, except: Option<SessionId>) -> Result<usize> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "UPDATE sessions SET revoked_at = ?1
+             WHERE user_id = ?2 AND revoked_at IS NULL AND id != ?3",
+            params![
+                Utc::now().to_rfc3339(),
+                user_id.0.to_string(),
+                except.map(|id| id.0.to_string()).unwrap_or_default(),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+
+    /// Store a new refresh token
+    pub fn create_refresh_token(&self, token: &RefreshToken) -> Result<RefreshToken> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO refresh_tokens (token_hash, session_id, created_at, expires_at, used_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                token.token_hash,
+                token.session_id.0.to_string(),
+                token.created_at.to_rfc3339(),
+                token.expires_at.to_rfc3339(),
+                token.used_at.map(|t| t.to_rfc3339()),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(token.clone())
+    }
+
+    /// Find a refresh token by the hash of its value
+    pub fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT token_hash, session_id, created_at, expires_at, used_at
+                 FROM refresh_tokens WHERE token_hash = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let token = stmt
+            .query_row(params![token_hash], row_to_refresh_token)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(token)
+    }
+
+    /// Exchange a refresh token for its successor
+    ///
+    /// Marks `used_hash` as used, stores `successor` and extends the session
+    /// to the successor's expiration, all in one transaction. Returns `false`
+    /// without changing anything if `used_hash` was already used, so that
+    /// two concurrent refreshes cannot both succeed.
+    pub fn rotate_refresh_token(&self, used_hash: &str, successor: &RefreshToken) -> Result<bool> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let rows_affected = tx
+            .execute(
+                "UPDATE refresh_tokens SET used_at = ?1 WHERE token_hash = ?2 AND used_at IS NULL",
+                params![Utc::now().to_rfc3339(), used_hash],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Ok(false);
+        }
+
+        tx.execute(
+            "INSERT INTO refresh_tokens (token_hash, session_id, created_at, expires_at, used_at)
+             VALUES (?1, ?2, ?3, ?4, NULL)",
+            params![
+                successor.token_hash,
+                successor.session_id.0.to_string(),
+                successor.created_at.to_rfc3339(),
+                successor.expires_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.execute(
+            "UPDATE sessions SET expires_at = ?1 WHERE id = ?2",
+            params![
+                successor.expires_at.to_rfc3339(),
+                successor.session_id.0.to_string()
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(true)
+    }
+
+    /// Delete sessions that expired before now
+    ///
+    /// Returns the number of sessions deleted.
+    pub fn delete_expired(&self) -> Result<usize> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "DELETE FROM sessions WHERE expires_at <= ?1",
+            params![Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+}
+
+/// Convert a database row to a Session
+fn row_to_session(row: &Row) -> rusqlite::Result<Session> {
+    let id_str: String = row.get(0)?;
+    let user_id_str: String = row.get(1)?;
+
+    Ok(Session {
+        id: SessionId(Uuid::parse_str(&id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        user_agent: row.get(2)?,
+        created_at: row.get(3)?,
+        expires_at: row.get(4)?,
+        revoked_at: row.get(5)?,
+    })
+}
+
+/// Convert a database row to a RefreshToken
+fn row_to_refresh_token(row: &Row) -> rusqlite::Result<RefreshToken> {
+    let session_id_str: String = row.get(1)?;
+
+    Ok(RefreshToken {
+        token_hash: row.get(0)?,
+        session_id: SessionId(Uuid::parse_str(&session_id_str).unwrap()),
+        created_at: row.get(2)?,
+        expires_at: row.get(3)?,
+        used_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool, migrations::run_migrations,
+        repositories::user_repository::UserRepository,
+    };
+    use chrono::Duration;
+    use worknest_core::models::User;
+
+    fn setup_test_repo() -> (SessionRepository, UserId) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        user_repo.create_with_password(&user, "hash").unwrap();
+
+        (SessionRepository::new(pool), user.id)
+    }
+
+    fn new_session(user_id: UserId, expires_in: Duration) -> Session {
+        Session::new(
+            SessionId::new(),
+            user_id,
+            Some("test-agent".to_string()),
+            Utc::now() + expires_in,
+        )
+    }
+
+    #[test]
+    fn test_create_and_revoke_session() {
+        let (repo, user_id) = setup_test_repo();
+        let session = new_session(user_id, Duration::hours(1));
+
+        repo.create(&session).unwrap();
+        let found = repo.find_by_id(session.id).unwrap().unwrap();
+        assert!(found.is_active());
+        assert_eq!(found.user_agent, Some("test-agent".to_string()));
+
+        repo.revoke(session.id).unwrap();
+        assert!(!repo.find_by_id(session.id).unwrap().unwrap().is_active());
+        assert!(repo.revoke(SessionId::new()).is_err());
+    }
+
+    #[test]
+    fn test_find_active_for_user() {
+        let (repo, user_id) = setup_test_repo();
+        let active = new_session(user_id, Duration::hours(1));
+        let expired = new_session(user_id, -Duration::hours(1));
+        let revoked = new_session(user_id, Duration::hours(1));
+
+        repo.create(&active).unwrap();
+        repo.create(&expired).unwrap();
+        repo.create(&revoked).unwrap();
+        repo.revoke(revoked.id).unwrap();
+
+        let sessions = repo.find_active_for_user(user_id).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, active.id);
+
+        assert_eq!(repo.delete_expired().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_revoke_all_for_user() {
+        let (repo, user_id) = setup_test_repo();
+        let current = new_session(user_id, Duration::hours(1));
+        let other = new_session(user_id, Duration::hours(1));
+
+        repo.create(&current).unwrap();
+        repo.create(&other).unwrap();
+
+        assert_eq!(
+            repo.revoke_all_for_user(user_id, Some(current.id)).unwrap(),
+            1
+        );
+        assert_eq!(repo.find_active_for_user(user_id).unwrap().len(), 1);
+
+        assert_eq!(repo.revoke_all_for_user(user_id, None).unwrap(), 1);
+        assert!(repo.find_active_for_user(user_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rotate_refresh_token() {
+        let (repo, user_id) = setup_test_repo();
+        let session = new_session(user_id, Duration::hours(1));
+        repo.create(&session).unwrap();
+
+        let first = RefreshToken::new(
+            "first".to_string(),
+            session.id,
+            Utc::now() + Duration::hours(1),
+        );
+        repo.create_refresh_token(&first).unwrap();
+        assert!(!repo.find_refresh_token("first").unwrap().unwrap().is_used());
+
+        let second = RefreshToken::new(
+            "second".to_string(),
+            session.id,
+            Utc::now() + Duration::days(30),
+        );
+        assert!(repo.rotate_refresh_token("first", &second).unwrap());
+        assert!(repo.find_refresh_token("first").unwrap().unwrap().is_used());
+        assert!(repo.find_refresh_token("second").unwrap().is_some());
+        assert_eq!(
+            repo.find_by_id(session.id).unwrap().unwrap().expires_at,
+            second.expires_at
+        );
+
+        // A used token cannot be rotated twice
+        let third = RefreshToken::new(
+            "third".to_string(),
+            session.id,
+            Utc::now() + Duration::days(30),
+        );
+        assert!(!repo.rotate_refresh_token("first", &third).unwrap());
+        assert!(repo.find_refresh_token("third").unwrap().is_none());
+    }
+}
@@ -0,0 +1,563 @@
+//! Sprint repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{ProjectId, Sprint, SprintId, SprintState, TicketId};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Repository for the `sprints` table and the sprint tickets are planned into
+pub struct SprintRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SprintRepository {
+    /// Create a new SprintRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find all sprints of a project, in start date order
+    ///
+    /// Sprints without a start date come last.
+    pub fn find_by_project(&self, project_id: ProjectId) -> Result<Vec<Sprint>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, goal, start_date, end_date, state, created_at, updated_at
+                 FROM sprints WHERE project_id = ?1
+                 ORDER BY start_date IS NULL, start_date, created_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let sprints = stmt
+            .query_map(params![project_id.0.to_string()], row_to_sprint)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(sprints)
+    }
+
+    /// Find the active sprint of a project, if any
+    pub fn find_active(&self, project_id: ProjectId) -> Result<Option<Sprint>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, goal, start_date, end_date, state, created_at, updated_at
+                 FROM sprints WHERE project_id = ?1 AND state = 'active'",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let sprint = stmt
+            .query_row(params![project_id.0.to_string()], row_to_sprint)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(sprint)
+    }
+
+    /// Start a planned sprint
+    ///
+    /// Fails with a constraint violation if another sprint of the project is
+    /// already active.
+    pub fn start(&self, id: SprintId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE sprints SET state = 'active', updated_at = ?1
+                 WHERE id = ?2 AND state = 'planned'",
+                params![Utc::now().to_rfc3339(), id.0.to_string()],
+            )
+            .map_err(map_one_active)?;
+
+        if rows_affected == 0 {
+            return Err(DbError::ConstraintViolation(
+                "Only planned sprints can be started".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Find the IDs of the tickets planned into a sprint
+    pub fn find_ticket_ids(&self, id: SprintId) -> Result<Vec<TicketId>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT id FROM tickets WHERE sprint_id = ?1 ORDER BY rowid")
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let ids = stmt
+            .query_map(params![id.0.to_string()], |row| row.get::<_, String>(0))
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        ids.iter()
+            .map(|id| TicketId::from_string(id).map_err(|e| DbError::Query(e.to_string())))
+            .collect()
+    }
+
+    /// Close an active sprint
+    ///
+    /// Tickets of the sprint whose status is not in the done category move
+    /// to `next`, or to the backlog if it is `None`. `next` must be a
+    /// planned sprint of the same project. Returns the IDs of the tickets
+    /// moved.
+    pub fn close(&self, id: SprintId, next: Option<SprintId>) -> Result<Vec<TicketId>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let rows_affected = tx
+            .execute(
+                "UPDATE sprints SET state = 'closed', updated_at = ?1
+                 WHERE id = ?2 AND state = 'active'",
+                params![Utc::now().to_rfc3339(), id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::ConstraintViolation(
+                "Only active sprints can be closed".to_string(),
+            ));
+        }
+
+        if let Some(next) = next {
+            let planned: bool = tx
+                .query_row(
+                    "SELECT EXISTS(SELECT 1 FROM sprints n JOIN sprints s ON s.project_id = n.project_id
+                                   WHERE s.id = ?1 AND n.id = ?2 AND n.state = 'planned')",
+                    params![id.0.to_string(), next.0.to_string()],
+                    |row| row.get(0),
+                )
+                .map_err(|e| DbError::Query(e.to_string()))?;
+
+            if !planned {
+                return Err(DbError::ConstraintViolation(
+                    "Unfinished tickets can only move to a planned sprint of the same project"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let moved = {
+            let mut stmt = tx
+                .prepare(
+                    "UPDATE tickets SET sprint_id = ?1, updated_at = ?2
+                     WHERE sprint_id = ?3 AND status_category <> 'done'
+                     RETURNING id",
+                )
+                .map_err(|e| DbError::Query(e.to_string()))?;
+            let ids = stmt
+                .query_map(
+                    params![
+                        next.map(|id| id.0.to_string()),
+                        Utc::now().to_rfc3339(),
+                        id.0.to_string()
+                    ],
+                    |row| row.get::<_, String>(0),
+                )
+                .map_err(|e| DbError::Query(e.to_string()))?
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| DbError::Query(e.to_string()))?;
+            ids.iter()
+                .map(|id| TicketId::from_string(id).map_err(|e| DbError::Query(e.to_string())))
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(moved)
+    }
+
+    /// Plan tickets into a sprint, taking them out of any other sprint
+    ///
+    /// The sprint must not be closed and every ticket must belong to its
+    /// project.
+    pub fn add_tickets(&self, id: SprintId, ticket_ids: &[TicketId]) -> Result<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let now = Utc::now().to_rfc3339();
+        for ticket_id in ticket_ids {
+            let updated = tx
+                .execute(
+                    "UPDATE tickets SET sprint_id = ?1, updated_at = ?2
+                     WHERE id = ?3 AND project_id =
+                         (SELECT project_id FROM sprints WHERE id = ?1 AND state <> 'closed')",
+                    params![id.0.to_string(), now, ticket_id.0.to_string()],
+                )
+                .map_err(|e| DbError::Query(e.to_string()))?;
+
+            if updated == 0 {
+                return Err(DbError::ConstraintViolation(format!(
+                    "Ticket {} cannot be added to this sprint",
+                    ticket_id
+                )));
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Move a ticket of a sprint back to the backlog
+    pub fn remove_ticket(&self, id: SprintId, ticket_id: TicketId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE tickets SET sprint_id = NULL, updated_at = ?1
+                 WHERE id = ?2 AND sprint_id = ?3",
+                params![
+                    Utc::now().to_rfc3339(),
+                    ticket_id.0.to_string(),
+                    id.0.to_string()
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(
+                "Ticket is not in this sprint".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Repository<Sprint, SprintId> for SprintRepository {
+    fn find_by_id(&self, id: SprintId) -> Result<Option<Sprint>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, goal, start_date, end_date, state, created_at, updated_at
+                 FROM sprints WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let sprint = stmt
+            .query_row(params![id.0.to_string()], row_to_sprint)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(sprint)
+    }
+
+    fn find_all(&self) -> Result<Vec<Sprint>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, name, goal, start_date, end_date, state, created_at, updated_at
+                 FROM sprints ORDER BY created_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let sprints = stmt
+            .query_map([], row_to_sprint)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(sprints)
+    }
+
+    fn create(&self, entity: &Sprint) -> Result<Sprint> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO sprints (id, project_id, name, goal, start_date, end_date, state,
+                                  created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entity.id.0.to_string(),
+                entity.project_id.0.to_string(),
+                entity.name.trim(),
+                entity.goal,
+                entity.start_date,
+                entity.end_date,
+                entity.state.as_str(),
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(map_one_active)?;
+
+        let mut created = entity.clone();
+        created.name = entity.name.trim().to_string();
+        Ok(created)
+    }
+
+    /// Update the name, goal and dates of a sprint
+    ///
+    /// The state only changes through [`SprintRepository::start`] and
+    /// [`SprintRepository::close`].
+    fn update(&self, entity: &Sprint) -> Result<Sprint> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let updated_at = Utc::now();
+        let rows_affected = conn
+            .execute(
+                "UPDATE sprints SET name = ?1, goal = ?2, start_date = ?3, end_date = ?4, updated_at = ?5
+                 WHERE id = ?6",
+                params![
+                    entity.name.trim(),
+                    entity.goal,
+                    entity.start_date,
+                    entity.end_date,
+                    updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Sprint not found".to_string()));
+        }
+
+        let mut updated = entity.clone();
+        updated.name = entity.name.trim().to_string();
+        updated.updated_at = updated_at;
+        Ok(updated)
+    }
+
+    /// Delete a sprint; its tickets go back to the backlog
+    fn delete(&self, id: SprintId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM sprints WHERE id = ?1",
+                params![id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Sprint not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Report a second active sprint in a project as a constraint violation
+fn map_one_active(e: rusqlite::Error) -> DbError {
+    if e.to_string().contains("UNIQUE constraint failed") {
+        DbError::ConstraintViolation("Another sprint is already active in this project".to_string())
+    } else {
+        DbError::Query(e.to_string())
+    }
+}
+
+/// Convert a database row to a Sprint
+fn row_to_sprint(row: &Row) -> rusqlite::Result<Sprint> {
+    let id_str: String = row.get(0)?;
+    let project_id_str: String = row.get(1)?;
+    let state: String = row.get(6)?;
+    let created_at: DateTime<Utc> = row.get(7)?;
+    let updated_at: DateTime<Utc> = row.get(8)?;
+
+    Ok(Sprint {
+        id: SprintId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        project_id: ProjectId::from_uuid(Uuid::parse_str(&project_id_str).unwrap()),
+        name: row.get(2)?,
+        goal: row.get(3)?,
+        start_date: row.get(4)?,
+        end_date: row.get(5)?,
+        state: SprintState::from_name(&state).unwrap_or(SprintState::Planned),
+        created_at,
+        updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_query::TicketQuery,
+            ticket_repository::TicketRepository, user_repository::UserRepository,
+        },
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User, Workflow};
+
+    fn setup() -> (Arc<DbPool>, Project, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        (pool, project, user)
+    }
+
+    #[test]
+    fn test_only_one_active_sprint() {
+        let (pool, project, _) = setup();
+        let repo = SprintRepository::new(pool);
+
+        let first = Sprint::new(project.id, "Sprint 1".to_string());
+        let second = Sprint::new(project.id, "Sprint 2".to_string());
+        repo.create(&first).unwrap();
+        repo.create(&second).unwrap();
+
+        repo.start(first.id).unwrap();
+        assert!(matches!(
+            repo.start(second.id),
+            Err(DbError::ConstraintViolation(_))
+        ));
+        assert_eq!(repo.find_active(project.id).unwrap().unwrap().id, first.id);
+
+        // A sprint cannot be started twice
+        assert!(repo.start(first.id).is_err());
+    }
+
+    #[test]
+    fn test_close_moves_unfinished_tickets() {
+        let (pool, project, user) = setup();
+        let repo = SprintRepository::new(Arc::clone(&pool));
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+        let workflow = Workflow::default_for(project.id);
+
+        let current = Sprint::new(project.id, "Sprint 1".to_string());
+        let next = Sprint::new(project.id, "Sprint 2".to_string());
+        repo.create(&current).unwrap();
+        repo.create(&next).unwrap();
+
+        let mut done = Ticket::new(project.id, "Done".to_string(), TicketType::Task, user.id);
+        done.update_status(workflow.status("done").unwrap());
+        let open = Ticket::new(project.id, "Open".to_string(), TicketType::Task, user.id);
+        tickets.create(&done).unwrap();
+        tickets.create(&open).unwrap();
+
+        repo.add_tickets(current.id, &[done.id, open.id]).unwrap();
+        repo.start(current.id).unwrap();
+
+        assert_eq!(
+            repo.find_ticket_ids(current.id).unwrap(),
+            vec![done.id, open.id]
+        );
+        assert_eq!(
+            repo.close(current.id, Some(next.id)).unwrap(),
+            vec![open.id]
+        );
+
+        let page = tickets.query(&TicketQuery::new().sprint(next.id)).unwrap();
+        assert_eq!(page.tickets.len(), 1);
+        assert_eq!(page.tickets[0].id, open.id);
+        let done = tickets.find_by_id(done.id).unwrap().unwrap();
+        assert_eq!(done.sprint_id, Some(current.id));
+
+        // Closed sprints cannot be closed again or take new tickets
+        assert!(repo.close(current.id, None).is_err());
+        assert!(matches!(
+            repo.add_tickets(current.id, &[open.id]),
+            Err(DbError::ConstraintViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_close_to_backlog_and_remove() {
+        let (pool, project, user) = setup();
+        let repo = SprintRepository::new(Arc::clone(&pool));
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+
+        let sprint = Sprint::new(project.id, "Sprint 1".to_string());
+        repo.create(&sprint).unwrap();
+
+        let first = Ticket::new(project.id, "First".to_string(), TicketType::Task, user.id);
+        let second = Ticket::new(project.id, "Second".to_string(), TicketType::Bug, user.id);
+        tickets.create(&first).unwrap();
+        tickets.create(&second).unwrap();
+        repo.add_tickets(sprint.id, &[first.id, second.id]).unwrap();
+
+        repo.remove_ticket(sprint.id, first.id).unwrap();
+        assert!(matches!(
+            repo.remove_ticket(sprint.id, first.id),
+            Err(DbError::NotFound(_))
+        ));
+
+        // Tickets of other projects cannot be added
+        let other = Project::new("Other".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&other)
+            .unwrap();
+        let foreign = Ticket::new(other.id, "Foreign".to_string(), TicketType::Task, user.id);
+        tickets.create(&foreign).unwrap();
+        assert!(repo.add_tickets(sprint.id, &[foreign.id]).is_err());
+
+        repo.start(sprint.id).unwrap();
+        assert_eq!(repo.close(sprint.id, None).unwrap(), vec![second.id]);
+
+        let backlog = tickets
+            .query(&TicketQuery::new().project(project.id).backlog())
+            .unwrap();
+        assert_eq!(backlog.total, 2);
+    }
+}
@@ -0,0 +1,558 @@
+//! Team repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{ProjectId, RoleId, Team, TeamId, TeamMember, UserId};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Team repository for database operations
+///
+/// Besides team CRUD, this repository manages `team_members` and the
+/// `project_teams` attachments through which team members inherit access
+/// to projects.
+pub struct TeamRepository {
+    pool: Arc<DbPool>,
+}
+
+impl TeamRepository {
+    /// Create a new TeamRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find all teams a user is a member of
+    pub fn find_by_member(&self, user_id: UserId) -> Result<Vec<Team>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.name, t.description, t.created_by, t.created_at, t.updated_at
+                 FROM teams t
+                 JOIN team_members tm ON tm.team_id = t.id
+                 WHERE tm.user_id = ?1
+                 ORDER BY t.name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let teams = stmt
+            .query_map(params![user_id.0.to_string()], row_to_team)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(teams)
+    }
+
+    /// Find all teams attached to a project
+    pub fn find_by_project(&self, project_id: ProjectId) -> Result<Vec<Team>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.name, t.description, t.created_by, t.created_at, t.updated_at
+                 FROM teams t
+                 JOIN project_teams pt ON pt.team_id = t.id
+                 WHERE pt.project_id = ?1
+                 ORDER BY t.name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let teams = stmt
+            .query_map(params![project_id.0.to_string()], row_to_team)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(teams)
+    }
+
+    /// Find the members of a team
+    pub fn find_members(&self, team_id: TeamId) -> Result<Vec<TeamMember>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT tm.team_id, tm.user_id, tm.role_id, tm.joined_at
+                 FROM team_members tm
+                 JOIN users u ON u.id = tm.user_id
+                 WHERE tm.team_id = ?1
+                 ORDER BY u.username",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let members = stmt
+            .query_map(params![team_id.0.to_string()], row_to_member)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(members)
+    }
+
+    /// Find a single member of a team
+    pub fn find_member(&self, team_id: TeamId, user_id: UserId) -> Result<Option<TeamMember>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT team_id, user_id, role_id, joined_at
+                 FROM team_members WHERE team_id = ?1 AND user_id = ?2",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let member = stmt
+            .query_row(
+                params![team_id.0.to_string(), user_id.0.to_string()],
+                row_to_member,
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(member)
+    }
+
+    /// Add a member to a team or change the role of an existing member
+    ///
+    /// Changing the role keeps the member's original join date.
+    pub fn set_member_role(
+        &self,
+        team_id: TeamId,
+        user_id: UserId,
+        role_id: &RoleId,
+    ) -> Result<TeamMember> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO team_members (team_id, user_id, role_id, joined_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (team_id, user_id) DO UPDATE SET role_id = excluded.role_id",
+            params![
+                team_id.0.to_string(),
+                user_id.0.to_string(),
+                role_id.as_str(),
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown team, user or role".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        drop(conn);
+        self.find_member(team_id, user_id)?
+            .ok_or_else(|| DbError::NotFound("Team member not found".to_string()))
+    }
+
+    /// Remove a member from a team
+    pub fn remove_member(&self, team_id: TeamId, user_id: UserId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM team_members WHERE team_id = ?1 AND user_id = ?2",
+                params![team_id.0.to_string(), user_id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Team member not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Count the members of a team holding a role
+    pub fn count_members_with_role(&self, team_id: TeamId, role_id: &RoleId) -> Result<i64> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.query_row(
+            "SELECT COUNT(*) FROM team_members WHERE team_id = ?1 AND role_id = ?2",
+            params![team_id.0.to_string(), role_id.as_str()],
+            |row| row.get(0),
+        )
+        .map_err(|e| DbError::Query(e.to_string()))
+    }
+
+    /// Attach a team to a project, granting its members access
+    ///
+    /// Attaching an already attached team is a no-op.
+    pub fn attach_to_project(&self, team_id: TeamId, project_id: ProjectId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO project_teams (project_id, team_id, added_at)
+             VALUES (?1, ?2, ?3)",
+            params![
+                project_id.0.to_string(),
+                team_id.0.to_string(),
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown team or project".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        Ok(())
+    }
+
+    /// Detach a team from a project
+    pub fn detach_from_project(&self, team_id: TeamId, project_id: ProjectId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM project_teams WHERE project_id = ?1 AND team_id = ?2",
+                params![project_id.0.to_string(), team_id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(
+                "Team is not attached to project".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Repository<Team, TeamId> for TeamRepository {
+    fn find_by_id(&self, id: TeamId) -> Result<Option<Team>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, description, created_by, created_at, updated_at
+                 FROM teams WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let team = stmt
+            .query_row(params![id.0.to_string()], row_to_team)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(team)
+    }
+
+    fn find_all(&self) -> Result<Vec<Team>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, description, created_by, created_at, updated_at
+                 FROM teams ORDER BY name",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let teams = stmt
+            .query_map([], row_to_team)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(teams)
+    }
+
+    fn create(&self, entity: &Team) -> Result<Team> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO teams (id, name, description, created_by, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entity.id.0.to_string(),
+                entity.name,
+                entity.description,
+                entity.created_by.0.to_string(),
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(entity.clone())
+    }
+
+    fn update(&self, entity: &Team) -> Result<Team> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let updated_at = Utc::now();
+        let rows_affected = conn
+            .execute(
+                "UPDATE teams SET name = ?1, description = ?2, updated_at = ?3 WHERE id = ?4",
+                params![
+                    entity.name,
+                    entity.description,
+                    updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Team not found".to_string()));
+        }
+
+        let mut updated = entity.clone();
+        updated.updated_at = updated_at;
+        Ok(updated)
+    }
+
+    fn delete(&self, id: TeamId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM teams WHERE id = ?1", params![id.0.to_string()])
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Team not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a database row to a Team
+fn row_to_team(row: &Row) -> rusqlite::Result<Team> {
+    let id_str: String = row.get(0)?;
+    let created_by_str: String = row.get(3)?;
+    let created_at: DateTime<Utc> = row.get(4)?;
+    let updated_at: DateTime<Utc> = row.get(5)?;
+
+    Ok(Team {
+        id: TeamId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        name: row.get(1)?,
+        description: row.get(2)?,
+        created_by: UserId::from_uuid(Uuid::parse_str(&created_by_str).unwrap()),
+        created_at,
+        updated_at,
+    })
+}
+
+/// Convert a `team_members` row to a TeamMember
+fn row_to_member(row: &Row) -> rusqlite::Result<TeamMember> {
+    let team_id_str: String = row.get(0)?;
+    let user_id_str: String = row.get(1)?;
+    let role_id_str: String = row.get(2)?;
+
+    Ok(TeamMember {
+        team_id: TeamId::from_uuid(Uuid::parse_str(&team_id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        role_id: RoleId::from_string(&role_id_str).unwrap(),
+        joined_at: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            permission_repository::PermissionRepository, project_repository::ProjectRepository,
+            role_repository::RoleRepository, user_repository::UserRepository,
+        },
+    };
+    use worknest_core::models::{Project, User};
+
+    struct Fixture {
+        repo: TeamRepository,
+        projects: ProjectRepository,
+        permissions: PermissionRepository,
+        roles: RoleRepository,
+        users: UserRepository,
+        user_id: UserId,
+    }
+
+    fn setup_test_repo() -> Fixture {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let users = UserRepository::new(Arc::clone(&pool));
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        users.create_with_password(&user, "hash").unwrap();
+
+        Fixture {
+            repo: TeamRepository::new(Arc::clone(&pool)),
+            projects: ProjectRepository::new(Arc::clone(&pool)),
+            permissions: PermissionRepository::new(Arc::clone(&pool)),
+            roles: RoleRepository::new(pool),
+            users,
+            user_id: user.id,
+        }
+    }
+
+    #[test]
+    fn test_create_update_delete_team() {
+        let f = setup_test_repo();
+        let mut team = Team::new("Platform".to_string(), None, f.user_id);
+
+        f.repo.create(&team).unwrap();
+        assert_eq!(
+            f.repo.find_by_id(team.id).unwrap().unwrap().name,
+            "Platform"
+        );
+
+        team.description = Some("Infra and tooling".to_string());
+        f.repo.update(&team).unwrap();
+        let found = f.repo.find_by_id(team.id).unwrap().unwrap();
+        assert_eq!(found.description, Some("Infra and tooling".to_string()));
+
+        assert_eq!(f.repo.find_all().unwrap().len(), 1);
+
+        f.repo.delete(team.id).unwrap();
+        assert!(f.repo.find_by_id(team.id).unwrap().is_none());
+        assert!(f.repo.delete(team.id).is_err());
+    }
+
+    #[test]
+    fn test_members() {
+        let f = setup_test_repo();
+        let team = Team::new("Platform".to_string(), None, f.user_id);
+        f.repo.create(&team).unwrap();
+
+        f.repo
+            .set_member_role(team.id, f.user_id, &RoleId::member())
+            .unwrap();
+        let member = f
+            .repo
+            .set_member_role(team.id, f.user_id, &RoleId::admin())
+            .unwrap();
+        assert_eq!(member.role_id, RoleId::admin());
+
+        assert_eq!(f.repo.find_members(team.id).unwrap().len(), 1);
+        assert_eq!(f.repo.find_by_member(f.user_id).unwrap().len(), 1);
+        assert_eq!(
+            f.repo
+                .count_members_with_role(team.id, &RoleId::admin())
+                .unwrap(),
+            1
+        );
+
+        f.repo.remove_member(team.id, f.user_id).unwrap();
+        assert!(f.repo.find_member(team.id, f.user_id).unwrap().is_none());
+        assert!(f.repo.remove_member(team.id, f.user_id).is_err());
+    }
+
+    #[test]
+    fn test_team_members_inherit_project_access() {
+        let f = setup_test_repo();
+        let dev = User::new("dev".to_string(), "dev@example.com".to_string());
+        f.users.create_with_password(&dev, "hash").unwrap();
+
+        let mut project = Project::new("Private".to_string(), f.user_id);
+        project.is_private = true;
+        f.projects.create(&project).unwrap();
+
+        let team = Team::new("Platform".to_string(), None, f.user_id);
+        f.repo.create(&team).unwrap();
+        f.repo
+            .set_member_role(team.id, dev.id, &RoleId::member())
+            .unwrap();
+
+        assert!(f.projects.find_visible_to(dev.id).unwrap().is_empty());
+
+        f.repo.attach_to_project(team.id, project.id).unwrap();
+        assert_eq!(f.repo.find_by_project(project.id).unwrap().len(), 1);
+
+        let visible = f.projects.find_visible_to(dev.id).unwrap();
+        assert_eq!(visible.len(), 1);
+
+        let perms = f
+            .permissions
+            .find_for_user(dev.id, Some(project.id))
+            .unwrap();
+        assert!(perms.iter().any(|p| p.matches("ticket", "update")));
+        assert!(!perms.iter().any(|p| p.matches("ticket", "delete")));
+
+        f.repo.detach_from_project(team.id, project.id).unwrap();
+        assert!(f.projects.find_visible_to(dev.id).unwrap().is_empty());
+        assert!(f.repo.detach_from_project(team.id, project.id).is_err());
+    }
+
+    #[test]
+    fn test_team_roles_are_capped_at_member() {
+        let f = setup_test_repo();
+        let lead = User::new("lead".to_string(), "lead@example.com".to_string());
+        f.users.create_with_password(&lead, "hash").unwrap();
+
+        let project = Project::new("Platform".to_string(), f.user_id);
+        f.projects.create(&project).unwrap();
+
+        let team = Team::new("Platform".to_string(), None, f.user_id);
+        f.repo.create(&team).unwrap();
+        f.repo
+            .set_member_role(team.id, lead.id, &RoleId::admin())
+            .unwrap();
+        f.repo.attach_to_project(team.id, project.id).unwrap();
+
+        let roles = f.roles.find_for_user(lead.id, Some(project.id)).unwrap();
+        assert!(!roles.iter().any(|r| r.id == RoleId::admin()));
+        assert!(roles.iter().any(|r| r.id == RoleId::member()));
+
+        let perms = f
+            .permissions
+            .find_for_user(lead.id, Some(project.id))
+            .unwrap();
+        assert!(perms.iter().any(|p| p.matches("ticket", "update")));
+        assert!(!perms.iter().any(|p| p.matches("ticket", "delete")));
+    }
+}
@@ -0,0 +1,920 @@
+//! Ticket query builder
+//!
+//! Builds parameterized SQL for filtering, sorting and keyset pagination of
+//! tickets, so that listing never has to load the whole table.
+
+use chrono::{DateTime, Utc};
+use rusqlite::types::Value as SqlValue;
+use serde_json::Value as JsonValue;
+
+use worknest_core::{
+    models::{
+        normalize_status_key, CustomFieldId, FieldValue, Priority, ProjectId, SprintId,
+        StatusCategory, Ticket, TicketType, UserId,
+    },
+    query::{CompareOp, Condition, Expr, Field, ProjectRef, UserRef, Value},
+};
+
+use crate::repositories::{
+    custom_field_repository::value_rows,
+    role_repository::project_access_predicate,
+    ticket_repository::{priority_to_string, ticket_type_to_string},
+};
+
+/// Field tickets can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TicketSortField {
+    CreatedAt,
+    UpdatedAt,
+    DueDate,
+    Priority,
+    Status,
+    Title,
+}
+
+impl TicketSortField {
+    /// Parse from the name used in the API
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s {
+            "created_at" => Some(TicketSortField::CreatedAt),
+            "updated_at" => Some(TicketSortField::UpdatedAt),
+            "due_date" => Some(TicketSortField::DueDate),
+            "priority" => Some(TicketSortField::Priority),
+            "status" => Some(TicketSortField::Status),
+            "title" => Some(TicketSortField::Title),
+            _ => None,
+        }
+    }
+
+    /// SQL expression the field sorts on
+    ///
+    /// Priority sorts by rank rather than by name. Status sorts by category
+    /// and then by position in the project's workflow. Tickets without a due
+    /// date sort as if it were the earliest possible one, so every key is
+    /// non-null and comparable in a keyset condition.
+    fn sql(&self) -> &'static str {
+        match self {
+            TicketSortField::CreatedAt => "t.created_at",
+            TicketSortField::UpdatedAt => "t.updated_at",
+            TicketSortField::DueDate => "COALESCE(t.due_date, '')",
+            TicketSortField::Priority => {
+                "(CASE t.priority WHEN 'Low' THEN 0 WHEN 'Medium' THEN 1
+                                  WHEN 'High' THEN 2 WHEN 'Critical' THEN 3 END)"
+            },
+            TicketSortField::Status => {
+                "((CASE t.status_category WHEN 'todo' THEN 0 WHEN 'in_progress' THEN 1 ELSE 2 END) * 1000
+                  + COALESCE((SELECT ws.position FROM workflow_statuses ws
+                              WHERE ws.project_id = t.project_id AND ws.key = t.status), 999))"
+            },
+            TicketSortField::Title => "t.title",
+        }
+    }
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    fn sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    fn comparison(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
+
+/// One key of a multi-key sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TicketSort {
+    pub field: TicketSortField,
+    pub direction: SortDirection,
+}
+
+impl TicketSort {
+    /// Sort ascending by `field`
+    pub fn asc(field: TicketSortField) -> Self {
+        Self {
+            field,
+            direction: SortDirection::Asc,
+        }
+    }
+
+    /// Sort descending by `field`
+    pub fn desc(field: TicketSortField) -> Self {
+        Self {
+            field,
+            direction: SortDirection::Desc,
+        }
+    }
+}
+
+/// Position after the last ticket of a page
+///
+/// Holds the sort key values of that ticket followed by its ID. It is
+/// handed to clients as an opaque hex string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicketCursor(Vec<SqlValue>);
+
+impl TicketCursor {
+    pub(crate) fn from_values(values: Vec<SqlValue>) -> Self {
+        TicketCursor(values)
+    }
+
+    /// Encode as an opaque string
+    pub fn encode(&self) -> String {
+        let values: Vec<JsonValue> = self
+            .0
+            .iter()
+            .map(|value| match value {
+                SqlValue::Integer(i) => JsonValue::from(*i),
+                SqlValue::Real(f) => JsonValue::from(*f),
+                SqlValue::Text(s) => JsonValue::from(s.as_str()),
+                SqlValue::Null | SqlValue::Blob(_) => JsonValue::Null,
+            })
+            .collect();
+
+        JsonValue::Array(values)
+            .to_string()
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Decode a string produced by [`TicketCursor::encode`]
+    pub fn decode(s: &str) -> Option<Self> {
+        if !s.len().is_multiple_of(2) || !s.is_ascii() {
+            return None;
+        }
+
+        let bytes = (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+
+        let values = match serde_json::from_slice(&bytes).ok()? {
+            JsonValue::Array(values) => values,
+            _ => return None,
+        };
+
+        values
+            .into_iter()
+            .map(|value| match value {
+                JsonValue::Number(n) => n
+                    .as_i64()
+                    .map(SqlValue::Integer)
+                    .or_else(|| n.as_f64().map(SqlValue::Real)),
+                JsonValue::String(s) => Some(SqlValue::Text(s)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(TicketCursor)
+    }
+}
+
+/// Builder for filtered, sorted and paginated ticket listings
+///
+/// Filters of the same kind are OR-ed together (`status("open")` and
+/// `status("review")` match either); different kinds are AND-ed.
+#[derive(Debug, Clone, Default)]
+pub struct TicketQuery {
+    visible_to: Option<UserId>,
+    project_ids: Vec<ProjectId>,
+    statuses: Vec<String>,
+    status_categories: Vec<StatusCategory>,
+    priorities: Vec<Priority>,
+    ticket_types: Vec<TicketType>,
+    assignees: Vec<UserId>,
+    unassigned: bool,
+    labels: Vec<String>,
+    sprints: Vec<SprintId>,
+    backlog: bool,
+    custom_fields: Vec<(CustomFieldId, FieldValue)>,
+    due_before: Option<DateTime<Utc>>,
+    due_after: Option<DateTime<Utc>>,
+    expr: Option<(Expr, UserId)>,
+    sort: Vec<TicketSort>,
+    after: Option<TicketCursor>,
+    limit: Option<u32>,
+}
+
+impl TicketQuery {
+    /// Create a query matching every ticket, newest first
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only tickets in projects where `user_id` may read tickets
+    pub fn visible_to(mut self, user_id: UserId) -> Self {
+        self.visible_to = Some(user_id);
+        self
+    }
+
+    /// Only tickets in the given project
+    pub fn project(mut self, project_id: ProjectId) -> Self {
+        self.project_ids.push(project_id);
+        self
+    }
+
+    /// Only tickets with the given workflow status key
+    ///
+    /// Keys compare ignoring case, `_`, `-` and spaces.
+    pub fn status(mut self, key: &str) -> Self {
+        self.statuses.push(normalize_status_key(key));
+        self
+    }
+
+    /// Only tickets whose status belongs to the given category
+    pub fn status_category(mut self, category: StatusCategory) -> Self {
+        self.status_categories.push(category);
+        self
+    }
+
+    /// Only tickets with the given priority
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priorities.push(priority);
+        self
+    }
+
+    /// Only tickets of the given type
+    pub fn ticket_type(mut self, ticket_type: TicketType) -> Self {
+        self.ticket_types.push(ticket_type);
+        self
+    }
+
+    /// Only tickets assigned to the given user
+    pub fn assignee(mut self, assignee_id: UserId) -> Self {
+        self.assignees.push(assignee_id);
+        self
+    }
+
+    /// Only tickets without an assignee
+    pub fn unassigned(mut self) -> Self {
+        self.unassigned = true;
+        self
+    }
+
+    /// Only tickets carrying a label with the given name, ignoring case
+    pub fn label(mut self, name: impl Into<String>) -> Self {
+        self.labels.push(name.into());
+        self
+    }
+
+    /// Only tickets planned into the given sprint
+    pub fn sprint(mut self, sprint_id: SprintId) -> Self {
+        self.sprints.push(sprint_id);
+        self
+    }
+
+    /// Only tickets in no sprint
+    pub fn backlog(mut self) -> Self {
+        self.backlog = true;
+        self
+    }
+
+    /// Only tickets whose custom field holds `value`
+    ///
+    /// Text and option values compare ignoring case. A multi-select value
+    /// matches tickets that have any of its options selected.
+    pub fn custom_field(mut self, field_id: CustomFieldId, value: FieldValue) -> Self {
+        self.custom_fields.push((field_id, value));
+        self
+    }
+
+    /// Only tickets due strictly before `at`
+    pub fn due_before(mut self, at: DateTime<Utc>) -> Self {
+        self.due_before = Some(at);
+        self
+    }
+
+    /// Only tickets due at or after `at`
+    pub fn due_after(mut self, at: DateTime<Utc>) -> Self {
+        self.due_after = Some(at);
+        self
+    }
+
+    /// Only tickets matching a parsed query, with `me` meaning `current_user`
+    pub fn matching(mut self, expr: Expr, current_user: UserId) -> Self {
+        self.expr = Some((expr, current_user));
+        self
+    }
+
+    /// Add a sort key; earlier keys take precedence
+    pub fn sort_by(mut self, sort: TicketSort) -> Self {
+        self.sort.push(sort);
+        self
+    }
+
+    /// Continue after the given cursor
+    pub fn after(mut self, cursor: TicketCursor) -> Self {
+        self.after = Some(cursor);
+        self
+    }
+
+    /// Return at most `limit` tickets
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub(crate) fn page_size(&self) -> Option<u32> {
+        self.limit
+    }
+
+    /// The effective sort keys, defaulting to newest first
+    fn sort_keys(&self) -> Vec<TicketSort> {
+        if self.sort.is_empty() {
+            vec![TicketSort::desc(TicketSortField::CreatedAt)]
+        } else {
+            self.sort.clone()
+        }
+    }
+
+    /// Build the `WHERE` clause shared by the count and page queries
+    fn filters(&self) -> (Vec<String>, Vec<SqlValue>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        // The access predicate binds the user as `?1`, so it must come first
+        if let Some(user_id) = self.visible_to {
+            conditions.push(project_access_predicate("perm_ticket_read"));
+            values.push(SqlValue::Text(user_id.0.to_string()));
+        }
+
+        push_in(
+            &mut conditions,
+            &mut values,
+            "t.project_id",
+            self.project_ids.iter().map(|id| id.0.to_string()),
+        );
+        push_in(
+            &mut conditions,
+            &mut values,
+            "REPLACE(t.status, '_', '')",
+            self.statuses.iter().cloned(),
+        );
+        push_in(
+            &mut conditions,
+            &mut values,
+            "t.status_category",
+            self.status_categories
+                .iter()
+                .map(|category| category.as_str().to_string()),
+        );
+        push_in(
+            &mut conditions,
+            &mut values,
+            "t.priority",
+            self.priorities.iter().map(priority_to_string),
+        );
+        push_in(
+            &mut conditions,
+            &mut values,
+            "t.ticket_type",
+            self.ticket_types.iter().map(ticket_type_to_string),
+        );
+
+        let mut assignee_conditions = Vec::new();
+        if !self.assignees.is_empty() {
+            assignee_conditions.push(format!(
+                "t.assignee_id IN ({})",
+                placeholders(self.assignees.len())
+            ));
+            values.extend(
+                self.assignees
+                    .iter()
+                    .map(|id| SqlValue::Text(id.0.to_string())),
+            );
+        }
+        if self.unassigned {
+            assignee_conditions.push("t.assignee_id IS NULL".to_string());
+        }
+        if !assignee_conditions.is_empty() {
+            conditions.push(format!("({})", assignee_conditions.join(" OR ")));
+        }
+
+        let mut sprint_conditions = Vec::new();
+        if !self.sprints.is_empty() {
+            sprint_conditions.push(format!(
+                "t.sprint_id IN ({})",
+                placeholders(self.sprints.len())
+            ));
+            values.extend(
+                self.sprints
+                    .iter()
+                    .map(|id| SqlValue::Text(id.0.to_string())),
+            );
+        }
+        if self.backlog {
+            sprint_conditions.push("t.sprint_id IS NULL".to_string());
+        }
+        if !sprint_conditions.is_empty() {
+            conditions.push(format!("({})", sprint_conditions.join(" OR ")));
+        }
+
+        if !self.labels.is_empty() {
+            conditions.push(format!(
+                "t.id IN (SELECT tl.ticket_id FROM ticket_labels tl
+                          JOIN labels l ON l.id = tl.label_id
+                          WHERE l.name IN ({}))",
+                placeholders(self.labels.len())
+            ));
+            values.extend(self.labels.iter().map(|name| SqlValue::Text(name.clone())));
+        }
+
+        let mut field_ids: Vec<CustomFieldId> = Vec::new();
+        for (field_id, _) in &self.custom_fields {
+            if !field_ids.contains(field_id) {
+                field_ids.push(*field_id);
+            }
+        }
+        for field_id in field_ids {
+            values.push(SqlValue::Text(field_id.0.to_string()));
+            let matches: Vec<String> = self
+                .custom_fields
+                .iter()
+                .filter(|(id, _)| *id == field_id)
+                .flat_map(|(_, value)| value_rows(value))
+                .map(|(column, value)| {
+                    values.push(value);
+                    if column == "value_text" {
+                        format!("{} = ? COLLATE NOCASE", column)
+                    } else {
+                        format!("{} = ?", column)
+                    }
+                })
+                .collect();
+            conditions.push(format!(
+                "t.id IN (SELECT ticket_id FROM ticket_field_values
+                          WHERE field_id = ? AND ({}))",
+                matches.join(" OR ")
+            ));
+        }
+
+        if let Some(before) = self.due_before {
+            conditions.push("t.due_date < ?".to_string());
+            values.push(SqlValue::Text(before.to_rfc3339()));
+        }
+        if let Some(after) = self.due_after {
+            conditions.push("t.due_date >= ?".to_string());
+            values.push(SqlValue::Text(after.to_rfc3339()));
+        }
+
+        if let Some((expr, current_user)) = &self.expr {
+            conditions.push(compile_expr(expr, *current_user, Utc::now(), &mut values));
+        }
+
+        (conditions, values)
+    }
+
+    /// SQL and parameters counting every ticket that matches the filters
+    pub(crate) fn count_sql(&self) -> (String, Vec<SqlValue>) {
+        let (conditions, values) = self.filters();
+        let sql = format!(
+            "SELECT COUNT(*) FROM tickets t JOIN projects p ON p.id = t.project_id {}",
+            where_clause(&conditions)
+        );
+        (sql, values)
+    }
+
+    /// SQL and parameters selecting one page of tickets
+    ///
+    /// Rows hold the ticket columns read by `row_to_ticket`, followed by the
+    /// sort key values and ID used to build the next cursor. One row more
+    /// than the limit is fetched to tell whether another page exists.
+    pub(crate) fn page_sql(&self) -> (String, Vec<SqlValue>) {
+        let (mut conditions, mut values) = self.filters();
+        let sort = self.sort_keys();
+
+        if let Some(TicketCursor(cursor)) = &self.after {
+            let (condition, cursor_values) = keyset_condition(&sort, cursor);
+            conditions.push(condition);
+            values.extend(cursor_values);
+        }
+
+        let key_columns: String = sort
+            .iter()
+            .map(|key| format!(", {}", key.field.sql()))
+            .collect();
+        let order_by: Vec<String> = sort
+            .iter()
+            .map(|key| format!("{} {}", key.field.sql(), key.direction.sql()))
+            .chain(std::iter::once("t.id ASC".to_string()))
+            .collect();
+
+        values.push(SqlValue::Integer(
+            self.limit.map(|l| i64::from(l) + 1).unwrap_or(-1),
+        ));
+
+        let sql = format!(
+            "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
+                    t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at,
+                    t.status_category, t.sprint_id, t.parent_id, t.number
+                    {}, t.id
+             FROM tickets t
+             JOIN projects p ON p.id = t.project_id
+             {}
+             ORDER BY {}
+             LIMIT ?",
+            key_columns,
+            where_clause(&conditions),
+            order_by.join(", ")
+        );
+        (sql, values)
+    }
+
+    /// SQL and parameters for a ranked full-text search within the filters
+    ///
+    /// Rows hold the ticket columns read by `row_to_ticket`, followed by the
+    /// relevance score (higher is better) and a highlighted snippet. Matches
+    /// in the title weigh most, then description, comments and attachment
+    /// filenames. The sort keys and cursor are ignored.
+    pub(crate) fn search_sql(&self, fts_query: &str) -> (String, Vec<SqlValue>) {
+        let (mut conditions, mut values) = self.filters();
+
+        conditions.push("tickets_fts MATCH ?".to_string());
+        values.push(SqlValue::Text(fts_query.to_string()));
+        values.push(SqlValue::Integer(self.limit.map(i64::from).unwrap_or(-1)));
+
+        let sql = format!(
+            "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
+                    t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at,
+                    t.status_category, t.sprint_id, t.parent_id, t.number,
+                    -bm25(tickets_fts, 10.0, 4.0, 2.0, 1.0) AS score,
+                    snippet(tickets_fts, -1, '[', ']', '...', 16)
+             FROM tickets_fts
+             JOIN tickets t ON t.rowid = tickets_fts.rowid
+             JOIN projects p ON p.id = t.project_id
+             {}
+             ORDER BY score DESC, t.id ASC
+             LIMIT ?",
+            where_clause(&conditions)
+        );
+        (sql, values)
+    }
+
+    /// Number of extra key columns `page_sql` appends to each row
+    pub(crate) fn cursor_width(&self) -> usize {
+        self.sort_keys().len() + 1
+    }
+}
+
+/// A page of tickets
+#[derive(Debug, Clone)]
+pub struct TicketPage {
+    pub tickets: Vec<Ticket>,
+    /// Number of tickets matching the filters across all pages
+    pub total: u64,
+    /// Cursor for the next page, if there is one
+    pub next_cursor: Option<TicketCursor>,
+}
+
+/// A full-text search match
+#[derive(Debug, Clone)]
+pub struct TicketSearchHit {
+    pub ticket: Ticket,
+    /// bm25 relevance; higher is better
+    pub score: f64,
+    /// Matching excerpt with the matched terms wrapped in `[` and `]`
+    pub snippet: String,
+}
+
+/// Turn user-entered search text into an FTS5 query
+///
+/// Every word is quoted, so FTS5 operators and punctuation in the input are
+/// matched literally, and treated as a prefix. Words are AND-ed. Returns
+/// `None` when the text contains no words.
+pub(crate) fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Compile a parsed query into a SQL condition, appending its parameters
+fn compile_expr(
+    expr: &Expr,
+    current_user: UserId,
+    now: DateTime<Utc>,
+    values: &mut Vec<SqlValue>,
+) -> String {
+    match expr {
+        Expr::And(left, right) => format!(
+            "({} AND {})",
+            compile_expr(left, current_user, now, values),
+            compile_expr(right, current_user, now, values)
+        ),
+        Expr::Or(left, right) => format!(
+            "({} OR {})",
+            compile_expr(left, current_user, now, values),
+            compile_expr(right, current_user, now, values)
+        ),
+        Expr::Not(inner) => format!("(NOT {})", compile_expr(inner, current_user, now, values)),
+        Expr::Condition(condition) => compile_condition(condition, current_user, now, values),
+        Expr::Text(text) => match fts_query(text) {
+            Some(fts_query) => {
+                values.push(SqlValue::Text(fts_query));
+                "t.rowid IN (SELECT rowid FROM tickets_fts WHERE tickets_fts MATCH ?)".to_string()
+            },
+            None => "1".to_string(),
+        },
+    }
+}
+
+/// Compile one field comparison
+///
+/// Equality tests never evaluate to NULL, so that `!=` and `NOT` also match
+/// tickets where the field is empty.
+fn compile_condition(
+    condition: &Condition,
+    current_user: UserId,
+    now: DateTime<Utc>,
+    values: &mut Vec<SqlValue>,
+) -> String {
+    if condition.op.is_equality() {
+        let alternatives: Vec<String> = condition
+            .values
+            .iter()
+            .map(|value| compile_equals(condition.field, value, current_user, now, values))
+            .collect();
+        let any = format!("({})", alternatives.join(" OR "));
+        return if condition.op == CompareOp::Ne {
+            format!("(NOT {})", any)
+        } else {
+            any
+        };
+    }
+
+    let op = match condition.op {
+        CompareOp::Lt => "<",
+        CompareOp::Le => "<=",
+        CompareOp::Gt => ">",
+        _ => ">=",
+    };
+
+    // The parser only allows a single value with an ordering operator
+    match (&condition.field, &condition.values[0]) {
+        (Field::Category, Value::Category(category)) => {
+            values.push(SqlValue::Integer(category_rank(*category)));
+            format!("{} {} ?", CATEGORY_RANK_SQL, op)
+        },
+        (Field::Priority, Value::Priority(priority)) => {
+            values.push(SqlValue::Integer(priority_rank(*priority)));
+            format!("{} {} ?", TicketSortField::Priority.sql(), op)
+        },
+        (field, Value::Date(date)) => {
+            let column = date_column(*field);
+            let (lower, upper) = date.bounds(now);
+            let whole_day = lower != upper;
+            let (op, bound) = match condition.op {
+                CompareOp::Lt => ("<", lower),
+                CompareOp::Le if whole_day => ("<", upper),
+                CompareOp::Gt if whole_day => (">=", upper),
+                CompareOp::Ge => (">=", lower),
+                _ => (op, lower),
+            };
+            values.push(SqlValue::Text(bound.to_rfc3339()));
+            format!("{} {} ?", column, op)
+        },
+        _ => "0".to_string(),
+    }
+}
+
+/// Compile `field = value` so that it is never NULL
+fn compile_equals(
+    field: Field,
+    value: &Value,
+    current_user: UserId,
+    now: DateTime<Utc>,
+    values: &mut Vec<SqlValue>,
+) -> String {
+    let mut bind = |value: String| values.push(SqlValue::Text(value));
+
+    match (field, value) {
+        (Field::Status, Value::Status(key)) => {
+            bind(key.clone());
+            "REPLACE(t.status, '_', '') = ?".to_string()
+        },
+        (Field::Category, Value::Category(category)) => {
+            bind(category.as_str().to_string());
+            "t.status_category = ?".to_string()
+        },
+        (Field::Priority, Value::Priority(priority)) => {
+            bind(priority_to_string(priority));
+            "t.priority = ?".to_string()
+        },
+        (Field::Type, Value::Type(ticket_type)) => {
+            bind(ticket_type_to_string(ticket_type));
+            "t.ticket_type = ?".to_string()
+        },
+        (Field::Assignee | Field::Creator, Value::User(user)) => {
+            let column = if field == Field::Assignee {
+                "t.assignee_id"
+            } else {
+                "t.created_by"
+            };
+            match user {
+                UserRef::Me => {
+                    bind(current_user.0.to_string());
+                    format!("{} IS ?", column)
+                },
+                UserRef::Id(id) => {
+                    bind(id.0.to_string());
+                    format!("{} IS ?", column)
+                },
+                UserRef::Username(name) => {
+                    bind(name.clone());
+                    format!(
+                        "COALESCE({} IN (SELECT id FROM users WHERE username = ? COLLATE NOCASE), 0)",
+                        column
+                    )
+                },
+            }
+        },
+        (Field::Assignee, Value::Empty) => "t.assignee_id IS NULL".to_string(),
+        (Field::Label, Value::Label(name)) => {
+            bind(name.clone());
+            "t.id IN (SELECT tl.ticket_id FROM ticket_labels tl
+                      JOIN labels l ON l.id = tl.label_id WHERE l.name = ?)"
+                .to_string()
+        },
+        (Field::Label, Value::Empty) => {
+            "t.id NOT IN (SELECT ticket_id FROM ticket_labels)".to_string()
+        },
+        (Field::Project, Value::Project(project)) => match project {
+            ProjectRef::Id(id) => {
+                bind(id.0.to_string());
+                "t.project_id = ?".to_string()
+            },
+            ProjectRef::Name(name) => {
+                bind(name.clone());
+                "p.name = ? COLLATE NOCASE".to_string()
+            },
+        },
+        (field, Value::Empty) => format!("{} IS NULL", date_column(field)),
+        (field, Value::Date(date)) => {
+            let (start, end) = date.day(now);
+            bind(start.to_rfc3339());
+            bind(end.to_rfc3339());
+            let column = date_column(field);
+            format!("COALESCE({} >= ? AND {} < ?, 0)", column, column)
+        },
+        _ => "0".to_string(),
+    }
+}
+
+fn date_column(field: Field) -> &'static str {
+    match field {
+        Field::Created => "t.created_at",
+        Field::Updated => "t.updated_at",
+        _ => "t.due_date",
+    }
+}
+
+/// Orders status categories from not started to finished
+const CATEGORY_RANK_SQL: &str =
+    "(CASE t.status_category WHEN 'todo' THEN 0 WHEN 'in_progress' THEN 1 ELSE 2 END)";
+
+/// Rank used by `CATEGORY_RANK_SQL`
+fn category_rank(category: StatusCategory) -> i64 {
+    match category {
+        StatusCategory::Todo => 0,
+        StatusCategory::InProgress => 1,
+        StatusCategory::Done => 2,
+    }
+}
+
+/// Rank used by the priority sort expression
+fn priority_rank(priority: Priority) -> i64 {
+    match priority {
+        Priority::Low => 0,
+        Priority::Medium => 1,
+        Priority::High => 2,
+        Priority::Critical => 3,
+    }
+}
+
+/// Build the condition selecting rows strictly after `cursor` in sort order
+///
+/// For keys `(a, b)` and ID tiebreaker this expands to
+/// `a > ? OR (a = ? AND b > ?) OR (a = ? AND b = ? AND t.id > ?)`, with the
+/// comparison flipped for descending keys. A cursor that does not match the
+/// sort shape matches nothing.
+fn keyset_condition(sort: &[TicketSort], cursor: &[SqlValue]) -> (String, Vec<SqlValue>) {
+    if cursor.len() != sort.len() + 1 {
+        return ("0".to_string(), Vec::new());
+    }
+
+    let keys: Vec<(&str, &str)> = sort
+        .iter()
+        .map(|key| (key.field.sql(), key.direction.comparison()))
+        .chain(std::iter::once(("t.id", ">")))
+        .collect();
+
+    let mut alternatives = Vec::new();
+    let mut values = Vec::new();
+    for (i, (column, comparison)) in keys.iter().enumerate() {
+        let mut terms = Vec::new();
+        for (j, (prefix_column, _)) in keys[..i].iter().enumerate() {
+            terms.push(format!("{} = ?", prefix_column));
+            values.push(cursor[j].clone());
+        }
+        terms.push(format!("{} {} ?", column, comparison));
+        values.push(cursor[i].clone());
+        alternatives.push(format!("({})", terms.join(" AND ")));
+    }
+
+    (format!("({})", alternatives.join(" OR ")), values)
+}
+
+fn push_in(
+    conditions: &mut Vec<String>,
+    values: &mut Vec<SqlValue>,
+    column: &str,
+    items: impl ExactSizeIterator<Item = String>,
+) {
+    if items.len() == 0 {
+        return;
+    }
+    conditions.push(format!("{} IN ({})", column, placeholders(items.len())));
+    values.extend(items.map(SqlValue::Text));
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = TicketCursor(vec![
+            SqlValue::Integer(3),
+            SqlValue::Text("2025-01-01T00:00:00+00:00".to_string()),
+            SqlValue::Text("id".to_string()),
+        ]);
+
+        assert_eq!(TicketCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(TicketCursor::decode("zz"), None);
+        assert_eq!(TicketCursor::decode("7b7d"), None);
+    }
+
+    #[test]
+    fn test_fts_query_quotes_terms() {
+        assert_eq!(
+            fts_query("login \"crash\" OR"),
+            Some("\"login\"* \"\"\"crash\"\"\"* \"OR\"*".to_string())
+        );
+        assert_eq!(fts_query("   "), None);
+    }
+
+    #[test]
+    fn test_keyset_condition_expands_per_key() {
+        let sort = [
+            TicketSort::desc(TicketSortField::Priority),
+            TicketSort::asc(TicketSortField::Title),
+        ];
+        let cursor = [
+            SqlValue::Integer(2),
+            SqlValue::Text("b".to_string()),
+            SqlValue::Text("id".to_string()),
+        ];
+
+        let (sql, values) = keyset_condition(&sort, &cursor);
+
+        assert_eq!(sql.matches(" < ?").count(), 1);
+        assert!(sql.contains("t.title > ?"));
+        assert!(sql.contains("t.id > ?"));
+        assert_eq!(values.len(), 6);
+
+        let (sql, values) = keyset_condition(&sort, &cursor[..1]);
+        assert_eq!(sql, "0");
+        assert!(values.is_empty());
+    }
+}
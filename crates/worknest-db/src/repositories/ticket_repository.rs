@@ -1,15 +1,24 @@
 //! Ticket repository implementation
 
 use chrono::{DateTime, Utc};
-use rusqlite::{params, OptionalExtension, Row};
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, OptionalExtension, Row};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use worknest_core::models::{
-    Priority, ProjectId, Ticket, TicketId, TicketStatus, TicketType, UserId,
+    Priority, ProjectId, SprintId, StatusCategory, Ticket, TicketId, TicketRollup, TicketType,
+    UserId, WorkflowStatus,
 };
 
-use crate::{connection::DbPool, repository::Repository, DbError, Result};
+use crate::{
+    connection::DbPool,
+    repositories::{
+        role_repository::project_access_predicate,
+        ticket_query::{fts_query, TicketCursor, TicketPage, TicketQuery, TicketSearchHit},
+    },
+    repository::Repository,
+    DbError, Result,
+};
 
 /// Ticket repository for database operations
 pub struct TicketRepository {
@@ -32,7 +41,8 @@ impl TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets WHERE project_id = ?1 ORDER BY created_at DESC",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -46,6 +56,40 @@ impl TicketRepository {
         Ok(tickets)
     }
 
+    /// Find all tickets in projects a user can read tickets of
+    ///
+    /// Tickets of private projects are only returned to project members and
+    /// global admins.
+    pub fn find_visible_to(&self, user_id: UserId) -> Result<Vec<Ticket>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let sql = format!(
+            "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
+                    t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at,
+                    t.status_category, t.sprint_id, t.parent_id, t.number
+             FROM tickets t
+             JOIN projects p ON p.id = t.project_id
+             WHERE {}
+             ORDER BY t.created_at DESC",
+            project_access_predicate("perm_ticket_read")
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let tickets = stmt
+            .query_map(params![user_id.0.to_string()], row_to_ticket)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(tickets)
+    }
+
     /// Find all tickets assigned to a user
     pub fn find_by_assignee(&self, assignee_id: UserId) -> Result<Vec<Ticket>> {
         let conn = self
@@ -56,7 +100,8 @@ impl TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets WHERE assignee_id = ?1 ORDER BY created_at DESC",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -70,8 +115,8 @@ impl TicketRepository {
         Ok(tickets)
     }
 
-    /// Find tickets by status
-    pub fn find_by_status(&self, status: TicketStatus) -> Result<Vec<Ticket>> {
+    /// Find tickets by status key
+    pub fn find_by_status(&self, status: &str) -> Result<Vec<Ticket>> {
         let conn = self
             .pool
             .get()
@@ -80,13 +125,14 @@ impl TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets WHERE status = ?1 ORDER BY created_at DESC",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
 
         let tickets = stmt
-            .query_map(params![status_to_string(&status)], row_to_ticket)
+            .query_map(params![status], row_to_ticket)
             .map_err(|e| DbError::Query(e.to_string()))?
             .collect::<std::result::Result<Vec<_>, _>>()
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -98,7 +144,7 @@ impl TicketRepository {
     pub fn find_by_project_and_status(
         &self,
         project_id: ProjectId,
-        status: TicketStatus,
+        status: &str,
     ) -> Result<Vec<Ticket>> {
         let conn = self
             .pool
@@ -108,16 +154,14 @@ impl TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets WHERE project_id = ?1 AND status = ?2 ORDER BY created_at DESC",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
 
         let tickets = stmt
-            .query_map(
-                params![project_id.0.to_string(), status_to_string(&status)],
-                row_to_ticket,
-            )
+            .query_map(params![project_id.0.to_string(), status], row_to_ticket)
             .map_err(|e| DbError::Query(e.to_string()))?
             .collect::<std::result::Result<Vec<_>, _>>()
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -135,7 +179,8 @@ impl TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets WHERE created_by = ?1 ORDER BY created_at DESC",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -149,8 +194,185 @@ impl TicketRepository {
         Ok(tickets)
     }
 
-    /// Update ticket status
-    pub fn update_status(&self, ticket_id: TicketId, status: TicketStatus) -> Result<()> {
+    /// Find a ticket by its number within a project
+    pub fn find_by_number(&self, project_id: ProjectId, number: u32) -> Result<Option<Ticket>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, title, description, ticket_type, status, priority,
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
+                 FROM tickets WHERE project_id = ?1 AND number = ?2",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let ticket = stmt
+            .query_row(params![project_id.0.to_string(), number], row_to_ticket)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(ticket)
+    }
+
+    /// Find a ticket by the first characters of its id, such as the eight
+    /// hex digits in a branch name; `None` unless exactly one ticket matches
+    pub fn find_by_id_prefix(&self, prefix: &str) -> Result<Option<Ticket>> {
+        if prefix.is_empty() || !prefix.bytes().all(|b| b.is_ascii_hexdigit() || b == b'-') {
+            return Ok(None);
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, title, description, ticket_type, status, priority,
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
+                 FROM tickets WHERE id LIKE ?1 || '%' LIMIT 2",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let mut tickets = stmt
+            .query_map(params![prefix.to_ascii_lowercase()], row_to_ticket)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(if tickets.len() == 1 {
+            tickets.pop()
+        } else {
+            None
+        })
+    }
+
+    /// Find the children of a ticket, oldest first
+    pub fn find_children(&self, parent_id: TicketId) -> Result<Vec<Ticket>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, title, description, ticket_type, status, priority,
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
+                 FROM tickets WHERE parent_id = ?1 ORDER BY created_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let tickets = stmt
+            .query_map(params![parent_id.0.to_string()], row_to_ticket)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(tickets)
+    }
+
+    /// Find the IDs of a ticket's parent, grandparent and so on, nearest
+    /// first
+    pub fn find_ancestor_ids(&self, ticket_id: TicketId) -> Result<Vec<TicketId>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        // The depth limit ends the walk should the links ever form a cycle
+        let mut stmt = conn
+            .prepare(
+                "WITH RECURSIVE ancestors(id, depth) AS (
+                     SELECT parent_id, 1 FROM tickets WHERE id = ?1 AND parent_id IS NOT NULL
+                     UNION ALL
+                     SELECT t.parent_id, a.depth + 1 FROM tickets t
+                     JOIN ancestors a ON t.id = a.id
+                     WHERE t.parent_id IS NOT NULL AND a.depth < 100
+                 )
+                 SELECT id FROM ancestors ORDER BY depth",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let ids = stmt
+            .query_map(params![ticket_id.0.to_string()], |row| {
+                let id: String = row.get(0)?;
+                Ok(TicketId::from_uuid(Uuid::parse_str(&id).unwrap()))
+            })
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(ids)
+    }
+
+    /// Roll up the progress of the descendants of several tickets
+    ///
+    /// Tickets without children are left out.
+    pub fn find_rollups(&self, ticket_ids: &[TicketId]) -> Result<Vec<(TicketId, TicketRollup)>> {
+        if ticket_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let placeholders = vec!["?"; ticket_ids.len()].join(", ");
+        let sql = format!(
+            "WITH RECURSIVE tree(root, id, depth) AS (
+                 SELECT parent_id, id, 1 FROM tickets WHERE parent_id IN ({})
+                 UNION ALL
+                 SELECT tree.root, t.id, tree.depth + 1 FROM tickets t
+                 JOIN tree ON t.parent_id = tree.id
+                 WHERE tree.depth < 100
+             )
+             SELECT tree.root,
+                    SUM(CASE WHEN tree.depth = 1 THEN 1 ELSE 0 END),
+                    COUNT(DISTINCT tree.id),
+                    COUNT(DISTINCT CASE WHEN t.status_category = 'done' THEN tree.id END),
+                    COALESCE(SUM(t.estimate_hours), 0)
+             FROM tree
+             JOIN tickets t ON t.id = tree.id
+             GROUP BY tree.root",
+            placeholders
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let rollups = stmt
+            .query_map(
+                params_from_iter(ticket_ids.iter().map(|id| id.0.to_string())),
+                |row| {
+                    let root: String = row.get(0)?;
+                    Ok((
+                        TicketId::from_uuid(Uuid::parse_str(&root).unwrap()),
+                        TicketRollup::new(
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get::<_, f64>(4)? as f32,
+                        ),
+                    ))
+                },
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(rollups)
+    }
+
+    /// Move a ticket to a workflow status
+    pub fn update_status(&self, ticket_id: TicketId, status: &WorkflowStatus) -> Result<()> {
         let conn = self
             .pool
             .get()
@@ -158,9 +380,10 @@ impl TicketRepository {
 
         let rows_affected = conn
             .execute(
-                "UPDATE tickets SET status = ?1, updated_at = ?2 WHERE id = ?3",
+                "UPDATE tickets SET status = ?1, status_category = ?2, updated_at = ?3 WHERE id = ?4",
                 params![
-                    status_to_string(&status),
+                    status.key,
+                    status.category.as_str(),
                     Utc::now().to_rfc3339(),
                     ticket_id.0.to_string()
                 ],
@@ -220,50 +443,127 @@ impl TicketRepository {
         Ok(())
     }
 
-    /// Search tickets using full-text search
-    pub fn search(&self, query: &str, project_id: Option<ProjectId>) -> Result<Vec<Ticket>> {
+    /// Search tickets using full-text search, best matches first
+    ///
+    /// Matches the ticket title, description, comment bodies and attachment
+    /// filenames. Only tickets matching the query's filters are returned,
+    /// at most its limit.
+    pub fn search(&self, text: &str, query: &TicketQuery) -> Result<Vec<TicketSearchHit>> {
+        let Some(fts_query) = fts_query(text) else {
+            return Ok(Vec::new());
+        };
+
         let conn = self
             .pool
             .get()
             .map_err(|e| DbError::Connection(e.to_string()))?;
 
-        let sql = if project_id.is_some() {
-            format!(
-                "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
-                        t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at
-                 FROM tickets t
-                 JOIN tickets_fts fts ON t.id = fts.ticket_id
-                 WHERE fts.tickets_fts MATCH ?1 AND t.project_id = ?2
-                 ORDER BY t.created_at DESC"
-            )
-        } else {
-            format!(
-                "SELECT t.id, t.project_id, t.title, t.description, t.ticket_type, t.status, t.priority,
-                        t.assignee_id, t.created_by, t.due_date, t.estimate_hours, t.created_at, t.updated_at
-                 FROM tickets t
-                 JOIN tickets_fts fts ON t.id = fts.ticket_id
-                 WHERE fts.tickets_fts MATCH ?1
-                 ORDER BY t.created_at DESC"
+        let (sql, values) = query.search_sql(&fts_query);
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let hits = stmt
+            .query_map(params_from_iter(values), |row| {
+                Ok(TicketSearchHit {
+                    ticket: row_to_ticket(row)?,
+                    score: row.get(TICKET_COLUMNS)?,
+                    snippet: row.get(TICKET_COLUMNS + 1)?,
+                })
+            })
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(hits)
+    }
+
+    /// Rebuild the full-text search index from scratch
+    ///
+    /// Repairs an index that has drifted from the tickets, comments and
+    /// attachments it covers. Returns the number of tickets indexed.
+    pub fn rebuild_search_index(&self) -> Result<usize> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        tx.execute("DELETE FROM tickets_fts", [])
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let indexed = tx
+            .execute(
+                "INSERT INTO tickets_fts(rowid, title, description, comments, attachments)
+                 SELECT t.rowid, t.title, COALESCE(t.description, ''),
+                        COALESCE((SELECT group_concat(c.content, ' ') FROM comments c
+                                  WHERE c.ticket_id = t.id), ''),
+                        COALESCE((SELECT group_concat(a.filename, ' ') FROM attachments a
+                                  WHERE a.ticket_id = t.id), '')
+                 FROM tickets t",
+                [],
             )
-        };
+            .map_err(|e| DbError::Query(e.to_string()))?;
 
+        tx.execute(
+            "INSERT INTO tickets_fts(tickets_fts) VALUES ('optimize')",
+            [],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(indexed)
+    }
+
+    /// Find one page of tickets matching a query, with the total match count
+    pub fn query(&self, query: &TicketQuery) -> Result<TicketPage> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let (count_sql, count_values) = query.count_sql();
+        let total: i64 = conn
+            .query_row(&count_sql, params_from_iter(count_values), |row| row.get(0))
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let (page_sql, page_values) = query.page_sql();
         let mut stmt = conn
-            .prepare(&sql)
+            .prepare(&page_sql)
             .map_err(|e| DbError::Query(e.to_string()))?;
 
-        let tickets = if let Some(proj_id) = project_id {
-            stmt.query_map(params![query, proj_id.0.to_string()], row_to_ticket)
-                .map_err(|e| DbError::Query(e.to_string()))?
-                .collect::<std::result::Result<Vec<_>, _>>()
-                .map_err(|e| DbError::Query(e.to_string()))?
-        } else {
-            stmt.query_map(params![query], row_to_ticket)
-                .map_err(|e| DbError::Query(e.to_string()))?
-                .collect::<std::result::Result<Vec<_>, _>>()
-                .map_err(|e| DbError::Query(e.to_string()))?
-        };
+        let width = query.cursor_width();
+        let mut rows = stmt
+            .query_map(params_from_iter(page_values), |row| {
+                let keys = (0..width)
+                    .map(|i| row.get::<_, SqlValue>(TICKET_COLUMNS + i))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok((row_to_ticket(row)?, keys))
+            })
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
 
-        Ok(tickets)
+        let mut next_cursor = None;
+        if let Some(limit) = query.page_size() {
+            if rows.len() > limit as usize {
+                rows.truncate(limit as usize);
+                next_cursor = rows
+                    .last()
+                    .map(|(_, keys)| TicketCursor::from_values(keys.clone()));
+            }
+        }
+
+        Ok(TicketPage {
+            tickets: rows.into_iter().map(|(ticket, _)| ticket).collect(),
+            total: total as u64,
+            next_cursor,
+        })
     }
 }
 
@@ -277,7 +577,8 @@ impl Repository<Ticket, TicketId> for TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets WHERE id = ?1",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -299,7 +600,8 @@ impl Repository<Ticket, TicketId> for TicketRepository {
         let mut stmt = conn
             .prepare(
                 "SELECT id, project_id, title, description, ticket_type, status, priority,
-                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at
+                        assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                        status_category, sprint_id, parent_id, number
                  FROM tickets ORDER BY created_at DESC",
             )
             .map_err(|e| DbError::Query(e.to_string()))?;
@@ -313,23 +615,40 @@ impl Repository<Ticket, TicketId> for TicketRepository {
         Ok(tickets)
     }
 
+    /// Store a new ticket, allocating the next number of its project
     fn create(&self, entity: &Ticket) -> Result<Ticket> {
-        let conn = self
+        let mut conn = self
             .pool
             .get()
             .map_err(|e| DbError::Connection(e.to_string()))?;
 
-        conn.execute(
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let number: u32 = tx
+            .query_row(
+                "UPDATE projects SET next_ticket_number = next_ticket_number + 1
+                 WHERE id = ?1 RETURNING next_ticket_number - 1",
+                params![entity.project_id.0.to_string()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .ok_or_else(|| DbError::NotFound("Project not found".to_string()))?;
+
+        tx.execute(
             "INSERT INTO tickets (id, project_id, title, description, ticket_type, status, priority,
-                                  assignee_id, created_by, due_date, estimate_hours, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                                  assignee_id, created_by, due_date, estimate_hours, created_at, updated_at,
+                                  status_category, sprint_id, parent_id, number)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
             params![
                 entity.id.0.to_string(),
                 entity.project_id.0.to_string(),
                 entity.title,
                 entity.description,
                 ticket_type_to_string(&entity.ticket_type),
-                status_to_string(&entity.status),
+                entity.status,
                 priority_to_string(&entity.priority),
                 entity.assignee_id.map(|id| id.0.to_string()),
                 entity.created_by.0.to_string(),
@@ -337,11 +656,21 @@ impl Repository<Ticket, TicketId> for TicketRepository {
                 entity.estimate_hours,
                 entity.created_at.to_rfc3339(),
                 entity.updated_at.to_rfc3339(),
+                entity.status_category.as_str(),
+                entity.sprint_id.map(|id| id.0.to_string()),
+                entity.parent_id.map(|id| id.0.to_string()),
+                number,
             ],
         )
         .map_err(|e| DbError::Query(e.to_string()))?;
 
-        Ok(entity.clone())
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(Ticket {
+            number,
+            ..entity.clone()
+        })
     }
 
     fn update(&self, entity: &Ticket) -> Result<Ticket> {
@@ -353,18 +682,21 @@ impl Repository<Ticket, TicketId> for TicketRepository {
         let rows_affected = conn
             .execute(
                 "UPDATE tickets SET title = ?1, description = ?2, ticket_type = ?3, status = ?4, priority = ?5,
-                                    assignee_id = ?6, due_date = ?7, estimate_hours = ?8, updated_at = ?9
-                 WHERE id = ?10",
+                                    assignee_id = ?6, due_date = ?7, estimate_hours = ?8, updated_at = ?9,
+                                    status_category = ?10, parent_id = ?11
+                 WHERE id = ?12",
                 params![
                     entity.title,
                     entity.description,
                     ticket_type_to_string(&entity.ticket_type),
-                    status_to_string(&entity.status),
+                    entity.status,
                     priority_to_string(&entity.priority),
                     entity.assignee_id.map(|id| id.0.to_string()),
                     entity.due_date.map(|d| d.to_rfc3339()),
                     entity.estimate_hours,
                     Utc::now().to_rfc3339(),
+                    entity.status_category.as_str(),
+                    entity.parent_id.map(|id| id.0.to_string()),
                     entity.id.0.to_string(),
                 ],
             )
@@ -398,8 +730,11 @@ impl Repository<Ticket, TicketId> for TicketRepository {
     }
 }
 
+/// Number of columns read by `row_to_ticket`
+const TICKET_COLUMNS: usize = 17;
+
 /// Convert a database row to a Ticket
-fn row_to_ticket(row: &Row) -> rusqlite::Result<Ticket> {
+pub(crate) fn row_to_ticket(row: &Row) -> rusqlite::Result<Ticket> {
     let id_str: String = row.get(0)?;
     let id = TicketId::from_uuid(Uuid::parse_str(&id_str).unwrap());
 
@@ -409,8 +744,15 @@ fn row_to_ticket(row: &Row) -> rusqlite::Result<Ticket> {
     let ticket_type_str: String = row.get(4)?;
     let ticket_type = string_to_ticket_type(&ticket_type_str);
 
-    let status_str: String = row.get(5)?;
-    let status = string_to_status(&status_str);
+    let status_category_str: String = row.get(13)?;
+    let status_category =
+        StatusCategory::from_name(&status_category_str).unwrap_or(StatusCategory::Todo);
+
+    let sprint_id: Option<String> = row.get(14)?;
+    let sprint_id = sprint_id.map(|s| SprintId::from_uuid(Uuid::parse_str(&s).unwrap()));
+
+    let parent_id: Option<String> = row.get(15)?;
+    let parent_id = parent_id.map(|s| TicketId::from_uuid(Uuid::parse_str(&s).unwrap()));
 
     let priority_str: String = row.get(6)?;
     let priority = string_to_priority(&priority_str);
@@ -441,27 +783,37 @@ fn row_to_ticket(row: &Row) -> rusqlite::Result<Ticket> {
     Ok(Ticket {
         id,
         project_id,
+        number: row.get(16)?,
         title: row.get(2)?,
         description: row.get(3)?,
         ticket_type,
-        status,
+        status: row.get(5)?,
+        status_category,
         priority,
         assignee_id,
         created_by,
         due_date,
         estimate_hours: row.get(10)?,
+        sprint_id,
+        parent_id,
         created_at,
         updated_at,
+        labels: Vec::new(),
+        custom_fields: Vec::new(),
+        rollup: None,
+        logged_minutes: 0,
     })
 }
 
 // Helper functions for enum conversions
-fn ticket_type_to_string(ticket_type: &TicketType) -> String {
+pub(crate) fn ticket_type_to_string(ticket_type: &TicketType) -> String {
     match ticket_type {
         TicketType::Task => "Task".to_string(),
         TicketType::Bug => "Bug".to_string(),
         TicketType::Feature => "Feature".to_string(),
         TicketType::Epic => "Epic".to_string(),
+        TicketType::Story => "Story".to_string(),
+        TicketType::Subtask => "Subtask".to_string(),
     }
 }
 
@@ -471,32 +823,13 @@ fn string_to_ticket_type(s: &str) -> TicketType {
         "Bug" => TicketType::Bug,
         "Feature" => TicketType::Feature,
         "Epic" => TicketType::Epic,
+        "Story" => TicketType::Story,
+        "Subtask" => TicketType::Subtask,
         _ => TicketType::Task,
     }
 }
 
-fn status_to_string(status: &TicketStatus) -> String {
-    match status {
-        TicketStatus::Open => "Open".to_string(),
-        TicketStatus::InProgress => "InProgress".to_string(),
-        TicketStatus::Review => "Review".to_string(),
-        TicketStatus::Done => "Done".to_string(),
-        TicketStatus::Closed => "Closed".to_string(),
-    }
-}
-
-fn string_to_status(s: &str) -> TicketStatus {
-    match s {
-        "Open" => TicketStatus::Open,
-        "InProgress" => TicketStatus::InProgress,
-        "Review" => TicketStatus::Review,
-        "Done" => TicketStatus::Done,
-        "Closed" => TicketStatus::Closed,
-        _ => TicketStatus::Open,
-    }
-}
-
-fn priority_to_string(priority: &Priority) -> String {
+pub(crate) fn priority_to_string(priority: &Priority) -> String {
     match priority {
         Priority::Low => "Low".to_string(),
         Priority::Medium => "Medium".to_string(),
@@ -521,9 +854,24 @@ mod tests {
     use crate::{
         connection::init_memory_pool,
         migrations::run_migrations,
-        repositories::{project_repository::ProjectRepository, user_repository::UserRepository},
+        repositories::{
+            attachment_repository::AttachmentRepository,
+            comment_repository::CommentRepository,
+            project_repository::ProjectRepository,
+            role_repository::RoleRepository,
+            ticket_query::{TicketSort, TicketSortField},
+            user_repository::UserRepository,
+        },
     };
-    use worknest_core::models::{Project, User};
+    use worknest_core::models::{Attachment, Comment, Project, RoleId, User, Workflow};
+
+    /// A status of the default workflow
+    fn status(key: &str) -> WorkflowStatus {
+        Workflow::default_for(ProjectId::new())
+            .status(key)
+            .unwrap()
+            .clone()
+    }
 
     fn setup_test_repo() -> (TicketRepository, ProjectId, UserId) {
         let pool = Arc::new(init_memory_pool().unwrap());
@@ -574,12 +922,13 @@ mod tests {
         repo.create(&ticket).unwrap();
 
         ticket.title = "Updated Ticket".to_string();
-        ticket.status = TicketStatus::InProgress;
+        ticket.update_status(&status("in_progress"));
         repo.update(&ticket).unwrap();
 
         let found = repo.find_by_id(ticket.id).unwrap().unwrap();
         assert_eq!(found.title, "Updated Ticket");
-        assert_eq!(found.status, TicketStatus::InProgress);
+        assert_eq!(found.status, "in_progress");
+        assert_eq!(found.status_category, StatusCategory::InProgress);
     }
 
     #[test]
@@ -611,10 +960,11 @@ mod tests {
         );
 
         repo.create(&ticket).unwrap();
-        repo.update_status(ticket.id, TicketStatus::Done).unwrap();
+        repo.update_status(ticket.id, &status("done")).unwrap();
 
         let found = repo.find_by_id(ticket.id).unwrap().unwrap();
-        assert_eq!(found.status, TicketStatus::Done);
+        assert_eq!(found.status, "done");
+        assert!(found.is_resolved());
     }
 
     #[test]
@@ -649,11 +999,11 @@ mod tests {
             TicketType::Task,
             user_id,
         );
-        ticket.status = TicketStatus::InProgress;
+        ticket.update_status(&status("in_progress"));
 
         repo.create(&ticket).unwrap();
 
-        let tickets = repo.find_by_status(TicketStatus::InProgress).unwrap();
+        let tickets = repo.find_by_status("in_progress").unwrap();
         assert_eq!(tickets.len(), 1);
         assert_eq!(tickets[0].title, "Test Ticket");
     }
@@ -674,4 +1024,563 @@ mod tests {
         let found = repo.find_by_id(ticket.id).unwrap();
         assert!(found.is_none());
     }
+
+    #[test]
+    fn test_ticket_numbers() {
+        let (repo, project_id, user_id) = setup_test_repo();
+
+        let numbers: Vec<u32> = (0..3)
+            .map(|i| {
+                let ticket = Ticket::new(
+                    project_id,
+                    format!("Ticket {}", i),
+                    TicketType::Task,
+                    user_id,
+                );
+                repo.create(&ticket).unwrap().number
+            })
+            .collect();
+        assert_eq!(numbers, [1, 2, 3]);
+
+        let found = repo.find_by_number(project_id, 2).unwrap().unwrap();
+        assert_eq!(found.title, "Ticket 1");
+        assert_eq!(found.number, 2);
+        assert!(repo.find_by_number(project_id, 4).unwrap().is_none());
+
+        // Numbers are not reused after a delete
+        repo.delete(found.id).unwrap();
+        let ticket = Ticket::new(
+            project_id,
+            "Ticket 3".to_string(),
+            TicketType::Task,
+            user_id,
+        );
+        assert_eq!(repo.create(&ticket).unwrap().number, 4);
+    }
+
+    #[test]
+    fn test_find_by_id_prefix() {
+        let (repo, project_id, user_id) = setup_test_repo();
+
+        let mut ids = Vec::new();
+        for id in [
+            "1a2b3c4d-0000-4000-8000-000000000001",
+            "1a2b3c4d-0000-4000-8000-000000000002",
+            "5e6f7a8b-0000-4000-8000-000000000003",
+        ] {
+            let mut ticket =
+                Ticket::new(project_id, "Ticket".to_string(), TicketType::Task, user_id);
+            ticket.id = TicketId::from_uuid(Uuid::parse_str(id).unwrap());
+            ids.push(repo.create(&ticket).unwrap().id);
+        }
+
+        let found = repo.find_by_id_prefix("5E6F7A8B").unwrap().unwrap();
+        assert_eq!(found.id, ids[2]);
+
+        // Ambiguous, unknown and malformed prefixes match nothing
+        assert!(repo.find_by_id_prefix("1a2b3c4d").unwrap().is_none());
+        assert!(repo.find_by_id_prefix("ffffffff").unwrap().is_none());
+        assert!(repo.find_by_id_prefix("%").unwrap().is_none());
+        assert!(repo.find_by_id_prefix("").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_hierarchy_children_ancestors_and_rollup() {
+        let (repo, project_id, user_id) = setup_test_repo();
+
+        let epic = Ticket::new(project_id, "Epic".to_string(), TicketType::Epic, user_id);
+        let mut story = Ticket::new(project_id, "Story".to_string(), TicketType::Story, user_id);
+        story.parent_id = Some(epic.id);
+        story.estimate_hours = Some(3.0);
+        let mut done = Ticket::new(project_id, "Done".to_string(), TicketType::Subtask, user_id);
+        done.parent_id = Some(story.id);
+        done.estimate_hours = Some(2.0);
+        done.update_status(&status("done"));
+        let mut open = Ticket::new(project_id, "Open".to_string(), TicketType::Subtask, user_id);
+        open.parent_id = Some(story.id);
+
+        for ticket in [&epic, &story, &done, &open] {
+            repo.create(ticket).unwrap();
+        }
+
+        let children = repo.find_children(story.id).unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(children.iter().all(|c| c.parent_id == Some(story.id)));
+
+        assert_eq!(
+            repo.find_ancestor_ids(open.id).unwrap(),
+            vec![story.id, epic.id]
+        );
+        assert!(repo.find_ancestor_ids(epic.id).unwrap().is_empty());
+
+        let rollups = repo.find_rollups(&[epic.id, story.id, open.id]).unwrap();
+        assert_eq!(rollups.len(), 2);
+        let (_, epic_rollup) = rollups.iter().find(|(id, _)| *id == epic.id).unwrap();
+        assert_eq!(epic_rollup.child_count, 1);
+        assert_eq!(epic_rollup.descendant_count, 3);
+        assert_eq!(epic_rollup.done_count, 1);
+        assert_eq!(epic_rollup.estimate_hours, 5.0);
+        assert_eq!(epic_rollup.percent_done, 33);
+        let (_, story_rollup) = rollups.iter().find(|(id, _)| *id == story.id).unwrap();
+        assert_eq!(story_rollup.child_count, 2);
+        assert_eq!(story_rollup.percent_done, 50);
+
+        // Deleting a parent leaves its children at the top level
+        repo.delete(epic.id).unwrap();
+        let story = repo.find_by_id(story.id).unwrap().unwrap();
+        assert_eq!(story.parent_id, None);
+    }
+
+    #[test]
+    fn test_find_visible_to_hides_private_projects() {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user_repo = UserRepository::new(Arc::clone(&pool));
+        let owner = User::new("owner".to_string(), "owner@example.com".to_string());
+        let other = User::new("other".to_string(), "other@example.com".to_string());
+        user_repo.create_with_password(&owner, "hash").unwrap();
+        user_repo.create_with_password(&other, "hash").unwrap();
+
+        let role_repo = RoleRepository::new(Arc::clone(&pool));
+        role_repo
+            .assign_to_user(other.id, &RoleId::member(), None)
+            .unwrap();
+
+        let project_repo = ProjectRepository::new(Arc::clone(&pool));
+        let public = Project::new("Public".to_string(), owner.id);
+        let mut private = Project::new("Private".to_string(), owner.id);
+        private.is_private = true;
+        project_repo.create(&public).unwrap();
+        project_repo.create(&private).unwrap();
+
+        let repo = TicketRepository::new(pool);
+        for project_id in [public.id, private.id] {
+            let ticket = Ticket::new(project_id, "Ticket".to_string(), TicketType::Task, owner.id);
+            repo.create(&ticket).unwrap();
+        }
+
+        let visible = repo.find_visible_to(other.id).unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].project_id, public.id);
+
+        project_repo
+            .set_member_role(private.id, other.id, &RoleId::viewer())
+            .unwrap();
+        assert_eq!(repo.find_visible_to(other.id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_query_filters_and_total() {
+        let (repo, project_id, user_id) = setup_test_repo();
+        for (title, priority, assigned) in [
+            ("A", Priority::High, true),
+            ("B", Priority::High, false),
+            ("C", Priority::Low, true),
+        ] {
+            let mut ticket = Ticket::new(project_id, title.to_string(), TicketType::Bug, user_id);
+            ticket.priority = priority;
+            if assigned {
+                ticket.assignee_id = Some(user_id);
+            }
+            repo.create(&ticket).unwrap();
+        }
+
+        let page = repo
+            .query(&TicketQuery::new().priority(Priority::High).limit(10))
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.tickets.len(), 2);
+        assert!(page.next_cursor.is_none());
+
+        let page = repo
+            .query(
+                &TicketQuery::new()
+                    .project(project_id)
+                    .assignee(user_id)
+                    .ticket_type(TicketType::Bug)
+                    .status("open"),
+            )
+            .unwrap();
+        assert_eq!(page.total, 2);
+
+        let page = repo.query(&TicketQuery::new().unassigned()).unwrap();
+        assert_eq!(page.tickets[0].title, "B");
+
+        let page = repo
+            .query(&TicketQuery::new().ticket_type(TicketType::Task))
+            .unwrap();
+        assert_eq!(page.total, 0);
+    }
+
+    #[test]
+    fn test_query_keyset_pagination_with_multi_sort() {
+        let (repo, project_id, user_id) = setup_test_repo();
+        let priorities = [
+            Priority::Low,
+            Priority::Critical,
+            Priority::Medium,
+            Priority::Critical,
+            Priority::High,
+        ];
+        for (i, priority) in priorities.into_iter().enumerate() {
+            let mut ticket = Ticket::new(project_id, format!("T{}", i), TicketType::Task, user_id);
+            ticket.priority = priority;
+            repo.create(&ticket).unwrap();
+        }
+
+        let base = TicketQuery::new()
+            .sort_by(TicketSort::desc(TicketSortField::Priority))
+            .sort_by(TicketSort::asc(TicketSortField::Title))
+            .limit(2);
+
+        let mut titles = Vec::new();
+        let mut query = base.clone();
+        loop {
+            let page = repo.query(&query).unwrap();
+            assert_eq!(page.total, 5);
+            titles.extend(page.tickets.into_iter().map(|t| t.title));
+            match page.next_cursor {
+                Some(cursor) => {
+                    let cursor = TicketCursor::decode(&cursor.encode()).unwrap();
+                    query = base.clone().after(cursor);
+                },
+                None => break,
+            }
+        }
+
+        assert_eq!(titles, ["T1", "T3", "T4", "T2", "T0"]);
+    }
+
+    #[test]
+    fn test_query_due_date_range() {
+        let (repo, project_id, user_id) = setup_test_repo();
+        let now = Utc::now();
+        for (title, due) in [("soon", Some(1)), ("later", Some(10)), ("none", None)] {
+            let mut ticket = Ticket::new(project_id, title.to_string(), TicketType::Task, user_id);
+            ticket.due_date = due.map(|days| now + chrono::Duration::days(days));
+            repo.create(&ticket).unwrap();
+        }
+
+        let page = repo
+            .query(&TicketQuery::new().due_before(now + chrono::Duration::days(5)))
+            .unwrap();
+        assert_eq!(page.tickets.len(), 1);
+        assert_eq!(page.tickets[0].title, "soon");
+
+        let page = repo
+            .query(
+                &TicketQuery::new()
+                    .due_after(now)
+                    .sort_by(TicketSort::desc(TicketSortField::DueDate)),
+            )
+            .unwrap();
+        let titles: Vec<_> = page.tickets.into_iter().map(|t| t.title).collect();
+        assert_eq!(titles, ["later", "soon"]);
+    }
+
+    #[test]
+    fn test_search_ranks_and_covers_comments_and_attachments() {
+        let (repo, project_id, user_id) = setup_test_repo();
+        let pool = Arc::clone(&repo.pool);
+
+        let mut in_title = Ticket::new(
+            project_id,
+            "Login crash on Safari".to_string(),
+            TicketType::Bug,
+            user_id,
+        );
+        in_title.description = Some("Users cannot sign in".to_string());
+        let mut in_description = Ticket::new(
+            project_id,
+            "Auth flakiness".to_string(),
+            TicketType::Bug,
+            user_id,
+        );
+        in_description.description = Some("Intermittent login failures".to_string());
+        let other = Ticket::new(
+            project_id,
+            "Dark mode".to_string(),
+            TicketType::Feature,
+            user_id,
+        );
+        for ticket in [&in_title, &in_description, &other] {
+            repo.create(ticket).unwrap();
+        }
+
+        let hits = repo.search("login", &TicketQuery::new()).unwrap();
+        let titles: Vec<_> = hits.iter().map(|h| h.ticket.title.as_str()).collect();
+        assert_eq!(titles, ["Login crash on Safari", "Auth flakiness"]);
+        assert!(hits[0].score > hits[1].score);
+        assert!(hits[0].snippet.contains("[Login]"));
+
+        // Prefixes match, and FTS syntax in the input is taken literally
+        assert_eq!(repo.search("saf", &TicketQuery::new()).unwrap().len(), 1);
+        assert!(repo
+            .search("\"login OR", &TicketQuery::new())
+            .unwrap()
+            .is_empty());
+
+        let comment_repo = CommentRepository::new(Arc::clone(&pool));
+        let comment = Comment::new(other.id, user_id, "Contrast is too low".to_string());
+        comment_repo.create(&comment).unwrap();
+
+        let attachment_repo = AttachmentRepository::new(Arc::clone(&pool));
+        attachment_repo
+            .create(&Attachment::new(
+                other.id,
+                "palette-mockup.png".to_string(),
+                10,
+                "image/png".to_string(),
+                "/tmp/palette-mockup.png".to_string(),
+                user_id,
+            ))
+            .unwrap();
+
+        let hits = repo.search("contrast", &TicketQuery::new()).unwrap();
+        assert_eq!(hits[0].ticket.id, other.id);
+        assert_eq!(repo.search("mockup", &TicketQuery::new()).unwrap().len(), 1);
+
+        comment_repo.delete(comment.id).unwrap();
+        assert!(repo
+            .search("contrast", &TicketQuery::new())
+            .unwrap()
+            .is_empty());
+
+        // Updates are reflected and deleted tickets leave the index
+        let mut renamed = repo.find_by_id(other.id).unwrap().unwrap();
+        renamed.title = "High contrast theme".to_string();
+        repo.update(&renamed).unwrap();
+        assert_eq!(repo.search("theme", &TicketQuery::new()).unwrap().len(), 1);
+
+        repo.delete(other.id).unwrap();
+        assert!(repo
+            .search("theme", &TicketQuery::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_respects_filters_and_rebuild() {
+        let (repo, project_id, user_id) = setup_test_repo();
+        for title in ["Export to CSV", "Export to PDF"] {
+            let mut ticket = Ticket::new(project_id, title.to_string(), TicketType::Task, user_id);
+            if title.ends_with("PDF") {
+                ticket.update_status(&status("done"));
+            }
+            repo.create(&ticket).unwrap();
+        }
+
+        let open = TicketQuery::new().status("open");
+        assert_eq!(repo.search("export", &open).unwrap().len(), 1);
+        assert_eq!(
+            repo.search("export", &TicketQuery::new().limit(1))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        {
+            let conn = repo.pool.get().unwrap();
+            conn.execute("DELETE FROM tickets_fts", []).unwrap();
+        }
+        assert!(repo
+            .search("export", &TicketQuery::new())
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(repo.rebuild_search_index().unwrap(), 2);
+        assert_eq!(repo.search("export", &TicketQuery::new()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_query_matching_parsed_expression() {
+        let (repo, project_id, user_id) = setup_test_repo();
+        let now = Utc::now();
+        let fixtures = [
+            ("Login crash", Priority::Critical, "open", true, Some(2)),
+            ("Slow dashboard", Priority::High, "review", false, Some(30)),
+            ("Typo in footer", Priority::Low, "open", true, None),
+            ("Old login bug", Priority::High, "closed", true, Some(1)),
+        ];
+        for (title, priority, key, assigned, due_in_days) in fixtures {
+            let mut ticket = Ticket::new(project_id, title.to_string(), TicketType::Bug, user_id);
+            ticket.priority = priority;
+            ticket.update_status(&status(key));
+            ticket.assignee_id = assigned.then_some(user_id);
+            ticket.due_date = due_in_days.map(|days| now + chrono::Duration::days(days));
+            repo.create(&ticket).unwrap();
+        }
+
+        let titles = |q: &str| -> Vec<String> {
+            let expr = worknest_core::query::parse(q).unwrap();
+            let mut titles: Vec<String> = repo
+                .query(&TicketQuery::new().matching(expr, user_id))
+                .unwrap()
+                .tickets
+                .into_iter()
+                .map(|t| t.title)
+                .collect();
+            titles.sort();
+            titles
+        };
+
+        assert_eq!(
+            titles("status:open,review priority>=high"),
+            ["Login crash", "Slow dashboard"]
+        );
+        assert_eq!(titles("assignee:me due<7d -status:closed"), ["Login crash"]);
+        assert_eq!(titles("assignee!=me"), ["Slow dashboard"]);
+        assert_eq!(titles("due:none OR priority<medium"), ["Typo in footer"]);
+        assert_eq!(titles("login AND NOT (status:closed)"), ["Login crash"]);
+        assert_eq!(
+            titles("assignee:TESTUSER project:\"test project\" due>7d"),
+            Vec::<String>::new()
+        );
+        assert_eq!(titles("creator:testuser due>7d"), ["Slow dashboard"]);
+        assert_eq!(titles("status:InProgress"), Vec::<String>::new());
+        assert_eq!(titles("category:in_progress"), ["Slow dashboard"]);
+        assert_eq!(
+            titles("category>=in_progress"),
+            ["Old login bug", "Slow dashboard"]
+        );
+    }
+
+    #[test]
+    fn test_query_by_label() {
+        use crate::repositories::label_repository::LabelRepository;
+        use worknest_core::models::Label;
+
+        let (repo, project_id, user_id) = setup_test_repo();
+        let label_repo = LabelRepository::new(Arc::clone(&repo.pool));
+        let ui = Label::new(project_id, "UI".to_string(), "#1d76db".to_string());
+        let backend = Label::new(project_id, "backend".to_string(), "#0e8a16".to_string());
+        label_repo.create(&ui).unwrap();
+        label_repo.create(&backend).unwrap();
+
+        for (title, labels) in [
+            ("Button color", vec![ui.id]),
+            ("Slow API", vec![backend.id]),
+            ("Form validation", vec![ui.id, backend.id]),
+            ("Unlabelled", vec![]),
+        ] {
+            let ticket = Ticket::new(project_id, title.to_string(), TicketType::Task, user_id);
+            repo.create(&ticket).unwrap();
+            label_repo.set_ticket_labels(ticket.id, &labels).unwrap();
+        }
+
+        let titles = |query: TicketQuery| -> Vec<String> {
+            let mut titles: Vec<String> = repo
+                .query(&query)
+                .unwrap()
+                .tickets
+                .into_iter()
+                .map(|t| t.title)
+                .collect();
+            titles.sort();
+            titles
+        };
+        let parsed =
+            |q: &str| TicketQuery::new().matching(worknest_core::query::parse(q).unwrap(), user_id);
+
+        assert_eq!(
+            titles(TicketQuery::new().label("ui")),
+            ["Button color", "Form validation"]
+        );
+        assert_eq!(
+            titles(TicketQuery::new().label("ui").label("BACKEND")).len(),
+            3
+        );
+        assert_eq!(
+            titles(parsed("label:ui label:backend")),
+            ["Form validation"]
+        );
+        assert_eq!(titles(parsed("-label:ui")), ["Slow API", "Unlabelled"]);
+        assert_eq!(titles(parsed("label:none")), ["Unlabelled"]);
+    }
+
+    #[test]
+    fn test_query_by_custom_field() {
+        use crate::repositories::custom_field_repository::CustomFieldRepository;
+        use worknest_core::models::{CustomField, CustomFieldType, CustomFieldValue, FieldValue};
+
+        let (repo, project_id, user_id) = setup_test_repo();
+        let field_repo = CustomFieldRepository::new(Arc::clone(&repo.pool));
+        let mut platforms = CustomField::new(
+            project_id,
+            "Platforms".to_string(),
+            CustomFieldType::MultiSelect,
+        );
+        platforms.options = vec!["web".to_string(), "ios".to_string(), "android".to_string()];
+        let points = CustomField::new(project_id, "Points".to_string(), CustomFieldType::Number);
+        field_repo.create(&platforms).unwrap();
+        field_repo.create(&points).unwrap();
+
+        for (title, options, estimate) in [
+            ("Login page", vec!["web"], 3.0),
+            ("Push notifications", vec!["ios", "android"], 5.0),
+            ("Dark mode", vec!["web", "ios"], 3.0),
+        ] {
+            let ticket = Ticket::new(project_id, title.to_string(), TicketType::Task, user_id);
+            repo.create(&ticket).unwrap();
+            field_repo
+                .set_ticket_values(
+                    ticket.id,
+                    &[
+                        CustomFieldValue {
+                            field_id: platforms.id,
+                            value: FieldValue::MultiSelect(
+                                options.into_iter().map(String::from).collect(),
+                            ),
+                        },
+                        CustomFieldValue {
+                            field_id: points.id,
+                            value: FieldValue::Number(estimate),
+                        },
+                    ],
+                )
+                .unwrap();
+        }
+
+        let titles = |query: TicketQuery| -> Vec<String> {
+            let mut titles: Vec<String> = repo
+                .query(&query)
+                .unwrap()
+                .tickets
+                .into_iter()
+                .map(|t| t.title)
+                .collect();
+            titles.sort();
+            titles
+        };
+
+        assert_eq!(
+            titles(TicketQuery::new().custom_field(
+                platforms.id,
+                FieldValue::MultiSelect(vec!["IOS".to_string()])
+            )),
+            ["Dark mode", "Push notifications"]
+        );
+        assert_eq!(
+            titles(
+                TicketQuery::new()
+                    .custom_field(platforms.id, FieldValue::SingleSelect("web".to_string()))
+                    .custom_field(points.id, FieldValue::Number(3.0))
+            ),
+            ["Dark mode", "Login page"]
+        );
+        assert_eq!(
+            titles(
+                TicketQuery::new()
+                    .custom_field(
+                        platforms.id,
+                        FieldValue::SingleSelect("android".to_string())
+                    )
+                    .custom_field(points.id, FieldValue::Number(3.0))
+            ),
+            Vec::<String>::new()
+        );
+    }
 }
@@ -0,0 +1,178 @@
+//! Ticket watcher repository implementation
+
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{TicketId, UserId};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for the users watching each ticket
+pub struct WatcherRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WatcherRepository {
+    /// Create a new WatcherRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Start watching a ticket; watching it again is a no-op
+    pub fn watch(&self, ticket_id: TicketId, user_id: UserId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO ticket_watchers (ticket_id, user_id, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![
+                ticket_id.0.to_string(),
+                user_id.0.to_string(),
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Stop watching a ticket; not watching it is a no-op
+    pub fn unwatch(&self, ticket_id: TicketId, user_id: UserId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "DELETE FROM ticket_watchers WHERE ticket_id = ?1 AND user_id = ?2",
+            params![ticket_id.0.to_string(), user_id.0.to_string()],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Find the users watching a ticket, in the order they started watching
+    pub fn find_watchers(&self, ticket_id: TicketId) -> Result<Vec<UserId>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT user_id FROM ticket_watchers
+                 WHERE ticket_id = ?1
+                 ORDER BY created_at, rowid",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let watchers = stmt
+            .query_map(params![ticket_id.0.to_string()], |row| {
+                let user_id_str: String = row.get(0)?;
+                Ok(UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()))
+            })
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(watchers)
+    }
+
+    /// Whether a user is watching a ticket
+    pub fn is_watching(&self, ticket_id: TicketId, user_id: UserId) -> Result<bool> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let found = conn
+            .query_row(
+                "SELECT 1 FROM ticket_watchers WHERE ticket_id = ?1 AND user_id = ?2",
+                params![ticket_id.0.to_string(), user_id.0.to_string()],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(found.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+        Repository,
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (WatcherRepository, TicketRepository, Ticket, User, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let users = UserRepository::new(Arc::clone(&pool));
+        let alice = User::new("alice".to_string(), "alice@example.com".to_string());
+        let bob = User::new("bob".to_string(), "bob@example.com".to_string());
+        users.create_with_password(&alice, "hash").unwrap();
+        users.create_with_password(&bob, "hash").unwrap();
+
+        let project = Project::new("Test Project".to_string(), alice.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+        let ticket = tickets
+            .create(&Ticket::new(
+                project.id,
+                "Watch me".to_string(),
+                TicketType::Task,
+                alice.id,
+            ))
+            .unwrap();
+
+        (WatcherRepository::new(pool), tickets, ticket, alice, bob)
+    }
+
+    #[test]
+    fn test_watch_and_unwatch() {
+        let (repo, _, ticket, alice, bob) = setup();
+
+        repo.watch(ticket.id, alice.id).unwrap();
+        repo.watch(ticket.id, bob.id).unwrap();
+        repo.watch(ticket.id, alice.id).unwrap();
+        assert_eq!(
+            repo.find_watchers(ticket.id).unwrap(),
+            vec![alice.id, bob.id]
+        );
+        assert!(repo.is_watching(ticket.id, bob.id).unwrap());
+
+        repo.unwatch(ticket.id, bob.id).unwrap();
+        repo.unwatch(ticket.id, bob.id).unwrap();
+        assert_eq!(repo.find_watchers(ticket.id).unwrap(), vec![alice.id]);
+        assert!(!repo.is_watching(ticket.id, bob.id).unwrap());
+    }
+
+    #[test]
+    fn test_watchers_go_with_their_ticket() {
+        let (repo, tickets, ticket, alice, _) = setup();
+        repo.watch(ticket.id, alice.id).unwrap();
+
+        tickets.delete(ticket.id).unwrap();
+        assert!(repo.find_watchers(ticket.id).unwrap().is_empty());
+    }
+}
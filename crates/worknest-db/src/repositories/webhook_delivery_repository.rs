@@ -0,0 +1,304 @@
+//! Webhook delivery repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{
+    DeliveryStatus, WebhookDelivery, WebhookDeliveryId, WebhookEvent, WebhookId,
+};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for the log of payloads delivered to webhooks
+pub struct WebhookDeliveryRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WebhookDeliveryRepository {
+    /// Create a new WebhookDeliveryRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Create a delivery
+    pub fn create(&self, delivery: &WebhookDelivery) -> Result<WebhookDelivery> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO webhook_deliveries (id, webhook_id, event, payload, status, attempts,
+                 response_code, error, next_attempt_at, last_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                delivery.id.0.to_string(),
+                delivery.webhook_id.0.to_string(),
+                delivery.event.as_str(),
+                delivery.payload,
+                delivery.status.as_str(),
+                delivery.attempts,
+                delivery.response_code,
+                delivery.error,
+                delivery.next_attempt_at.map(|t| t.to_rfc3339()),
+                delivery.last_attempt_at.map(|t| t.to_rfc3339()),
+                delivery.created_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+
+        Ok(delivery.clone())
+    }
+
+    /// Find a delivery by id
+    pub fn find_by_id(&self, id: WebhookDeliveryId) -> Result<Option<WebhookDelivery>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let delivery = conn
+            .query_row(
+                "SELECT id, webhook_id, event, payload, status, attempts, response_code, error,
+                        next_attempt_at, last_attempt_at, created_at
+                 FROM webhook_deliveries WHERE id = ?1",
+                params![id.0.to_string()],
+                row_to_delivery,
+            )
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(delivery)
+    }
+
+    /// Find the deliveries of a webhook, newest first
+    pub fn find_by_webhook(
+        &self,
+        webhook_id: WebhookId,
+        limit: Option<u32>,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, webhook_id, event, payload, status, attempts, response_code, error,
+                        next_attempt_at, last_attempt_at, created_at
+                 FROM webhook_deliveries
+                 WHERE webhook_id = ?1
+                 ORDER BY created_at DESC, rowid DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let deliveries = stmt
+            .query_map(
+                params![webhook_id.0.to_string(), limit.map(i64::from).unwrap_or(-1)],
+                row_to_delivery,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(deliveries)
+    }
+
+    /// Find pending deliveries of active webhooks whose next attempt is due
+    /// at `now`, the longest waiting first
+    pub fn find_due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<WebhookDelivery>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT d.id, d.webhook_id, d.event, d.payload, d.status, d.attempts,
+                        d.response_code, d.error, d.next_attempt_at, d.last_attempt_at, d.created_at
+                 FROM webhook_deliveries d
+                 JOIN webhooks w ON w.id = d.webhook_id
+                 WHERE d.status = 'pending' AND w.active = 1 AND d.next_attempt_at <= ?1
+                 ORDER BY d.next_attempt_at, d.rowid
+                 LIMIT ?2",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let deliveries = stmt
+            .query_map(params![now.to_rfc3339(), limit], row_to_delivery)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(deliveries)
+    }
+
+    /// Save the outcome of a delivery attempt
+    pub fn update(&self, delivery: &WebhookDelivery) -> Result<WebhookDelivery> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE webhook_deliveries
+                 SET status = ?1, attempts = ?2, response_code = ?3, error = ?4,
+                     next_attempt_at = ?5, last_attempt_at = ?6
+                 WHERE id = ?7",
+                params![
+                    delivery.status.as_str(),
+                    delivery.attempts,
+                    delivery.response_code,
+                    delivery.error,
+                    delivery.next_attempt_at.map(|t| t.to_rfc3339()),
+                    delivery.last_attempt_at.map(|t| t.to_rfc3339()),
+                    delivery.id.0.to_string(),
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Webhook delivery not found".to_string()));
+        }
+
+        Ok(delivery.clone())
+    }
+}
+
+/// Convert a database row to a WebhookDelivery
+fn row_to_delivery(row: &Row) -> rusqlite::Result<WebhookDelivery> {
+    let id_str: String = row.get(0)?;
+    let webhook_id_str: String = row.get(1)?;
+    let event_str: String = row.get(2)?;
+    let status_str: String = row.get(4)?;
+    let next_attempt_at: Option<String> = row.get(8)?;
+    let last_attempt_at: Option<String> = row.get(9)?;
+    let created_at: String = row.get(10)?;
+
+    let parse_time = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+    Ok(WebhookDelivery {
+        id: WebhookDeliveryId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        webhook_id: WebhookId::from_uuid(Uuid::parse_str(&webhook_id_str).unwrap()),
+        event: WebhookEvent::from_name(&event_str).unwrap_or(WebhookEvent::TicketUpdated),
+        payload: row.get(3)?,
+        status: DeliveryStatus::from_name(&status_str).unwrap_or(DeliveryStatus::Failed),
+        attempts: row.get(5)?,
+        response_code: row.get(6)?,
+        error: row.get(7)?,
+        next_attempt_at: next_attempt_at.as_deref().map(parse_time),
+        last_attempt_at: last_attempt_at.as_deref().map(parse_time),
+        created_at: parse_time(&created_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, user_repository::UserRepository,
+            webhook_repository::WebhookRepository,
+        },
+        Repository,
+    };
+    use chrono::Duration;
+    use worknest_core::models::{Project, User, Webhook};
+
+    fn setup() -> (WebhookDeliveryRepository, WebhookRepository, Webhook) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let webhooks = WebhookRepository::new(Arc::clone(&pool));
+        let webhook = Webhook::new(
+            project.id,
+            "https://chat.example.com/hooks/1".to_string(),
+            "s3cret".to_string(),
+            vec![WebhookEvent::TicketCreated],
+            user.id,
+        );
+        webhooks.create(&webhook).unwrap();
+
+        (WebhookDeliveryRepository::new(pool), webhooks, webhook)
+    }
+
+    fn delivery(webhook: &Webhook) -> WebhookDelivery {
+        WebhookDelivery::new(
+            webhook.id,
+            WebhookEvent::TicketCreated,
+            r#"{"event":"ticket.created"}"#.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_attempts_are_logged() {
+        let (repo, _, webhook) = setup();
+
+        let mut first = repo.create(&delivery(&webhook)).unwrap();
+        let second = repo.create(&delivery(&webhook)).unwrap();
+
+        first.record_attempt(Some(503), None, Utc::now());
+        repo.update(&first).unwrap();
+        assert_eq!(repo.find_by_id(first.id).unwrap(), Some(first.clone()));
+
+        let log = repo.find_by_webhook(webhook.id, None).unwrap();
+        assert_eq!(
+            log.iter().map(|d| d.id).collect::<Vec<_>>(),
+            vec![second.id, first.id]
+        );
+        assert_eq!(repo.find_by_webhook(webhook.id, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_find_due() {
+        let (repo, webhooks, mut webhook) = setup();
+        let now = Utc::now();
+
+        let due = repo.create(&delivery(&webhook)).unwrap();
+        let mut retrying = delivery(&webhook);
+        retrying.record_attempt(Some(500), None, now);
+        repo.create(&retrying).unwrap();
+        let mut done = delivery(&webhook);
+        done.record_attempt(Some(200), None, now);
+        repo.create(&done).unwrap();
+
+        let found = repo.find_due(now + Duration::seconds(1), 10).unwrap();
+        assert_eq!(found.iter().map(|d| d.id).collect::<Vec<_>>(), vec![due.id]);
+
+        let later = now + WebhookDelivery::retry_delay(1) + Duration::seconds(1);
+        assert_eq!(repo.find_due(later, 10).unwrap().len(), 2);
+        assert_eq!(repo.find_due(later, 1).unwrap().len(), 1);
+
+        // Deliveries wait while their webhook is disabled
+        webhook.active = false;
+        webhooks.update(&webhook).unwrap();
+        assert!(repo.find_due(later, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deliveries_go_with_their_webhook() {
+        let (repo, webhooks, webhook) = setup();
+        let created = repo.create(&delivery(&webhook)).unwrap();
+
+        webhooks.delete(webhook.id).unwrap();
+        assert!(repo.find_by_id(created.id).unwrap().is_none());
+    }
+}
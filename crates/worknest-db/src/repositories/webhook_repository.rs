@@ -0,0 +1,313 @@
+//! Webhook repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{ProjectId, UserId, Webhook, WebhookEvent, WebhookId};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Repository for project webhooks
+pub struct WebhookRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WebhookRepository {
+    /// Create a new WebhookRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find all webhooks of a project, oldest first
+    pub fn find_by_project(&self, project_id: ProjectId) -> Result<Vec<Webhook>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, url, secret, events, active, created_by, created_at, updated_at
+                 FROM webhooks WHERE project_id = ?1 ORDER BY created_at, rowid",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let webhooks = stmt
+            .query_map(params![project_id.0.to_string()], row_to_webhook)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(webhooks)
+    }
+
+    /// Find the webhooks of a project that receive an event
+    pub fn find_subscribed(
+        &self,
+        project_id: ProjectId,
+        event: WebhookEvent,
+    ) -> Result<Vec<Webhook>> {
+        Ok(self
+            .find_by_project(project_id)?
+            .into_iter()
+            .filter(|webhook| webhook.subscribes_to(event))
+            .collect())
+    }
+}
+
+impl Repository<Webhook, WebhookId> for WebhookRepository {
+    fn find_by_id(&self, id: WebhookId) -> Result<Option<Webhook>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, url, secret, events, active, created_by, created_at, updated_at
+                 FROM webhooks WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let webhook = stmt
+            .query_row(params![id.0.to_string()], row_to_webhook)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(webhook)
+    }
+
+    fn find_all(&self) -> Result<Vec<Webhook>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, project_id, url, secret, events, active, created_by, created_at, updated_at
+                 FROM webhooks ORDER BY created_at, rowid",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let webhooks = stmt
+            .query_map([], row_to_webhook)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(webhooks)
+    }
+
+    fn create(&self, entity: &Webhook) -> Result<Webhook> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO webhooks (id, project_id, url, secret, events, active, created_by, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entity.id.0.to_string(),
+                entity.project_id.0.to_string(),
+                entity.url,
+                entity.secret,
+                events_to_string(&entity.events),
+                entity.active,
+                entity.created_by.0.to_string(),
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| DbError::ConstraintViolation(e.to_string()))?;
+
+        Ok(entity.clone())
+    }
+
+    fn update(&self, entity: &Webhook) -> Result<Webhook> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let updated_at = Utc::now();
+        let rows_affected = conn
+            .execute(
+                "UPDATE webhooks SET url = ?1, secret = ?2, events = ?3, active = ?4, updated_at = ?5
+                 WHERE id = ?6",
+                params![
+                    entity.url,
+                    entity.secret,
+                    events_to_string(&entity.events),
+                    entity.active,
+                    updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Webhook not found".to_string()));
+        }
+
+        let mut updated = entity.clone();
+        updated.updated_at = updated_at;
+        Ok(updated)
+    }
+
+    fn delete(&self, id: WebhookId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        // Removes the delivery log through the cascade
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM webhooks WHERE id = ?1",
+                params![id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Webhook not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Store events as a comma-separated list of their names
+fn events_to_string(events: &[WebhookEvent]) -> String {
+    events
+        .iter()
+        .map(|event| event.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Convert a database row to a Webhook
+fn row_to_webhook(row: &Row) -> rusqlite::Result<Webhook> {
+    let id_str: String = row.get(0)?;
+    let project_id_str: String = row.get(1)?;
+    let events_str: String = row.get(4)?;
+    let created_by_str: String = row.get(6)?;
+    let created_at: String = row.get(7)?;
+    let updated_at: String = row.get(8)?;
+
+    let parse_time = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+    Ok(Webhook {
+        id: WebhookId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        project_id: ProjectId::from_uuid(Uuid::parse_str(&project_id_str).unwrap()),
+        url: row.get(2)?,
+        secret: row.get(3)?,
+        // Names no longer known are dropped rather than failing the row
+        events: events_str
+            .split(',')
+            .filter_map(|name| WebhookEvent::from_name(name).ok())
+            .collect(),
+        active: row.get(5)?,
+        created_by: UserId::from_uuid(Uuid::parse_str(&created_by_str).unwrap()),
+        created_at: parse_time(&created_at),
+        updated_at: parse_time(&updated_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{project_repository::ProjectRepository, user_repository::UserRepository},
+    };
+    use worknest_core::models::{Project, User};
+
+    fn setup() -> (WebhookRepository, ProjectRepository, Project, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let projects = ProjectRepository::new(Arc::clone(&pool));
+        let project = Project::new("Test Project".to_string(), user.id);
+        projects.create(&project).unwrap();
+
+        (WebhookRepository::new(pool), projects, project, user)
+    }
+
+    fn webhook(project: &Project, user: &User, events: Vec<WebhookEvent>) -> Webhook {
+        Webhook::new(
+            project.id,
+            "https://chat.example.com/hooks/1".to_string(),
+            "s3cret".to_string(),
+            events,
+            user.id,
+        )
+    }
+
+    #[test]
+    fn test_create_update_and_delete() {
+        let (repo, _, project, user) = setup();
+
+        let mut hook = webhook(
+            &project,
+            &user,
+            vec![WebhookEvent::TicketCreated, WebhookEvent::CommentCreated],
+        );
+        repo.create(&hook).unwrap();
+        assert_eq!(repo.find_by_id(hook.id).unwrap(), Some(hook.clone()));
+
+        hook.url = "https://deploy.example.com/worknest".to_string();
+        hook.events = vec![WebhookEvent::TicketUpdated];
+        hook.active = false;
+        repo.update(&hook).unwrap();
+
+        let found = repo.find_by_id(hook.id).unwrap().unwrap();
+        assert_eq!(found.url, "https://deploy.example.com/worknest");
+        assert_eq!(found.events, vec![WebhookEvent::TicketUpdated]);
+        assert!(!found.active);
+
+        repo.delete(hook.id).unwrap();
+        assert!(repo.find_by_id(hook.id).unwrap().is_none());
+        assert!(matches!(repo.delete(hook.id), Err(DbError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_find_subscribed() {
+        let (repo, _, project, user) = setup();
+
+        let tickets = webhook(&project, &user, vec![WebhookEvent::TicketCreated]);
+        let comments = webhook(&project, &user, vec![WebhookEvent::CommentCreated]);
+        let mut disabled = webhook(&project, &user, vec![WebhookEvent::TicketCreated]);
+        disabled.active = false;
+        for hook in [&tickets, &comments, &disabled] {
+            repo.create(hook).unwrap();
+        }
+
+        let subscribed = repo
+            .find_subscribed(project.id, WebhookEvent::TicketCreated)
+            .unwrap();
+        assert_eq!(subscribed, vec![tickets]);
+        assert_eq!(repo.find_by_project(project.id).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_webhooks_go_with_their_project() {
+        let (repo, projects, project, user) = setup();
+        let hook = webhook(&project, &user, vec![WebhookEvent::TicketCreated]);
+        repo.create(&hook).unwrap();
+
+        projects.delete(project.id).unwrap();
+        assert!(repo.find_by_id(hook.id).unwrap().is_none());
+    }
+}
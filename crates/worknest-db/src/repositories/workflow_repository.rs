@@ -0,0 +1,291 @@
+//! Workflow repository implementation
+
+use rusqlite::{params, Connection, Row};
+use std::sync::Arc;
+
+use worknest_core::models::{
+    ProjectId, StatusCategory, Workflow, WorkflowStatus, WorkflowTransition,
+};
+
+use crate::{connection::DbPool, DbError, Result};
+
+/// Repository for the `workflow_statuses` and `workflow_transitions` of
+/// projects
+pub struct WorkflowRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WorkflowRepository {
+    /// Create a new WorkflowRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find the workflow of a project
+    ///
+    /// Projects without stored statuses use `Workflow::default_for`.
+    pub fn find_by_project(&self, project_id: ProjectId) -> Result<Workflow> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT key, name, category FROM workflow_statuses
+                 WHERE project_id = ?1 ORDER BY position, key",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let statuses = stmt
+            .query_map(params![project_id.0.to_string()], row_to_status)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if statuses.is_empty() {
+            return Ok(Workflow::default_for(project_id));
+        }
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT wt.from_key, wt.to_key FROM workflow_transitions wt
+                 JOIN workflow_statuses f ON f.project_id = wt.project_id AND f.key = wt.from_key
+                 JOIN workflow_statuses t ON t.project_id = wt.project_id AND t.key = wt.to_key
+                 WHERE wt.project_id = ?1 ORDER BY f.position, t.position",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let transitions = stmt
+            .query_map(params![project_id.0.to_string()], |row| {
+                Ok(WorkflowTransition {
+                    from: row.get(0)?,
+                    to: row.get(1)?,
+                })
+            })
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(Workflow {
+            project_id,
+            statuses,
+            transitions,
+        })
+    }
+
+    /// Replace the workflow of a project
+    ///
+    /// Fails with a constraint violation if a status that tickets are still
+    /// in is removed. Tickets take on the category of their status if it
+    /// changed.
+    pub fn save(&self, workflow: &Workflow) -> Result<Workflow> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        let project_id = workflow.project_id.0.to_string();
+
+        let mut stmt = tx
+            .prepare("SELECT DISTINCT status FROM tickets WHERE project_id = ?1 ORDER BY status")
+            .map_err(|e| DbError::Query(e.to_string()))?;
+        let used: Vec<String> = stmt
+            .query_map(params![project_id], |row| row.get(0))
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+        drop(stmt);
+
+        if let Some(key) = used
+            .iter()
+            .find(|key| !workflow.statuses.iter().any(|s| &s.key == *key))
+        {
+            return Err(DbError::ConstraintViolation(format!(
+                "Status '{}' cannot be removed while tickets are in it",
+                key
+            )));
+        }
+
+        tx.execute(
+            "DELETE FROM workflow_transitions WHERE project_id = ?1",
+            params![project_id],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+        tx.execute(
+            "DELETE FROM workflow_statuses WHERE project_id = ?1",
+            params![project_id],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        insert_workflow(&tx, workflow)?;
+
+        for status in &workflow.statuses {
+            tx.execute(
+                "UPDATE tickets SET status_category = ?1
+                 WHERE project_id = ?2 AND status = ?3 AND status_category <> ?1",
+                params![status.category.as_str(), project_id, status.key],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+        }
+
+        tx.commit()
+            .map_err(|e| DbError::Transaction(e.to_string()))?;
+
+        Ok(workflow.clone())
+    }
+}
+
+/// Insert the statuses and transitions of a workflow
+///
+/// The project must not have any stored yet.
+pub(crate) fn insert_workflow(conn: &Connection, workflow: &Workflow) -> Result<()> {
+    let project_id = workflow.project_id.0.to_string();
+
+    for (position, status) in workflow.statuses.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO workflow_statuses (project_id, key, name, category, position)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                project_id,
+                status.key,
+                status.name,
+                status.category.as_str(),
+                position as i64,
+            ],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+    }
+
+    for transition in &workflow.transitions {
+        conn.execute(
+            "INSERT OR IGNORE INTO workflow_transitions (project_id, from_key, to_key)
+             VALUES (?1, ?2, ?3)",
+            params![project_id, transition.from, transition.to],
+        )
+        .map_err(|e| DbError::Query(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+fn row_to_status(row: &Row) -> rusqlite::Result<WorkflowStatus> {
+    let category: String = row.get(2)?;
+
+    Ok(WorkflowStatus {
+        key: row.get(0)?,
+        name: row.get(1)?,
+        category: StatusCategory::from_name(&category).unwrap_or(StatusCategory::Todo),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+        repository::Repository,
+    };
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (Arc<DbPool>, Project, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        (pool, project, user)
+    }
+
+    fn kanban(project_id: ProjectId) -> Workflow {
+        Workflow {
+            project_id,
+            statuses: vec![
+                WorkflowStatus::new("open", "Backlog", StatusCategory::Todo),
+                WorkflowStatus::new("doing", "Doing", StatusCategory::InProgress),
+                WorkflowStatus::new("done", "Shipped", StatusCategory::Done),
+            ],
+            transitions: vec![
+                WorkflowTransition {
+                    from: "open".to_string(),
+                    to: "doing".to_string(),
+                },
+                WorkflowTransition {
+                    from: "doing".to_string(),
+                    to: "done".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_new_projects_get_the_default_workflow() {
+        let (pool, project, _) = setup();
+        let repo = WorkflowRepository::new(pool);
+
+        let workflow = repo.find_by_project(project.id).unwrap();
+        assert_eq!(workflow, Workflow::default_for(project.id));
+    }
+
+    #[test]
+    fn test_save_replaces_workflow() {
+        let (pool, project, _) = setup();
+        let repo = WorkflowRepository::new(pool);
+
+        repo.save(&kanban(project.id)).unwrap();
+
+        let workflow = repo.find_by_project(project.id).unwrap();
+        assert_eq!(workflow, kanban(project.id));
+        assert!(workflow.can_transition("open", "doing"));
+        assert!(!workflow.can_transition("open", "done"));
+    }
+
+    #[test]
+    fn test_save_keeps_statuses_in_use() {
+        let (pool, project, user) = setup();
+        let repo = WorkflowRepository::new(Arc::clone(&pool));
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+
+        let workflow = Workflow::default_for(project.id);
+        let mut ticket = Ticket::new(
+            project.id,
+            "Review me".to_string(),
+            TicketType::Task,
+            user.id,
+        );
+        ticket.update_status(workflow.status("review").unwrap());
+        tickets.create(&ticket).unwrap();
+
+        let result = repo.save(&kanban(project.id));
+        assert!(matches!(result, Err(DbError::ConstraintViolation(_))));
+        assert_eq!(repo.find_by_project(project.id).unwrap(), workflow);
+
+        // Recategorizing a status moves its tickets along
+        let mut workflow = workflow;
+        workflow.statuses[2].category = StatusCategory::Done;
+        repo.save(&workflow).unwrap();
+
+        let found = tickets.find_by_id(ticket.id).unwrap().unwrap();
+        assert_eq!(found.status, "review");
+        assert_eq!(found.status_category, StatusCategory::Done);
+    }
+}
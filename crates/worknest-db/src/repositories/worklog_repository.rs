@@ -0,0 +1,368 @@
+//! Worklog repository implementation
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, OptionalExtension, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use worknest_core::models::{TicketId, UserId, Worklog, WorklogId};
+
+use crate::{connection::DbPool, repository::Repository, DbError, Result};
+
+/// Repository for the time users log against tickets
+pub struct WorklogRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WorklogRepository {
+    /// Create a new WorklogRepository
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find the worklogs of a ticket, earliest first
+    pub fn find_by_ticket(&self, ticket_id: TicketId) -> Result<Vec<Worklog>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, ticket_id, user_id, started_at, duration_minutes, note, created_at, updated_at
+                 FROM worklogs WHERE ticket_id = ?1 ORDER BY started_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let worklogs = stmt
+            .query_map(params![ticket_id.0.to_string()], row_to_worklog)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(worklogs)
+    }
+
+    /// Find a user's worklogs that started in `[from, to)`, earliest first
+    pub fn find_by_user_between(
+        &self,
+        user_id: UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Worklog>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, ticket_id, user_id, started_at, duration_minutes, note, created_at, updated_at
+                 FROM worklogs
+                 WHERE user_id = ?1 AND started_at >= ?2 AND started_at < ?3
+                 ORDER BY started_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let worklogs = stmt
+            .query_map(
+                params![user_id.0.to_string(), from.to_rfc3339(), to.to_rfc3339()],
+                row_to_worklog,
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(worklogs)
+    }
+
+    /// Total minutes logged against each of the given tickets
+    ///
+    /// Tickets without worklogs are left out.
+    pub fn logged_minutes(&self, ticket_ids: &[TicketId]) -> Result<Vec<(TicketId, u32)>> {
+        if ticket_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let placeholders = vec!["?"; ticket_ids.len()].join(", ");
+        let sql = format!(
+            "SELECT ticket_id, SUM(duration_minutes) FROM worklogs
+             WHERE ticket_id IN ({}) GROUP BY ticket_id",
+            placeholders
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let totals = stmt
+            .query_map(
+                params_from_iter(ticket_ids.iter().map(|id| id.0.to_string())),
+                |row| {
+                    let ticket_id: String = row.get(0)?;
+                    Ok((
+                        TicketId::from_uuid(Uuid::parse_str(&ticket_id).unwrap()),
+                        row.get(1)?,
+                    ))
+                },
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(totals)
+    }
+}
+
+impl Repository<Worklog, WorklogId> for WorklogRepository {
+    fn find_by_id(&self, id: WorklogId) -> Result<Option<Worklog>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, ticket_id, user_id, started_at, duration_minutes, note, created_at, updated_at
+                 FROM worklogs WHERE id = ?1",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let worklog = stmt
+            .query_row(params![id.0.to_string()], row_to_worklog)
+            .optional()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(worklog)
+    }
+
+    fn find_all(&self) -> Result<Vec<Worklog>> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, ticket_id, user_id, started_at, duration_minutes, note, created_at, updated_at
+                 FROM worklogs ORDER BY started_at",
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        let worklogs = stmt
+            .query_map([], row_to_worklog)
+            .map_err(|e| DbError::Query(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(worklogs)
+    }
+
+    fn create(&self, entity: &Worklog) -> Result<Worklog> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO worklogs (id, ticket_id, user_id, started_at, duration_minutes, note, created_at,
+                                   updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                entity.id.0.to_string(),
+                entity.ticket_id.0.to_string(),
+                entity.user_id.0.to_string(),
+                entity.started_at.to_rfc3339(),
+                entity.duration_minutes,
+                entity.note,
+                entity.created_at.to_rfc3339(),
+                entity.updated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("FOREIGN KEY constraint failed") {
+                DbError::ConstraintViolation("Unknown ticket or user".to_string())
+            } else {
+                DbError::Query(e.to_string())
+            }
+        })?;
+
+        Ok(entity.clone())
+    }
+
+    fn update(&self, entity: &Worklog) -> Result<Worklog> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE worklogs SET started_at = ?1, duration_minutes = ?2, note = ?3, updated_at = ?4
+                 WHERE id = ?5",
+                params![
+                    entity.started_at.to_rfc3339(),
+                    entity.duration_minutes,
+                    entity.note,
+                    entity.updated_at.to_rfc3339(),
+                    entity.id.0.to_string(),
+                ],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Worklog not found".to_string()));
+        }
+
+        Ok(entity.clone())
+    }
+
+    fn delete(&self, id: WorklogId) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| DbError::Connection(e.to_string()))?;
+
+        let rows_affected = conn
+            .execute(
+                "DELETE FROM worklogs WHERE id = ?1",
+                params![id.0.to_string()],
+            )
+            .map_err(|e| DbError::Query(e.to_string()))?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound("Worklog not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a database row to a Worklog
+fn row_to_worklog(row: &Row) -> rusqlite::Result<Worklog> {
+    let id_str: String = row.get(0)?;
+    let ticket_id_str: String = row.get(1)?;
+    let user_id_str: String = row.get(2)?;
+
+    let parse_time = |s: String| {
+        DateTime::parse_from_rfc3339(&s)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+
+    Ok(Worklog {
+        id: WorklogId::from_uuid(Uuid::parse_str(&id_str).unwrap()),
+        ticket_id: TicketId::from_uuid(Uuid::parse_str(&ticket_id_str).unwrap()),
+        user_id: UserId::from_uuid(Uuid::parse_str(&user_id_str).unwrap()),
+        started_at: parse_time(row.get(3)?),
+        duration_minutes: row.get(4)?,
+        note: row.get(5)?,
+        created_at: parse_time(row.get(6)?),
+        updated_at: parse_time(row.get(7)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::init_memory_pool,
+        migrations::run_migrations,
+        repositories::{
+            project_repository::ProjectRepository, ticket_repository::TicketRepository,
+            user_repository::UserRepository,
+        },
+    };
+    use chrono::{Duration, TimeZone};
+    use worknest_core::models::{Project, Ticket, TicketType, User};
+
+    fn setup() -> (WorklogRepository, TicketRepository, Ticket, User) {
+        let pool = Arc::new(init_memory_pool().unwrap());
+        let mut conn = pool.get().unwrap();
+        run_migrations(&mut conn).unwrap();
+        drop(conn);
+
+        let user = User::new("testuser".to_string(), "test@example.com".to_string());
+        UserRepository::new(Arc::clone(&pool))
+            .create_with_password(&user, "hash")
+            .unwrap();
+
+        let project = Project::new("Test Project".to_string(), user.id);
+        ProjectRepository::new(Arc::clone(&pool))
+            .create(&project)
+            .unwrap();
+
+        let tickets = TicketRepository::new(Arc::clone(&pool));
+        let ticket = tickets
+            .create(&Ticket::new(
+                project.id,
+                "Log me".to_string(),
+                TicketType::Task,
+                user.id,
+            ))
+            .unwrap();
+
+        (WorklogRepository::new(pool), tickets, ticket, user)
+    }
+
+    #[test]
+    fn test_worklog_crud_and_totals() {
+        let (repo, _, ticket, user) = setup();
+        let start = Utc.with_ymd_and_hms(2025, 3, 3, 9, 0, 0).unwrap();
+
+        let mut worklog = Worklog::new(ticket.id, user.id, start, 90);
+        worklog.note = Some("Investigation".to_string());
+        repo.create(&worklog).unwrap();
+        repo.create(&Worklog::new(
+            ticket.id,
+            user.id,
+            start + Duration::days(7),
+            30,
+        ))
+        .unwrap();
+
+        assert_eq!(repo.find_by_id(worklog.id).unwrap().unwrap(), worklog);
+        assert_eq!(repo.find_by_ticket(ticket.id).unwrap().len(), 2);
+        assert_eq!(
+            repo.logged_minutes(&[ticket.id, TicketId::new()]).unwrap(),
+            [(ticket.id, 120)]
+        );
+
+        let week = repo
+            .find_by_user_between(user.id, start, start + Duration::days(7))
+            .unwrap();
+        assert_eq!(week, [worklog.clone()]);
+
+        worklog.duration_minutes = 60;
+        repo.update(&worklog).unwrap();
+        assert_eq!(
+            repo.logged_minutes(&[ticket.id]).unwrap(),
+            [(ticket.id, 90)]
+        );
+
+        repo.delete(worklog.id).unwrap();
+        assert!(repo.find_by_id(worklog.id).unwrap().is_none());
+        assert!(matches!(repo.delete(worklog.id), Err(DbError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_worklogs_go_with_their_ticket() {
+        let (repo, tickets, ticket, user) = setup();
+        repo.create(&Worklog::new(ticket.id, user.id, Utc::now(), 15))
+            .unwrap();
+
+        tickets.delete(ticket.id).unwrap();
+        assert!(repo.find_by_ticket(ticket.id).unwrap().is_empty());
+
+        let orphan = Worklog::new(ticket.id, user.id, Utc::now(), 15);
+        assert!(matches!(
+            repo.create(&orphan),
+            Err(DbError::ConstraintViolation(_))
+        ));
+    }
+}
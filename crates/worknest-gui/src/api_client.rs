@@ -1,14 +1,43 @@
 //! API client for communicating with the Worknest backend server
 
 use anyhow::{anyhow, Result};
+use futures_util::future::{FutureExt, LocalBoxFuture, Shared};
+use gloo_storage::{LocalStorage, Storage};
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
-use worknest_core::models::{Comment, Project, Ticket, User};
+use worknest_core::models::{
+    CodeLink, Comment, CustomField, CustomFieldValue, Notification, NotificationPreferences,
+    Project, SavedView, Sprint, Ticket, User, ViewDisplay, Workflow, Worklog,
+};
 
+/// Number of tickets requested per page when listing tickets
+const TICKET_PAGE_SIZE: u32 = 200;
+
+/// Access and refresh token of the signed-in session
+#[derive(Debug, Clone)]
+struct SessionTokens {
+    access_token: String,
+    refresh_token: String,
+}
+
+/// A token refresh in flight, resolving to the new access token
+type PendingRefresh = Shared<LocalBoxFuture<'static, std::result::Result<String, String>>>;
+
+/// Client for the Worknest REST API
+///
+/// Authenticated requests use the most recent access token known to the
+/// client. When the server answers 401, the client exchanges its refresh
+/// token for new tokens once and retries the request. Requests failing
+/// together share a single refresh.
 #[derive(Clone)]
 pub struct ApiClient {
     base_url: String,
     client: reqwest::Client,
+    tokens: Arc<Mutex<Option<SessionTokens>>>,
+    refreshing: Rc<RefCell<Option<PendingRefresh>>>,
 }
 
 impl ApiClient {
@@ -16,6 +45,8 @@ impl ApiClient {
         Self {
             base_url,
             client: reqwest::Client::new(),
+            tokens: Arc::new(Mutex::new(None)),
+            refreshing: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -28,6 +59,135 @@ impl ApiClient {
         format!("{}/api{}", self.base_url, path)
     }
 
+    /// Remember the tokens of a new session and persist them to localStorage
+    pub fn set_tokens(&self, access_token: String, refresh_token: String) {
+        let _ = LocalStorage::set("auth_token", &access_token);
+        let _ = LocalStorage::set("refresh_token", &refresh_token);
+
+        if let Ok(mut tokens) = self.tokens.lock() {
+            *tokens = Some(SessionTokens {
+                access_token,
+                refresh_token,
+            });
+        }
+    }
+
+    /// Restore the session tokens persisted in localStorage
+    pub fn restore_tokens(&self) -> bool {
+        let access_token: Result<String, _> = LocalStorage::get("auth_token");
+        let refresh_token: Result<String, _> = LocalStorage::get("refresh_token");
+
+        if let (Ok(access_token), Ok(refresh_token)) = (access_token, refresh_token) {
+            self.set_tokens(access_token, refresh_token);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forget the session tokens
+    pub fn clear_tokens(&self) {
+        LocalStorage::delete("refresh_token");
+
+        if let Ok(mut tokens) = self.tokens.lock() {
+            *tokens = None;
+        }
+    }
+
+    fn access_token(&self) -> Option<String> {
+        self.tokens
+            .lock()
+            .ok()
+            .and_then(|tokens| tokens.as_ref().map(|t| t.access_token.clone()))
+    }
+
+    /// Send an authenticated request, refreshing the session once on 401
+    ///
+    /// `build` receives the access token to use and is called again for the
+    /// retry. `token` is only used when the client holds no token itself.
+    async fn send_authorized<F>(&self, token: &str, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn(&str) -> reqwest::RequestBuilder,
+    {
+        let access_token = self.access_token().unwrap_or_else(|| token.to_string());
+        let response = build(&access_token).send().await?;
+
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        match self.refresh(&access_token).await {
+            Ok(refreshed) => Ok(build(&refreshed).send().await?),
+            Err(e) => {
+                tracing::warn!("Token refresh failed: {:?}", e);
+                Ok(response)
+            },
+        }
+    }
+
+    /// Exchange the refresh token for new tokens
+    ///
+    /// If the tokens were already rotated since `stale_access_token` was
+    /// used, the current access token is returned instead, and a refresh
+    /// already in flight is awaited rather than started again: refresh
+    /// tokens are single-use and presenting one twice revokes the session.
+    async fn refresh(&self, stale_access_token: &str) -> Result<String> {
+        let pending = {
+            let tokens = self
+                .tokens
+                .lock()
+                .map_err(|_| anyhow!("Token store unavailable"))?;
+            let refresh_token = match tokens.as_ref() {
+                Some(t) if t.access_token != stale_access_token => {
+                    return Ok(t.access_token.clone());
+                },
+                Some(t) => t.refresh_token.clone(),
+                None => return Err(anyhow!("No refresh token")),
+            };
+
+            let mut refreshing = self.refreshing.borrow_mut();
+            match refreshing.as_ref() {
+                Some(pending) => pending.clone(),
+                None => {
+                    let client = self.clone();
+                    let pending = async move {
+                        let result = client
+                            .exchange_refresh_token(refresh_token)
+                            .await
+                            .map_err(|e| e.to_string());
+                        *client.refreshing.borrow_mut() = None;
+                        result
+                    }
+                    .boxed_local()
+                    .shared();
+                    *refreshing = Some(pending.clone());
+                    pending
+                },
+            }
+        };
+
+        pending.await.map_err(|e| anyhow!(e))
+    }
+
+    /// Post the refresh token and store the tokens that replace it
+    async fn exchange_refresh_token(&self, refresh_token: String) -> Result<String> {
+        let response = self
+            .client
+            .post(self.api_url("/auth/refresh"))
+            .json(&RefreshRequest { refresh_token })
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let refreshed: RefreshResponse = response.json().await?;
+            self.set_tokens(refreshed.token.clone(), refreshed.refresh_token);
+            Ok(refreshed.token)
+        } else {
+            self.clear_tokens();
+            Err(anyhow!("Token refresh failed: {}", response.status()))
+        }
+    }
+
     // Auth endpoints
     pub async fn register(&self, request: RegisterRequest) -> Result<AuthResponse> {
         let response = self
@@ -38,7 +198,9 @@ impl ApiClient {
             .await?;
 
         if response.status().is_success() {
-            Ok(response.json().await?)
+            let auth: AuthResponse = response.json().await?;
+            self.set_tokens(auth.token.clone(), auth.refresh_token.clone());
+            Ok(auth)
         } else {
             Err(anyhow!("Registration failed: {}", response.status()))
         }
@@ -53,21 +215,72 @@ impl ApiClient {
             .await?;
 
         if response.status().is_success() {
-            Ok(response.json().await?)
+            let auth: AuthResponse = response.json().await?;
+            self.set_tokens(auth.token.clone(), auth.refresh_token.clone());
+            Ok(auth)
         } else {
             Err(anyhow!("Login failed: {}", response.status()))
         }
     }
 
-    // User endpoints
-    pub async fn get_current_user(&self, token: &str) -> Result<User> {
+    /// Revoke the session behind a token on the server and forget its tokens
+    pub async fn logout(&self, token: &str) -> Result<()> {
+        let token = self.access_token().unwrap_or_else(|| token.to_string());
+        self.clear_tokens();
+
         let response = self
             .client
-            .get(self.api_url("/users/me"))
+            .post(self.api_url("/auth/logout"))
             .bearer_auth(token)
             .send()
             .await?;
 
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!("Logout failed: {}", response.status()))
+        }
+    }
+
+    /// URL of the server's change stream, carrying a fresh stream ticket
+    ///
+    /// `EventSource` cannot send headers, so a single-use ticket goes in the
+    /// query string instead of the access token. The stream ends when the
+    /// session is revoked or its access token expires.
+    pub async fn change_stream_url(&self, token: &str) -> Result<String> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url("/events/ticket"))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to open change stream: {}",
+                response.status()
+            ));
+        }
+        let ticket: StreamTicket = response.json().await?;
+
+        Ok(format!(
+            "{}?ticket={}",
+            self.api_url("/events"),
+            ticket.ticket
+        ))
+    }
+
+    // User endpoints
+    pub async fn get_current_user(&self, token: &str) -> Result<User> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url("/users/me"))
+                    .bearer_auth(token)
+            })
+            .await?;
+
         if response.status().is_success() {
             Ok(response.json().await?)
         } else {
@@ -77,10 +290,9 @@ impl ApiClient {
 
     pub async fn get_users(&self, token: &str) -> Result<Vec<User>> {
         let response = self
-            .client
-            .get(self.api_url("/users"))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client.get(self.api_url("/users")).bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -90,13 +302,55 @@ impl ApiClient {
         }
     }
 
+    pub async fn update_profile(&self, token: &str, request: UpdateProfileRequest) -> Result<User> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url("/users/me"))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to update profile: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
+    /// Change the password; the server signs out every other session
+    pub async fn change_password(&self, token: &str, request: ChangePasswordRequest) -> Result<()> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url("/users/me/password"))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to change password: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
     // Project endpoints
     pub async fn get_projects(&self, token: &str) -> Result<Vec<Project>> {
         let response = self
-            .client
-            .get(self.api_url("/projects"))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url("/projects"))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -108,10 +362,11 @@ impl ApiClient {
 
     pub async fn get_project(&self, token: &str, id: Uuid) -> Result<Project> {
         let response = self
-            .client
-            .get(self.api_url(&format!("/projects/{}", id)))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/projects/{}", id)))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -127,11 +382,12 @@ impl ApiClient {
         request: CreateProjectRequest,
     ) -> Result<Project> {
         let response = self
-            .client
-            .post(self.api_url("/projects"))
-            .bearer_auth(token)
-            .json(&request)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url("/projects"))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -148,11 +404,12 @@ impl ApiClient {
         request: UpdateProjectRequest,
     ) -> Result<Project> {
         let response = self
-            .client
-            .put(self.api_url(&format!("/projects/{}", id)))
-            .bearer_auth(token)
-            .json(&request)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url(&format!("/projects/{}", id)))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -164,10 +421,11 @@ impl ApiClient {
 
     pub async fn delete_project(&self, token: &str, id: Uuid) -> Result<()> {
         let response = self
-            .client
-            .delete(self.api_url(&format!("/projects/{}", id)))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .delete(self.api_url(&format!("/projects/{}", id)))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -179,15 +437,17 @@ impl ApiClient {
 
     pub async fn archive_project(&self, token: &str, id: Uuid) -> Result<Project> {
         let response = self
-            .client
-            .put(self.api_url(&format!("/projects/{}", id)))
-            .bearer_auth(token)
-            .json(&UpdateProjectRequest {
-                name: None,
-                description: None,
-                is_archived: Some(true),
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url(&format!("/projects/{}", id)))
+                    .bearer_auth(token)
+                    .json(&UpdateProjectRequest {
+                        name: None,
+                        key: None,
+                        description: None,
+                        is_archived: Some(true),
+                    })
             })
-            .send()
             .await?;
 
         if response.status().is_success() {
@@ -199,15 +459,17 @@ impl ApiClient {
 
     pub async fn unarchive_project(&self, token: &str, id: Uuid) -> Result<Project> {
         let response = self
-            .client
-            .put(self.api_url(&format!("/projects/{}", id)))
-            .bearer_auth(token)
-            .json(&UpdateProjectRequest {
-                name: None,
-                description: None,
-                is_archived: Some(false),
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url(&format!("/projects/{}", id)))
+                    .bearer_auth(token)
+                    .json(&UpdateProjectRequest {
+                        name: None,
+                        key: None,
+                        description: None,
+                        is_archived: Some(false),
+                    })
             })
-            .send()
             .await?;
 
         if response.status().is_success() {
@@ -221,47 +483,110 @@ impl ApiClient {
     }
 
     // Ticket endpoints
+    /// Fetch every ticket visible to the user, following the server's
+    /// page cursors
     pub async fn get_tickets(&self, token: &str, project_id: Option<Uuid>) -> Result<Vec<Ticket>> {
+        self.query_tickets(token, project_id, None, None).await
+    }
+
+    /// Fetch every ticket matching a query in the ticket query language,
+    /// optionally in a given sort order
+    ///
+    /// Without a query this is the same as [`ApiClient::get_tickets`].
+    pub async fn query_tickets(
+        &self,
+        token: &str,
+        project_id: Option<Uuid>,
+        query: Option<&str>,
+        sort: Option<&str>,
+    ) -> Result<Vec<Ticket>> {
         let url = self.api_url("/tickets");
+        let mut tickets = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let response = self
+                .send_authorized(token, |token| {
+                    let mut request = self
+                        .client
+                        .get(&url)
+                        .bearer_auth(token)
+                        .query(&[("limit", TICKET_PAGE_SIZE)]);
+
+                    // Add project_id as query parameter if provided
+                    if let Some(pid) = project_id {
+                        request = request.query(&[("project_id", pid.to_string())]);
+                    }
+                    if let Some(query) = query {
+                        request = request.query(&[("q", query)]);
+                    }
+                    if let Some(sort) = sort {
+                        request = request.query(&[("sort", sort)]);
+                    }
+                    if let Some(cursor) = &cursor {
+                        request = request.query(&[("cursor", cursor)]);
+                    }
+                    request
+                })
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(anyhow!(error_message(response).await));
+            }
 
-        let mut request = self.client.get(&url).bearer_auth(token);
+            let page: TicketPage = response.json().await?;
+            tickets.extend(page.items);
 
-        // Add project_id as query parameter if provided
-        if let Some(pid) = project_id {
-            request = request.query(&[("project_id", pid.to_string())]);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(tickets),
+            }
         }
+    }
 
-        let response = request.send().await?;
+    pub async fn get_ticket(&self, token: &str, id: Uuid) -> Result<Ticket> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/tickets/{}", id)))
+                    .bearer_auth(token)
+            })
+            .await?;
 
         if response.status().is_success() {
             Ok(response.json().await?)
         } else {
-            Err(anyhow!("Failed to get tickets: {}", response.status()))
+            Err(anyhow!("Failed to get ticket: {}", response.status()))
         }
     }
 
-    pub async fn get_ticket(&self, token: &str, id: Uuid) -> Result<Ticket> {
+    pub async fn get_ticket_children(&self, token: &str, id: Uuid) -> Result<Vec<Ticket>> {
         let response = self
-            .client
-            .get(self.api_url(&format!("/tickets/{}", id)))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/tickets/{}/children", id)))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
             Ok(response.json().await?)
         } else {
-            Err(anyhow!("Failed to get ticket: {}", response.status()))
+            Err(anyhow!(
+                "Failed to get child tickets: {}",
+                response.status()
+            ))
         }
     }
 
     pub async fn create_ticket(&self, token: &str, request: CreateTicketRequest) -> Result<Ticket> {
         let response = self
-            .client
-            .post(self.api_url("/tickets"))
-            .bearer_auth(token)
-            .json(&request)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url("/tickets"))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -278,26 +603,31 @@ impl ApiClient {
         request: UpdateTicketRequest,
     ) -> Result<Ticket> {
         let response = self
-            .client
-            .put(self.api_url(&format!("/tickets/{}", id)))
-            .bearer_auth(token)
-            .json(&request)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url(&format!("/tickets/{}", id)))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
             .await?;
 
         if response.status().is_success() {
             Ok(response.json().await?)
         } else {
-            Err(anyhow!("Failed to update ticket: {}", response.status()))
+            Err(anyhow!(
+                "Failed to update ticket: {}",
+                error_message(response).await
+            ))
         }
     }
 
     pub async fn delete_ticket(&self, token: &str, id: Uuid) -> Result<()> {
         let response = self
-            .client
-            .delete(self.api_url(&format!("/tickets/{}", id)))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .delete(self.api_url(&format!("/tickets/{}", id)))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -307,13 +637,126 @@ impl ApiClient {
         }
     }
 
+    // Custom field endpoints
+    pub async fn get_custom_fields(
+        &self,
+        token: &str,
+        project_id: Uuid,
+    ) -> Result<Vec<CustomField>> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/projects/{}/fields", project_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to get custom fields: {}",
+                response.status()
+            ))
+        }
+    }
+
+    // Workflow endpoints
+    pub async fn get_workflow(&self, token: &str, project_id: Uuid) -> Result<Workflow> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/projects/{}/workflow", project_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!("Failed to get workflow: {}", response.status()))
+        }
+    }
+
+    // Sprint endpoints
+    pub async fn get_sprints(&self, token: &str, project_id: Uuid) -> Result<Vec<Sprint>> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/projects/{}/sprints", project_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!("Failed to get sprints: {}", response.status()))
+        }
+    }
+
+    // Saved view endpoints
+    pub async fn get_views(&self, token: &str) -> Result<Vec<SavedView>> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client.get(self.api_url("/views")).bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!("Failed to get saved views: {}", response.status()))
+        }
+    }
+
+    pub async fn create_view(&self, token: &str, request: CreateViewRequest) -> Result<SavedView> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url("/views"))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to save view: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
+    pub async fn delete_view(&self, token: &str, id: Uuid) -> Result<()> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .delete(self.api_url(&format!("/views/{}", id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to delete view: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
     // Comment endpoints
     pub async fn get_ticket_comments(&self, token: &str, ticket_id: Uuid) -> Result<Vec<Comment>> {
         let response = self
-            .client
-            .get(self.api_url(&format!("/tickets/{}/comments", ticket_id)))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/tickets/{}/comments", ticket_id)))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -330,11 +773,12 @@ impl ApiClient {
         request: CreateCommentRequest,
     ) -> Result<Comment> {
         let response = self
-            .client
-            .post(self.api_url(&format!("/tickets/{}/comments", ticket_id)))
-            .bearer_auth(token)
-            .json(&request)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url(&format!("/tickets/{}/comments", ticket_id)))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -351,11 +795,12 @@ impl ApiClient {
         request: UpdateCommentRequest,
     ) -> Result<Comment> {
         let response = self
-            .client
-            .put(self.api_url(&format!("/comments/{}", comment_id)))
-            .bearer_auth(token)
-            .json(&request)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url(&format!("/comments/{}", comment_id)))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -367,10 +812,11 @@ impl ApiClient {
 
     pub async fn delete_comment(&self, token: &str, comment_id: Uuid) -> Result<()> {
         let response = self
-            .client
-            .delete(self.api_url(&format!("/comments/{}", comment_id)))
-            .bearer_auth(token)
-            .send()
+            .send_authorized(token, |token| {
+                self.client
+                    .delete(self.api_url(&format!("/comments/{}", comment_id)))
+                    .bearer_auth(token)
+            })
             .await?;
 
         if response.status().is_success() {
@@ -379,10 +825,242 @@ impl ApiClient {
             Err(anyhow!("Failed to delete comment: {}", response.status()))
         }
     }
+
+    // Worklog endpoints
+    pub async fn get_ticket_worklogs(&self, token: &str, ticket_id: Uuid) -> Result<Vec<Worklog>> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/tickets/{}/worklogs", ticket_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!("Failed to get worklogs: {}", response.status()))
+        }
+    }
+
+    pub async fn create_worklog(
+        &self,
+        token: &str,
+        ticket_id: Uuid,
+        request: CreateWorklogRequest,
+    ) -> Result<Worklog> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url(&format!("/tickets/{}/worklogs", ticket_id)))
+                    .bearer_auth(token)
+                    .json(&request)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to log time: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
+    pub async fn delete_worklog(&self, token: &str, worklog_id: Uuid) -> Result<()> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .delete(self.api_url(&format!("/worklogs/{}", worklog_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to delete worklog: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
+    // Code link endpoints
+    pub async fn get_ticket_links(&self, token: &str, ticket_id: Uuid) -> Result<Vec<CodeLink>> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url(&format!("/tickets/{}/links", ticket_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!("Failed to get code links: {}", response.status()))
+        }
+    }
+
+    // Notification endpoints
+
+    pub async fn get_notifications(&self, token: &str) -> Result<Vec<Notification>> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url("/notifications"))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to get notifications: {}",
+                response.status()
+            ))
+        }
+    }
+
+    pub async fn get_unread_notification_count(&self, token: &str) -> Result<u32> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url("/notifications/unread-count"))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            let body: UnreadCountResponse = response.json().await?;
+            Ok(body.count)
+        } else {
+            Err(anyhow!(
+                "Failed to get unread notifications: {}",
+                response.status()
+            ))
+        }
+    }
+
+    pub async fn mark_notification_read(
+        &self,
+        token: &str,
+        notification_id: Uuid,
+    ) -> Result<Notification> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url(&format!("/notifications/{}/read", notification_id)))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to mark notification read: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
+    pub async fn mark_all_notifications_read(&self, token: &str) -> Result<()> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .post(self.api_url("/notifications/read-all"))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to mark notifications read: {}",
+                error_message(response).await
+            ))
+        }
+    }
+
+    pub async fn get_notification_preferences(
+        &self,
+        token: &str,
+    ) -> Result<NotificationPreferences> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .get(self.api_url("/users/me/notification-preferences"))
+                    .bearer_auth(token)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to get notification preferences: {}",
+                response.status()
+            ))
+        }
+    }
+
+    pub async fn update_notification_preferences(
+        &self,
+        token: &str,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences> {
+        let response = self
+            .send_authorized(token, |token| {
+                self.client
+                    .put(self.api_url("/users/me/notification-preferences"))
+                    .bearer_auth(token)
+                    .json(preferences)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow!(
+                "Failed to save notification preferences: {}",
+                error_message(response).await
+            ))
+        }
+    }
+}
+
+/// Extract the error message from an API error response
+async fn error_message(response: reqwest::Response) -> String {
+    let status = response.status();
+
+    match response.json::<ErrorResponse>().await {
+        Ok(body) => body.error,
+        Err(_) => status.to_string(),
+    }
 }
 
 // Request/Response types
 
+#[derive(Debug, Clone, Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct UnreadCountResponse {
+    count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct StreamTicket {
+    ticket: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub username: String,
@@ -399,22 +1077,57 @@ pub struct LoginRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user: User,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RefreshRequest {
+    refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RefreshResponse {
+    token: String,
+    refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateProfileRequest {
+    pub username: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateProjectRequest {
     pub name: String,
+    /// Ticket key prefix; the server derives one from the name when `None`
+    pub key: Option<String>,
     pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateProjectRequest {
     pub name: Option<String>,
+    pub key: Option<String>,
     pub description: Option<String>,
     pub is_archived: Option<bool>,
 }
 
+/// One page of `GET /tickets`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketPage {
+    pub items: Vec<Ticket>,
+    pub total: u64,
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTicketRequest {
     pub project_id: Uuid,
@@ -432,6 +1145,8 @@ pub struct UpdateTicketRequest {
     pub priority: Option<String>,
     pub ticket_type: Option<String>,
     pub assigned_to: Option<Uuid>,
+    /// Replaces every custom field value when present
+    pub custom_fields: Option<Vec<CustomFieldValue>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -443,3 +1158,20 @@ pub struct CreateCommentRequest {
 pub struct UpdateCommentRequest {
     pub content: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWorklogRequest {
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duration_minutes: u32,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateViewRequest {
+    pub name: String,
+    pub query: String,
+    pub sort: String,
+    pub display: ViewDisplay,
+    pub project_id: Option<Uuid>,
+    pub shared: bool,
+}
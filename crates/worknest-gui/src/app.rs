@@ -10,8 +10,9 @@ use crate::{
     api_client::ApiClient,
     components::{Breadcrumb, CommandAction, CommandPalette, ShortcutsHelp, Sidebar, ToastManager},
     screens::{
-        DashboardScreen, LoginScreen, ProjectDetailScreen, ProjectListScreen, RegisterScreen,
-        Screen, SettingsScreen, TicketBoardScreen, TicketDetailScreen, TicketListScreen,
+        DashboardScreen, LoginScreen, NotificationsScreen, ProjectDetailScreen, ProjectListScreen,
+        RegisterScreen, Screen, SettingsScreen, TicketBoardScreen, TicketDetailScreen,
+        TicketListScreen,
     },
     state::AppState,
     theme::Theme,
@@ -31,6 +32,7 @@ pub struct WorknestApp {
     register_screen: RegisterScreen,
     dashboard_screen: DashboardScreen,
     project_list_screen: ProjectListScreen,
+    notifications_screen: NotificationsScreen,
     settings_screen: SettingsScreen,
     // Detail screens are created on demand
     project_detail_screen: Option<ProjectDetailScreen>,
@@ -97,6 +99,7 @@ impl WorknestApp {
             register_screen: RegisterScreen::new(),
             dashboard_screen: DashboardScreen::new(),
             project_list_screen: ProjectListScreen::new(),
+            notifications_screen: NotificationsScreen::new(),
             settings_screen: SettingsScreen::new(),
             project_detail_screen: None,
             ticket_list_screen: None,
@@ -150,6 +153,12 @@ impl eframe::App for WorknestApp {
         // Process any pending events from async operations
         self.state.process_events();
 
+        // Changes pushed by the server arrive without user input, so look
+        // for them regularly while signed in
+        if self.state.is_authenticated() {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+
         // Hide loading screen on first frame (only on WASM)
         #[cfg(target_arch = "wasm32")]
         if self.first_frame {
@@ -271,6 +280,9 @@ impl eframe::App for WorknestApp {
                     screen.render(ctx, &mut self.state);
                 }
             },
+            Screen::Notifications => {
+                self.notifications_screen.render(ctx, &mut self.state);
+            },
             Screen::Settings => {
                 self.settings_screen.render(ctx, &mut self.state);
             },
@@ -0,0 +1,68 @@
+//! Live updates pushed by the server over Server-Sent Events
+
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{EventSource, MessageEvent};
+use worknest_core::models::ChangeEvent;
+
+use crate::events::{AppEvent, EventQueue};
+
+/// Connection to the server's change stream
+///
+/// Changes are pushed to the event queue as [`AppEvent::RemoteChange`]. The
+/// connection is closed when the stream is dropped.
+pub struct ChangeStream {
+    source: EventSource,
+    // The callbacks must live as long as the connection
+    _on_change: Closure<dyn FnMut(MessageEvent)>,
+    _on_resync: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl ChangeStream {
+    /// Connect to the change stream at `url`
+    pub fn connect(url: &str, event_queue: EventQueue) -> Result<Self, JsValue> {
+        let source = EventSource::new(url)?;
+
+        let queue = event_queue.clone();
+        let on_change = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(data) = event.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<ChangeEvent>(&data) {
+                Ok(change) => queue.push(AppEvent::RemoteChange { change }),
+                Err(e) => tracing::warn!("Ignoring malformed change: {:?}", e),
+            }
+        });
+        source.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())?;
+
+        let queue = event_queue.clone();
+        let on_resync = Closure::<dyn FnMut(MessageEvent)>::new(move |_: MessageEvent| {
+            queue.push(AppEvent::ChangeStreamResync);
+        });
+        source.add_event_listener_with_callback("resync", on_resync.as_ref().unchecked_ref())?;
+
+        // The browser would retry a dropped connection with the same URL, but
+        // its stream ticket is spent, so close it and reconnect with a fresh
+        // ticket instead
+        let closed_source = source.clone();
+        let on_error = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            closed_source.close();
+            event_queue.push(AppEvent::ChangeStreamClosed);
+        });
+        source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        Ok(Self {
+            source,
+            _on_change: on_change,
+            _on_resync: on_resync,
+            _on_error: on_error,
+        })
+    }
+}
+
+impl Drop for ChangeStream {
+    fn drop(&mut self) {
+        self.source.set_onerror(None);
+        self.source.close();
+    }
+}
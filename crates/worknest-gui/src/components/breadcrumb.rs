@@ -125,6 +125,9 @@ impl Breadcrumb {
                     items.push(BreadcrumbItem::current("Ticket"));
                 }
             },
+            Screen::Notifications => {
+                items.push(BreadcrumbItem::current("Inbox"));
+            },
             Screen::Settings => {
                 items.push(BreadcrumbItem::current("Settings"));
             },
@@ -104,6 +104,12 @@ impl CommandPalette {
                 category: CommandCategory::Navigation,
                 action: CommandAction::Navigate(Screen::TicketList { project_id: None }),
             },
+            Command {
+                name: "Go to Inbox".to_string(),
+                description: "View your notifications".to_string(),
+                category: CommandCategory::Navigation,
+                action: CommandAction::Navigate(Screen::Notifications),
+            },
             Command {
                 name: "Go to Settings".to_string(),
                 description: "Manage your account settings".to_string(),
@@ -0,0 +1,40 @@
+//! Colored label chips for ticket cards
+
+use egui::{Color32, RichText, Ui};
+use worknest_core::models::Label;
+
+/// Render one label as a small colored chip
+pub fn label_chip(ui: &mut Ui, label: &Label) {
+    let (r, g, b) = label.rgb().unwrap_or((0x6b, 0x72, 0x80));
+    let fill = Color32::from_rgb(r, g, b);
+
+    // Pick black or white text by the perceived brightness of the fill
+    let brightness = (u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000;
+    let text_color = if brightness > 150 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    };
+
+    egui::Frame::NONE
+        .fill(fill)
+        .inner_margin(egui::Margin::symmetric(6, 1))
+        .corner_radius(8.0)
+        .show(ui, |ui| {
+            ui.label(RichText::new(&label.name).small().color(text_color));
+        });
+}
+
+/// Render all labels of a ticket in a wrapping row
+pub fn label_chips(ui: &mut Ui, labels: &[Label]) {
+    if labels.is_empty() {
+        return;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        for label in labels {
+            label_chip(ui, label);
+        }
+    });
+}
@@ -3,6 +3,7 @@
 pub mod breadcrumb;
 pub mod command_palette;
 pub mod empty_state;
+pub mod label_chip;
 pub mod shortcuts;
 pub mod sidebar;
 pub mod skeleton;
@@ -11,6 +12,7 @@ pub mod toast;
 pub use breadcrumb::{Breadcrumb, BreadcrumbItem};
 pub use command_palette::{Command, CommandAction, CommandCategory, CommandPalette};
 pub use empty_state::{CallToAction, EmptyState, EmptyStateAction, EmptyStates};
+pub use label_chip::{label_chip, label_chips};
 pub use shortcuts::{ShortcutDefinition, ShortcutsHelp};
 pub use sidebar::Sidebar;
 pub use skeleton::{ProjectCardSkeleton, SkeletonLoader, TicketSkeletonLoader};
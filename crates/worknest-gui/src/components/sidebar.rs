@@ -1,6 +1,7 @@
 //! Left sidebar navigation component
 
 use egui::{Context, Key, KeyboardShortcut, Modifiers, RichText, Ui};
+use worknest_core::models::ViewDisplay;
 
 use crate::{
     screens::Screen,
@@ -16,6 +17,8 @@ pub struct Sidebar {
     pub expanded_width: f32,
     /// Width of the collapsed sidebar (icons only)
     pub collapsed_width: f32,
+    /// Whether the saved views were fetched for the current session
+    views_loaded: bool,
 }
 
 impl Default for Sidebar {
@@ -30,6 +33,7 @@ impl Sidebar {
             is_expanded: true,
             expanded_width: 220.0,
             collapsed_width: 50.0,
+            views_loaded: false,
         }
     }
 
@@ -55,6 +59,16 @@ impl Sidebar {
             self.toggle();
         }
 
+        // Fetch saved views once per session
+        if state.is_authenticated() {
+            if !self.views_loaded {
+                state.load_saved_views();
+                self.views_loaded = true;
+            }
+        } else {
+            self.views_loaded = false;
+        }
+
         let width = self.width();
 
         egui::SidePanel::left("sidebar")
@@ -146,6 +160,28 @@ impl Sidebar {
             || state.navigate_to(Screen::TicketList { project_id: None }),
         );
 
+        // Inbox, with the number of unread notifications as a badge
+        let unread = state.unread_notifications;
+        let badge = match unread {
+            0 => String::new(),
+            1..=99 => unread.to_string(),
+            _ => "99+".to_string(),
+        };
+        let (icon, label) = if badge.is_empty() {
+            ("🔔".to_string(), "Inbox".to_string())
+        } else if self.is_expanded {
+            ("🔔".to_string(), format!("Inbox ({})", badge))
+        } else {
+            (format!("🔔{}", badge), format!("Inbox ({} unread)", badge))
+        };
+        self.render_nav_item(
+            ui,
+            &icon,
+            &label,
+            matches!(state.current_screen, Screen::Notifications),
+            || state.navigate_to(Screen::Notifications),
+        );
+
         ui.add_space(Spacing::MEDIUM);
         ui.separator();
         ui.add_space(Spacing::MEDIUM);
@@ -196,6 +232,90 @@ impl Sidebar {
             ui.add_space(Spacing::MEDIUM);
             ui.separator();
             ui.add_space(Spacing::MEDIUM);
+
+            self.render_saved_views(ui, state);
+        }
+    }
+
+    fn render_saved_views(&self, ui: &mut Ui, state: &mut AppState) {
+        ui.label(
+            RichText::new("SAVED VIEWS")
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(Spacing::SMALL);
+
+        if state.saved_views.is_empty() {
+            ui.label(
+                RichText::new("Save a ticket filter to see it here")
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+            return;
+        }
+
+        let current_user_id = state.current_user.as_ref().map(|u| u.id);
+        let mut open = None;
+        let mut delete = None;
+
+        for view in &state.saved_views {
+            let icon = match view.display {
+                ViewDisplay::List => "☰",
+                ViewDisplay::Board => "▦",
+            };
+            let label = if view.shared {
+                format!("{} {} 👥", icon, view.name)
+            } else {
+                format!("{} {}", icon, view.name)
+            };
+
+            let mut response = ui.add(
+                egui::Button::new(RichText::new(label).size(14.0))
+                    .fill(egui::Color32::TRANSPARENT)
+                    .frame(false),
+            );
+            if !view.query.is_empty() {
+                response = response.on_hover_text(&view.query);
+            }
+
+            if response.clicked() {
+                open = Some(view.clone());
+            }
+
+            if current_user_id == Some(view.owner_id) {
+                response.context_menu(|ui| {
+                    if ui.button("Delete view").clicked() {
+                        delete = Some(view.id);
+                        ui.close();
+                    }
+                });
+            }
+        }
+
+        if let Some(view) = open {
+            state.open_view(view);
+        }
+
+        if let Some(view_id) = delete {
+            let api_client = state.api_client.clone();
+            let event_queue = state.event_queue.clone();
+            if let Some(token) = state.auth_token.clone() {
+                wasm_bindgen_futures::spawn_local(async move {
+                    use crate::events::AppEvent;
+
+                    match api_client.delete_view(&token, view_id.0).await {
+                        Ok(()) => {
+                            event_queue.push(AppEvent::SavedViewDeleted { view_id });
+                        },
+                        Err(e) => {
+                            tracing::error!("Failed to delete saved view: {:?}", e);
+                            event_queue.push(AppEvent::SavedViewError {
+                                message: e.to_string(),
+                            });
+                        },
+                    }
+                });
+            }
         }
     }
 
@@ -1,7 +1,11 @@
 //! Event system for async API callbacks
 
+use crate::state::RunningTimer;
 use std::sync::{Arc, Mutex};
-use worknest_core::models::{Comment, Project, Ticket, User};
+use worknest_core::models::{
+    ChangeEvent, CodeLink, Comment, CustomField, Notification, NotificationPreferences, Project,
+    ProjectId, SavedView, SavedViewId, Sprint, Ticket, TicketId, User, Workflow, Worklog,
+};
 
 /// Event queue for handling async API responses
 #[derive(Clone)]
@@ -43,35 +47,194 @@ impl Default for EventQueue {
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     // Authentication events
-    LoginSuccess { user: User, token: String },
-    LoginError { message: String },
-    RegisterSuccess { user: User, token: String },
-    RegisterError { message: String },
+    LoginSuccess {
+        user: User,
+        token: String,
+    },
+    LoginError {
+        message: String,
+    },
+    RegisterSuccess {
+        user: User,
+        token: String,
+    },
+    RegisterError {
+        message: String,
+    },
+
+    // Profile events
+    ProfileUpdated {
+        user: User,
+    },
+    ProfileError {
+        message: String,
+    },
+    PasswordChanged,
+    PasswordError {
+        message: String,
+    },
 
     // Project events
-    ProjectsLoaded { projects: Vec<Project> },
-    ProjectLoaded { project: Project },
-    ProjectCreated { project: Project },
-    ProjectUpdated { project: Project },
-    ProjectDeleted { project_id: String },
-    ProjectError { message: String },
+    ProjectsLoaded {
+        projects: Vec<Project>,
+    },
+    ProjectLoaded {
+        project: Project,
+    },
+    ProjectCreated {
+        project: Project,
+    },
+    ProjectUpdated {
+        project: Project,
+    },
+    ProjectDeleted {
+        project_id: String,
+    },
+    ProjectError {
+        message: String,
+    },
 
     // Ticket events
-    TicketsLoaded { tickets: Vec<Ticket> },
-    TicketLoaded { ticket: Ticket },
-    TicketCreated { ticket: Ticket },
-    TicketUpdated { ticket: Ticket },
-    TicketDeleted { ticket_id: String },
-    TicketError { message: String },
+    TicketsLoaded {
+        tickets: Vec<Ticket>,
+    },
+    TicketLoaded {
+        ticket: Ticket,
+    },
+    TicketCreated {
+        ticket: Ticket,
+    },
+    TicketUpdated {
+        ticket: Ticket,
+    },
+    TicketDeleted {
+        ticket_id: String,
+    },
+    TicketError {
+        message: String,
+    },
+    TicketQueryResults {
+        tickets: Vec<Ticket>,
+    },
+    TicketQueryError {
+        message: String,
+    },
+    TicketChildrenLoaded {
+        parent_id: TicketId,
+        children: Vec<Ticket>,
+    },
+
+    // Custom field events
+    CustomFieldsLoaded {
+        project_id: ProjectId,
+        fields: Vec<CustomField>,
+    },
+    UsersLoaded {
+        users: Vec<User>,
+    },
+
+    // Workflow events
+    WorkflowLoaded {
+        workflow: Workflow,
+    },
+
+    // Sprint events
+    SprintsLoaded {
+        project_id: ProjectId,
+        sprints: Vec<Sprint>,
+    },
+
+    // Saved view events
+    SavedViewsLoaded {
+        views: Vec<SavedView>,
+    },
+    SavedViewCreated {
+        view: SavedView,
+    },
+    SavedViewDeleted {
+        view_id: SavedViewId,
+    },
+    SavedViewError {
+        message: String,
+    },
 
     // Comment events
-    CommentsLoaded { comments: Vec<Comment> },
-    CommentCreated { comment: Comment },
-    CommentUpdated { comment: Comment },
-    CommentDeleted { comment_id: String },
-    CommentError { message: String },
+    CommentsLoaded {
+        ticket_id: TicketId,
+        comments: Vec<Comment>,
+    },
+    CommentCreated {
+        comment: Comment,
+    },
+    CommentUpdated {
+        comment: Comment,
+    },
+    CommentDeleted {
+        comment_id: String,
+    },
+    CommentError {
+        message: String,
+    },
+
+    // Worklog events
+    WorklogsLoaded {
+        ticket_id: TicketId,
+        worklogs: Vec<Worklog>,
+    },
+    WorklogCreated {
+        worklog: Worklog,
+        /// The stopped timer the worklog was logged from
+        timer: Option<RunningTimer>,
+    },
+    WorklogDeleted {
+        worklog: Worklog,
+    },
+    WorklogError {
+        message: String,
+        /// The stopped timer whose time could not be logged
+        timer: Option<RunningTimer>,
+    },
+
+    // Code link events
+    CodeLinksLoaded {
+        ticket_id: TicketId,
+        links: Vec<CodeLink>,
+    },
+
+    // Notification inbox events
+    NotificationsLoaded {
+        notifications: Vec<Notification>,
+    },
+    UnreadNotificationsCounted {
+        count: u32,
+    },
+    NotificationRead {
+        notification: Notification,
+    },
+    AllNotificationsRead,
+    NotificationError {
+        message: String,
+    },
+    NotificationPreferencesLoaded {
+        preferences: NotificationPreferences,
+    },
+    NotificationPreferencesSaved {
+        preferences: NotificationPreferences,
+    },
+
+    // Live update events
+    ChangeStreamReady {
+        url: String,
+    },
+    RemoteChange {
+        change: ChangeEvent,
+    },
+    ChangeStreamResync,
+    ChangeStreamClosed,
 
     // Generic events
-    ApiError { message: String },
+    ApiError {
+        message: String,
+    },
     LoadingComplete,
 }
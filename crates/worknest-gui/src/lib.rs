@@ -3,6 +3,7 @@
 //! This library provides the web application interface for Worknest.
 
 pub mod api_client;
+pub mod change_stream;
 pub mod components;
 pub mod events;
 pub mod screens;
@@ -4,6 +4,7 @@ use worknest_core::models::{ProjectId, TicketId};
 
 pub mod dashboard;
 pub mod login;
+pub mod notifications;
 pub mod project_detail;
 pub mod project_list;
 pub mod register;
@@ -14,6 +15,7 @@ pub mod ticket_list;
 
 pub use dashboard::DashboardScreen;
 pub use login::LoginScreen;
+pub use notifications::NotificationsScreen;
 pub use project_detail::ProjectDetailScreen;
 pub use project_list::ProjectListScreen;
 pub use register::RegisterScreen;
@@ -41,6 +43,8 @@ pub enum Screen {
     TicketBoard { project_id: ProjectId },
     /// Ticket detail view
     TicketDetail(TicketId),
+    /// Notification inbox
+    Notifications,
     /// Settings
     Settings,
 }
@@ -0,0 +1,144 @@
+//! Notification inbox screen
+
+use egui::{RichText, ScrollArea};
+use worknest_core::models::{NotificationId, NotificationKind, TicketId};
+
+use crate::{
+    components::EmptyState,
+    screens::Screen,
+    state::AppState,
+    theme::{Colors, Spacing},
+};
+
+/// Inbox of notifications about mentions, assignments and watched tickets
+pub struct NotificationsScreen {
+    /// Hide notifications that were already read
+    unread_only: bool,
+}
+
+impl Default for NotificationsScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationsScreen {
+    pub fn new() -> Self {
+        Self { unread_only: false }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, state: &mut AppState) {
+        let mut mark_read: Option<NotificationId> = None;
+        let mut open_ticket: Option<TicketId> = None;
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.add_space(Spacing::LARGE);
+
+            // Header
+            ui.horizontal(|ui| {
+                ui.heading(RichText::new("Inbox").size(24.0));
+                if state.unread_notifications > 0 {
+                    ui.label(
+                        RichText::new(format!("{} unread", state.unread_notifications))
+                            .color(Colors::PRIMARY),
+                    );
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add_enabled(
+                            state.unread_notifications > 0,
+                            egui::Button::new("✓ Mark all as read"),
+                        )
+                        .clicked()
+                    {
+                        state.mark_all_notifications_read();
+                    }
+                    if ui.button("⟳ Refresh").clicked() {
+                        state.load_notifications();
+                    }
+                    ui.checkbox(&mut self.unread_only, "Unread only");
+                });
+            });
+
+            ui.add_space(Spacing::MEDIUM);
+            ui.separator();
+            ui.add_space(Spacing::MEDIUM);
+
+            let visible: Vec<_> = state
+                .inbox
+                .iter()
+                .filter(|n| !self.unread_only || !n.is_read())
+                .collect();
+
+            if visible.is_empty() {
+                EmptyState::new(
+                    "🔔",
+                    "You're All Caught Up",
+                    "Mentions, assignments and changes to tickets you watch show up here",
+                )
+                .render(ui, state);
+                return;
+            }
+
+            ScrollArea::vertical().show(ui, |ui| {
+                for notification in visible {
+                    let is_read = notification.is_read();
+
+                    ui.horizontal(|ui| {
+                        // Unread marker
+                        ui.label(
+                            RichText::new(if is_read { " " } else { "●" }).color(Colors::PRIMARY),
+                        );
+                        ui.label(kind_icon(notification.kind));
+
+                        let message = RichText::new(&notification.message).size(14.0);
+                        let message = if is_read {
+                            message.color(Colors::TEXT_SECONDARY)
+                        } else {
+                            message.strong()
+                        };
+                        if ui.link(message).on_hover_text("Open ticket").clicked() {
+                            open_ticket = Some(notification.ticket_id);
+                            if !is_read {
+                                mark_read = Some(notification.id);
+                            }
+                        }
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if !is_read && ui.small_button("Mark read").clicked() {
+                                mark_read = Some(notification.id);
+                            }
+                            ui.label(
+                                RichText::new(
+                                    notification.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                                )
+                                .small()
+                                .color(Colors::TEXT_SECONDARY),
+                            );
+                        });
+                    });
+                    ui.add_space(Spacing::SMALL);
+                }
+            });
+        });
+
+        if let Some(notification_id) = mark_read {
+            state.mark_notification_read(notification_id);
+        }
+        if let Some(ticket_id) = open_ticket {
+            state.navigate_to(Screen::TicketDetail(ticket_id));
+        }
+    }
+}
+
+/// Icon shown next to a notification
+fn kind_icon(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::Mentioned => "@",
+        NotificationKind::Commented => "💬",
+        NotificationKind::Assigned => "👤",
+        NotificationKind::StatusChanged => "🔀",
+        NotificationKind::Updated => "✏",
+    }
+}
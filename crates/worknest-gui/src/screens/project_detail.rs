@@ -2,7 +2,7 @@
 
 use egui::{RichText, ScrollArea};
 
-use worknest_core::models::{Priority, Project, ProjectId, Ticket, TicketType};
+use worknest_core::models::{Priority, Project, ProjectId, StatusCategory, Ticket, TicketType};
 
 use crate::{
     screens::Screen,
@@ -17,6 +17,7 @@ pub struct ProjectDetailScreen {
     tickets: Vec<Ticket>,
     is_editing: bool,
     edit_name: String,
+    edit_key: String,
     edit_description: String,
     edit_color: String,
     data_loaded: bool,
@@ -36,6 +37,7 @@ impl ProjectDetailScreen {
             tickets: Vec::new(),
             is_editing: false,
             edit_name: String::new(),
+            edit_key: String::new(),
             edit_description: String::new(),
             edit_color: String::new(),
             data_loaded: false,
@@ -51,6 +53,7 @@ impl ProjectDetailScreen {
     pub fn render(&mut self, ctx: &egui::Context, state: &mut AppState) {
         if !self.data_loaded {
             self.load_data(state);
+            state.load_workflow(self.project_id);
             self.data_loaded = true;
         }
 
@@ -75,6 +78,8 @@ impl ProjectDetailScreen {
             .cloned()
             .collect();
 
+        let workflow = state.workflow(self.project_id);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ScrollArea::vertical().show(ui, |ui| {
                 ui.add_space(Spacing::LARGE);
@@ -189,81 +194,39 @@ impl ProjectDetailScreen {
 
                     ui.add_space(Spacing::MEDIUM);
 
-                    // Ticket stats
-                    let open_count = self
-                        .tickets
-                        .iter()
-                        .filter(|t| t.status == worknest_core::models::TicketStatus::Open)
-                        .count();
-                    let in_progress_count = self
-                        .tickets
-                        .iter()
-                        .filter(|t| t.status == worknest_core::models::TicketStatus::InProgress)
-                        .count();
-                    let review_count = self
-                        .tickets
-                        .iter()
-                        .filter(|t| t.status == worknest_core::models::TicketStatus::Review)
-                        .count();
-                    let done_count = self
-                        .tickets
-                        .iter()
-                        .filter(|t| {
-                            t.status == worknest_core::models::TicketStatus::Done
-                                || t.status == worknest_core::models::TicketStatus::Closed
-                        })
-                        .count();
+                    // Ticket stats, by status category since workflows differ
+                    // between projects
+                    let category_count = |category: StatusCategory| {
+                        self.tickets
+                            .iter()
+                            .filter(|t| t.status_category == category)
+                            .count()
+                    };
 
                     ui.horizontal(|ui| {
-                        ui.scope(|ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(
-                                    RichText::new(format!("{}", open_count))
-                                        .size(24.0)
-                                        .color(Colors::INFO),
-                                );
-                                ui.label("Open");
-                            });
-                        });
-
-                        ui.add_space(Spacing::LARGE);
-
-                        ui.scope(|ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(
-                                    RichText::new(format!("{}", in_progress_count))
-                                        .size(24.0)
-                                        .color(Colors::WARNING),
-                                );
-                                ui.label("In Progress");
-                            });
-                        });
-
-                        ui.add_space(Spacing::LARGE);
-
-                        ui.scope(|ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(
-                                    RichText::new(format!("{}", review_count))
-                                        .size(24.0)
-                                        .color(Colors::PRIMARY),
-                                );
-                                ui.label("Review");
-                            });
-                        });
-
-                        ui.add_space(Spacing::LARGE);
+                        for (idx, (category, color)) in [
+                            (StatusCategory::Todo, Colors::INFO),
+                            (StatusCategory::InProgress, Colors::WARNING),
+                            (StatusCategory::Done, Colors::SUCCESS),
+                        ]
+                        .into_iter()
+                        .enumerate()
+                        {
+                            if idx > 0 {
+                                ui.add_space(Spacing::LARGE);
+                            }
 
-                        ui.scope(|ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(
-                                    RichText::new(format!("{}", done_count))
-                                        .size(24.0)
-                                        .color(Colors::SUCCESS),
-                                );
-                                ui.label("Done");
+                            ui.scope(|ui| {
+                                ui.vertical_centered(|ui| {
+                                    ui.label(
+                                        RichText::new(format!("{}", category_count(category)))
+                                            .size(24.0)
+                                            .color(color),
+                                    );
+                                    ui.label(category.to_string());
+                                });
                             });
-                        });
+                        }
                     });
 
                     ui.add_space(Spacing::LARGE);
@@ -347,22 +310,13 @@ impl ProjectDetailScreen {
                                     );
 
                                     // Status badge
-                                    let (status_text, status_color) = match ticket.status {
-                                        worknest_core::models::TicketStatus::Open => {
-                                            ("Open", Colors::INFO)
-                                        },
-                                        worknest_core::models::TicketStatus::InProgress => {
-                                            ("In Progress", Colors::WARNING)
-                                        },
-                                        worknest_core::models::TicketStatus::Review => {
-                                            ("Review", Colors::PRIMARY)
-                                        },
-                                        worknest_core::models::TicketStatus::Done => {
-                                            ("Done", Colors::SUCCESS)
-                                        },
-                                        worknest_core::models::TicketStatus::Closed => {
-                                            ("Closed", egui::Color32::DARK_GRAY)
-                                        },
+                                    let status_text = workflow
+                                        .status(&ticket.status)
+                                        .map_or(ticket.status.as_str(), |s| s.name.as_str());
+                                    let status_color = match ticket.status_category {
+                                        StatusCategory::Todo => Colors::INFO,
+                                        StatusCategory::InProgress => Colors::WARNING,
+                                        StatusCategory::Done => Colors::SUCCESS,
                                     };
                                     ui.label(
                                         RichText::new(status_text).small().color(status_color),
@@ -420,6 +374,20 @@ impl ProjectDetailScreen {
 
                 ui.add_space(Spacing::MEDIUM);
 
+                ui.label("Key");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.edit_key)
+                        .char_limit(10)
+                        .desired_width(200.0),
+                );
+                ui.label(
+                    RichText::new("Tickets stay reachable under the old key")
+                        .small()
+                        .color(egui::Color32::GRAY),
+                );
+
+                ui.add_space(Spacing::MEDIUM);
+
                 ui.label("Description");
                 ui.add(
                     egui::TextEdit::multiline(&mut self.edit_description)
@@ -453,6 +421,7 @@ impl ProjectDetailScreen {
     fn start_editing(&mut self, project: &Project) {
         self.is_editing = true;
         self.edit_name = project.name.clone();
+        self.edit_key = project.key.clone();
         self.edit_description = project.description.clone().unwrap_or_default();
         self.edit_color = project.color.clone().unwrap_or_default();
     }
@@ -460,6 +429,8 @@ impl ProjectDetailScreen {
     fn save_changes(&mut self, state: &mut AppState) {
         if let Some(project) = self.project.clone() {
             let name = self.edit_name.clone();
+            let key =
+                Some(self.edit_key.trim().to_ascii_uppercase()).filter(|key| *key != project.key);
             let description = if self.edit_description.is_empty() {
                 None
             } else {
@@ -490,6 +461,7 @@ impl ProjectDetailScreen {
 
                     let request = UpdateProjectRequest {
                         name: Some(name),
+                        key,
                         description,
                         is_archived: None,
                     };
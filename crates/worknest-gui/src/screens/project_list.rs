@@ -17,6 +17,7 @@ pub struct ProjectListScreen {
     search_query: String,
     show_create_dialog: bool,
     new_project_name: String,
+    new_project_key: String,
     new_project_description: String,
     new_project_color: String,
     data_loaded: bool,
@@ -30,6 +31,7 @@ impl Default for ProjectListScreen {
             search_query: String::new(),
             show_create_dialog: false,
             new_project_name: String::new(),
+            new_project_key: String::new(),
             new_project_description: String::new(),
             new_project_color: String::from("#3B82F6"),
             data_loaded: false,
@@ -264,6 +266,16 @@ impl ProjectListScreen {
 
                 ui.add_space(Spacing::MEDIUM);
 
+                ui.label("Key (optional)");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.new_project_key)
+                        .hint_text(Project::key_from_name(&self.new_project_name))
+                        .char_limit(10)
+                        .desired_width(f32::INFINITY),
+                );
+
+                ui.add_space(Spacing::MEDIUM);
+
                 ui.label("Description (optional)");
                 ui.add(
                     egui::TextEdit::multiline(&mut self.new_project_description)
@@ -313,6 +325,8 @@ impl ProjectListScreen {
 
         if let Some(user) = &state.current_user {
             let mut project = Project::new(self.new_project_name.clone(), user.id);
+            let key = Some(self.new_project_key.trim().to_ascii_uppercase())
+                .filter(|key| !key.is_empty());
 
             if !self.new_project_description.is_empty() {
                 project.description = Some(self.new_project_description.clone());
@@ -340,6 +354,7 @@ impl ProjectListScreen {
 
                     let request = CreateProjectRequest {
                         name: project.name,
+                        key,
                         description: project.description,
                     };
 
@@ -364,6 +379,7 @@ impl ProjectListScreen {
 
     fn clear_create_form(&mut self) {
         self.new_project_name.clear();
+        self.new_project_key.clear();
         self.new_project_description.clear();
         self.new_project_color = String::from("#3B82F6");
     }
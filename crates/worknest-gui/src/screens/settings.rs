@@ -13,6 +13,7 @@ use crate::{
 enum SettingsTab {
     Profile,
     Application,
+    Notifications,
     About,
 }
 
@@ -100,6 +101,19 @@ impl SettingsScreen {
 
                     ui.separator();
 
+                    if ui
+                        .selectable_label(
+                            self.active_tab == SettingsTab::Notifications,
+                            RichText::new("Notifications").size(16.0),
+                        )
+                        .clicked()
+                    {
+                        self.active_tab = SettingsTab::Notifications;
+                        state.load_notification_preferences();
+                    }
+
+                    ui.separator();
+
                     if ui
                         .selectable_label(
                             self.active_tab == SettingsTab::About,
@@ -117,6 +131,7 @@ impl SettingsScreen {
                 match self.active_tab {
                     SettingsTab::Profile => self.render_profile_tab(ui, state),
                     SettingsTab::Application => self.render_application_tab(ui, state),
+                    SettingsTab::Notifications => self.render_notifications_tab(ui, state),
                     SettingsTab::About => self.render_about_tab(ui, state),
                 }
             });
@@ -294,6 +309,78 @@ impl SettingsScreen {
         });
     }
 
+    fn render_notifications_tab(&mut self, ui: &mut egui::Ui, state: &mut AppState) {
+        ui.heading("Email Notifications");
+        ui.add_space(Spacing::MEDIUM);
+
+        // Edited in place and sent as a whole on save
+        let Some(preferences) = state.notification_preferences.as_mut() else {
+            ui.spinner();
+            return;
+        };
+
+        ui.group(|ui| {
+            ui.set_min_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.label(RichText::new("Instant Emails").strong());
+                ui.add_space(Spacing::SMALL);
+
+                ui.checkbox(
+                    &mut preferences.email_assigned,
+                    "When a ticket is assigned to me",
+                );
+                ui.checkbox(&mut preferences.email_mentioned, "When someone mentions me");
+                ui.checkbox(
+                    &mut preferences.email_status_changed,
+                    "When a ticket I watch changes status",
+                );
+            });
+        });
+
+        ui.add_space(Spacing::LARGE);
+
+        ui.group(|ui| {
+            ui.set_min_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.label(RichText::new("Daily Digest").strong());
+                ui.add_space(Spacing::SMALL);
+
+                ui.checkbox(
+                    &mut preferences.daily_digest,
+                    "Email me a daily digest of unread notifications",
+                );
+                ui.add_enabled_ui(preferences.daily_digest, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Send from");
+                        ui.add(
+                            egui::Slider::new(&mut preferences.digest_hour, 0..=23)
+                                .suffix(":00 UTC"),
+                        );
+                    });
+                });
+
+                if let Some(last) = preferences.last_digest_at {
+                    ui.add_space(Spacing::SMALL);
+                    ui.label(
+                        RichText::new(format!(
+                            "Last digest sent {}",
+                            last.format("%Y-%m-%d %H:%M UTC")
+                        ))
+                        .color(Colors::TEXT_SECONDARY)
+                        .small(),
+                    );
+                }
+            });
+        });
+
+        ui.add_space(Spacing::MEDIUM);
+
+        if ui.button("Save Preferences").clicked() {
+            let preferences = preferences.clone();
+            state.save_notification_preferences(preferences);
+        }
+    }
+
     fn render_about_tab(&mut self, ui: &mut egui::Ui, _state: &mut AppState) {
         ui.heading("About Worknest");
         ui.add_space(Spacing::MEDIUM);
@@ -338,8 +425,50 @@ impl SettingsScreen {
     }
 
     fn save_profile(&mut self, state: &mut AppState) {
-        // API integration coming soon
-        state.notify_info("Profile update API integration coming soon".to_string());
+        let Some(user) = state.current_user.clone() else {
+            return;
+        };
+        let Some(token) = state.auth_token.clone() else {
+            state.notify_error("Not authenticated".to_string());
+            return;
+        };
+
+        let username = self.edit_username.trim().to_string();
+        let email = self.edit_email.trim().to_string();
+
+        if username.is_empty() || email.is_empty() {
+            state.notify_error("Username and email are required".to_string());
+            return;
+        }
+
+        if username == user.username && email == user.email {
+            state.notify_info("No changes to save".to_string());
+            return;
+        }
+
+        let request = crate::api_client::UpdateProfileRequest {
+            username: (username != user.username).then_some(username),
+            email: (email != user.email).then_some(email),
+        };
+
+        let api_client = state.api_client.clone();
+        let event_queue = state.event_queue.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            use crate::events::AppEvent;
+
+            match api_client.update_profile(&token, request).await {
+                Ok(user) => {
+                    event_queue.push(AppEvent::ProfileUpdated { user });
+                },
+                Err(e) => {
+                    tracing::error!("Profile update failed: {:?}", e);
+                    event_queue.push(AppEvent::ProfileError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
     }
 
     fn change_password(&mut self, state: &mut AppState) {
@@ -353,7 +482,34 @@ impl SettingsScreen {
             return;
         }
 
-        // API integration coming soon
-        state.notify_info("Password change API integration coming soon".to_string());
+        let Some(token) = state.auth_token.clone() else {
+            state.notify_error("Not authenticated".to_string());
+            return;
+        };
+
+        let request = crate::api_client::ChangePasswordRequest {
+            current_password: std::mem::take(&mut self.current_password),
+            new_password: std::mem::take(&mut self.new_password),
+        };
+        self.confirm_password.clear();
+
+        let api_client = state.api_client.clone();
+        let event_queue = state.event_queue.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            use crate::events::AppEvent;
+
+            match api_client.change_password(&token, request).await {
+                Ok(()) => {
+                    event_queue.push(AppEvent::PasswordChanged);
+                },
+                Err(e) => {
+                    tracing::error!("Password change failed: {:?}", e);
+                    event_queue.push(AppEvent::PasswordError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
     }
 }
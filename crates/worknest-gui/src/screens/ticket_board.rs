@@ -3,9 +3,13 @@
 use egui::{RichText, ScrollArea};
 use std::sync::Arc;
 
-use worknest_core::models::{Priority, ProjectId, Ticket, TicketId, TicketStatus, TicketType};
+use worknest_core::models::{
+    Priority, ProjectId, SprintId, SprintState, StatusCategory, Ticket, TicketId, TicketType,
+    Workflow, WorkflowStatus,
+};
 
 use crate::{
+    components::label_chips,
     screens::Screen,
     state::AppState,
     theme::{Colors, Spacing},
@@ -15,17 +19,40 @@ use crate::{
 #[derive(Clone, Debug)]
 struct TicketDragPayload {
     ticket_id: TicketId,
-    source_status: TicketStatus,
+    /// Workflow status key the ticket was dragged from
+    source_status: String,
+}
+
+/// Which tickets the board shows, by sprint
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SprintFilter {
+    All,
+    /// Tickets in no sprint
+    Backlog,
+    Sprint(SprintId),
+}
+
+impl SprintFilter {
+    fn matches(&self, ticket: &Ticket) -> bool {
+        match self {
+            SprintFilter::All => true,
+            SprintFilter::Backlog => ticket.sprint_id.is_none(),
+            SprintFilter::Sprint(id) => ticket.sprint_id == Some(*id),
+        }
+    }
 }
 
 /// Ticket board screen
 pub struct TicketBoardScreen {
     pub project_id: ProjectId,
     tickets: Vec<Ticket>,
+    /// Name of the open saved view
+    view_name: Option<String>,
+    sprint_filter: SprintFilter,
     data_loaded: bool,
     // Drag and drop state
     dragging_ticket: Option<TicketId>,
-    drag_hover_status: Option<TicketStatus>,
+    drag_hover_status: Option<String>,
 }
 
 impl TicketBoardScreen {
@@ -33,6 +60,8 @@ impl TicketBoardScreen {
         Self {
             project_id,
             tickets: Vec::new(),
+            view_name: None,
+            sprint_filter: SprintFilter::All,
             data_loaded: false,
             dragging_ticket: None,
             drag_hover_status: None,
@@ -41,17 +70,40 @@ impl TicketBoardScreen {
 
     pub fn render(&mut self, ctx: &egui::Context, state: &mut AppState) {
         if !self.data_loaded {
+            state.ticket_query_results = None;
+            state.ticket_query_error = None;
             self.load_tickets(state);
+            state.load_workflow(self.project_id);
+            state.load_sprints(self.project_id);
             self.data_loaded = true;
         }
 
-        // Sync tickets from state
-        self.tickets = state
-            .tickets
-            .iter()
-            .filter(|t| t.project_id == self.project_id)
-            .cloned()
-            .collect();
+        // Apply a saved view opened from the sidebar
+        if let Some(view) = state.pending_view.take() {
+            state.run_ticket_query(Some(self.project_id), &view.query, &view.sort);
+            self.view_name = Some(view.name);
+        }
+
+        // Sync tickets from state, narrowed to the saved view's matches
+        self.tickets = match &state.ticket_query_results {
+            Some(ids) => ids
+                .iter()
+                .filter_map(|id| state.tickets.iter().find(|t| t.id == *id))
+                .filter(|t| t.project_id == self.project_id)
+                .filter(|t| self.sprint_filter.matches(t))
+                .cloned()
+                .collect(),
+            None => state
+                .tickets
+                .iter()
+                .filter(|t| t.project_id == self.project_id)
+                .filter(|t| self.sprint_filter.matches(t))
+                .cloned()
+                .collect(),
+        };
+
+        // Columns follow the project's workflow
+        let workflow = state.workflow(self.project_id);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.add_space(Spacing::LARGE);
@@ -66,6 +118,55 @@ impl TicketBoardScreen {
 
                 ui.heading(RichText::new("Kanban Board").size(28.0));
 
+                if let Some(name) = &self.view_name {
+                    ui.label(RichText::new(name).size(18.0).color(egui::Color32::GRAY));
+                }
+
+                if let Some(error) = &state.ticket_query_error {
+                    ui.label(RichText::new(error).color(Colors::ERROR));
+                }
+
+                ui.add_space(Spacing::MEDIUM);
+
+                ui.label("Sprint:");
+                let sprints: Vec<(SprintFilter, String)> = state
+                    .project_sprints(self.project_id)
+                    .into_iter()
+                    .map(|s| {
+                        let name = match s.state {
+                            SprintState::Active => format!("{} (active)", s.name),
+                            SprintState::Closed => format!("{} (closed)", s.name),
+                            SprintState::Planned => s.name.clone(),
+                        };
+                        (SprintFilter::Sprint(s.id), name)
+                    })
+                    .collect();
+                let selected = match self.sprint_filter {
+                    SprintFilter::All => "All tickets",
+                    SprintFilter::Backlog => "Backlog",
+                    SprintFilter::Sprint(_) => sprints
+                        .iter()
+                        .find(|(filter, _)| *filter == self.sprint_filter)
+                        .map_or("All tickets", |(_, name)| name.as_str()),
+                };
+                egui::ComboBox::from_id_salt("sprint_filter")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut self.sprint_filter,
+                            SprintFilter::All,
+                            "All tickets",
+                        );
+                        ui.selectable_value(
+                            &mut self.sprint_filter,
+                            SprintFilter::Backlog,
+                            "Backlog",
+                        );
+                        for (filter, name) in &sprints {
+                            ui.selectable_value(&mut self.sprint_filter, *filter, name.as_str());
+                        }
+                    });
+
                 ui.add_space(Spacing::MEDIUM);
 
                 if ui.button("List View").clicked() {
@@ -81,24 +182,19 @@ impl TicketBoardScreen {
             ScrollArea::horizontal().show(ui, |ui| {
                 // Calculate responsive column width
                 let available_width = ui.available_width();
-                let column_count = 4.0;
+                let column_count = workflow.statuses.len().max(1) as f32;
                 let spacing = Spacing::MEDIUM * (column_count - 1.0);
                 let calculated_width = (available_width - spacing) / column_count;
                 let column_width = calculated_width.max(280.0); // Minimum 280px per column
 
                 ui.horizontal_top(|ui| {
-                    let columns = vec![
-                        TicketStatus::Open,
-                        TicketStatus::InProgress,
-                        TicketStatus::Review,
-                        TicketStatus::Done,
-                    ];
+                    let columns = &workflow.statuses;
 
                     for (idx, status) in columns.iter().enumerate() {
                         if idx > 0 {
                             ui.add_space(Spacing::MEDIUM);
                         }
-                        self.render_column(ui, *status, state, column_width);
+                        self.render_column(ui, status, &workflow, state, column_width);
                     }
                 });
             });
@@ -108,25 +204,31 @@ impl TicketBoardScreen {
     fn render_column(
         &mut self,
         ui: &mut egui::Ui,
-        status: TicketStatus,
+        status: &WorkflowStatus,
+        workflow: &Workflow,
         state: &mut AppState,
         column_width: f32,
     ) {
         let column_tickets: Vec<_> = self
             .tickets
             .iter()
-            .filter(|t| t.status == status)
+            .filter(|t| t.status == status.key)
             .cloned()
             .collect();
 
-        let (column_title, column_color) = match status {
-            TicketStatus::Open => ("Open", Colors::INFO),
-            TicketStatus::InProgress => ("In Progress", Colors::WARNING),
-            TicketStatus::Review => ("Review", Colors::PRIMARY),
-            TicketStatus::Done => ("Done", Colors::SUCCESS),
-            TicketStatus::Closed => ("Closed", egui::Color32::GRAY),
+        let column_title = status.name.as_str();
+        let column_color = match status.category {
+            StatusCategory::Todo => Colors::INFO,
+            StatusCategory::InProgress => Colors::WARNING,
+            StatusCategory::Done => Colors::SUCCESS,
         };
 
+        // Whether the ticket being dragged may move into this column
+        let accepts_drag = self
+            .dragging_ticket
+            .and_then(|id| self.tickets.iter().find(|t| t.id == id))
+            .is_none_or(|t| workflow.can_transition(&t.status, &status.key));
+
         // Calculate available height for column content
         let available_height = ui.available_height() - 120.0; // Reserve space for header and padding
 
@@ -187,7 +289,7 @@ impl TicketBoardScreen {
                                 });
                             } else {
                                 for ticket in &column_tickets {
-                                    self.render_draggable_card(ui, ticket, state, status);
+                                    self.render_draggable_card(ui, ticket, state);
                                     ui.add_space(Spacing::MEDIUM);
                                 }
                             }
@@ -196,18 +298,25 @@ impl TicketBoardScreen {
 
             // Handle dropped ticket
             if let Some(payload) = dropped_payload {
-                self.handle_ticket_drop(payload, status, state);
+                self.handle_ticket_drop(payload, status, workflow, state);
             }
 
-            // Visual feedback for drag hover
+            // Visual feedback for drag hover, in red where the workflow
+            // does not allow the move
             if drop_response.response.hovered() && self.dragging_ticket.is_some() {
-                self.drag_hover_status = Some(status);
+                self.drag_hover_status = Some(status.key.clone());
+
+                let stroke_color = if accepts_drag {
+                    column_color
+                } else {
+                    Colors::ERROR
+                };
 
                 // Draw highlight border around drop zone
                 ui.painter().rect_stroke(
                     drop_response.response.rect,
                     8.0,
-                    egui::Stroke::new(2.0, column_color.linear_multiply(0.8)),
+                    egui::Stroke::new(2.0, stroke_color.linear_multiply(0.8)),
                     egui::StrokeKind::Outside,
                 );
             }
@@ -215,17 +324,11 @@ impl TicketBoardScreen {
     }
 
     /// Render a draggable card with drag and drop support
-    fn render_draggable_card(
-        &mut self,
-        ui: &mut egui::Ui,
-        ticket: &Ticket,
-        state: &mut AppState,
-        current_status: TicketStatus,
-    ) {
+    fn render_draggable_card(&mut self, ui: &mut egui::Ui, ticket: &Ticket, state: &mut AppState) {
         let drag_id = ui.id().with(("drag_ticket", ticket.id.0));
         let payload = TicketDragPayload {
             ticket_id: ticket.id,
-            source_status: current_status,
+            source_status: ticket.status.clone(),
         };
 
         let is_being_dragged = self.dragging_ticket == Some(ticket.id);
@@ -278,6 +381,8 @@ impl TicketBoardScreen {
             TicketType::Bug => ("Bug", Colors::TYPE_BUG),
             TicketType::Feature => ("Feature", Colors::TYPE_FEATURE),
             TicketType::Epic => ("Epic", Colors::TYPE_EPIC),
+            TicketType::Story => ("Story", Colors::TYPE_STORY),
+            TicketType::Subtask => ("Subtask", Colors::TYPE_SUBTASK),
         };
 
         // Card background - adjust for drag state
@@ -311,7 +416,13 @@ impl TicketBoardScreen {
                     ui.colored_label(priority_color, "▊");
 
                     ui.vertical(|ui| {
-                        // Ticket title
+                        // Ticket key and title
+                        ui.label(
+                            RichText::new(state.ticket_key(ticket))
+                                .small()
+                                .monospace()
+                                .color(egui::Color32::GRAY),
+                        );
                         ui.label(RichText::new(&ticket.title).strong().size(14.0));
 
                         // Description with proper ellipsis
@@ -345,6 +456,11 @@ impl TicketBoardScreen {
                     ui.label(RichText::new(priority_text).small().color(priority_color));
                 });
 
+                if !ticket.labels.is_empty() {
+                    ui.add_space(Spacing::SMALL);
+                    label_chips(ui, &ticket.labels);
+                }
+
                 ui.add_space(Spacing::SMALL);
 
                 // View button
@@ -361,27 +477,39 @@ impl TicketBoardScreen {
     fn handle_ticket_drop(
         &mut self,
         payload: Arc<TicketDragPayload>,
-        target_status: TicketStatus,
+        target_status: &WorkflowStatus,
+        workflow: &Workflow,
         state: &mut AppState,
     ) {
         // Don't process if dropped in same column
-        if payload.source_status == target_status {
+        if payload.source_status == target_status.key {
+            return;
+        }
+
+        // The server would reject moves the workflow does not allow
+        if !workflow.can_transition(&payload.source_status, &target_status.key) {
+            let source_name = workflow
+                .status(&payload.source_status)
+                .map_or(payload.source_status.as_str(), |s| s.name.as_str());
+            state.notify_error(format!(
+                "Cannot move ticket from {} to {}",
+                source_name, target_status.name
+            ));
             return;
         }
 
         let ticket_id = payload.ticket_id;
 
         tracing::info!(
-            "Dropping ticket {:?} from {:?} to {:?}",
+            "Dropping ticket {:?} from {} to {}",
             ticket_id,
             payload.source_status,
-            target_status
+            target_status.key
         );
 
         // Optimistic update in state
         if let Some(ticket) = state.tickets.iter_mut().find(|t| t.id == ticket_id) {
-            ticket.status = target_status;
-            ticket.updated_at = chrono::Utc::now();
+            ticket.update_status(target_status);
         }
 
         // Trigger API call
@@ -395,15 +523,7 @@ impl TicketBoardScreen {
             },
         };
 
-        // Convert status to string format expected by API
-        let status_str = match target_status {
-            TicketStatus::Open => "open",
-            TicketStatus::InProgress => "in progress", // Backend now accepts both "inprogress" and "in progress"
-            TicketStatus::Review => "review",
-            TicketStatus::Done => "done",
-            TicketStatus::Closed => "closed",
-        }
-        .to_string();
+        let status_str = target_status.key.clone();
 
         wasm_bindgen_futures::spawn_local(async move {
             use crate::api_client::UpdateTicketRequest;
@@ -416,6 +536,7 @@ impl TicketBoardScreen {
                 priority: None,
                 ticket_type: None,
                 assigned_to: None,
+                custom_fields: None,
             };
 
             match api_client.update_ticket(&token, ticket_id.0, request).await {
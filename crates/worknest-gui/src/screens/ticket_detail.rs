@@ -3,13 +3,15 @@
 use egui::{RichText, ScrollArea};
 
 use worknest_core::models::{
-    Comment, CommentId, Priority, Ticket, TicketId, TicketStatus, TicketType,
+    CodeLinkKind, Comment, CommentId, CustomField, CustomFieldId, CustomFieldType,
+    CustomFieldValue, FieldValue, Priority, PullRequestState, Ticket, TicketId, TicketType, User,
+    UserId, WorkflowStatus, Worklog,
 };
 
 use crate::{
     api_client::{CreateCommentRequest, UpdateCommentRequest},
     screens::Screen,
-    state::AppState,
+    state::{format_minutes, AppState},
     theme::{Colors, Spacing},
 };
 
@@ -21,9 +23,12 @@ pub struct TicketDetailScreen {
     edit_title: String,
     edit_description: String,
     edit_type: TicketType,
-    edit_status: TicketStatus,
+    /// Workflow status key picked in the edit form
+    edit_status: String,
     edit_priority: Priority,
+    edit_fields: Vec<(CustomFieldId, FieldDraft)>,
     data_loaded: bool,
+    fields_loaded: bool,
     // Comment fields
     new_comment_content: String,
     editing_comment_id: Option<CommentId>,
@@ -39,9 +44,11 @@ impl TicketDetailScreen {
             edit_title: String::new(),
             edit_description: String::new(),
             edit_type: TicketType::Task,
-            edit_status: TicketStatus::Open,
+            edit_status: String::new(),
             edit_priority: Priority::Medium,
+            edit_fields: Vec::new(),
             data_loaded: false,
+            fields_loaded: false,
             new_comment_content: String::new(),
             editing_comment_id: None,
             edit_comment_content: String::new(),
@@ -63,11 +70,29 @@ impl TicketDetailScreen {
                 .cloned();
         }
 
+        // Custom fields and workflows are defined per project, so wait for
+        // the ticket
+        if let Some(ticket) = &self.ticket {
+            if !self.fields_loaded {
+                state.load_custom_fields(ticket.project_id);
+                state.load_workflow(ticket.project_id);
+                state.load_ticket_children(ticket.id);
+                state.load_worklogs(ticket.id);
+                state.load_code_links(ticket.id);
+                if state.users.is_empty() {
+                    state.load_users();
+                }
+                self.fields_loaded = true;
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ScrollArea::vertical().show(ui, |ui| {
                 ui.add_space(Spacing::LARGE);
 
                 if let Some(ticket) = self.ticket.clone() {
+                    let workflow = state.workflow(ticket.project_id);
+
                     // Header
                     ui.horizontal(|ui| {
                         if ui.button("← Back").clicked() {
@@ -80,12 +105,17 @@ impl TicketDetailScreen {
 
                         if !self.is_editing {
                             ui.horizontal(|ui| {
+                                ui.heading(
+                                    RichText::new(state.ticket_key(&ticket))
+                                        .size(24.0)
+                                        .color(egui::Color32::GRAY),
+                                );
                                 ui.heading(RichText::new(&ticket.title).size(24.0));
 
                                 ui.add_space(Spacing::MEDIUM);
 
                                 if ui.button("Edit").clicked() {
-                                    self.start_editing(&ticket);
+                                    self.start_editing(&ticket, state);
                                 }
 
                                 if ui
@@ -116,18 +146,16 @@ impl TicketDetailScreen {
                                         TicketType::Bug => ("Bug", Colors::TYPE_BUG),
                                         TicketType::Feature => ("Feature", Colors::TYPE_FEATURE),
                                         TicketType::Epic => ("Epic", Colors::TYPE_EPIC),
+                                        TicketType::Story => ("Story", Colors::TYPE_STORY),
+                                        TicketType::Subtask => ("Subtask", Colors::TYPE_SUBTASK),
                                     };
                                     ui.label(RichText::new(type_text).color(type_color));
 
                                     ui.separator();
 
-                                    let status_text = match ticket.status {
-                                        TicketStatus::Open => "Open",
-                                        TicketStatus::InProgress => "In Progress",
-                                        TicketStatus::Review => "Review",
-                                        TicketStatus::Done => "Done",
-                                        TicketStatus::Closed => "Closed",
-                                    };
+                                    let status_text = workflow
+                                        .status(&ticket.status)
+                                        .map_or(ticket.status.as_str(), |s| s.name.as_str());
                                     ui.label(status_text);
 
                                     ui.separator();
@@ -161,6 +189,14 @@ impl TicketDetailScreen {
 
                                 ui.add_space(Spacing::LARGE);
 
+                                self.render_custom_fields(ui, state, &ticket);
+
+                                self.render_hierarchy(ui, state, &ticket);
+
+                                self.render_time_tracking(ui, state, &ticket);
+
+                                self.render_code_links(ui, state, &ticket);
+
                                 // Additional info
                                 ui.horizontal(|ui| {
                                     ui.label(RichText::new("Created:").strong());
@@ -196,30 +232,16 @@ impl TicketDetailScreen {
 
                         ui.add_space(Spacing::XLARGE);
 
-                        // Quick status update
+                        // Quick status update, offering the moves the
+                        // project's workflow allows
                         ui.heading("Quick Actions");
                         ui.add_space(Spacing::MEDIUM);
 
                         ui.horizontal(|ui| {
-                            if ticket.status != TicketStatus::Open
-                                && ui.button("Mark as Open").clicked()
-                            {
-                                self.update_status(state, TicketStatus::Open);
-                            }
-                            if ticket.status != TicketStatus::InProgress
-                                && ui.button("Start Progress").clicked()
-                            {
-                                self.update_status(state, TicketStatus::InProgress);
-                            }
-                            if ticket.status != TicketStatus::Review
-                                && ui.button("Move to Review").clicked()
-                            {
-                                self.update_status(state, TicketStatus::Review);
-                            }
-                            if ticket.status != TicketStatus::Done
-                                && ui.button("Mark as Done").clicked()
-                            {
-                                self.update_status(state, TicketStatus::Done);
+                            for status in workflow.next_statuses(&ticket.status) {
+                                if ui.button(format!("Move to {}", status.name)).clicked() {
+                                    self.update_status(state, status.clone());
+                                }
                             }
                         });
 
@@ -263,22 +285,29 @@ impl TicketDetailScreen {
                     ui.radio_value(&mut self.edit_type, TicketType::Bug, "Bug");
                     ui.radio_value(&mut self.edit_type, TicketType::Feature, "Feature");
                     ui.radio_value(&mut self.edit_type, TicketType::Epic, "Epic");
+                    ui.radio_value(&mut self.edit_type, TicketType::Story, "Story");
+                    ui.radio_value(&mut self.edit_type, TicketType::Subtask, "Subtask");
                 });
 
                 ui.add_space(Spacing::MEDIUM);
 
-                ui.horizontal(|ui| {
-                    ui.label("Status:");
-                    ui.radio_value(&mut self.edit_status, TicketStatus::Open, "Open");
-                    ui.radio_value(
-                        &mut self.edit_status,
-                        TicketStatus::InProgress,
-                        "In Progress",
-                    );
-                    ui.radio_value(&mut self.edit_status, TicketStatus::Review, "Review");
-                    ui.radio_value(&mut self.edit_status, TicketStatus::Done, "Done");
-                    ui.radio_value(&mut self.edit_status, TicketStatus::Closed, "Closed");
-                });
+                if let Some(ticket) = self.ticket.clone() {
+                    let workflow = state.workflow(ticket.project_id);
+
+                    // Only the current status and those it may move to
+                    ui.horizontal(|ui| {
+                        ui.label("Status:");
+                        for status in &workflow.statuses {
+                            if workflow.can_transition(&ticket.status, &status.key) {
+                                ui.radio_value(
+                                    &mut self.edit_status,
+                                    status.key.clone(),
+                                    status.name.as_str(),
+                                );
+                            }
+                        }
+                    });
+                }
 
                 ui.add_space(Spacing::MEDIUM);
 
@@ -290,6 +319,10 @@ impl TicketDetailScreen {
                     ui.radio_value(&mut self.edit_priority, Priority::Critical, "Critical");
                 });
 
+                ui.add_space(Spacing::MEDIUM);
+
+                self.render_field_inputs(ui, state);
+
                 ui.add_space(Spacing::LARGE);
 
                 ui.horizontal(|ui| {
@@ -308,16 +341,398 @@ impl TicketDetailScreen {
         });
     }
 
-    fn start_editing(&mut self, ticket: &Ticket) {
+    /// Show the ticket's parent, the roll-up over its descendants and a
+    /// collapsible list of its children
+    fn render_hierarchy(&self, ui: &mut egui::Ui, state: &mut AppState, ticket: &Ticket) {
+        if let Some(parent_id) = ticket.parent_id {
+            let parent_title = state
+                .tickets
+                .iter()
+                .find(|t| t.id == parent_id)
+                .map_or_else(|| "Parent ticket".to_string(), |t| t.title.clone());
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Parent:").strong());
+                if ui.link(parent_title).clicked() {
+                    state.navigate_to(Screen::TicketDetail(parent_id));
+                }
+            });
+            ui.add_space(Spacing::MEDIUM);
+        }
+
+        let Some(rollup) = ticket.rollup else {
+            return;
+        };
+
+        ui.label(RichText::new("Progress").strong().size(16.0));
+        ui.add(
+            egui::ProgressBar::new(f32::from(rollup.percent_done) / 100.0).text(format!(
+                "{}/{} done ({}%)",
+                rollup.done_count, rollup.descendant_count, rollup.percent_done
+            )),
+        );
+        if rollup.estimate_hours > 0.0 {
+            ui.label(
+                RichText::new(format!("{} hours estimated", rollup.estimate_hours))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        }
+
+        let children: Vec<Ticket> = state
+            .ticket_children(ticket.id)
+            .into_iter()
+            .cloned()
+            .collect();
+        let workflow = state.workflow(ticket.project_id);
+
+        egui::CollapsingHeader::new(format!("Child tickets ({})", rollup.child_count))
+            .id_salt(("ticket_children", ticket.id.0))
+            .default_open(true)
+            .show(ui, |ui| {
+                if children.is_empty() {
+                    ui.label(RichText::new("Loading...").color(egui::Color32::GRAY));
+                }
+                for child in &children {
+                    ui.horizontal(|ui| {
+                        if ui.link(&child.title).clicked() {
+                            state.navigate_to(Screen::TicketDetail(child.id));
+                        }
+                        let status_text = workflow
+                            .status(&child.status)
+                            .map_or(child.status.as_str(), |s| s.name.as_str());
+                        ui.label(
+                            RichText::new(status_text)
+                                .small()
+                                .color(egui::Color32::GRAY),
+                        );
+                        if let Some(rollup) = child.rollup {
+                            ui.label(
+                                RichText::new(format!("{}%", rollup.percent_done))
+                                    .small()
+                                    .color(egui::Color32::GRAY),
+                            );
+                        }
+                    });
+                }
+            });
+
+        ui.add_space(Spacing::LARGE);
+    }
+
+    /// Show logged versus remaining time, the timer and the ticket's worklogs
+    fn render_time_tracking(&self, ui: &mut egui::Ui, state: &mut AppState, ticket: &Ticket) {
+        ui.label(RichText::new("Time Tracking").strong().size(16.0));
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Logged:").strong());
+            ui.label(format_minutes(ticket.logged_minutes));
+            if let Some(remaining) = ticket.remaining_minutes() {
+                ui.separator();
+                ui.label(RichText::new("Remaining:").strong());
+                ui.label(format_minutes(remaining));
+            }
+        });
+        if let Some(estimate) = ticket.estimate_hours.filter(|hours| *hours > 0.0) {
+            let fraction = ticket.logged_minutes as f32 / (estimate * 60.0);
+            let bar = egui::ProgressBar::new(fraction.min(1.0));
+            ui.add(if fraction > 1.0 {
+                bar.fill(Colors::ERROR).text("Over estimate")
+            } else {
+                bar
+            });
+        }
+
+        // The timer is kept in LocalStorage, so it survives page reloads
+        ui.horizontal(|ui| match state.running_timer {
+            Some(timer) if timer.ticket_id == ticket.id => {
+                let elapsed = (chrono::Utc::now() - timer.started_at).num_seconds().max(0);
+                ui.label(
+                    RichText::new(format!(
+                        "⏱ {}:{:02}:{:02}",
+                        elapsed / 3600,
+                        elapsed / 60 % 60,
+                        elapsed % 60
+                    ))
+                    .color(Colors::PRIMARY),
+                );
+                if ui.button("Stop timer").clicked() {
+                    state.stop_timer();
+                }
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_secs(1));
+            },
+            Some(timer) => {
+                let running_on = state
+                    .tickets
+                    .iter()
+                    .find(|t| t.id == timer.ticket_id)
+                    .map_or_else(|| "another ticket".to_string(), |t| state.ticket_key(t));
+                if ui
+                    .button("Start timer")
+                    .on_hover_text(format!("Stops the timer running on {}", running_on))
+                    .clicked()
+                {
+                    state.start_timer(ticket.id);
+                }
+            },
+            None => {
+                if ui.button("Start timer").clicked() {
+                    state.start_timer(ticket.id);
+                }
+            },
+        });
+
+        let worklogs: Vec<Worklog> = state
+            .ticket_worklogs(ticket.id)
+            .into_iter()
+            .cloned()
+            .collect();
+        let current_user_id = state.current_user.as_ref().map(|u| u.id);
+
+        egui::CollapsingHeader::new(format!("Worklogs ({})", worklogs.len()))
+            .id_salt(("ticket_worklogs", ticket.id.0))
+            .show(ui, |ui| {
+                if worklogs.is_empty() {
+                    ui.label(
+                        RichText::new("No time logged yet")
+                            .color(egui::Color32::GRAY)
+                            .italics(),
+                    );
+                }
+                for worklog in worklogs {
+                    ui.horizontal(|ui| {
+                        ui.label(worklog.started_at.format("%Y-%m-%d %H:%M").to_string());
+                        ui.label(RichText::new(format_minutes(worklog.duration_minutes)).strong());
+                        ui.label(
+                            RichText::new(user_name(worklog.user_id, &state.users))
+                                .color(Colors::PRIMARY),
+                        );
+                        if let Some(note) = &worklog.note {
+                            ui.label(RichText::new(note).color(egui::Color32::GRAY));
+                        }
+                        if current_user_id == Some(worklog.user_id)
+                            && ui
+                                .add(egui::Button::new("Delete").fill(Colors::ERROR).small())
+                                .clicked()
+                        {
+                            state.delete_worklog(worklog.clone());
+                        }
+                    });
+                }
+            });
+
+        ui.add_space(Spacing::LARGE);
+    }
+
+    /// List the pull requests and commits that refer to the ticket, linking
+    /// to them on the Git forge
+    fn render_code_links(&self, ui: &mut egui::Ui, state: &AppState, ticket: &Ticket) {
+        let links = state.ticket_code_links(ticket.id);
+        if links.is_empty() {
+            return;
+        }
+
+        ui.label(RichText::new("Development").strong().size(16.0));
+        for link in links {
+            ui.horizontal(|ui| {
+                ui.label(match link.kind {
+                    CodeLinkKind::PullRequest => "⤴",
+                    CodeLinkKind::Commit => "•",
+                });
+                ui.hyperlink_to(RichText::new(link.short_reference()).monospace(), &link.url)
+                    .on_hover_text(&link.repository);
+                ui.label(&link.title);
+                if let Some(pr_state) = link.state {
+                    let (text, color) = match pr_state {
+                        PullRequestState::Open => ("Open", Colors::SUCCESS),
+                        PullRequestState::Merged => ("Merged", Colors::TYPE_FEATURE),
+                        PullRequestState::Closed => ("Closed", Colors::TEXT_SECONDARY),
+                    };
+                    ui.label(RichText::new(text).small().color(color));
+                }
+                if let Some(author) = &link.author {
+                    ui.label(RichText::new(author).color(egui::Color32::GRAY));
+                }
+            });
+        }
+
+        ui.add_space(Spacing::LARGE);
+    }
+
+    /// Show the ticket's custom field values, in the project's field order
+    fn render_custom_fields(&self, ui: &mut egui::Ui, state: &AppState, ticket: &Ticket) {
+        let fields: Vec<&CustomField> = state
+            .custom_fields
+            .iter()
+            .filter(|f| f.project_id == ticket.project_id)
+            .collect();
+        if fields.is_empty() {
+            return;
+        }
+
+        ui.label(RichText::new("Fields").strong().size(16.0));
+        egui::Grid::new("ticket_custom_fields")
+            .num_columns(2)
+            .spacing([Spacing::LARGE, Spacing::SMALL])
+            .show(ui, |ui| {
+                for field in fields {
+                    ui.label(RichText::new(&field.name).strong());
+                    match ticket.custom_fields.iter().find(|v| v.field_id == field.id) {
+                        Some(value) => {
+                            ui.label(format_field_value(&value.value, &state.users));
+                        },
+                        None => {
+                            ui.label(RichText::new("—").color(egui::Color32::GRAY));
+                        },
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(Spacing::LARGE);
+    }
+
+    /// Inputs for the custom fields of the ticket's project, one per type
+    fn render_field_inputs(&mut self, ui: &mut egui::Ui, state: &AppState) {
+        if self.edit_fields.is_empty() {
+            return;
+        }
+
+        ui.label("Fields");
+        egui::Grid::new("ticket_custom_field_inputs")
+            .num_columns(2)
+            .spacing([Spacing::LARGE, Spacing::SMALL])
+            .show(ui, |ui| {
+                for (field_id, draft) in &mut self.edit_fields {
+                    let Some(field) = state.custom_fields.iter().find(|f| f.id == *field_id) else {
+                        continue;
+                    };
+
+                    let name = if field.required {
+                        format!("{} *", field.name)
+                    } else {
+                        field.name.clone()
+                    };
+                    ui.label(name);
+
+                    match field.field_type {
+                        CustomFieldType::Text => {
+                            ui.text_edit_singleline(&mut draft.text);
+                        },
+                        CustomFieldType::Number => {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut draft.text).hint_text("e.g. 3.5"),
+                            );
+                        },
+                        CustomFieldType::Date => {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut draft.text).hint_text("YYYY-MM-DD"),
+                            );
+                        },
+                        CustomFieldType::SingleSelect => {
+                            egui::ComboBox::from_id_salt(("custom_field", field.id.0))
+                                .selected_text(if draft.text.is_empty() {
+                                    "None"
+                                } else {
+                                    draft.text.as_str()
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut draft.text, String::new(), "None");
+                                    for option in &field.options {
+                                        ui.selectable_value(
+                                            &mut draft.text,
+                                            option.clone(),
+                                            option.as_str(),
+                                        );
+                                    }
+                                });
+                        },
+                        CustomFieldType::MultiSelect => {
+                            ui.horizontal_wrapped(|ui| {
+                                for option in &field.options {
+                                    let mut checked = draft.options.contains(option);
+                                    if ui.checkbox(&mut checked, option.as_str()).changed() {
+                                        if checked {
+                                            draft.options.push(option.clone());
+                                        } else {
+                                            draft.options.retain(|o| o != option);
+                                        }
+                                    }
+                                }
+                            });
+                        },
+                        CustomFieldType::User => {
+                            let selected = draft
+                                .user
+                                .map(|id| user_name(id, &state.users))
+                                .unwrap_or_else(|| "None".to_string());
+                            egui::ComboBox::from_id_salt(("custom_field", field.id.0))
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut draft.user, None, "None");
+                                    for user in &state.users {
+                                        ui.selectable_value(
+                                            &mut draft.user,
+                                            Some(user.id),
+                                            user.username.as_str(),
+                                        );
+                                    }
+                                });
+                        },
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn start_editing(&mut self, ticket: &Ticket, state: &AppState) {
         self.is_editing = true;
         self.edit_title = ticket.title.clone();
         self.edit_description = ticket.description.clone().unwrap_or_default();
         self.edit_type = ticket.ticket_type;
-        self.edit_status = ticket.status;
+        self.edit_status = ticket.status.clone();
         self.edit_priority = ticket.priority;
+        self.edit_fields = state
+            .custom_fields
+            .iter()
+            .filter(|f| f.project_id == ticket.project_id)
+            .map(|field| {
+                let value = ticket
+                    .custom_fields
+                    .iter()
+                    .find(|v| v.field_id == field.id)
+                    .map(|v| &v.value);
+                (field.id, FieldDraft::new(value))
+            })
+            .collect();
+    }
+
+    /// Turn the field inputs into values, skipping fields left empty
+    fn custom_field_values(&self, state: &AppState) -> Result<Vec<CustomFieldValue>, String> {
+        let mut values = Vec::new();
+        for (field_id, draft) in &self.edit_fields {
+            let Some(field) = state.custom_fields.iter().find(|f| f.id == *field_id) else {
+                continue;
+            };
+            if let Some(value) = draft.to_value(field)? {
+                values.push(CustomFieldValue {
+                    field_id: *field_id,
+                    value,
+                });
+            }
+        }
+        Ok(values)
     }
 
     fn save_changes(&mut self, state: &mut AppState) {
+        let custom_fields = match self.custom_field_values(state) {
+            Ok(values) => values,
+            Err(message) => {
+                state.notify_error(message);
+                return;
+            },
+        };
+
         if let Some(mut ticket) = self.ticket.clone() {
             ticket.title = self.edit_title.clone();
             ticket.description = if self.edit_description.is_empty() {
@@ -326,8 +741,11 @@ impl TicketDetailScreen {
                 Some(self.edit_description.clone())
             };
             ticket.ticket_type = self.edit_type;
-            ticket.status = self.edit_status;
+            if let Some(status) = state.workflow(ticket.project_id).status(&self.edit_status) {
+                ticket.update_status(status);
+            }
             ticket.priority = self.edit_priority;
+            ticket.custom_fields = custom_fields.clone();
 
             if false {
                 // Demo mode: Update in-memory state
@@ -356,7 +774,7 @@ impl TicketDetailScreen {
                 } else {
                     Some(self.edit_description.clone())
                 };
-                let status = self.edit_status.to_string().to_lowercase();
+                let status = self.edit_status.clone();
                 let priority = self.edit_priority.to_string().to_lowercase();
                 let ticket_type = self.edit_type.to_string().to_lowercase();
 
@@ -374,6 +792,7 @@ impl TicketDetailScreen {
                         priority: Some(priority),
                         ticket_type: Some(ticket_type),
                         assigned_to: None,
+                        custom_fields: Some(custom_fields),
                     };
 
                     match api_client
@@ -398,14 +817,14 @@ impl TicketDetailScreen {
         }
     }
 
-    fn update_status(&mut self, state: &mut AppState, new_status: TicketStatus) {
+    fn update_status(&mut self, state: &mut AppState, new_status: WorkflowStatus) {
         if false {
             // Demo mode: Update in-memory state
             if let Some(ticket) = state.tickets.iter_mut().find(|t| t.id == self.ticket_id) {
-                ticket.status = new_status;
+                ticket.update_status(&new_status);
                 state.notify_success(format!(
-                    "Ticket status updated to {:?} (Demo Mode)",
-                    new_status
+                    "Ticket status updated to {} (Demo Mode)",
+                    new_status.name
                 ));
                 self.load_data(state);
             }
@@ -422,7 +841,7 @@ impl TicketDetailScreen {
             };
 
             let ticket_id_uuid = self.ticket_id.0;
-            let status_string = new_status.to_string().to_lowercase();
+            let status_string = new_status.key;
 
             state.is_loading = true;
 
@@ -437,6 +856,7 @@ impl TicketDetailScreen {
                     priority: None,
                     ticket_type: None,
                     assigned_to: None,
+                    custom_fields: None,
                 };
 
                 match api_client
@@ -444,7 +864,7 @@ impl TicketDetailScreen {
                     .await
                 {
                     Ok(updated_ticket) => {
-                        tracing::info!("Ticket status updated to: {:?}", updated_ticket.status);
+                        tracing::info!("Ticket status updated to: {}", updated_ticket.status);
                         event_queue.push(AppEvent::TicketUpdated {
                             ticket: updated_ticket,
                         });
@@ -872,3 +1292,82 @@ impl TicketDetailScreen {
         }
     }
 }
+
+/// Edit buffer for one custom field
+struct FieldDraft {
+    /// Text, number, date or single-select input
+    text: String,
+    /// Selected options of a multi-select field
+    options: Vec<String>,
+    /// Selected user of a user field
+    user: Option<UserId>,
+}
+
+impl FieldDraft {
+    fn new(value: Option<&FieldValue>) -> Self {
+        let mut draft = Self {
+            text: String::new(),
+            options: Vec::new(),
+            user: None,
+        };
+        match value {
+            Some(FieldValue::Text(text)) | Some(FieldValue::SingleSelect(text)) => {
+                draft.text = text.clone();
+            },
+            Some(FieldValue::Number(number)) => draft.text = number.to_string(),
+            Some(FieldValue::Date(date)) => draft.text = date.format("%Y-%m-%d").to_string(),
+            Some(FieldValue::MultiSelect(options)) => draft.options = options.clone(),
+            Some(FieldValue::User(user_id)) => draft.user = Some(*user_id),
+            None => {},
+        }
+        draft
+    }
+
+    /// The value entered for `field`, or `None` when left empty
+    fn to_value(&self, field: &CustomField) -> Result<Option<FieldValue>, String> {
+        let text = self.text.trim();
+        let value = match field.field_type {
+            CustomFieldType::Text if !text.is_empty() => FieldValue::Text(text.to_string()),
+            CustomFieldType::SingleSelect if !text.is_empty() => {
+                FieldValue::SingleSelect(text.to_string())
+            },
+            CustomFieldType::Number if !text.is_empty() => FieldValue::Number(
+                text.parse()
+                    .map_err(|_| format!("{} must be a number", field.name))?,
+            ),
+            CustomFieldType::Date if !text.is_empty() => FieldValue::Date(
+                chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                    .map_err(|_| format!("{} must be a date like 2025-01-31", field.name))?,
+            ),
+            CustomFieldType::MultiSelect if !self.options.is_empty() => {
+                FieldValue::MultiSelect(self.options.clone())
+            },
+            CustomFieldType::User => match self.user {
+                Some(user_id) => FieldValue::User(user_id),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+}
+
+/// Display text for a custom field value
+fn format_field_value(value: &FieldValue, users: &[User]) -> String {
+    match value {
+        FieldValue::Text(text) | FieldValue::SingleSelect(text) => text.clone(),
+        FieldValue::Number(number) => number.to_string(),
+        FieldValue::Date(date) => date.format("%Y-%m-%d").to_string(),
+        FieldValue::MultiSelect(options) => options.join(", "),
+        FieldValue::User(user_id) => user_name(*user_id, users),
+    }
+}
+
+/// Username of a user, falling back to the ID when not loaded
+fn user_name(user_id: UserId, users: &[User]) -> String {
+    users
+        .iter()
+        .find(|u| u.id == user_id)
+        .map(|u| u.username.clone())
+        .unwrap_or_else(|| user_id.to_string())
+}
@@ -2,21 +2,51 @@
 
 use egui::{RichText, ScrollArea};
 
-use worknest_core::models::{Priority, ProjectId, Ticket, TicketStatus, TicketType};
+use worknest_core::models::{
+    Priority, ProjectId, SavedView, StatusCategory, Ticket, TicketType, ViewDisplay,
+};
 
 use crate::{
+    components::label_chips,
     screens::Screen,
     state::AppState,
     theme::{Colors, Spacing},
 };
 
+/// Status filter of the list
+///
+/// Within a project it picks one of the project's workflow statuses; across
+/// projects, whose workflows differ, it picks a status category.
+#[derive(Debug, Clone, PartialEq)]
+enum StatusFilter {
+    Status(String),
+    Category(StatusCategory),
+}
+
+impl StatusFilter {
+    fn matches(&self, ticket: &Ticket) -> bool {
+        match self {
+            StatusFilter::Status(key) => ticket.status == *key,
+            StatusFilter::Category(category) => ticket.status_category == *category,
+        }
+    }
+}
+
 /// Ticket list screen
 pub struct TicketListScreen {
     pub project_id: Option<ProjectId>,
     tickets: Vec<Ticket>,
-    filter_status: Option<TicketStatus>,
-    search_query: String,
+    filter_status: Option<StatusFilter>,
+    query: String,
+    /// Sort order of the open saved view, passed to the server as is
+    sort: String,
+    /// Name of the open saved view
+    view_name: Option<String>,
     show_create_dialog: bool,
+    show_save_view_dialog: bool,
+    new_view_name: String,
+    new_view_shared: bool,
+    new_view_board: bool,
     new_ticket_title: String,
     new_ticket_description: String,
     new_ticket_type: TicketType,
@@ -30,8 +60,14 @@ impl TicketListScreen {
             project_id,
             tickets: Vec::new(),
             filter_status: None,
-            search_query: String::new(),
+            query: String::new(),
+            sort: String::new(),
+            view_name: None,
             show_create_dialog: false,
+            show_save_view_dialog: false,
+            new_view_name: String::new(),
+            new_view_shared: false,
+            new_view_board: false,
             new_ticket_title: String::new(),
             new_ticket_description: String::new(),
             new_ticket_type: TicketType::Task,
@@ -42,10 +78,20 @@ impl TicketListScreen {
 
     pub fn render(&mut self, ctx: &egui::Context, state: &mut AppState) {
         if !self.data_loaded {
+            state.ticket_query_results = None;
+            state.ticket_query_error = None;
             self.load_tickets(state);
+            if let Some(project_id) = self.project_id {
+                state.load_workflow(project_id);
+            }
             self.data_loaded = true;
         }
 
+        // Apply a saved view opened from the sidebar
+        if let Some(view) = state.pending_view.take() {
+            self.apply_view(view, state);
+        }
+
         // Sync tickets from state
         self.tickets = if let Some(project_id) = self.project_id {
             state
@@ -62,6 +108,10 @@ impl TicketListScreen {
             self.render_create_dialog(ctx, state);
         }
 
+        if self.show_save_view_dialog {
+            self.render_save_view_dialog(ctx, state);
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.add_space(Spacing::LARGE);
 
@@ -80,6 +130,10 @@ impl TicketListScreen {
                 ui.horizontal(|ui| {
                     ui.heading(RichText::new("Tickets").size(28.0));
 
+                    if let Some(name) = &self.view_name {
+                        ui.label(RichText::new(name).size(18.0).color(egui::Color32::GRAY));
+                    }
+
                     ui.add_space(Spacing::MEDIUM);
 
                     let button_enabled = self.project_id.is_some();
@@ -105,67 +159,79 @@ impl TicketListScreen {
 
             // Filters
             ui.horizontal(|ui| {
-                ui.label("Search:");
-                ui.add(
-                    egui::TextEdit::singleline(&mut self.search_query)
-                        .hint_text("Search tickets...")
-                        .desired_width(300.0),
+                ui.label("Filter:");
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("status:open assignee:me priority>=high due<7d")
+                        .desired_width(360.0),
                 );
 
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if submitted || ui.button("Apply").clicked() {
+                    self.run_query(state);
+                }
+                if ui.button("Save View").clicked() {
+                    self.new_view_name = self.view_name.clone().unwrap_or_default();
+                    self.show_save_view_dialog = true;
+                }
                 ui.add_space(Spacing::MEDIUM);
 
                 ui.label("Status:");
+                let options: Vec<(StatusFilter, String)> = match self.project_id {
+                    Some(project_id) => state
+                        .workflow(project_id)
+                        .statuses
+                        .into_iter()
+                        .map(|s| (StatusFilter::Status(s.key), s.name))
+                        .collect(),
+                    None => [
+                        StatusCategory::Todo,
+                        StatusCategory::InProgress,
+                        StatusCategory::Done,
+                    ]
+                    .into_iter()
+                    .map(|c| (StatusFilter::Category(c), c.to_string()))
+                    .collect(),
+                };
+                let selected = options
+                    .iter()
+                    .find(|(filter, _)| Some(filter) == self.filter_status.as_ref())
+                    .map_or("All", |(_, name)| name.as_str());
                 egui::ComboBox::from_id_salt("status_filter")
-                    .selected_text(match self.filter_status {
-                        Some(s) => format!("{:?}", s),
-                        None => "All".to_string(),
-                    })
+                    .selected_text(selected)
                     .show_ui(ui, |ui| {
                         ui.selectable_value(&mut self.filter_status, None, "All");
-                        ui.selectable_value(
-                            &mut self.filter_status,
-                            Some(TicketStatus::Open),
-                            "Open",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_status,
-                            Some(TicketStatus::InProgress),
-                            "In Progress",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_status,
-                            Some(TicketStatus::Review),
-                            "Review",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_status,
-                            Some(TicketStatus::Done),
-                            "Done",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_status,
-                            Some(TicketStatus::Closed),
-                            "Closed",
-                        );
+                        for (filter, name) in &options {
+                            ui.selectable_value(
+                                &mut self.filter_status,
+                                Some(filter.clone()),
+                                name.as_str(),
+                            );
+                        }
                     });
             });
 
+            if let Some(error) = &state.ticket_query_error {
+                ui.label(RichText::new(error).monospace().color(Colors::ERROR));
+            }
+
             ui.add_space(Spacing::MEDIUM);
             ui.separator();
             ui.add_space(Spacing::MEDIUM);
 
             // Ticket list
             ScrollArea::vertical().show(ui, |ui| {
-                let filtered_tickets: Vec<_> = self
-                    .tickets
-                    .iter()
-                    .filter(|t| {
-                        (self.filter_status.is_none() || Some(t.status) == self.filter_status)
-                            && (self.search_query.is_empty()
-                                || t.title
-                                    .to_lowercase()
-                                    .contains(&self.search_query.to_lowercase()))
-                    })
+                let matching: Vec<&Ticket> = match &state.ticket_query_results {
+                    Some(ids) => ids
+                        .iter()
+                        .filter_map(|id| self.tickets.iter().find(|t| t.id == *id))
+                        .collect(),
+                    None => self.tickets.iter().collect(),
+                };
+                let filtered_tickets: Vec<_> = matching
+                    .into_iter()
+                    .filter(|t| self.filter_status.as_ref().is_none_or(|f| f.matches(t)))
                     .collect();
 
                 if filtered_tickets.is_empty() {
@@ -202,6 +268,11 @@ impl TicketListScreen {
 
                 ui.vertical(|ui| {
                     ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(state.ticket_key(ticket))
+                                .monospace()
+                                .color(egui::Color32::GRAY),
+                        );
                         ui.label(RichText::new(&ticket.title).strong());
 
                         // Type badge
@@ -210,6 +281,8 @@ impl TicketListScreen {
                             TicketType::Bug => ("Bug", Colors::TYPE_BUG),
                             TicketType::Feature => ("Feature", Colors::TYPE_FEATURE),
                             TicketType::Epic => ("Epic", Colors::TYPE_EPIC),
+                            TicketType::Story => ("Story", Colors::TYPE_STORY),
+                            TicketType::Subtask => ("Subtask", Colors::TYPE_SUBTASK),
                         };
                         ui.label(RichText::new(type_text).small().color(type_color));
                     });
@@ -222,15 +295,14 @@ impl TicketListScreen {
                         );
                     }
 
+                    label_chips(ui, &ticket.labels);
+
                     ui.horizontal(|ui| {
                         // Status
-                        let status_text = match ticket.status {
-                            TicketStatus::Open => "Open",
-                            TicketStatus::InProgress => "In Progress",
-                            TicketStatus::Review => "Review",
-                            TicketStatus::Done => "Done",
-                            TicketStatus::Closed => "Closed",
-                        };
+                        let status_text = state
+                            .workflow(ticket.project_id)
+                            .status(&ticket.status)
+                            .map_or_else(|| ticket.status.clone(), |s| s.name.clone());
                         ui.label(RichText::new(status_text).small());
 
                         // Priority
@@ -298,6 +370,8 @@ impl TicketListScreen {
                     ui.radio_value(&mut self.new_ticket_type, TicketType::Bug, "Bug");
                     ui.radio_value(&mut self.new_ticket_type, TicketType::Feature, "Feature");
                     ui.radio_value(&mut self.new_ticket_type, TicketType::Epic, "Epic");
+                    ui.radio_value(&mut self.new_ticket_type, TicketType::Story, "Story");
+                    ui.radio_value(&mut self.new_ticket_type, TicketType::Subtask, "Subtask");
                 });
 
                 ui.add_space(Spacing::MEDIUM);
@@ -430,6 +504,138 @@ impl TicketListScreen {
         self.new_ticket_priority = Priority::Medium;
     }
 
+    /// Run the filter query on the server, after checking its syntax locally
+    fn run_query(&mut self, state: &mut AppState) {
+        let query = self.query.trim().to_string();
+
+        if !query.is_empty() {
+            if let Err(e) = worknest_core::query::parse(&query) {
+                state.ticket_query_error = Some(format!(
+                    "{}\n{}\n{}^",
+                    e.message,
+                    query,
+                    " ".repeat(e.position)
+                ));
+                return;
+            }
+        }
+
+        state.run_ticket_query(self.project_id, &query, &self.sort);
+    }
+
+    /// Show the tickets of a saved view
+    fn apply_view(&mut self, view: SavedView, state: &mut AppState) {
+        self.query = view.query;
+        self.sort = view.sort;
+        self.view_name = Some(view.name);
+        self.run_query(state);
+    }
+
+    fn render_save_view_dialog(&mut self, ctx: &egui::Context, state: &mut AppState) {
+        egui::Window::new("Save View")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.set_min_width(400.0);
+
+                ui.label("Name");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.new_view_name)
+                        .hint_text("Enter view name")
+                        .desired_width(f32::INFINITY),
+                );
+
+                ui.add_space(Spacing::SMALL);
+                ui.label(
+                    RichText::new(if self.query.trim().is_empty() {
+                        "All tickets"
+                    } else {
+                        self.query.trim()
+                    })
+                    .monospace()
+                    .color(egui::Color32::GRAY),
+                );
+
+                // Sharing and the board display are tied to a project
+                if self.project_id.is_some() {
+                    ui.add_space(Spacing::MEDIUM);
+                    ui.checkbox(&mut self.new_view_shared, "Share with project members");
+                    ui.checkbox(&mut self.new_view_board, "Open as board");
+                }
+
+                ui.add_space(Spacing::LARGE);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_sized([100.0, 32.0], egui::Button::new("Cancel"))
+                        .clicked()
+                    {
+                        self.show_save_view_dialog = false;
+                    }
+
+                    if ui
+                        .add_sized(
+                            [100.0, 32.0],
+                            egui::Button::new("Save").fill(Colors::PRIMARY),
+                        )
+                        .clicked()
+                    {
+                        self.save_view(state);
+                    }
+                });
+            });
+    }
+
+    fn save_view(&mut self, state: &mut AppState) {
+        let name = self.new_view_name.trim().to_string();
+        if name.is_empty() {
+            state.notify_error("View name is required".to_string());
+            return;
+        }
+
+        let api_client = state.api_client.clone();
+        let event_queue = state.event_queue.clone();
+        let token = match &state.auth_token {
+            Some(t) => t.clone(),
+            None => {
+                state.notify_error("Not authenticated".to_string());
+                return;
+            },
+        };
+
+        let request = crate::api_client::CreateViewRequest {
+            name: name.clone(),
+            query: self.query.trim().to_string(),
+            sort: self.sort.clone(),
+            display: if self.project_id.is_some() && self.new_view_board {
+                ViewDisplay::Board
+            } else {
+                ViewDisplay::List
+            },
+            project_id: self.project_id.map(|pid| pid.0),
+            shared: self.project_id.is_some() && self.new_view_shared,
+        };
+
+        self.show_save_view_dialog = false;
+        self.view_name = Some(name);
+
+        wasm_bindgen_futures::spawn_local(async move {
+            use crate::events::AppEvent;
+
+            match api_client.create_view(&token, request).await {
+                Ok(view) => {
+                    event_queue.push(AppEvent::SavedViewCreated { view });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to save view: {:?}", e);
+                    event_queue.push(AppEvent::SavedViewError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
     fn load_tickets(&mut self, state: &AppState) {
         if false {
             // Demo mode: Load from in-memory state
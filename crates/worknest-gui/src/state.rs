@@ -1,13 +1,72 @@
 //! Application state management
 
-use crate::api_client::ApiClient;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::api_client::{ApiClient, CreateWorklogRequest};
+use crate::change_stream::ChangeStream;
 use crate::events::{AppEvent, EventQueue};
 use crate::screens::Screen;
-use worknest_core::models::{Comment, Project, Ticket, User};
+use worknest_core::models::{
+    ActivityAction, ChangeEvent, ChangeResource, CodeLink, Comment, CommentId, CustomField,
+    NotificationId, NotificationPreferences, Project, ProjectId, SavedView, Sprint, Ticket,
+    TicketId, User, ViewDisplay, Workflow, Worklog,
+};
 
 // Use web_time::Instant for WASM compatibility instead of std::time::Instant
 use web_time::Instant;
 
+/// Delay before reconnecting to the change stream after it closed
+const CHANGE_STREAM_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// LocalStorage key of the running time tracking timer
+const TIMER_STORAGE_KEY: &str = "worklog_timer";
+
+/// Time tracking timer started from a ticket
+///
+/// The timer lives in LocalStorage so it keeps running across page reloads;
+/// stopping it logs the elapsed time as a worklog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RunningTimer {
+    pub ticket_id: TicketId,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RunningTimer {
+    /// Whole minutes since the timer started, rounded up and capped at the
+    /// longest worklog allowed
+    pub fn elapsed_minutes(&self) -> u32 {
+        let seconds = (chrono::Utc::now() - self.started_at).num_seconds().max(0);
+        let minutes = u32::try_from((seconds + 59) / 60).unwrap_or(u32::MAX);
+        minutes.clamp(1, Worklog::MAX_DURATION_MINUTES)
+    }
+}
+
+/// Log the time of a stopped timer as a worklog on its ticket
+async fn log_timer(
+    api_client: &ApiClient,
+    token: &str,
+    timer: RunningTimer,
+) -> anyhow::Result<Worklog> {
+    let request = CreateWorklogRequest {
+        started_at: Some(timer.started_at),
+        duration_minutes: timer.elapsed_minutes(),
+        note: None,
+    };
+    api_client
+        .create_worklog(token, timer.ticket_id.0, request)
+        .await
+}
+
+/// Remove a timer from LocalStorage once its time is logged, unless a new
+/// timer has replaced it there
+fn forget_persisted_timer(timer: RunningTimer) {
+    use gloo_storage::{LocalStorage, Storage};
+    if LocalStorage::get::<RunningTimer>(TIMER_STORAGE_KEY).ok() == Some(timer) {
+        LocalStorage::delete(TIMER_STORAGE_KEY);
+    }
+}
+
 /// Main application state
 #[derive(Clone)]
 pub struct AppState {
@@ -23,6 +82,12 @@ pub struct AppState {
     pub event_queue: EventQueue,
     /// Notification messages
     pub notifications: Vec<Notification>,
+    /// Notifications in the user's inbox, newest first
+    pub inbox: Vec<worknest_core::models::Notification>,
+    /// Number of unread notifications in the inbox, shown as a badge
+    pub unread_notifications: u32,
+    /// The user's email notification preferences, once loaded
+    pub notification_preferences: Option<NotificationPreferences>,
     /// Loading state
     pub is_loading: bool,
     /// Cached projects from API
@@ -31,6 +96,34 @@ pub struct AppState {
     pub tickets: Vec<Ticket>,
     /// Cached comments from API
     pub comments: Vec<Comment>,
+    /// Cached worklogs of the tickets opened so far
+    pub worklogs: Vec<Worklog>,
+    /// Cached commits and pull requests linked to the tickets opened so far
+    pub code_links: Vec<CodeLink>,
+    /// Running time tracking timer, if any
+    pub running_timer: Option<RunningTimer>,
+    /// Connection to the server's change stream while signed in
+    change_stream: Option<Rc<ChangeStream>>,
+    /// When to reconnect to the change stream after it closed
+    change_stream_retry_at: Option<Instant>,
+    /// Whether changes may have been missed since the change stream closed
+    change_stream_missed: bool,
+    /// Cached custom field definitions of the projects loaded so far
+    pub custom_fields: Vec<CustomField>,
+    /// Cached users, for picking values of user fields
+    pub users: Vec<User>,
+    /// Cached workflows of the projects loaded so far
+    pub workflows: Vec<Workflow>,
+    /// Cached sprints of the projects loaded so far
+    pub sprints: Vec<Sprint>,
+    /// Tickets matching the last ticket query, in server order
+    pub ticket_query_results: Option<Vec<TicketId>>,
+    /// Why the last ticket query failed
+    pub ticket_query_error: Option<String>,
+    /// Saved views visible to the user
+    pub saved_views: Vec<SavedView>,
+    /// View opened from the sidebar, picked up by the next ticket screen
+    pub pending_view: Option<SavedView>,
 }
 
 impl AppState {
@@ -43,15 +136,40 @@ impl AppState {
             api_client,
             event_queue: EventQueue::new(),
             notifications: Vec::new(),
+            inbox: Vec::new(),
+            unread_notifications: 0,
+            notification_preferences: None,
             is_loading: false,
             projects: Vec::new(),
             tickets: Vec::new(),
             comments: Vec::new(),
+            worklogs: Vec::new(),
+            code_links: Vec::new(),
+            running_timer: None,
+            change_stream: None,
+            change_stream_retry_at: None,
+            change_stream_missed: false,
+            custom_fields: Vec::new(),
+            users: Vec::new(),
+            workflows: Vec::new(),
+            sprints: Vec::new(),
+            ticket_query_results: None,
+            ticket_query_error: None,
+            saved_views: Vec::new(),
+            pending_view: None,
         }
     }
 
     /// Process pending events from async operations
     pub fn process_events(&mut self) {
+        if self
+            .change_stream_retry_at
+            .is_some_and(|at| Instant::now() >= at)
+        {
+            self.change_stream_retry_at = None;
+            self.connect_change_stream();
+        }
+
         let events = self.event_queue.drain();
 
         for event in events {
@@ -70,6 +188,23 @@ impl AppState {
                 AppEvent::RegisterError { message } => {
                     self.notify_error(format!("Registration failed: {}", message));
                 },
+                AppEvent::ProfileUpdated { user } => {
+                    use gloo_storage::{LocalStorage, Storage};
+                    let _ = LocalStorage::set("current_user", &user);
+                    self.current_user = Some(user);
+                    self.notify_success("Profile updated successfully!".to_string());
+                },
+                AppEvent::ProfileError { message } => {
+                    self.notify_error(message);
+                },
+                AppEvent::PasswordChanged => {
+                    self.notify_success(
+                        "Password changed. Other sessions have been signed out.".to_string(),
+                    );
+                },
+                AppEvent::PasswordError { message } => {
+                    self.notify_error(message);
+                },
                 AppEvent::ProjectsLoaded { projects } => {
                     self.projects = projects;
                     self.is_loading = false;
@@ -99,7 +234,8 @@ impl AppState {
                     self.is_loading = false;
                 },
                 AppEvent::TicketCreated { ticket } => {
-                    self.tickets.push(ticket);
+                    // The change stream may have delivered the ticket already
+                    self.upsert_ticket(ticket);
                     self.notify_success("Ticket created successfully!".to_string());
                 },
                 AppEvent::TicketUpdated { ticket } => {
@@ -118,12 +254,78 @@ impl AppState {
                 AppEvent::TicketError { message } => {
                     self.notify_error(format!("Ticket error: {}", message));
                 },
-                AppEvent::CommentsLoaded { comments } => {
-                    self.comments = comments;
+                AppEvent::TicketQueryResults { tickets } => {
+                    self.ticket_query_results = Some(tickets.iter().map(|t| t.id).collect());
+                    self.ticket_query_error = None;
+                    for ticket in tickets {
+                        self.upsert_ticket(ticket);
+                    }
+                    self.is_loading = false;
+                },
+                AppEvent::TicketQueryError { message } => {
+                    self.ticket_query_error = Some(message);
+                    self.is_loading = false;
+                },
+                AppEvent::CustomFieldsLoaded { project_id, fields } => {
+                    self.custom_fields.retain(|f| f.project_id != project_id);
+                    self.custom_fields.extend(fields);
+                },
+                AppEvent::UsersLoaded { users } => {
+                    self.users = users;
+                },
+                AppEvent::WorkflowLoaded { workflow } => {
+                    self.workflows
+                        .retain(|w| w.project_id != workflow.project_id);
+                    self.workflows.push(workflow);
+                },
+                AppEvent::TicketChildrenLoaded {
+                    parent_id,
+                    children,
+                } => {
+                    // Children that moved elsewhere come back with the next load
+                    self.tickets.retain(|t| {
+                        t.parent_id != Some(parent_id) || children.iter().any(|c| c.id == t.id)
+                    });
+                    for child in children {
+                        if let Some(t) = self.tickets.iter_mut().find(|t| t.id == child.id) {
+                            *t = child;
+                        } else {
+                            self.tickets.push(child);
+                        }
+                    }
+                },
+                AppEvent::SprintsLoaded {
+                    project_id,
+                    sprints,
+                } => {
+                    self.sprints.retain(|s| s.project_id != project_id);
+                    self.sprints.extend(sprints);
+                },
+                AppEvent::SavedViewsLoaded { views } => {
+                    self.saved_views = views;
+                },
+                AppEvent::SavedViewCreated { view } => {
+                    self.saved_views.push(view);
+                    self.saved_views.sort_by_key(|v| v.name.to_lowercase());
+                    self.notify_success("View saved successfully!".to_string());
+                },
+                AppEvent::SavedViewDeleted { view_id } => {
+                    self.saved_views.retain(|v| v.id != view_id);
+                    self.notify_success("View deleted successfully!".to_string());
+                },
+                AppEvent::SavedViewError { message } => {
+                    self.notify_error(format!("View error: {}", message));
+                },
+                AppEvent::CommentsLoaded {
+                    ticket_id,
+                    comments,
+                } => {
+                    self.comments.retain(|c| c.ticket_id != ticket_id);
+                    self.comments.extend(comments);
                     self.is_loading = false;
                 },
                 AppEvent::CommentCreated { comment } => {
-                    self.comments.push(comment);
+                    self.upsert_comment(comment);
                     self.notify_success("Comment added successfully!".to_string());
                 },
                 AppEvent::CommentUpdated { comment } => {
@@ -142,6 +344,121 @@ impl AppState {
                 AppEvent::CommentError { message } => {
                     self.notify_error(format!("Comment error: {}", message));
                 },
+                AppEvent::WorklogsLoaded {
+                    ticket_id,
+                    worklogs,
+                } => {
+                    self.worklogs.retain(|w| w.ticket_id != ticket_id);
+                    self.worklogs.extend(worklogs);
+                },
+                AppEvent::WorklogCreated { worklog, timer } => {
+                    if let Some(timer) = timer {
+                        forget_persisted_timer(timer);
+                    }
+                    if let Some(t) = self.tickets.iter_mut().find(|t| t.id == worklog.ticket_id) {
+                        t.logged_minutes += worklog.duration_minutes;
+                    }
+                    self.notify_success(format!(
+                        "Logged {}",
+                        format_minutes(worklog.duration_minutes)
+                    ));
+                    self.worklogs.push(worklog);
+                },
+                AppEvent::WorklogDeleted { worklog } => {
+                    if let Some(t) = self.tickets.iter_mut().find(|t| t.id == worklog.ticket_id) {
+                        t.logged_minutes =
+                            t.logged_minutes.saturating_sub(worklog.duration_minutes);
+                    }
+                    self.worklogs.retain(|w| w.id != worklog.id);
+                    self.notify_success("Worklog deleted successfully!".to_string());
+                },
+                AppEvent::WorklogError { message, timer } => {
+                    // Keep the time of a timer that could not be logged,
+                    // unless another timer was started meanwhile
+                    if let Some(timer) = timer {
+                        if self.running_timer.is_none() {
+                            use gloo_storage::{LocalStorage, Storage};
+                            let _ = LocalStorage::set(TIMER_STORAGE_KEY, timer);
+                            self.running_timer = Some(timer);
+                        }
+                    }
+                    self.notify_error(format!("Worklog error: {}", message));
+                },
+                AppEvent::CodeLinksLoaded { ticket_id, links } => {
+                    self.code_links.retain(|l| l.ticket_id != ticket_id);
+                    self.code_links.extend(links);
+                },
+                AppEvent::NotificationsLoaded { notifications } => {
+                    self.unread_notifications =
+                        notifications.iter().filter(|n| !n.is_read()).count() as u32;
+                    self.inbox = notifications;
+                },
+                AppEvent::UnreadNotificationsCounted { count } => {
+                    self.unread_notifications = count;
+                },
+                AppEvent::NotificationRead { notification } => {
+                    if let Some(n) = self.inbox.iter_mut().find(|n| n.id == notification.id) {
+                        if !n.is_read() {
+                            self.unread_notifications = self.unread_notifications.saturating_sub(1);
+                        }
+                        *n = notification;
+                    }
+                },
+                AppEvent::AllNotificationsRead => {
+                    let now = chrono::Utc::now();
+                    for n in self.inbox.iter_mut().filter(|n| !n.is_read()) {
+                        n.read_at = Some(now);
+                    }
+                    self.unread_notifications = 0;
+                },
+                AppEvent::NotificationPreferencesLoaded { preferences } => {
+                    self.notification_preferences = Some(preferences);
+                },
+                AppEvent::NotificationPreferencesSaved { preferences } => {
+                    self.notification_preferences = Some(preferences);
+                    self.notify_success("Notification preferences saved!".to_string());
+                },
+                AppEvent::NotificationError { message } => {
+                    self.notify_error(format!("Notification error: {}", message));
+                },
+                AppEvent::ChangeStreamReady { url } => {
+                    // Signed out while the URL was fetched
+                    if !self.is_authenticated() {
+                        continue;
+                    }
+                    match ChangeStream::connect(&url, self.event_queue.clone()) {
+                        Ok(stream) => {
+                            self.change_stream = Some(Rc::new(stream));
+                            // Changes made while disconnected never arrive
+                            if std::mem::take(&mut self.change_stream_missed) {
+                                self.resync();
+                            }
+                        },
+                        Err(e) => {
+                            tracing::warn!("Failed to open change stream: {:?}", e);
+                            self.change_stream_retry_at =
+                                Some(Instant::now() + CHANGE_STREAM_RETRY_DELAY);
+                        },
+                    }
+                },
+                AppEvent::RemoteChange { change } => {
+                    // Other users' changes may have landed in the inbox
+                    if self.current_user.as_ref().map(|u| u.id) != Some(change.actor_id) {
+                        self.refresh_inbox();
+                    }
+                    self.apply_change(change);
+                },
+                AppEvent::ChangeStreamResync => {
+                    self.resync();
+                },
+                AppEvent::ChangeStreamClosed => {
+                    self.change_stream = None;
+                    if self.is_authenticated() {
+                        self.change_stream_missed = true;
+                        self.change_stream_retry_at =
+                            Some(Instant::now() + CHANGE_STREAM_RETRY_DELAY);
+                    }
+                },
                 AppEvent::ApiError { message } => {
                     self.notify_error(format!("API error: {}", message));
                     self.is_loading = false;
@@ -165,11 +482,700 @@ impl AppState {
         }
     }
 
+    /// Subscribe to the server's change stream, so the cached tickets and
+    /// comments follow changes made by other users
+    pub fn connect_change_stream(&mut self) {
+        self.change_stream = None;
+
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.change_stream_url(&token).await {
+                Ok(url) => {
+                    event_queue.push(AppEvent::ChangeStreamReady { url });
+                },
+                Err(e) => {
+                    tracing::warn!("Failed to authenticate change stream: {:?}", e);
+                    event_queue.push(AppEvent::ChangeStreamClosed);
+                },
+            }
+        });
+    }
+
+    /// Apply a change pushed by the server to the caches
+    fn apply_change(&mut self, change: ChangeEvent) {
+        match (change.resource, change.action) {
+            (ChangeResource::Ticket, ActivityAction::Deleted) => {
+                if let Ok(id) = TicketId::from_string(&change.resource_id) {
+                    self.tickets.retain(|t| t.id != id);
+                    self.comments.retain(|c| c.ticket_id != id);
+                    self.worklogs.retain(|w| w.ticket_id != id);
+                    self.code_links.retain(|l| l.ticket_id != id);
+                }
+            },
+            (ChangeResource::Ticket, _) => {
+                match change.data.map(serde_json::from_value::<Ticket>) {
+                    Some(Ok(ticket)) => self.upsert_ticket(ticket),
+                    _ => tracing::warn!("Ignoring ticket change without a ticket"),
+                }
+            },
+            (ChangeResource::Comment, ActivityAction::Deleted) => {
+                if let Ok(id) = CommentId::from_string(&change.resource_id) {
+                    self.comments.retain(|c| c.id != id);
+                }
+            },
+            (ChangeResource::Comment, _) => {
+                match change.data.map(serde_json::from_value::<Comment>) {
+                    Some(Ok(comment)) => self.upsert_comment(comment),
+                    _ => tracing::warn!("Ignoring comment change without a comment"),
+                }
+            },
+            // Attachments are not cached
+            (ChangeResource::Attachment, _) => {},
+        }
+    }
+
+    /// Replace a cached ticket, or add it when it is not cached yet
+    fn upsert_ticket(&mut self, ticket: Ticket) {
+        match self.tickets.iter_mut().find(|t| t.id == ticket.id) {
+            Some(cached) => *cached = ticket,
+            None => self.tickets.push(ticket),
+        }
+    }
+
+    /// Replace a cached comment, or add it when it is not cached yet
+    fn upsert_comment(&mut self, comment: Comment) {
+        match self.comments.iter_mut().find(|c| c.id == comment.id) {
+            Some(cached) => *cached = comment,
+            None => self.comments.push(comment),
+        }
+    }
+
+    /// Reload what the change stream keeps up to date, after missing changes
+    ///
+    /// Attachments are not cached, so the tickets, the open ticket's
+    /// comments and the inbox are all there is to reload.
+    fn resync(&self) {
+        self.reload_tickets();
+        if let Screen::TicketDetail(ticket_id) = self.current_screen {
+            self.reload_comments(ticket_id);
+        }
+        self.refresh_inbox();
+    }
+
+    /// Reload the comments of a ticket
+    fn reload_comments(&self, ticket_id: TicketId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_ticket_comments(&token, ticket_id.0).await {
+                Ok(comments) => {
+                    event_queue.push(AppEvent::CommentsLoaded {
+                        ticket_id,
+                        comments,
+                    });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to reload comments: {:?}", e);
+                    event_queue.push(AppEvent::CommentError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Reload every ticket the user can see, after missing changes
+    fn reload_tickets(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_tickets(&token, None).await {
+                Ok(tickets) => {
+                    event_queue.push(AppEvent::TicketsLoaded { tickets });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to reload tickets: {:?}", e);
+                    event_queue.push(AppEvent::TicketError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
     /// Navigate to a screen
     pub fn navigate_to(&mut self, screen: Screen) {
+        // The inbox is fetched afresh whenever it is opened
+        if screen == Screen::Notifications {
+            self.load_notifications();
+        }
         self.current_screen = screen;
     }
 
+    /// Open a saved view in the ticket list or on the project board
+    pub fn open_view(&mut self, view: SavedView) {
+        let screen = match (view.display, view.project_id) {
+            (ViewDisplay::Board, Some(project_id)) => Screen::TicketBoard { project_id },
+            (_, project_id) => Screen::TicketList { project_id },
+        };
+        self.pending_view = Some(view);
+        self.navigate_to(screen);
+    }
+
+    /// Fetch the saved views visible to the user
+    pub fn load_saved_views(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_views(&token).await {
+                Ok(views) => {
+                    event_queue.push(AppEvent::SavedViewsLoaded { views });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load saved views: {:?}", e);
+                    event_queue.push(AppEvent::SavedViewError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the custom fields of a project
+    pub fn load_custom_fields(&self, project_id: ProjectId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_custom_fields(&token, project_id.0).await {
+                Ok(fields) => {
+                    event_queue.push(AppEvent::CustomFieldsLoaded { project_id, fields });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load custom fields: {:?}", e);
+                    event_queue.push(AppEvent::ApiError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the workflow of a project
+    pub fn load_workflow(&self, project_id: ProjectId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_workflow(&token, project_id.0).await {
+                Ok(workflow) => {
+                    event_queue.push(AppEvent::WorkflowLoaded { workflow });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load workflow: {:?}", e);
+                    event_queue.push(AppEvent::ApiError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// The cached workflow of a project, or the default one until it has
+    /// been loaded
+    pub fn workflow(&self, project_id: ProjectId) -> Workflow {
+        self.workflows
+            .iter()
+            .find(|w| w.project_id == project_id)
+            .cloned()
+            .unwrap_or_else(|| Workflow::default_for(project_id))
+    }
+
+    /// Fetch the sprints of a project
+    pub fn load_sprints(&self, project_id: ProjectId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_sprints(&token, project_id.0).await {
+                Ok(sprints) => {
+                    event_queue.push(AppEvent::SprintsLoaded {
+                        project_id,
+                        sprints,
+                    });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load sprints: {:?}", e);
+                    event_queue.push(AppEvent::ApiError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// The human-readable key of a ticket, such as `WN-123`, or just its
+    /// number until its project has been loaded
+    pub fn ticket_key(&self, ticket: &Ticket) -> String {
+        match self.projects.iter().find(|p| p.id == ticket.project_id) {
+            Some(project) => format!("{}-{}", project.key, ticket.number),
+            None => format!("#{}", ticket.number),
+        }
+    }
+
+    /// Fetch the direct children of a ticket into the ticket cache
+    pub fn load_ticket_children(&self, ticket_id: TicketId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_ticket_children(&token, ticket_id.0).await {
+                Ok(children) => {
+                    event_queue.push(AppEvent::TicketChildrenLoaded {
+                        parent_id: ticket_id,
+                        children,
+                    });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load child tickets: {:?}", e);
+                    event_queue.push(AppEvent::ApiError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the notifications in the user's inbox
+    pub fn load_notifications(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_notifications(&token).await {
+                Ok(notifications) => {
+                    event_queue.push(AppEvent::NotificationsLoaded { notifications });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load notifications: {:?}", e);
+                    event_queue.push(AppEvent::NotificationError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the number of unread notifications for the sidebar badge
+    pub fn refresh_unread_notifications(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_unread_notification_count(&token).await {
+                Ok(count) => {
+                    event_queue.push(AppEvent::UnreadNotificationsCounted { count });
+                },
+                // The badge is not worth an error message
+                Err(e) => tracing::warn!("Failed to count notifications: {:?}", e),
+            }
+        });
+    }
+
+    /// Bring the inbox up to date: the whole list while it is open, the
+    /// unread count otherwise
+    fn refresh_inbox(&self) {
+        if self.current_screen == Screen::Notifications {
+            self.load_notifications();
+        } else {
+            self.refresh_unread_notifications();
+        }
+    }
+
+    /// Mark a notification in the inbox as read
+    pub fn mark_notification_read(&self, notification_id: NotificationId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client
+                .mark_notification_read(&token, notification_id.0)
+                .await
+            {
+                Ok(notification) => {
+                    event_queue.push(AppEvent::NotificationRead { notification });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to mark notification read: {:?}", e);
+                    event_queue.push(AppEvent::NotificationError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Mark every notification in the inbox as read
+    pub fn mark_all_notifications_read(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.mark_all_notifications_read(&token).await {
+                Ok(()) => {
+                    event_queue.push(AppEvent::AllNotificationsRead);
+                },
+                Err(e) => {
+                    tracing::error!("Failed to mark notifications read: {:?}", e);
+                    event_queue.push(AppEvent::NotificationError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the user's email notification preferences
+    pub fn load_notification_preferences(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_notification_preferences(&token).await {
+                Ok(preferences) => {
+                    event_queue.push(AppEvent::NotificationPreferencesLoaded { preferences });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load notification preferences: {:?}", e);
+                    event_queue.push(AppEvent::NotificationError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Save the user's email notification preferences
+    pub fn save_notification_preferences(&self, preferences: NotificationPreferences) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client
+                .update_notification_preferences(&token, &preferences)
+                .await
+            {
+                Ok(preferences) => {
+                    event_queue.push(AppEvent::NotificationPreferencesSaved { preferences });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to save notification preferences: {:?}", e);
+                    event_queue.push(AppEvent::NotificationError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the worklogs of a ticket
+    pub fn load_worklogs(&self, ticket_id: TicketId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_ticket_worklogs(&token, ticket_id.0).await {
+                Ok(worklogs) => {
+                    event_queue.push(AppEvent::WorklogsLoaded {
+                        ticket_id,
+                        worklogs,
+                    });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load worklogs: {:?}", e);
+                    event_queue.push(AppEvent::WorklogError {
+                        message: e.to_string(),
+                        timer: None,
+                    });
+                },
+            }
+        });
+    }
+
+    /// Fetch the commits and pull requests linked to a ticket
+    ///
+    /// Failures are only logged; the ticket is usable without its links.
+    pub fn load_code_links(&self, ticket_id: TicketId) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_ticket_links(&token, ticket_id.0).await {
+                Ok(links) => {
+                    event_queue.push(AppEvent::CodeLinksLoaded { ticket_id, links });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load code links: {:?}", e);
+                },
+            }
+        });
+    }
+
+    /// The cached links of a ticket: pull requests first, then commits,
+    /// the newest first within each
+    pub fn ticket_code_links(&self, ticket_id: TicketId) -> Vec<&CodeLink> {
+        self.code_links
+            .iter()
+            .filter(|l| l.ticket_id == ticket_id)
+            .collect()
+    }
+
+    /// The cached worklogs of a ticket, earliest first
+    pub fn ticket_worklogs(&self, ticket_id: TicketId) -> Vec<&Worklog> {
+        let mut worklogs: Vec<&Worklog> = self
+            .worklogs
+            .iter()
+            .filter(|w| w.ticket_id == ticket_id)
+            .collect();
+        worklogs.sort_by_key(|w| w.started_at);
+        worklogs
+    }
+
+    /// Start the time tracking timer on a ticket
+    ///
+    /// Only one timer runs at a time; a timer running on another ticket is
+    /// stopped and its time logged first.
+    pub fn start_timer(&mut self, ticket_id: TicketId) {
+        if self
+            .running_timer
+            .is_some_and(|timer| timer.ticket_id != ticket_id)
+        {
+            self.stop_timer();
+        }
+        if self.running_timer.is_some() {
+            return;
+        }
+
+        let timer = RunningTimer {
+            ticket_id,
+            started_at: chrono::Utc::now(),
+        };
+        use gloo_storage::{LocalStorage, Storage};
+        let _ = LocalStorage::set(TIMER_STORAGE_KEY, timer);
+        self.running_timer = Some(timer);
+    }
+
+    /// Stop the running timer and log the elapsed time against its ticket
+    ///
+    /// The timer stays persisted until the worklog is created, and runs
+    /// again if logging its time fails.
+    pub fn stop_timer(&mut self) {
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+        let Some(timer) = self.running_timer.take() else {
+            return;
+        };
+
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match log_timer(&api_client, &token, timer).await {
+                Ok(worklog) => {
+                    event_queue.push(AppEvent::WorklogCreated {
+                        worklog,
+                        timer: Some(timer),
+                    });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to log time: {:?}", e);
+                    event_queue.push(AppEvent::WorklogError {
+                        message: e.to_string(),
+                        timer: Some(timer),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Delete a worklog
+    pub fn delete_worklog(&self, worklog: Worklog) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.delete_worklog(&token, worklog.id.0).await {
+                Ok(()) => {
+                    event_queue.push(AppEvent::WorklogDeleted { worklog });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to delete worklog: {:?}", e);
+                    event_queue.push(AppEvent::WorklogError {
+                        message: e.to_string(),
+                        timer: None,
+                    });
+                },
+            }
+        });
+    }
+
+    /// The cached children of a ticket
+    pub fn ticket_children(&self, ticket_id: TicketId) -> Vec<&Ticket> {
+        self.tickets
+            .iter()
+            .filter(|t| t.parent_id == Some(ticket_id))
+            .collect()
+    }
+
+    /// The cached sprints of a project, in start date order
+    pub fn project_sprints(&self, project_id: ProjectId) -> Vec<&Sprint> {
+        self.sprints
+            .iter()
+            .filter(|s| s.project_id == project_id)
+            .collect()
+    }
+
+    /// Fetch every user, for picking values of user fields
+    pub fn load_users(&self) {
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            match api_client.get_users(&token).await {
+                Ok(users) => {
+                    event_queue.push(AppEvent::UsersLoaded { users });
+                },
+                Err(e) => {
+                    tracing::error!("Failed to load users: {:?}", e);
+                    event_queue.push(AppEvent::ApiError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
+    /// Run a ticket query on the server and store the matching ticket ids
+    ///
+    /// An empty query with an empty sort clears the previous results.
+    pub fn run_ticket_query(&mut self, project_id: Option<ProjectId>, query: &str, sort: &str) {
+        let query = query.trim().to_string();
+        let sort = sort.trim().to_string();
+        if query.is_empty() && sort.is_empty() {
+            self.ticket_query_results = None;
+            self.ticket_query_error = None;
+            return;
+        }
+
+        let api_client = self.api_client.clone();
+        let event_queue = self.event_queue.clone();
+        let token = match &self.auth_token {
+            Some(t) => t.clone(),
+            None => return,
+        };
+        let project_id_uuid = project_id.map(|pid| pid.0);
+        self.is_loading = true;
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let query = Some(query.as_str()).filter(|q| !q.is_empty());
+            let sort = Some(sort.as_str()).filter(|s| !s.is_empty());
+
+            match api_client
+                .query_tickets(&token, project_id_uuid, query, sort)
+                .await
+            {
+                Ok(tickets) => {
+                    event_queue.push(AppEvent::TicketQueryResults { tickets });
+                },
+                Err(e) => {
+                    tracing::error!("Ticket query failed: {:?}", e);
+                    event_queue.push(AppEvent::TicketQueryError {
+                        message: e.to_string(),
+                    });
+                },
+            }
+        });
+    }
+
     /// Check if user is authenticated
     pub fn is_authenticated(&self) -> bool {
         self.current_user.is_some() && self.auth_token.is_some()
@@ -185,6 +1191,8 @@ impl AppState {
         let _ = LocalStorage::set("auth_token", token);
         let _ = LocalStorage::set("current_user", user);
 
+        self.connect_change_stream();
+        self.refresh_unread_notifications();
         self.navigate_to(Screen::Dashboard);
     }
 
@@ -196,8 +1204,12 @@ impl AppState {
         let user: Result<User, _> = LocalStorage::get("current_user");
 
         if let (Ok(token), Ok(user)) = (token, user) {
+            self.api_client.restore_tokens();
             self.current_user = Some(user);
             self.auth_token = Some(token);
+            self.running_timer = LocalStorage::get(TIMER_STORAGE_KEY).ok();
+            self.connect_change_stream();
+            self.refresh_unread_notifications();
             self.navigate_to(Screen::Dashboard);
             true
         } else {
@@ -208,8 +1220,41 @@ impl AppState {
     /// Logout user
     #[allow(clippy::let_unit_value)]
     pub fn logout(&mut self) {
+        let timer = self.running_timer.take();
+
+        // Revoke the session server-side; the local state is cleared regardless
+        if let Some(token) = self.auth_token.take() {
+            let api_client = self.api_client.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                // Log the running timer's time while the session is still
+                // valid; the timer ends with the session either way
+                if let Some(timer) = timer {
+                    if let Err(e) = log_timer(&api_client, &token, timer).await {
+                        tracing::warn!("Failed to log time on logout: {:?}", e);
+                    }
+                    forget_persisted_timer(timer);
+                }
+                if let Err(e) = api_client.logout(&token).await {
+                    tracing::warn!("Server-side logout failed: {:?}", e);
+                }
+            });
+        }
+
         self.current_user = None;
-        self.auth_token = None;
+        self.saved_views.clear();
+        self.pending_view = None;
+        self.custom_fields.clear();
+        self.users.clear();
+        self.workflows.clear();
+        self.sprints.clear();
+        self.worklogs.clear();
+        self.code_links.clear();
+        self.inbox.clear();
+        self.unread_notifications = 0;
+        self.notification_preferences = None;
+        self.change_stream = None;
+        self.change_stream_retry_at = None;
+        self.change_stream_missed = false;
         self.navigate_to(Screen::Login);
 
         // Clear local storage
@@ -255,6 +1300,15 @@ impl AppState {
     }
 }
 
+/// Format a number of minutes as hours and minutes, such as `2h 15m`
+pub fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
 /// Notification for user feedback
 #[derive(Debug, Clone)]
 pub struct Notification {
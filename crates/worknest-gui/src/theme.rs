@@ -53,6 +53,8 @@ impl Colors {
     pub const TYPE_BUG: Color32 = Color32::from_rgb(239, 68, 68); // Red
     pub const TYPE_FEATURE: Color32 = Color32::from_rgb(168, 85, 247); // Purple
     pub const TYPE_EPIC: Color32 = Color32::from_rgb(251, 191, 36); // Yellow
+    pub const TYPE_STORY: Color32 = Color32::from_rgb(34, 197, 94); // Green
+    pub const TYPE_SUBTASK: Color32 = Color32::from_rgb(100, 116, 139); // Slate
 
     // Text colors (will use theme defaults)
     pub const TEXT_PRIMARY: Color32 = Color32::from_rgb(17, 24, 39);
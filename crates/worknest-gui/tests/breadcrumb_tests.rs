@@ -3,7 +3,7 @@
 use chrono::Utc;
 use wasm_bindgen_test::*;
 use worknest_core::models::{
-    Priority, Project, ProjectId, Ticket, TicketId, TicketStatus, TicketType, UserId,
+    Priority, Project, ProjectId, StatusCategory, Ticket, TicketId, TicketType, UserId,
 };
 use worknest_gui::{
     api_client::ApiClient,
@@ -22,9 +22,11 @@ fn create_test_state() -> AppState {
     let project = Project {
         id: ProjectId::from_string("urn:uuid:00000000-0000-0000-0000-000000000001").unwrap(),
         name: "Test Project".to_string(),
+        key: "TP".to_string(),
         description: Some("Test Description".to_string()),
         color: None,
         archived: false,
+        is_private: false,
         created_by: UserId::from_string("urn:uuid:00000000-0000-0000-0000-000000000010").unwrap(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
@@ -36,17 +38,25 @@ fn create_test_state() -> AppState {
         id: TicketId::from_string("urn:uuid:00000000-0000-0000-0000-000000000100").unwrap(),
         project_id: ProjectId::from_string("urn:uuid:00000000-0000-0000-0000-000000000001")
             .unwrap(),
+        number: 1,
         title: "Test Ticket".to_string(),
         description: Some("Test Description".to_string()),
         ticket_type: TicketType::Task,
-        status: TicketStatus::InProgress,
+        status: "in_progress".to_string(),
+        status_category: StatusCategory::InProgress,
         priority: Priority::Medium,
         assignee_id: None,
         created_by: UserId::from_string("urn:uuid:00000000-0000-0000-0000-000000000010").unwrap(),
         due_date: None,
         estimate_hours: None,
+        sprint_id: None,
+        parent_id: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        labels: Vec::new(),
+        custom_fields: Vec::new(),
+        rollup: None,
+        logged_minutes: 0,
     };
     state.tickets.push(ticket);
 
@@ -7,7 +7,7 @@
 //! mouse events available.
 
 use wasm_bindgen_test::*;
-use worknest_core::models::{Priority, Project, Ticket, TicketStatus, TicketType, User};
+use worknest_core::models::{Priority, Project, Ticket, TicketType, User, Workflow};
 use worknest_gui::{api_client::ApiClient, screens::Screen, state::AppState};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -261,7 +261,11 @@ fn test_ticket_board_cards_missing_sense() {
         TicketType::Bug,
         user.id,
     );
-    ticket2.status = TicketStatus::InProgress;
+    ticket2.update_status(
+        Workflow::default_for(project.id)
+            .status("in_progress")
+            .unwrap(),
+    );
 
     state.projects.push(project.clone());
     state.tickets.push(ticket1);
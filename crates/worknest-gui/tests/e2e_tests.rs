@@ -4,7 +4,9 @@
 //! ensuring that all components work together correctly.
 
 use wasm_bindgen_test::*;
-use worknest_core::models::{Priority, Project, TicketStatus, TicketType, User};
+use worknest_core::models::{
+    Priority, Project, ProjectId, TicketType, User, Workflow, WorkflowStatus,
+};
 use worknest_gui::{api_client::ApiClient, screens::Screen, state::AppState};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -19,6 +21,14 @@ fn create_test_state() -> AppState {
     AppState::new(api_client)
 }
 
+/// A status of the default workflow
+fn status(key: &str) -> WorkflowStatus {
+    Workflow::default_for(ProjectId::new())
+        .status(key)
+        .unwrap()
+        .clone()
+}
+
 /// Create and login a test user
 fn setup_authenticated_state() -> AppState {
     let mut state = create_test_state();
@@ -214,7 +224,7 @@ fn e2e_complete_ticket_lifecycle() {
 
     // Update ticket status
     if let Some(t) = state.tickets.iter_mut().find(|t| t.id == ticket.id) {
-        t.status = TicketStatus::InProgress;
+        t.update_status(&status("in_progress"));
     }
     assert_eq!(
         state
@@ -223,7 +233,7 @@ fn e2e_complete_ticket_lifecycle() {
             .find(|t| t.id == ticket.id)
             .unwrap()
             .status,
-        TicketStatus::InProgress
+        "in_progress"
     );
 
     // Update ticket priority
@@ -242,7 +252,7 @@ fn e2e_complete_ticket_lifecycle() {
 
     // Complete ticket
     if let Some(t) = state.tickets.iter_mut().find(|t| t.id == ticket.id) {
-        t.status = TicketStatus::Done;
+        t.update_status(&status("done"));
     }
     assert_eq!(
         state
@@ -251,7 +261,7 @@ fn e2e_complete_ticket_lifecycle() {
             .find(|t| t.id == ticket.id)
             .unwrap()
             .status,
-        TicketStatus::Done
+        "done"
     );
 
     // Delete ticket
@@ -272,7 +282,7 @@ fn e2e_ticket_board_workflow() {
         TicketType::Task,
         user_id,
     );
-    ticket_open.status = TicketStatus::Open;
+    ticket_open.update_status(&status("open"));
     state.tickets.push(ticket_open.clone());
 
     let mut ticket_progress = worknest_core::models::Ticket::new(
@@ -281,7 +291,7 @@ fn e2e_ticket_board_workflow() {
         TicketType::Task,
         user_id,
     );
-    ticket_progress.status = TicketStatus::InProgress;
+    ticket_progress.update_status(&status("in_progress"));
     state.tickets.push(ticket_progress.clone());
 
     let mut ticket_done = worknest_core::models::Ticket::new(
@@ -290,7 +300,7 @@ fn e2e_ticket_board_workflow() {
         TicketType::Task,
         user_id,
     );
-    ticket_done.status = TicketStatus::Done;
+    ticket_done.update_status(&status("done"));
     state.tickets.push(ticket_done.clone());
 
     // Navigate to board view
@@ -308,21 +318,21 @@ fn e2e_ticket_board_workflow() {
     let open_tickets: Vec<_> = state
         .tickets
         .iter()
-        .filter(|t| t.project_id == project.id && t.status == TicketStatus::Open)
+        .filter(|t| t.project_id == project.id && t.status == "open")
         .collect();
     assert_eq!(open_tickets.len(), 1);
 
     let progress_tickets: Vec<_> = state
         .tickets
         .iter()
-        .filter(|t| t.project_id == project.id && t.status == TicketStatus::InProgress)
+        .filter(|t| t.project_id == project.id && t.status == "in_progress")
         .collect();
     assert_eq!(progress_tickets.len(), 1);
 
     let done_tickets: Vec<_> = state
         .tickets
         .iter()
-        .filter(|t| t.project_id == project.id && t.status == TicketStatus::Done)
+        .filter(|t| t.project_id == project.id && t.status == "done")
         .collect();
     assert_eq!(done_tickets.len(), 1);
 }
@@ -716,7 +726,7 @@ fn e2e_complete_user_journey() {
 
     // 7. Update ticket status
     if let Some(t) = state.tickets.iter_mut().find(|t| t.id == ticket.id) {
-        t.status = TicketStatus::InProgress;
+        t.update_status(&status("in_progress"));
     }
 
     // 8. Add another comment
@@ -727,7 +737,7 @@ fn e2e_complete_user_journey() {
 
     // 9. Mark ticket as done
     if let Some(t) = state.tickets.iter_mut().find(|t| t.id == ticket.id) {
-        t.status = TicketStatus::Done;
+        t.update_status(&status("done"));
     }
     assert_eq!(
         state
@@ -736,7 +746,7 @@ fn e2e_complete_user_journey() {
             .find(|t| t.id == ticket.id)
             .unwrap()
             .status,
-        TicketStatus::Done
+        "done"
     );
 
     // 10. Navigate to board view to see completed ticket
@@ -748,7 +758,7 @@ fn e2e_complete_user_journey() {
     let done_tickets: Vec<_> = state
         .tickets
         .iter()
-        .filter(|t| t.project_id == project.id && t.status == TicketStatus::Done)
+        .filter(|t| t.project_id == project.id && t.status == "done")
         .collect();
     assert_eq!(done_tickets.len(), 1);
 
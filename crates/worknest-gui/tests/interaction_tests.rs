@@ -319,3 +319,135 @@ fn test_dialog_visibility_flag() {
         "Cancel should set dialog visibility flag to false"
     );
 }
+
+#[wasm_bindgen_test]
+fn test_ticket_detail_child_list() {
+    // Test that the ticket detail screen lists child tickets in a collapsible section
+    let ticket_detail_source = include_str!("../src/screens/ticket_detail.rs");
+
+    assert!(
+        ticket_detail_source.contains("egui::CollapsingHeader::new(format!(\"Child tickets"),
+        "ticket_detail.rs should show child tickets in a collapsible section"
+    );
+
+    assert!(
+        ticket_detail_source.contains("state.load_ticket_children(ticket.id)"),
+        "ticket_detail.rs should load the children of the ticket"
+    );
+
+    assert!(
+        ticket_detail_source.contains("state.navigate_to(Screen::TicketDetail(child.id))"),
+        "Clicking a child ticket should open it"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_ticket_detail_time_tracking() {
+    // Test that the ticket detail screen offers a timer that survives page reloads
+    let ticket_detail_source = include_str!("../src/screens/ticket_detail.rs");
+    let state_source = include_str!("../src/state.rs");
+
+    assert!(
+        ticket_detail_source.contains("state.start_timer(ticket.id)")
+            && ticket_detail_source.contains("state.stop_timer()"),
+        "ticket_detail.rs should start and stop the time tracking timer"
+    );
+
+    assert!(
+        ticket_detail_source.contains("egui::CollapsingHeader::new(format!(\"Worklogs"),
+        "ticket_detail.rs should list the ticket's worklogs"
+    );
+
+    assert!(
+        state_source.contains("LocalStorage::set(TIMER_STORAGE_KEY")
+            && state_source.contains("LocalStorage::get(TIMER_STORAGE_KEY)"),
+        "The running timer should be persisted in LocalStorage"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_live_updates_applied_to_state() {
+    // Test that the app subscribes to the server's change stream and applies pushed changes
+    let state_source = include_str!("../src/state.rs");
+
+    assert!(
+        state_source.contains("self.connect_change_stream()"),
+        "state.rs should subscribe to the change stream once signed in"
+    );
+
+    assert!(
+        state_source.contains("AppEvent::RemoteChange { change } => {")
+            && state_source.contains("self.apply_change(change)"),
+        "process_events should apply changes pushed by the server"
+    );
+
+    assert!(
+        state_source.contains("AppEvent::ChangeStreamClosed => {")
+            && state_source.contains("CHANGE_STREAM_RETRY_DELAY"),
+        "A closed change stream should be reconnected"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_notification_inbox_with_unread_badge() {
+    // Test that the inbox screen is reachable from the sidebar, which shows the unread count
+    let sidebar_source = include_str!("../src/components/sidebar.rs");
+    let inbox_source = include_str!("../src/screens/notifications.rs");
+
+    assert!(
+        sidebar_source.contains("state.unread_notifications")
+            && sidebar_source.contains("state.navigate_to(Screen::Notifications)"),
+        "Sidebar should link to the inbox with an unread badge"
+    );
+
+    assert!(
+        inbox_source.contains("state.mark_notification_read(")
+            && inbox_source.contains("state.mark_all_notifications_read()"),
+        "Inbox should let users mark notifications as read"
+    );
+
+    assert!(
+        inbox_source.contains("Screen::TicketDetail(ticket_id)"),
+        "Opening a notification should navigate to its ticket"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_notification_preferences_in_settings() {
+    // Test that email notification preferences are loaded and saved from the settings screen
+    let settings_source = include_str!("../src/screens/settings.rs");
+
+    assert!(
+        settings_source.contains("SettingsTab::Notifications")
+            && settings_source.contains("state.load_notification_preferences()"),
+        "Settings should have a notifications tab that loads the preferences"
+    );
+
+    assert!(
+        settings_source.contains("preferences.daily_digest")
+            && settings_source.contains("preferences.digest_hour"),
+        "Notifications tab should offer the daily digest and its hour"
+    );
+
+    assert!(
+        settings_source.contains("state.save_notification_preferences("),
+        "Notifications tab should save the preferences"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_ticket_detail_lists_code_links() {
+    // Test that the ticket detail screen shows commits and pull requests linked from Git forges
+    let detail_source = include_str!("../src/screens/ticket_detail.rs");
+
+    assert!(
+        detail_source.contains("state.load_code_links(ticket.id)"),
+        "Ticket detail should load the ticket's code links"
+    );
+
+    assert!(
+        detail_source.contains("state.ticket_code_links(ticket.id)")
+            && detail_source.contains("ui.hyperlink_to("),
+        "Ticket detail should link to each commit and pull request"
+    );
+}
@@ -46,7 +46,7 @@ fn test_kanban_responsive_column_width() {
 
     // Should pass column_width as parameter
     assert!(
-        ticket_board_source.contains("fn render_column(&mut self, ui: &mut egui::Ui, status: TicketStatus, state: &mut AppState, column_width: f32)"),
+        ticket_board_source.contains("column_width: f32,"),
         "ticket_board.rs render_column should accept column_width parameter"
     );
 }
@@ -130,34 +130,28 @@ fn test_kanban_column_background_styling() {
 
 #[wasm_bindgen_test]
 fn test_kanban_column_header_color_coding() {
-    // Test that column headers are color-coded by status
+    // Test that column headers are color-coded by status category
     let ticket_board_source = include_str!("../src/screens/ticket_board.rs");
 
     assert!(
-        ticket_board_source.contains("let (column_title, column_color) = match status"),
-        "ticket_board.rs should color-code column headers by status"
+        ticket_board_source.contains("let column_color = match status.category"),
+        "ticket_board.rs should color-code column headers by status category"
     );
 
-    // Should have color for each status
+    // Should have color for each status category
     assert!(
-        ticket_board_source.contains("TicketStatus::Open => (\"Open\", Colors::INFO)"),
-        "ticket_board.rs should color Open columns with INFO color"
+        ticket_board_source.contains("StatusCategory::Todo => Colors::INFO"),
+        "ticket_board.rs should color to-do columns with INFO color"
     );
 
     assert!(
-        ticket_board_source
-            .contains("TicketStatus::InProgress => (\"In Progress\", Colors::WARNING)"),
-        "ticket_board.rs should color InProgress columns with WARNING color"
+        ticket_board_source.contains("StatusCategory::InProgress => Colors::WARNING"),
+        "ticket_board.rs should color in-progress columns with WARNING color"
     );
 
     assert!(
-        ticket_board_source.contains("TicketStatus::Review => (\"Review\", Colors::PRIMARY)"),
-        "ticket_board.rs should color Review columns with PRIMARY color"
-    );
-
-    assert!(
-        ticket_board_source.contains("TicketStatus::Done => (\"Done\", Colors::SUCCESS)"),
-        "ticket_board.rs should color Done columns with SUCCESS color"
+        ticket_board_source.contains("StatusCategory::Done => Colors::SUCCESS"),
+        "ticket_board.rs should color done columns with SUCCESS color"
     );
 }
 
@@ -324,3 +318,24 @@ fn test_kanban_no_columns_function() {
         "ticket_board.rs should NOT use ui.columns() (causes interaction panics)"
     );
 }
+
+#[wasm_bindgen_test]
+fn test_kanban_sprint_filter() {
+    // Test that the board can be narrowed to a sprint or the backlog
+    let ticket_board_source = include_str!("../src/screens/ticket_board.rs");
+
+    assert!(
+        ticket_board_source.contains("egui::ComboBox::from_id_salt(\"sprint_filter\")"),
+        "ticket_board.rs should offer a sprint filter"
+    );
+
+    assert!(
+        ticket_board_source.contains("SprintFilter::Backlog => ticket.sprint_id.is_none()"),
+        "ticket_board.rs should show tickets in no sprint as the backlog"
+    );
+
+    assert!(
+        ticket_board_source.contains(".filter(|t| self.sprint_filter.matches(t))"),
+        "ticket_board.rs should apply the sprint filter to the columns"
+    );
+}
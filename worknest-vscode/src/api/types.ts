@@ -11,6 +11,7 @@ export interface UserDto {
 export interface ProjectDto {
     id: string;
     name: string;
+    key: string;
     description?: string;
     color?: string;
     archived: boolean;
@@ -19,13 +20,18 @@ export interface ProjectDto {
     updated_at: string;
 }
 
-export type TicketType = 'Task' | 'Bug' | 'Feature' | 'Epic';
+export type TicketType = 'Task' | 'Bug' | 'Feature' | 'Epic' | 'Story' | 'Subtask';
 export type TicketStatus = 'Open' | 'InProgress' | 'Review' | 'Done' | 'Closed';
 export type Priority = 'Low' | 'Medium' | 'High' | 'Critical';
 
+/** Matches a ticket key such as `WN-123`, e.g. in a branch name */
+export const TICKET_KEY_PATTERN = /\b([A-Z][A-Z0-9]{1,9}-\d+)\b/;
+
 export interface TicketDto {
     id: string;
     project_id: string;
+    number: number;
+    key: string;
     title: string;
     description?: string;
     ticket_type: TicketType;
@@ -1,6 +1,6 @@
 import * as vscode from 'vscode';
 import { WorknestApiClient } from '../api/client';
-import { TicketDto } from '../api/types';
+import { TicketDto, TICKET_KEY_PATTERN } from '../api/types';
 
 export class GitCommands {
     constructor(private client: WorknestApiClient) {}
@@ -22,14 +22,13 @@ export class GitCommands {
             const repo = api.repositories[0];
 
             // Generate branch name
-            const ticketIdShort = ticket.id.substring(0, 8);
             const titleSlug = ticket.title
                 .toLowerCase()
                 .replace(/[^a-z0-9]+/g, '-')
                 .replace(/^-|-$/g, '');
 
             const branchPrefix = ticket.ticket_type === 'Bug' ? 'fix' : 'feature';
-            const branchName = `${branchPrefix}/${ticketIdShort}-${titleSlug}`;
+            const branchName = `${branchPrefix}/${ticket.key}-${titleSlug}`;
 
             // Ask for confirmation
             const confirm = await vscode.window.showInputBox({
@@ -57,8 +56,7 @@ export class GitCommands {
             return;
         }
 
-        const ticketIdShort = ticket.id.substring(0, 8);
-        const reference = `// Worknest: ${ticketIdShort} - ${ticket.title}`;
+        const reference = `// Worknest: ${ticket.key} - ${ticket.title}`;
 
         editor.edit(editBuilder => {
             editBuilder.insert(editor.selection.active, reference);
@@ -85,6 +83,21 @@ export class GitCommands {
             }
 
             // Try to extract ticket ID from branch name
+            // Ticket keys such as WN-123 resolve directly
+            let ticket: TicketDto | undefined;
+            const keyMatch = branchName.match(TICKET_KEY_PATTERN);
+            if (keyMatch) {
+                try {
+                    ticket = await this.client.getTicket(keyMatch[1]);
+                } catch {
+                    // Fall back to the older patterns
+                }
+            }
+            if (ticket) {
+                repo.inputBox.value = `[${ticket.key}] ${ticket.title}: `;
+                return;
+            }
+
             const patterns = [
                 /([0-9a-f]{8})/i,  // UUID prefix
                 /TICKET-(\d+)/i     // TICKET-123 format
@@ -99,8 +112,7 @@ export class GitCommands {
                         const tickets = await this.client.searchTickets(ticketId);
                         if (tickets.length > 0) {
                             const ticket = tickets[0];
-                            const ticketIdShort = ticket.id.substring(0, 8);
-                            const message = `[${ticketIdShort}] ${ticket.title}: `;
+                            const message = `[${ticket.key}] ${ticket.title}: `;
 
                             // Set input box value
                             repo.inputBox.value = message;
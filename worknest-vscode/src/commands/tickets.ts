@@ -216,8 +216,8 @@ export class TicketCommands {
             <h1>${ticket.title}</h1>
 
             <div class="metadata">
-                <div class="label">ID:</div>
-                <div>${ticket.id}</div>
+                <div class="label">Key:</div>
+                <div>${ticket.key}</div>
 
                 <div class="label">Type:</div>
                 <div>${ticket.ticket_type}</div>
@@ -1,6 +1,6 @@
 import * as vscode from 'vscode';
 import { WorknestApiClient } from '../api/client';
-import { TicketDto } from '../api/types';
+import { TicketDto, TICKET_KEY_PATTERN } from '../api/types';
 
 export class StatusBarManager {
     private statusBarItem: vscode.StatusBarItem;
@@ -23,7 +23,7 @@ export class StatusBarManager {
 
             if (ticket) {
                 this.currentTicket = ticket;
-                this.statusBarItem.text = `$(issue-opened) ${ticket.id.substring(0, 8)}: ${ticket.title}`;
+                this.statusBarItem.text = `$(issue-opened) ${ticket.key}: ${ticket.title}`;
                 this.statusBarItem.tooltip = `${ticket.title}\nStatus: ${ticket.status}\nPriority: ${ticket.priority}`;
                 this.statusBarItem.show();
             } else {
@@ -60,6 +60,16 @@ export class StatusBarManager {
 
             // Try to extract ticket ID from branch name
             // Patterns: feature/TICKET-123, TICKET-123-description, etc.
+            // Ticket keys such as WN-123 resolve directly
+            const keyMatch = branchName.match(TICKET_KEY_PATTERN);
+            if (keyMatch) {
+                try {
+                    return await this.client.getTicket(keyMatch[1]);
+                } catch {
+                    // Fall back to the older patterns
+                }
+            }
+
             const patterns = [
                 /([0-9a-f]{8})/i,  // UUID prefix
                 /TICKET-(\d+)/i     // TICKET-123 format
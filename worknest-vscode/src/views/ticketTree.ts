@@ -65,7 +65,7 @@ class ProjectTreeItem extends vscode.TreeItem {
 
 class TicketTreeItem extends vscode.TreeItem {
     constructor(public readonly ticket: TicketDto) {
-        super(ticket.title, vscode.TreeItemCollapsibleState.None);
+        super(`${ticket.key} ${ticket.title}`, vscode.TreeItemCollapsibleState.None);
         this.contextValue = 'ticket';
         this.description = `${ticket.ticket_type} - ${ticket.status}`;
         this.tooltip = this.buildTooltip();